@@ -0,0 +1,172 @@
+/*!
+    A window with a Direct2D canvas. Draws gradients, a rotated path, styled text and a masked bitmap.
+
+    `cargo run --example canvas_d` from the native-windows-canvas directory.
+*/
+
+extern crate native_windows_canvas as nwc;
+extern crate native_windows_derive as nwd;
+extern crate native_windows_gui as nwg;
+
+use nwc::Canvas;
+use nwd::NwgUi;
+use nwg::NativeUi;
+use std::cell::RefCell;
+
+/// The device resources must be created again if the render target is lost
+struct Resources {
+    background: nwc::Brush,
+    star_fill: nwc::Brush,
+    outline: nwc::Brush,
+    text: nwc::Brush,
+    highlight: nwc::Brush,
+    cat: nwc::Bitmap,
+    layer: nwc::Layer,
+}
+
+#[derive(Default, NwgUi)]
+pub struct CanvasApp {
+    #[nwg_control(size: (600, 400), position: (300, 300), title: "Canvas", flags: "MAIN_WINDOW|VISIBLE")]
+    #[nwg_events( OnWindowClose: [nwg::stop_thread_dispatch()] )]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window, margin: [0, 0, 0, 0], spacing: 0)]
+    layout: nwg::GridLayout,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: layout, col: 0, row: 0)]
+    #[nwg_events( OnPaint: [CanvasApp::paint], OnResize: [CanvasApp::invalidate] )]
+    canvas: Canvas,
+
+    #[nwg_resource]
+    decoder: nwg::ImageDecoder,
+
+    resources: RefCell<Option<Resources>>,
+}
+
+impl CanvasApp {
+    fn invalidate(&self) {
+        self.canvas.invalidate();
+    }
+
+    fn paint(&self) {
+        if let Err(e) = self.draw() {
+            if let nwc::CanvasError::TargetLost = e {
+                *self.resources.borrow_mut() = None;
+                self.canvas.invalidate();
+            } else {
+                println!("{}", e);
+            }
+        }
+    }
+
+    fn create_resources(&self) -> Result<Resources, nwc::CanvasError> {
+        let c = &self.canvas;
+
+        let stops = [
+            nwc::GradientStop::new(0.0, nwc::Color::rgb(30, 40, 80)),
+            nwc::GradientStop::new(1.0, nwc::Color::rgb(90, 140, 200)),
+        ];
+
+        let star_stops = [
+            nwc::GradientStop::new(0.0, nwc::Color::rgb(255, 240, 120)),
+            nwc::GradientStop::new(1.0, nwc::Color::rgb(240, 140, 20)),
+        ];
+
+        Ok(Resources {
+            background: c.linear_gradient_brush(
+                (0.0, 0.0),
+                (0.0, 400.0),
+                &stops,
+                nwc::ExtendMode::Clamp,
+            )?,
+            star_fill: c.radial_gradient_brush(
+                (0.0, 0.0),
+                (0.0, 0.0),
+                60.0,
+                60.0,
+                &star_stops,
+                nwc::ExtendMode::Clamp,
+            )?,
+            outline: c.solid_brush(nwc::Color::rgb(80, 40, 0))?,
+            text: c.solid_brush(nwc::Color::WHITE)?,
+            highlight: c.solid_brush(nwc::Color::rgb(255, 200, 60))?,
+            cat: c.load_bitmap(&self.decoder, "../native-windows-gui/test_rc/cat.jpg")?,
+            layer: c.layer()?,
+        })
+    }
+
+    fn draw(&self) -> Result<(), nwc::CanvasError> {
+        let mut resources = self.resources.borrow_mut();
+        if resources.is_none() {
+            *resources = Some(self.create_resources()?);
+        }
+
+        let r = resources.as_ref().unwrap();
+        let c = &self.canvas;
+
+        // Device independent resources
+        let star = c
+            .path()?
+            .begin_figure((0.0, -60.0), true)
+            .line_to((35.0, 48.0))
+            .line_to((-57.0, -18.0))
+            .line_to((57.0, -18.0))
+            .line_to((-35.0, 48.0))
+            .end_figure(true)
+            .build()?;
+
+        let dashed = c
+            .stroke_style()
+            .line_join(nwc::LineJoin::Round)
+            .dash_style(nwc::DashStyle::Dash)
+            .build()?;
+
+        let format = c.text_format().family("Segoe UI").size(22.0).build()?;
+        let layout = c.text_layout("Hello from Direct2D", &format, 300.0, 40.0)?;
+        layout.set_weight(11..19, 700);
+        layout.set_brush(11..19, &r.highlight);
+
+        let mask = c.ellipse_geometry((470.0, 230.0), 90.0, 90.0)?;
+
+        let (w, h) = c.size();
+        let draw = c.begin_draw()?;
+        draw.clear(nwc::Color::BLACK);
+        draw.fill_rect(nwc::Rect::new(0.0, 0.0, w as f32, h as f32), &r.background);
+
+        let center = nwc::Transform::translation(150.0, 200.0);
+        draw.set_transform(&nwc::Transform::rotation(15.0, nwc::Point::default()).then(&center));
+        draw.fill_geometry(&star, &r.star_fill);
+        draw.draw_geometry(&star, &r.outline, 3.0, Some(&dashed));
+        draw.set_transform(&nwc::Transform::identity());
+
+        draw.draw_text_layout((20.0, 20.0), &layout, &r.text);
+
+        let params = nwc::LayerParameters {
+            mask: Some(&mask),
+            opacity: 0.9,
+            ..Default::default()
+        };
+
+        draw.push_layer(&r.layer, &params);
+        draw.draw_bitmap(
+            &r.cat,
+            nwc::Rect::new(380.0, 140.0, 180.0, 180.0),
+            1.0,
+            nwc::Interpolation::Linear,
+            None,
+        );
+        draw.pop_layer();
+
+        draw.end()
+    }
+}
+
+fn main() {
+    nwg::init().expect("Failed to init Native Windows GUI");
+    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
+
+    let _app = CanvasApp::build_ui(Default::default()).expect("Failed to build UI");
+
+    nwg::dispatch_thread_events();
+}
@@ -0,0 +1,99 @@
+use crate::error::check;
+use crate::{release, Canvas, CanvasError};
+use native_windows_gui::{ImageData, ImageDecoder};
+use std::ptr;
+use winapi::um::d2d1::*;
+use winapi::um::wincodec::{
+    GUID_WICPixelFormat32bppPBGRA, IWICBitmapSource, WICBitmapDitherTypeNone,
+    WICBitmapPaletteTypeMedianCut,
+};
+
+/// How a bitmap is sampled when it is scaled
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    NearestNeighbor,
+    Linear,
+}
+
+impl Interpolation {
+    pub(crate) fn d2d(self) -> D2D1_BITMAP_INTERPOLATION_MODE {
+        match self {
+            Interpolation::NearestNeighbor => D2D1_BITMAP_INTERPOLATION_MODE_NEAREST_NEIGHBOR,
+            Interpolation::Linear => D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
+        }
+    }
+}
+
+/**
+    A Direct2D bitmap. Bitmaps are device resources: they can only be used with the canvas that created them.
+*/
+pub struct Bitmap {
+    pub bitmap: *mut ID2D1Bitmap,
+    size: (u32, u32),
+}
+
+impl Bitmap {
+    /// Return the size of the bitmap in pixels
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+}
+
+impl Drop for Bitmap {
+    fn drop(&mut self) {
+        unsafe { release(self.bitmap) }
+    }
+}
+
+impl Canvas {
+    /**
+        Upload an image frame decoded by `decoder` to the canvas.
+        The pixels are converted to premultiplied BGRA first.
+    */
+    pub fn bitmap(&self, decoder: &ImageDecoder, image: &ImageData) -> Result<Bitmap, CanvasError> {
+        if decoder.factory.is_null() {
+            panic!("ImageDecoder is not yet bound to a winapi object");
+        }
+
+        let target = self.render_target()?;
+
+        unsafe {
+            let mut converter = ptr::null_mut();
+            let hr = (&*decoder.factory).CreateFormatConverter(&mut converter);
+            check(hr, "Failed to create the bitmap format converter")?;
+
+            let hr = (&*converter).Initialize(
+                image.frame,
+                &GUID_WICPixelFormat32bppPBGRA,
+                WICBitmapDitherTypeNone,
+                ptr::null(),
+                0.0,
+                WICBitmapPaletteTypeMedianCut,
+            );
+            if let Err(e) = check(hr, "Failed to convert the image pixels") {
+                release(converter);
+                return Err(e);
+            }
+
+            let mut bitmap = ptr::null_mut();
+            let hr = (&*target).CreateBitmapFromWicBitmap(
+                converter as *mut IWICBitmapSource,
+                ptr::null(),
+                &mut bitmap,
+            );
+            release(converter);
+            check(hr, "Failed to create the bitmap")?;
+
+            Ok(Bitmap {
+                bitmap,
+                size: image.size(),
+            })
+        }
+    }
+
+    /// Decode the first frame of the image at `path` and upload it to the canvas
+    pub fn load_bitmap(&self, decoder: &ImageDecoder, path: &str) -> Result<Bitmap, CanvasError> {
+        let image = decoder.from_filename(path)?.frame(0)?;
+        self.bitmap(decoder, &image)
+    }
+}
@@ -0,0 +1,202 @@
+use crate::error::check;
+use crate::{release, Bitmap, Canvas, CanvasError, Color, Interpolation, Point, Transform};
+use std::ptr;
+use winapi::um::d2d1::*;
+
+/// How a gradient or a bitmap brush paints the area outside of its normal range
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExtendMode {
+    /// Repeat the edge pixels or colors
+    Clamp,
+
+    /// Repeat the content
+    Wrap,
+
+    /// Repeat the content, flipping every other copy
+    Mirror,
+}
+
+impl ExtendMode {
+    fn d2d(self) -> D2D1_EXTEND_MODE {
+        match self {
+            ExtendMode::Clamp => D2D1_EXTEND_MODE_CLAMP,
+            ExtendMode::Wrap => D2D1_EXTEND_MODE_WRAP,
+            ExtendMode::Mirror => D2D1_EXTEND_MODE_MIRROR,
+        }
+    }
+}
+
+/// A color at a position (`0.0..=1.0`) of a gradient
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GradientStop {
+    pub position: f32,
+    pub color: Color,
+}
+
+impl GradientStop {
+    pub fn new(position: f32, color: Color) -> GradientStop {
+        GradientStop { position, color }
+    }
+}
+
+/**
+    A Direct2D brush. Brushes are device resources: they can only be used with the canvas that created them.
+*/
+pub struct Brush {
+    pub brush: *mut ID2D1Brush,
+}
+
+impl Brush {
+    pub fn opacity(&self) -> f32 {
+        unsafe { (&*self.brush).GetOpacity() }
+    }
+
+    pub fn set_opacity(&self, opacity: f32) {
+        unsafe { (&*self.brush).SetOpacity(opacity) }
+    }
+
+    /// Return the transformation applied to the brush content (gradients and bitmaps)
+    pub fn transform(&self) -> Transform {
+        let mut m = Transform::identity().into();
+        unsafe { (&*self.brush).GetTransform(&mut m) };
+        m.into()
+    }
+
+    pub fn set_transform(&self, t: &Transform) {
+        let m = (*t).into();
+        unsafe { (&*self.brush).SetTransform(&m) }
+    }
+}
+
+impl Drop for Brush {
+    fn drop(&mut self) {
+        unsafe { release(self.brush) }
+    }
+}
+
+impl Canvas {
+    /// Create a brush that paints an area with a single color
+    pub fn solid_brush(&self, color: Color) -> Result<Brush, CanvasError> {
+        let target = self.render_target()?;
+        let mut brush = ptr::null_mut();
+        let hr =
+            unsafe { (&*target).CreateSolidColorBrush(&color.into(), ptr::null(), &mut brush) };
+        check(hr, "Failed to create a solid color brush")?;
+
+        Ok(Brush {
+            brush: brush as *mut ID2D1Brush,
+        })
+    }
+
+    /// Create a brush that paints a gradient along the line going from `start` to `end`
+    pub fn linear_gradient_brush<P: Into<Point>>(
+        &self,
+        start: P,
+        end: P,
+        stops: &[GradientStop],
+        extend: ExtendMode,
+    ) -> Result<Brush, CanvasError> {
+        let target = self.render_target()?;
+        let props = D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+            startPoint: start.into().into(),
+            endPoint: end.into().into(),
+        };
+
+        unsafe {
+            let stops = gradient_stops(target, stops, extend)?;
+            let mut brush = ptr::null_mut();
+            let hr = (&*target).CreateLinearGradientBrush(&props, ptr::null(), stops, &mut brush);
+            release(stops);
+            check(hr, "Failed to create a linear gradient brush")?;
+
+            Ok(Brush {
+                brush: brush as *mut ID2D1Brush,
+            })
+        }
+    }
+
+    /**
+        Create a brush that paints a gradient in the ellipse defined by `center`, `radius_x` and `radius_y`.
+        `origin_offset` moves the origin of the gradient relative to the center.
+    */
+    pub fn radial_gradient_brush<P: Into<Point>>(
+        &self,
+        center: P,
+        origin_offset: P,
+        radius_x: f32,
+        radius_y: f32,
+        stops: &[GradientStop],
+        extend: ExtendMode,
+    ) -> Result<Brush, CanvasError> {
+        let target = self.render_target()?;
+        let props = D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+            center: center.into().into(),
+            gradientOriginOffset: origin_offset.into().into(),
+            radiusX: radius_x,
+            radiusY: radius_y,
+        };
+
+        unsafe {
+            let stops = gradient_stops(target, stops, extend)?;
+            let mut brush = ptr::null_mut();
+            let hr = (&*target).CreateRadialGradientBrush(&props, ptr::null(), stops, &mut brush);
+            release(stops);
+            check(hr, "Failed to create a radial gradient brush")?;
+
+            Ok(Brush {
+                brush: brush as *mut ID2D1Brush,
+            })
+        }
+    }
+
+    /// Create a brush that paints an area with a bitmap
+    pub fn bitmap_brush(
+        &self,
+        bitmap: &Bitmap,
+        extend_x: ExtendMode,
+        extend_y: ExtendMode,
+        interpolation: Interpolation,
+    ) -> Result<Brush, CanvasError> {
+        let target = self.render_target()?;
+        let props = D2D1_BITMAP_BRUSH_PROPERTIES {
+            extendModeX: extend_x.d2d(),
+            extendModeY: extend_y.d2d(),
+            interpolationMode: interpolation.d2d(),
+        };
+
+        let mut brush = ptr::null_mut();
+        let hr =
+            unsafe { (&*target).CreateBitmapBrush(bitmap.bitmap, &props, ptr::null(), &mut brush) };
+        check(hr, "Failed to create a bitmap brush")?;
+
+        Ok(Brush {
+            brush: brush as *mut ID2D1Brush,
+        })
+    }
+}
+
+unsafe fn gradient_stops(
+    target: *mut ID2D1RenderTarget,
+    stops: &[GradientStop],
+    extend: ExtendMode,
+) -> Result<*mut ID2D1GradientStopCollection, CanvasError> {
+    let stops: Vec<D2D1_GRADIENT_STOP> = stops
+        .iter()
+        .map(|s| D2D1_GRADIENT_STOP {
+            position: s.position,
+            color: s.color.into(),
+        })
+        .collect();
+
+    let mut collection = ptr::null_mut();
+    let hr = (&*target).CreateGradientStopCollection(
+        stops.as_ptr(),
+        stops.len() as u32,
+        D2D1_GAMMA_2_2,
+        extend.d2d(),
+        &mut collection,
+    );
+    check(hr, "Failed to create the gradient stops")?;
+
+    Ok(collection)
+}
@@ -0,0 +1,613 @@
+use crate::layer::antialias_mode;
+use crate::types::{ellipse, rounded_rect};
+use crate::{
+    release, Bitmap, Brush, CanvasError, Color, Geometry, Interpolation, Layer, LayerParameters,
+    Point, Rect, StrokeStyle, TextFormat, TextLayout, Transform,
+};
+use native_windows_gui::d2d::{
+    client_size, compare_snapshot, D2DFactories, SnapshotResult, SoftwareBitmap,
+};
+use native_windows_gui::{ControlHandle, ExternCanvas, ExternCanvasFlags, NwgError};
+use std::{cell::Cell, ptr};
+use winapi::shared::winerror::{D2DERR_RECREATE_TARGET, E_FAIL, S_OK};
+use winapi::um::d2d1::*;
+use winapi::um::dcommon::DWRITE_MEASURING_MODE_NATURAL;
+
+const NOT_BOUND: &'static str = "Canvas is not yet bound to a winapi object";
+
+struct Renderer {
+    factories: D2DFactories,
+
+    // Null after the target was lost. Rebuilt on the next use.
+    target: Cell<*mut ID2D1RenderTarget>,
+
+    // The bitmap of a software canvas. `target` draws into it.
+    software: Option<SoftwareBitmap>,
+
+    size: Cell<(u32, u32)>,
+    drawing: Cell<bool>,
+}
+
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        unsafe { release(self.target.get()) }
+    }
+}
+
+/**
+    A control that is painted with Direct2D. The canvas wraps an `ExternCanvas` and derefs to it, so
+    it can be used everywhere an `ExternCanvas` can (events, layouts, size and position).
+
+    Drawing happens between `begin_draw` and the end of the returned `CanvasDraw`. This is usually done in the `OnPaint` event.
+
+    Coordinates are in device independent pixels (1/96 inch), so drawing commands are not affected by the dpi scale.
+
+    A canvas created with `Canvas::software` draws into a bitmap in memory instead of a window. See `Canvas::software`.
+
+    **Builder parameters:**
+      * `parent`: The canvas parent. If no parent is set, the canvas is a top level window.
+      * `size`: The canvas size.
+      * `position`: The canvas position.
+      * `flags`: The window flags when the canvas is a top level window. See `ExternCanvasFlags`.
+      * `ex_flags`: A combination of win32 window extended flags.
+      * `title`: The window title when the canvas is a top level window.
+
+    **Control events:**
+      * The `ExternCanvas` events. Most importantly `OnPaint` and `OnResize`.
+
+    With native-windows-derive, `Canvas` must be imported in the module of the UI struct because the
+    generated code refers to the control type by its name.
+
+    ```rust
+    use native_windows_gui as nwg;
+    use native_windows_canvas as nwc;
+
+    fn paint(canvas: &nwc::Canvas, brush: &nwc::Brush) -> Result<(), nwc::CanvasError> {
+        let draw = canvas.begin_draw()?;
+        draw.clear(nwc::Color::WHITE);
+        draw.fill_rect(nwc::Rect::new(10.0, 10.0, 100.0, 50.0), brush);
+        draw.end()
+    }
+
+    fn build_canvas(canvas: &mut nwc::Canvas, window: &nwg::Window) -> Result<(), nwg::NwgError> {
+        nwc::Canvas::builder()
+            .size((300, 300))
+            .parent(window)
+            .build(canvas)
+    }
+    ```
+*/
+#[derive(Default)]
+pub struct Canvas {
+    pub canvas: ExternCanvas,
+    renderer: Option<Renderer>,
+}
+
+native_windows_gui::subclass_control!(Canvas, ExternCanvas, canvas);
+
+impl Canvas {
+    pub fn builder<'a>() -> CanvasBuilder<'a> {
+        CanvasBuilder {
+            title: "",
+            size: (500, 500),
+            position: (0, 0),
+            flags: None,
+            ex_flags: 0,
+            parent: None,
+        }
+    }
+
+    /**
+        Create a canvas that draws into a bitmap of `width` by `height` pixels with the Direct2D software rasterizer.
+        It works without a window or a display, so the output can be compared against reference images in tests.
+
+        The canvas is not bound to a window: the `ExternCanvas` methods must not be used.
+        Coordinates are in pixels. COM must be initialized on the current thread (`nwg::init` does it).
+
+        ```rust,no_run
+        use native_windows_gui as nwg;
+        use native_windows_canvas as nwc;
+
+        fn rect_test() {
+            nwg::init().unwrap();
+
+            let canvas = nwc::Canvas::software(200, 100).unwrap();
+            let brush = canvas.solid_brush(nwc::Color::rgb(200, 30, 30)).unwrap();
+
+            let draw = canvas.begin_draw().unwrap();
+            draw.clear(nwc::Color::WHITE);
+            draw.fill_rect(nwc::Rect::new(50.0, 25.0, 100.0, 50.0), &brush);
+            draw.end().unwrap();
+
+            let result = canvas.compare_snapshot("test_rc/snapshots/rect.png", 2).unwrap();
+            assert!(result.is_match(), "{:?}", result);
+        }
+        ```
+    */
+    pub fn software(width: u32, height: u32) -> Result<Canvas, CanvasError> {
+        let init_error = |e: &'static str| CanvasError::RendererInit(e.into());
+
+        let factories = D2DFactories::new().map_err(init_error)?;
+        let bitmap = SoftwareBitmap::new(width, height).map_err(init_error)?;
+        let target = factories
+            .create_software_render_target(&bitmap)
+            .map_err(init_error)?;
+
+        let renderer = Renderer {
+            factories,
+            target: Cell::new(target),
+            software: Some(bitmap),
+            size: Cell::new((width, height)),
+            drawing: Cell::new(false),
+        };
+
+        Ok(Canvas {
+            canvas: Default::default(),
+            renderer: Some(renderer),
+        })
+    }
+
+    /**
+        Begin drawing on the canvas. The commands are presented when the returned object is ended or dropped.
+
+        Resizes the render target if the canvas size changed since the last draw, and rebuilds
+        it if it was lost.
+
+        Panics if the canvas is not initialized or if another draw is in progress.
+    */
+    pub fn begin_draw(&self) -> Result<CanvasDraw<'_>, CanvasError> {
+        let renderer = self.renderer();
+        if renderer.drawing.get() {
+            panic!("Canvas::begin_draw was called while another draw was in progress");
+        }
+
+        let target = self.render_target()?;
+        if renderer.software.is_none() {
+            let hwnd = self.canvas.handle.hwnd().expect(NOT_BOUND);
+            let size = unsafe { client_size(hwnd) };
+            if size != renderer.size.get() {
+                let pixel_size = D2D1_SIZE_U {
+                    width: size.0,
+                    height: size.1,
+                };
+
+                let hwnd_target = target as *mut ID2D1HwndRenderTarget;
+                unsafe { (&*hwnd_target).Resize(&pixel_size) };
+                renderer.size.set(size);
+            }
+        }
+
+        unsafe {
+            (&*target).BeginDraw();
+            (&*target).SetTransform(&Transform::identity().into());
+        }
+
+        renderer.drawing.set(true);
+
+        Ok(CanvasDraw {
+            canvas: self,
+            target,
+            ended: false,
+        })
+    }
+
+    /// Return the Direct2D and DirectWrite factories of the canvas
+    pub fn factories(&self) -> &D2DFactories {
+        &self.renderer().factories
+    }
+
+    /**
+        Return the render target of the canvas. Rebuilds it if it was lost.
+        The target is an `ID2D1HwndRenderTarget` unless the canvas was created with `Canvas::software`.
+    */
+    pub fn render_target(&self) -> Result<*mut ID2D1RenderTarget, CanvasError> {
+        let renderer = self.renderer();
+        let target = renderer.target.get();
+        if !target.is_null() {
+            return Ok(target);
+        }
+
+        let target = match renderer.software.as_ref() {
+            Some(bitmap) => renderer.factories.create_software_render_target(bitmap),
+            None => {
+                let hwnd = self.canvas.handle.hwnd().expect(NOT_BOUND);
+                renderer.size.set(unsafe { client_size(hwnd) });
+                unsafe { renderer.factories.create_hwnd_render_target(hwnd) }
+                    .map(|t| t as *mut ID2D1RenderTarget)
+            }
+        }
+        .map_err(|e| CanvasError::RendererInit(e.into()))?;
+
+        renderer.target.set(target);
+
+        Ok(target)
+    }
+
+    /// Return the bitmap of a software canvas. Returns `None` if the canvas draws into a window.
+    pub fn software_bitmap(&self) -> Option<&SoftwareBitmap> {
+        self.renderer().software.as_ref()
+    }
+
+    /**
+        Return the pixels of a software canvas as RGBA with straight alpha, row by row.
+
+        Panics if the canvas was not created with `Canvas::software`.
+    */
+    pub fn to_rgba(&self) -> Result<Vec<u8>, CanvasError> {
+        self.expect_software()
+            .to_rgba()
+            .map_err(|e| CanvasError::resource(E_FAIL, e))
+    }
+
+    /**
+        Save the pixels of a software canvas to a PNG file.
+
+        Panics if the canvas was not created with `Canvas::software`.
+    */
+    pub fn save_png(&self, path: &str) -> Result<(), CanvasError> {
+        self.expect_software()
+            .save_png(path)
+            .map_err(|e| CanvasError::resource(E_FAIL, e))
+    }
+
+    /**
+        Compare the pixels of a software canvas against the reference PNG at `path`. Two pixels are equal if none of
+        their channels differ by more than `tolerance`. See `native_windows_gui::d2d::compare_snapshot` for how
+        the reference files are created.
+
+        Panics if the canvas was not created with `Canvas::software`.
+    */
+    pub fn compare_snapshot(
+        &self,
+        path: &str,
+        tolerance: u8,
+    ) -> Result<SnapshotResult, CanvasError> {
+        let bitmap = self.expect_software();
+        let pixels = self.to_rgba()?;
+        compare_snapshot(path, bitmap.size(), &pixels, tolerance)
+            .map_err(|e| CanvasError::resource(E_FAIL, e))
+    }
+
+    fn expect_software(&self) -> &SoftwareBitmap {
+        self.software_bitmap()
+            .expect("The canvas was not created with Canvas::software")
+    }
+
+    fn renderer(&self) -> &Renderer {
+        self.renderer.as_ref().expect(NOT_BOUND)
+    }
+}
+
+/**
+    The drawing commands of a canvas. Returned by `Canvas::begin_draw`.
+
+    The commands are presented when `end` is called or when the object is dropped.
+    Call `end` to know if drawing failed.
+*/
+pub struct CanvasDraw<'a> {
+    canvas: &'a Canvas,
+    target: *mut ID2D1RenderTarget,
+    ended: bool,
+}
+
+impl<'a> CanvasDraw<'a> {
+    /// Fill the whole canvas with `color`. Ignores the transformation and the clip.
+    pub fn clear(&self, color: Color) {
+        unsafe { self.target().Clear(&color.into()) }
+    }
+
+    /// Return the transformation applied to the drawing commands
+    pub fn transform(&self) -> Transform {
+        let mut m = Transform::identity().into();
+        unsafe { self.target().GetTransform(&mut m) };
+        m.into()
+    }
+
+    pub fn set_transform(&self, t: &Transform) {
+        let m = (*t).into();
+        unsafe { self.target().SetTransform(&m) }
+    }
+
+    pub fn draw_line<P: Into<Point>>(
+        &self,
+        p0: P,
+        p1: P,
+        brush: &Brush,
+        width: f32,
+        style: Option<&StrokeStyle>,
+    ) {
+        unsafe {
+            self.target().DrawLine(
+                p0.into().into(),
+                p1.into().into(),
+                brush.brush,
+                width,
+                stroke_style(style),
+            )
+        }
+    }
+
+    pub fn draw_rect(&self, rect: Rect, brush: &Brush, width: f32, style: Option<&StrokeStyle>) {
+        unsafe {
+            self.target()
+                .DrawRectangle(&rect.into(), brush.brush, width, stroke_style(style))
+        }
+    }
+
+    pub fn fill_rect(&self, rect: Rect, brush: &Brush) {
+        unsafe { self.target().FillRectangle(&rect.into(), brush.brush) }
+    }
+
+    pub fn draw_rounded_rect(
+        &self,
+        rect: Rect,
+        radius_x: f32,
+        radius_y: f32,
+        brush: &Brush,
+        width: f32,
+        style: Option<&StrokeStyle>,
+    ) {
+        let rect = rounded_rect(rect, radius_x, radius_y);
+        unsafe {
+            self.target()
+                .DrawRoundedRectangle(&rect, brush.brush, width, stroke_style(style))
+        }
+    }
+
+    pub fn fill_rounded_rect(&self, rect: Rect, radius_x: f32, radius_y: f32, brush: &Brush) {
+        let rect = rounded_rect(rect, radius_x, radius_y);
+        unsafe { self.target().FillRoundedRectangle(&rect, brush.brush) }
+    }
+
+    pub fn draw_ellipse<P: Into<Point>>(
+        &self,
+        center: P,
+        radius_x: f32,
+        radius_y: f32,
+        brush: &Brush,
+        width: f32,
+        style: Option<&StrokeStyle>,
+    ) {
+        let ellipse = ellipse(center.into(), radius_x, radius_y);
+        unsafe {
+            self.target()
+                .DrawEllipse(&ellipse, brush.brush, width, stroke_style(style))
+        }
+    }
+
+    pub fn fill_ellipse<P: Into<Point>>(
+        &self,
+        center: P,
+        radius_x: f32,
+        radius_y: f32,
+        brush: &Brush,
+    ) {
+        let ellipse = ellipse(center.into(), radius_x, radius_y);
+        unsafe { self.target().FillEllipse(&ellipse, brush.brush) }
+    }
+
+    pub fn draw_geometry(
+        &self,
+        geometry: &Geometry,
+        brush: &Brush,
+        width: f32,
+        style: Option<&StrokeStyle>,
+    ) {
+        unsafe {
+            self.target()
+                .DrawGeometry(geometry.geometry, brush.brush, width, stroke_style(style))
+        }
+    }
+
+    pub fn fill_geometry(&self, geometry: &Geometry, brush: &Brush) {
+        unsafe {
+            self.target()
+                .FillGeometry(geometry.geometry, brush.brush, ptr::null_mut())
+        }
+    }
+
+    /// Lay out and draw `text` in `rect`. Use a `TextLayout` to draw the same text many times.
+    pub fn draw_text(&self, text: &str, format: &TextFormat, rect: Rect, brush: &Brush) {
+        let text: Vec<u16> = text.encode_utf16().collect();
+        unsafe {
+            self.target().DrawText(
+                text.as_ptr(),
+                text.len() as u32,
+                format.format,
+                &rect.into(),
+                brush.brush,
+                D2D1_DRAW_TEXT_OPTIONS_NONE,
+                DWRITE_MEASURING_MODE_NATURAL,
+            )
+        }
+    }
+
+    /// Draw a text layout with its top left corner at `origin`
+    pub fn draw_text_layout<P: Into<Point>>(&self, origin: P, layout: &TextLayout, brush: &Brush) {
+        unsafe {
+            self.target().DrawTextLayout(
+                origin.into().into(),
+                layout.layout,
+                brush.brush,
+                D2D1_DRAW_TEXT_OPTIONS_NONE,
+            )
+        }
+    }
+
+    /// Draw the `source` area of `bitmap` (or the whole bitmap) scaled into `dest`
+    pub fn draw_bitmap(
+        &self,
+        bitmap: &Bitmap,
+        dest: Rect,
+        opacity: f32,
+        interpolation: Interpolation,
+        source: Option<Rect>,
+    ) {
+        let source = source.map(D2D1_RECT_F::from);
+        let source_ptr = source
+            .as_ref()
+            .map(|s| s as *const _)
+            .unwrap_or(ptr::null());
+        unsafe {
+            self.target().DrawBitmap(
+                bitmap.bitmap,
+                &dest.into(),
+                opacity,
+                interpolation.d2d(),
+                source_ptr,
+            )
+        }
+    }
+
+    /// Clip the next commands to `rect` until `pop_clip` is called. The rect is transformed by the current transformation.
+    pub fn push_clip(&self, rect: Rect, antialias: bool) {
+        unsafe {
+            self.target()
+                .PushAxisAlignedClip(&rect.into(), antialias_mode(antialias))
+        }
+    }
+
+    pub fn pop_clip(&self) {
+        unsafe { self.target().PopAxisAlignedClip() }
+    }
+
+    /// Redirect the next commands to `layer` until `pop_layer` is called
+    pub fn push_layer(&self, layer: &Layer, params: &LayerParameters) {
+        let params = params.d2d();
+        unsafe { self.target().PushLayer(&params, layer.layer) }
+    }
+
+    /// Composite the last pushed layer on the canvas
+    pub fn pop_layer(&self) {
+        unsafe { self.target().PopLayer() }
+    }
+
+    /// Present the commands. If the render target was lost, returns `CanvasError::TargetLost`.
+    pub fn end(mut self) -> Result<(), CanvasError> {
+        self.finish()
+    }
+
+    fn target(&self) -> &ID2D1RenderTarget {
+        unsafe { &*self.target }
+    }
+
+    fn finish(&mut self) -> Result<(), CanvasError> {
+        use winapi::um::winuser::ValidateRect;
+
+        if self.ended {
+            return Ok(());
+        }
+
+        self.ended = true;
+
+        let renderer = self.canvas.renderer();
+        renderer.drawing.set(false);
+
+        let hr = unsafe { self.target().EndDraw(ptr::null_mut(), ptr::null_mut()) };
+
+        // The whole canvas was painted, the pending WM_PAINT can be dropped
+        if let Some(hwnd) = self.canvas.canvas.handle.hwnd() {
+            unsafe { ValidateRect(hwnd, ptr::null()) };
+        }
+
+        match hr {
+            S_OK => Ok(()),
+            D2DERR_RECREATE_TARGET => {
+                unsafe { release(renderer.target.replace(ptr::null_mut())) };
+                Err(CanvasError::TargetLost)
+            }
+            e => Err(CanvasError::Draw(e)),
+        }
+    }
+}
+
+impl<'a> Drop for CanvasDraw<'a> {
+    fn drop(&mut self) {
+        self.finish().ok();
+    }
+}
+
+fn stroke_style(style: Option<&StrokeStyle>) -> *mut ID2D1StrokeStyle {
+    style.map(|s| s.style).unwrap_or(ptr::null_mut())
+}
+
+pub struct CanvasBuilder<'a> {
+    title: &'a str,
+    size: (i32, i32),
+    position: (i32, i32),
+    flags: Option<ExternCanvasFlags>,
+    ex_flags: u32,
+    parent: Option<ControlHandle>,
+}
+
+impl<'a> CanvasBuilder<'a> {
+    pub fn flags(mut self, flags: ExternCanvasFlags) -> CanvasBuilder<'a> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn ex_flags(mut self, flags: u32) -> CanvasBuilder<'a> {
+        self.ex_flags = flags;
+        self
+    }
+
+    pub fn title(mut self, text: &'a str) -> CanvasBuilder<'a> {
+        self.title = text;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> CanvasBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> CanvasBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> CanvasBuilder<'a> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut Canvas) -> Result<(), NwgError> {
+        *out = Default::default();
+
+        let mut builder = ExternCanvas::builder()
+            .title(self.title)
+            .size(self.size)
+            .position(self.position)
+            .ex_flags(self.ex_flags)
+            .parent(self.parent);
+
+        if let Some(flags) = self.flags {
+            builder = builder.flags(flags);
+        }
+
+        builder.build(&mut out.canvas)?;
+
+        let hwnd = out.canvas.handle.hwnd().unwrap();
+        let renderer = D2DFactories::new().and_then(|factories| {
+            let target = unsafe { factories.create_hwnd_render_target(hwnd)? };
+            Ok(Renderer {
+                factories,
+                target: Cell::new(target as *mut ID2D1RenderTarget),
+                software: None,
+                size: Cell::new(unsafe { client_size(hwnd) }),
+                drawing: Cell::new(false),
+            })
+        });
+
+        match renderer {
+            Ok(r) => {
+                out.renderer = Some(r);
+                Ok(())
+            }
+            Err(e) => {
+                *out = Default::default();
+                Err(NwgError::control_create(e))
+            }
+        }
+    }
+}
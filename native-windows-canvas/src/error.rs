@@ -0,0 +1,57 @@
+/**
+    Errors that can be returned when using the canvas
+*/
+#[derive(Debug, Clone)]
+pub enum CanvasError {
+    /// The Direct2D factories or the render target could not be created
+    RendererInit(String),
+
+    /// A drawing resource could not be created. Holds the HRESULT of the failed call.
+    Resource(i32, String),
+
+    /// The render target was lost (ex: the display adapter changed). The target is recreated on the next
+    /// `begin_draw`, but the brushes, bitmaps and layers created before must be created again.
+    TargetLost,
+
+    /// Drawing failed. Holds the HRESULT returned by `EndDraw`.
+    Draw(i32),
+}
+
+impl CanvasError {
+    pub(crate) fn resource<S: Into<String>>(code: i32, e: S) -> CanvasError {
+        CanvasError::Resource(code, e.into())
+    }
+}
+
+impl std::fmt::Display for CanvasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use CanvasError::*;
+        match self {
+            RendererInit(reason) => write!(f, "Canvas renderer creation failed: {}", reason),
+            Resource(code, reason) => write!(f, "{} (HRESULT 0x{:08X})", reason, code),
+            TargetLost => write!(f, "The canvas render target was lost"),
+            Draw(code) => write!(f, "Canvas drawing failed (HRESULT 0x{:08X})", code),
+        }
+    }
+}
+
+impl std::error::Error for CanvasError {}
+
+/// Map the result of a resource creation call to a `CanvasError`
+pub(crate) fn check(hr: i32, msg: &'static str) -> Result<(), CanvasError> {
+    match hr {
+        winapi::shared::winerror::S_OK => Ok(()),
+        e => Err(CanvasError::resource(e, msg)),
+    }
+}
+
+impl From<native_windows_gui::NwgError> for CanvasError {
+    fn from(e: native_windows_gui::NwgError) -> Self {
+        use native_windows_gui::NwgError;
+        match e {
+            NwgError::ImageDecoderError(code, reason)
+            | NwgError::ImageEncoderError(code, reason) => CanvasError::Resource(code, reason),
+            e => CanvasError::Resource(winapi::shared::winerror::E_FAIL, e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use crate::error::check;
+use crate::types::{ellipse, rounded_rect};
+use crate::{release, Canvas, CanvasError, Point, Rect, StrokeStyle, Transform};
+use std::{mem, ptr};
+use winapi::shared::minwindef::FALSE;
+use winapi::um::d2d1::*;
+
+/// How the inside of a geometry is computed when its figures overlap
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillMode {
+    /// A point is inside if a ray from the point crosses an odd number of segments
+    Alternate,
+
+    /// A point is inside if the winding number of the segments around the point is not zero
+    Winding,
+}
+
+/// The angle between the start and the end of an arc
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArcSize {
+    Small,
+    Large,
+}
+
+/// The direction in which an arc is drawn
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SweepDirection {
+    Clockwise,
+    CounterClockwise,
+}
+
+/**
+    A shape that can be filled, stroked or used as a layer mask.
+    Geometries do not depend on the render target and can be shared between canvas.
+*/
+pub struct Geometry {
+    pub geometry: *mut ID2D1Geometry,
+}
+
+impl Geometry {
+    /// Return the bounds of the geometry after applying `transform`
+    pub fn bounds(&self, transform: Option<&Transform>) -> Rect {
+        let transform = transform.map(|&t| D2D1_MATRIX_3X2_F::from(t));
+        let transform_ptr = transform
+            .as_ref()
+            .map(|t| t as *const _)
+            .unwrap_or(ptr::null());
+
+        let mut bounds: D2D1_RECT_F = unsafe { mem::zeroed() };
+        unsafe {
+            (&*self.geometry).GetBounds(transform_ptr, &mut bounds);
+        }
+
+        bounds.into()
+    }
+
+    /// Check if `point` is inside the filled area of the geometry after applying `transform`
+    pub fn contains<P: Into<Point>>(&self, point: P, transform: Option<&Transform>) -> bool {
+        let transform = transform.map(|&t| D2D1_MATRIX_3X2_F::from(t));
+        let transform_ptr = transform
+            .as_ref()
+            .map(|t| t as *const _)
+            .unwrap_or(ptr::null());
+
+        let mut contains = FALSE;
+        unsafe {
+            (&*self.geometry).FillContainsPoint(
+                point.into().into(),
+                transform_ptr,
+                D2D1_DEFAULT_FLATTENING_TOLERANCE,
+                &mut contains,
+            );
+        }
+
+        contains != FALSE
+    }
+
+    /// Check if `point` is on the stroke of the geometry after applying `transform`
+    pub fn stroke_contains<P: Into<Point>>(
+        &self,
+        point: P,
+        width: f32,
+        style: Option<&StrokeStyle>,
+        transform: Option<&Transform>,
+    ) -> bool {
+        let transform = transform.map(|&t| D2D1_MATRIX_3X2_F::from(t));
+        let transform_ptr = transform
+            .as_ref()
+            .map(|t| t as *const _)
+            .unwrap_or(ptr::null());
+        let style = style.map(|s| s.style).unwrap_or(ptr::null_mut());
+
+        let mut contains = FALSE;
+        unsafe {
+            (&*self.geometry).StrokeContainsPoint(
+                point.into().into(),
+                width,
+                style,
+                transform_ptr,
+                D2D1_DEFAULT_FLATTENING_TOLERANCE,
+                &mut contains,
+            );
+        }
+
+        contains != FALSE
+    }
+}
+
+impl Drop for Geometry {
+    fn drop(&mut self) {
+        unsafe { release(self.geometry) }
+    }
+}
+
+impl Canvas {
+    pub fn rectangle_geometry(&self, rect: Rect) -> Result<Geometry, CanvasError> {
+        let mut geometry = ptr::null_mut();
+        let hr = unsafe {
+            (&*self.factories().d2d).CreateRectangleGeometry(&rect.into(), &mut geometry)
+        };
+        check(hr, "Failed to create a rectangle geometry")?;
+
+        Ok(Geometry {
+            geometry: geometry as *mut ID2D1Geometry,
+        })
+    }
+
+    pub fn rounded_rectangle_geometry(
+        &self,
+        rect: Rect,
+        radius_x: f32,
+        radius_y: f32,
+    ) -> Result<Geometry, CanvasError> {
+        let rect = rounded_rect(rect, radius_x, radius_y);
+        let mut geometry = ptr::null_mut();
+        let hr = unsafe {
+            (&*self.factories().d2d).CreateRoundedRectangleGeometry(&rect, &mut geometry)
+        };
+        check(hr, "Failed to create a rounded rectangle geometry")?;
+
+        Ok(Geometry {
+            geometry: geometry as *mut ID2D1Geometry,
+        })
+    }
+
+    pub fn ellipse_geometry<P: Into<Point>>(
+        &self,
+        center: P,
+        radius_x: f32,
+        radius_y: f32,
+    ) -> Result<Geometry, CanvasError> {
+        let ellipse = ellipse(center.into(), radius_x, radius_y);
+        let mut geometry = ptr::null_mut();
+        let hr = unsafe { (&*self.factories().d2d).CreateEllipseGeometry(&ellipse, &mut geometry) };
+        check(hr, "Failed to create an ellipse geometry")?;
+
+        Ok(Geometry {
+            geometry: geometry as *mut ID2D1Geometry,
+        })
+    }
+
+    /**
+        Start building a geometry made of lines, curves and arcs.
+
+        ```rust
+        use native_windows_canvas as nwc;
+
+        fn triangle(canvas: &nwc::Canvas) -> Result<nwc::Geometry, nwc::CanvasError> {
+            canvas.path()?
+                .begin_figure((50.0, 0.0), true)
+                .line_to((100.0, 100.0))
+                .line_to((0.0, 100.0))
+                .end_figure(true)
+                .build()
+        }
+        ```
+    */
+    pub fn path(&self) -> Result<PathBuilder, CanvasError> {
+        let mut path = ptr::null_mut();
+        let hr = unsafe { (&*self.factories().d2d).CreatePathGeometry(&mut path) };
+        check(hr, "Failed to create a path geometry")?;
+
+        let mut sink = ptr::null_mut();
+        let hr = unsafe { (&*path).Open(&mut sink) };
+        if let Err(e) = check(hr, "Failed to open a path geometry") {
+            unsafe { release(path) };
+            return Err(e);
+        }
+
+        Ok(PathBuilder {
+            path,
+            sink,
+            figure_open: false,
+        })
+    }
+}
+
+/**
+    Builds a path geometry figure by figure.
+
+    Every figure starts with `begin_figure` and ends with `end_figure`. A figure left open is ended when
+    a new one begins or when the path is built. Segments added outside of a figure make `build` fail.
+*/
+pub struct PathBuilder {
+    path: *mut ID2D1PathGeometry,
+    sink: *mut ID2D1GeometrySink,
+    figure_open: bool,
+}
+
+impl PathBuilder {
+    /// Set how the inside of the path is computed. Must be called before the first figure.
+    pub fn fill_mode(self, mode: FillMode) -> PathBuilder {
+        let mode = match mode {
+            FillMode::Alternate => D2D1_FILL_MODE_ALTERNATE,
+            FillMode::Winding => D2D1_FILL_MODE_WINDING,
+        };
+
+        unsafe { (&*self.sink).SetFillMode(mode) };
+        self
+    }
+
+    /// Start a new figure at `start`. If `filled` is false, the figure is ignored when the path is filled.
+    pub fn begin_figure<P: Into<Point>>(mut self, start: P, filled: bool) -> PathBuilder {
+        if self.figure_open {
+            self = self.end_figure(false);
+        }
+
+        let begin = match filled {
+            true => D2D1_FIGURE_BEGIN_FILLED,
+            false => D2D1_FIGURE_BEGIN_HOLLOW,
+        };
+
+        unsafe { (&*self.sink).BeginFigure(start.into().into(), begin) };
+        self.figure_open = true;
+        self
+    }
+
+    pub fn line_to<P: Into<Point>>(self, point: P) -> PathBuilder {
+        unsafe { (&*self.sink).AddLine(point.into().into()) };
+        self
+    }
+
+    /// A cubic bezier curve from the current point to `end`
+    pub fn bezier_to<P: Into<Point>>(self, control1: P, control2: P, end: P) -> PathBuilder {
+        let segment = D2D1_BEZIER_SEGMENT {
+            point1: control1.into().into(),
+            point2: control2.into().into(),
+            point3: end.into().into(),
+        };
+
+        unsafe { (&*self.sink).AddBezier(&segment) };
+        self
+    }
+
+    /// A quadratic bezier curve from the current point to `end`
+    pub fn quadratic_bezier_to<P: Into<Point>>(self, control: P, end: P) -> PathBuilder {
+        let segment = D2D1_QUADRATIC_BEZIER_SEGMENT {
+            point1: control.into().into(),
+            point2: end.into().into(),
+        };
+
+        unsafe { (&*self.sink).AddQuadraticBezier(&segment) };
+        self
+    }
+
+    /// An elliptical arc from the current point to `end`. `rotation` is the rotation of the ellipse in degrees.
+    pub fn arc_to<P: Into<Point>>(
+        self,
+        end: P,
+        radius: (f32, f32),
+        rotation: f32,
+        sweep: SweepDirection,
+        size: ArcSize,
+    ) -> PathBuilder {
+        let segment = D2D1_ARC_SEGMENT {
+            point: end.into().into(),
+            size: D2D1_SIZE_F {
+                width: radius.0,
+                height: radius.1,
+            },
+            rotationAngle: rotation,
+            sweepDirection: match sweep {
+                SweepDirection::Clockwise => D2D1_SWEEP_DIRECTION_CLOCKWISE,
+                SweepDirection::CounterClockwise => D2D1_SWEEP_DIRECTION_COUNTER_CLOCKWISE,
+            },
+            arcSize: match size {
+                ArcSize::Small => D2D1_ARC_SIZE_SMALL,
+                ArcSize::Large => D2D1_ARC_SIZE_LARGE,
+            },
+        };
+
+        unsafe { (&*self.sink).AddArc(&segment) };
+        self
+    }
+
+    /// End the current figure. If `closed` is true, a line is added from the last point to the start of the figure.
+    pub fn end_figure(mut self, closed: bool) -> PathBuilder {
+        let end = match closed {
+            true => D2D1_FIGURE_END_CLOSED,
+            false => D2D1_FIGURE_END_OPEN,
+        };
+
+        unsafe { (&*self.sink).EndFigure(end) };
+        self.figure_open = false;
+        self
+    }
+
+    pub fn build(mut self) -> Result<Geometry, CanvasError> {
+        if self.figure_open {
+            self = self.end_figure(false);
+        }
+
+        let hr = unsafe { (&*self.sink).Close() };
+        check(hr, "Failed to build the path geometry")?;
+
+        let path = mem::replace(&mut self.path, ptr::null_mut());
+        Ok(Geometry {
+            geometry: path as *mut ID2D1Geometry,
+        })
+    }
+}
+
+impl Drop for PathBuilder {
+    fn drop(&mut self) {
+        unsafe {
+            release(self.sink);
+            release(self.path);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::error::check;
+use crate::{release, Brush, Canvas, CanvasError, Geometry, Rect, Transform};
+use std::ptr;
+use winapi::um::d2d1::*;
+
+/**
+    An offscreen surface used to composite a group of drawing commands with an opacity or a mask.
+    Layers are device resources: they can only be used with the canvas that created them.
+    A layer can be reused every frame, but can only be pushed once at a time.
+*/
+pub struct Layer {
+    pub layer: *mut ID2D1Layer,
+}
+
+impl Drop for Layer {
+    fn drop(&mut self) {
+        unsafe { release(self.layer) }
+    }
+}
+
+/// The content bounds, mask and opacity of a pushed layer
+#[derive(Copy, Clone)]
+pub struct LayerParameters<'a> {
+    /// Drawing outside of the bounds is clipped
+    pub content_bounds: Rect,
+
+    /// Drawing outside of the geometry is clipped
+    pub mask: Option<&'a Geometry>,
+
+    /// Transformation applied to `mask`
+    pub mask_transform: Transform,
+
+    /// Antialias the edges of `mask`
+    pub antialias: bool,
+
+    pub opacity: f32,
+
+    /// The alpha of the brush multiplies the opacity of the layer content
+    pub opacity_brush: Option<&'a Brush>,
+}
+
+impl<'a> Default for LayerParameters<'a> {
+    fn default() -> LayerParameters<'a> {
+        LayerParameters {
+            content_bounds: Rect::infinite(),
+            mask: None,
+            mask_transform: Transform::identity(),
+            antialias: true,
+            opacity: 1.0,
+            opacity_brush: None,
+        }
+    }
+}
+
+impl<'a> LayerParameters<'a> {
+    pub(crate) fn d2d(&self) -> D2D1_LAYER_PARAMETERS {
+        D2D1_LAYER_PARAMETERS {
+            contentBounds: self.content_bounds.into(),
+            geometricMask: self.mask.map(|m| m.geometry).unwrap_or(ptr::null_mut()),
+            maskAntialiasMode: antialias_mode(self.antialias),
+            maskTransform: self.mask_transform.into(),
+            opacity: self.opacity,
+            opacityBrush: self
+                .opacity_brush
+                .map(|b| b.brush)
+                .unwrap_or(ptr::null_mut()),
+            layerOptions: D2D1_LAYER_OPTIONS_NONE,
+        }
+    }
+}
+
+pub(crate) fn antialias_mode(antialias: bool) -> D2D1_ANTIALIAS_MODE {
+    match antialias {
+        true => D2D1_ANTIALIAS_MODE_PER_PRIMITIVE,
+        false => D2D1_ANTIALIAS_MODE_ALIASED,
+    }
+}
+
+impl Canvas {
+    pub fn layer(&self) -> Result<Layer, CanvasError> {
+        let target = self.render_target()?;
+        let mut layer = ptr::null_mut();
+        let hr = unsafe { (&*target).CreateLayer(ptr::null(), &mut layer) };
+        check(hr, "Failed to create a layer")?;
+
+        Ok(Layer { layer })
+    }
+}
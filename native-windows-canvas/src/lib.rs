@@ -1,7 +1,87 @@
+/*!
+    A low level Direct2D and DirectWrite wrapper for native-windows-gui.
+
+    The `Canvas` control wraps an `ExternCanvas` and a Direct2D render target. Shapes, geometries, text and bitmaps
+    are drawn between `Canvas::begin_draw` and the end of the returned `CanvasDraw`.
+
+    Resources are created from the canvas:
+      * Device independent resources (`Geometry`, `StrokeStyle`, `TextFormat`, `TextLayout`) can be kept forever.
+      * Device resources (`Brush`, `Bitmap`, `Layer`) belong to the render target. If drawing returns `CanvasError::TargetLost`,
+        they must be created again.
+
+    The Direct2D factories are created with `native_windows_gui::d2d`, the same setup used by the plotters control.
+
+    `Canvas::software` creates a canvas that draws into a bitmap in memory with the Direct2D software rasterizer.
+    Its output can be compared against reference PNG files with `Canvas::compare_snapshot`.
+
+    ```rust
+    use native_windows_canvas as nwc;
+
+    fn paint(canvas: &nwc::Canvas) -> Result<(), nwc::CanvasError> {
+        let red = canvas.solid_brush(nwc::Color::rgb(200, 30, 30))?;
+        let black = canvas.solid_brush(nwc::Color::BLACK)?;
+        let format = canvas.text_format().family("Segoe UI").size(18.0).build()?;
+        let star = canvas.path()?
+            .begin_figure((50.0, 0.0), true)
+            .line_to((80.0, 90.0))
+            .line_to((5.0, 35.0))
+            .line_to((95.0, 35.0))
+            .line_to((20.0, 90.0))
+            .end_figure(true)
+            .build()?;
+
+        let draw = canvas.begin_draw()?;
+        draw.clear(nwc::Color::WHITE);
+        draw.set_transform(&nwc::Transform::translation(10.0, 10.0));
+        draw.fill_geometry(&star, &red);
+        draw.draw_geometry(&star, &black, 2.0, None);
+        draw.set_transform(&nwc::Transform::identity());
+        draw.draw_text("Hello", &format, nwc::Rect::new(120.0, 10.0, 200.0, 30.0), &black);
+        draw.end()
+    }
+    ```
+*/
+extern crate native_windows_gui;
+extern crate winapi;
+
+mod error;
+pub use error::CanvasError;
+
+mod types;
+pub use types::{Color, Point, Rect, Transform};
+
+mod canvas;
+pub use canvas::{Canvas, CanvasBuilder, CanvasDraw};
+
+mod brush;
+pub use brush::{Brush, ExtendMode, GradientStop};
+
+mod stroke;
+pub use stroke::{CapStyle, DashStyle, LineJoin, StrokeStyle, StrokeStyleBuilder};
+
+mod geometry;
+pub use geometry::{ArcSize, FillMode, Geometry, PathBuilder, SweepDirection};
+
+mod text;
+pub use text::{
+    FontStyle, ParagraphAlign, TextAlign, TextFormat, TextFormatBuilder, TextHit, TextLayout,
+    TextMetrics,
+};
+
+mod bitmap;
+pub use bitmap::{Bitmap, Interpolation};
+
+mod layer;
+pub use layer::{Layer, LayerParameters};
+
 #[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
+mod tests;
+
+/// Release a COM object if `ptr` is not null
+pub(crate) unsafe fn release<T>(ptr: *mut T) {
+    use winapi::um::unknwnbase::IUnknown;
+
+    if !ptr.is_null() {
+        (&*(ptr as *mut IUnknown)).Release();
     }
 }
@@ -0,0 +1,201 @@
+use crate::error::check;
+use crate::{release, Canvas, CanvasError};
+use std::ptr;
+use winapi::um::d2d1::*;
+
+/// The shape at the end of a line or of a dash
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CapStyle {
+    Flat,
+    Square,
+    Round,
+    Triangle,
+}
+
+impl CapStyle {
+    fn d2d(self) -> D2D1_CAP_STYLE {
+        match self {
+            CapStyle::Flat => D2D1_CAP_STYLE_FLAT,
+            CapStyle::Square => D2D1_CAP_STYLE_SQUARE,
+            CapStyle::Round => D2D1_CAP_STYLE_ROUND,
+            CapStyle::Triangle => D2D1_CAP_STYLE_TRIANGLE,
+        }
+    }
+}
+
+/// The shape of the joint between two segments
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LineJoin {
+    Miter,
+    Bevel,
+    Round,
+
+    /// A miter, unless the miter limit is exceeded. Then a bevel.
+    MiterOrBevel,
+}
+
+impl LineJoin {
+    fn d2d(self) -> D2D1_LINE_JOIN {
+        match self {
+            LineJoin::Miter => D2D1_LINE_JOIN_MITER,
+            LineJoin::Bevel => D2D1_LINE_JOIN_BEVEL,
+            LineJoin::Round => D2D1_LINE_JOIN_ROUND,
+            LineJoin::MiterOrBevel => D2D1_LINE_JOIN_MITER_OR_BEVEL,
+        }
+    }
+}
+
+/// A predefined dash pattern
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DashStyle {
+    Solid,
+    Dash,
+    Dot,
+    DashDot,
+    DashDotDot,
+}
+
+impl DashStyle {
+    fn d2d(self) -> D2D1_DASH_STYLE {
+        match self {
+            DashStyle::Solid => D2D1_DASH_STYLE_SOLID,
+            DashStyle::Dash => D2D1_DASH_STYLE_DASH,
+            DashStyle::Dot => D2D1_DASH_STYLE_DOT,
+            DashStyle::DashDot => D2D1_DASH_STYLE_DASH_DOT,
+            DashStyle::DashDotDot => D2D1_DASH_STYLE_DASH_DOT_DOT,
+        }
+    }
+}
+
+/**
+    Describes the caps, joints and dashes of a stroke.
+    Stroke styles do not depend on the render target and can be shared between canvas.
+
+    ```rust
+    use native_windows_canvas as nwc;
+
+    fn dotted(canvas: &nwc::Canvas) -> Result<nwc::StrokeStyle, nwc::CanvasError> {
+        canvas.stroke_style()
+            .dash_cap(nwc::CapStyle::Round)
+            .dash_style(nwc::DashStyle::Dot)
+            .build()
+    }
+    ```
+*/
+pub struct StrokeStyle {
+    pub style: *mut ID2D1StrokeStyle,
+}
+
+impl Drop for StrokeStyle {
+    fn drop(&mut self) {
+        unsafe { release(self.style) }
+    }
+}
+
+impl Canvas {
+    pub fn stroke_style(&self) -> StrokeStyleBuilder<'_> {
+        StrokeStyleBuilder {
+            canvas: self,
+            start_cap: CapStyle::Flat,
+            end_cap: CapStyle::Flat,
+            dash_cap: CapStyle::Flat,
+            line_join: LineJoin::Miter,
+            miter_limit: 10.0,
+            dash_style: DashStyle::Solid,
+            dashes: None,
+            dash_offset: 0.0,
+        }
+    }
+}
+
+pub struct StrokeStyleBuilder<'a> {
+    canvas: &'a Canvas,
+    start_cap: CapStyle,
+    end_cap: CapStyle,
+    dash_cap: CapStyle,
+    line_join: LineJoin,
+    miter_limit: f32,
+    dash_style: DashStyle,
+    dashes: Option<&'a [f32]>,
+    dash_offset: f32,
+}
+
+impl<'a> StrokeStyleBuilder<'a> {
+    pub fn start_cap(mut self, cap: CapStyle) -> StrokeStyleBuilder<'a> {
+        self.start_cap = cap;
+        self
+    }
+
+    pub fn end_cap(mut self, cap: CapStyle) -> StrokeStyleBuilder<'a> {
+        self.end_cap = cap;
+        self
+    }
+
+    pub fn dash_cap(mut self, cap: CapStyle) -> StrokeStyleBuilder<'a> {
+        self.dash_cap = cap;
+        self
+    }
+
+    pub fn line_join(mut self, join: LineJoin) -> StrokeStyleBuilder<'a> {
+        self.line_join = join;
+        self
+    }
+
+    pub fn miter_limit(mut self, limit: f32) -> StrokeStyleBuilder<'a> {
+        self.miter_limit = limit;
+        self
+    }
+
+    pub fn dash_style(mut self, style: DashStyle) -> StrokeStyleBuilder<'a> {
+        self.dash_style = style;
+        self
+    }
+
+    /// A custom dash pattern. The lengths alternate between dashes and gaps and are multiplied by the stroke width.
+    /// Overrides `dash_style`.
+    pub fn dashes(mut self, dashes: &'a [f32]) -> StrokeStyleBuilder<'a> {
+        self.dashes = Some(dashes);
+        self
+    }
+
+    pub fn dash_offset(mut self, offset: f32) -> StrokeStyleBuilder<'a> {
+        self.dash_offset = offset;
+        self
+    }
+
+    pub fn build(self) -> Result<StrokeStyle, CanvasError> {
+        let dash_style = match self.dashes {
+            Some(_) => D2D1_DASH_STYLE_CUSTOM,
+            None => self.dash_style.d2d(),
+        };
+
+        let props = D2D1_STROKE_STYLE_PROPERTIES {
+            startCap: self.start_cap.d2d(),
+            endCap: self.end_cap.d2d(),
+            dashCap: self.dash_cap.d2d(),
+            lineJoin: self.line_join.d2d(),
+            miterLimit: self.miter_limit,
+            dashStyle: dash_style,
+            dashOffset: self.dash_offset,
+        };
+
+        let dashes = self.dashes.unwrap_or(&[]);
+        let dashes_ptr = match dashes.is_empty() {
+            true => ptr::null(),
+            false => dashes.as_ptr(),
+        };
+
+        let mut style = ptr::null_mut();
+        let hr = unsafe {
+            (&*self.canvas.factories().d2d).CreateStrokeStyle(
+                &props,
+                dashes_ptr,
+                dashes.len() as u32,
+                &mut style,
+            )
+        };
+        check(hr, "Failed to create a stroke style")?;
+
+        Ok(StrokeStyle { style })
+    }
+}
@@ -0,0 +1,118 @@
+/*!
+    Snapshot tests. The canvas renders into a bitmap in memory and the result is compared
+    against the reference images in `test_rc/snapshots`.
+
+    The reference images are rendered by Direct2D on Windows. A missing reference image fails the test.
+    Run the tests with `NWG_UPDATE_SNAPSHOTS=1` to create or update the reference images after an intended change.
+*/
+use crate::*;
+
+fn software_canvas(width: u32, height: u32) -> Canvas {
+    native_windows_gui::init().expect("Failed to init Native Windows GUI");
+    Canvas::software(width, height).expect("Failed to create the software canvas")
+}
+
+fn assert_snapshot(canvas: &Canvas, name: &str) {
+    let path = format!("test_rc/snapshots/{}.png", name);
+    let result = canvas.compare_snapshot(&path, 2).unwrap();
+    assert!(result.is_match(), "{}: {:?}", name, result);
+}
+
+#[test]
+fn software_pixels() {
+    let canvas = software_canvas(4, 2);
+    let red = canvas.solid_brush(Color::rgb(255, 0, 0)).unwrap();
+
+    let draw = canvas.begin_draw().unwrap();
+    draw.clear(Color::TRANSPARENT);
+    draw.fill_rect(Rect::new(0.0, 0.0, 2.0, 2.0), &red);
+    draw.end().unwrap();
+
+    let pixels = canvas.to_rgba().unwrap();
+    assert_eq!(pixels.len(), 4 * 2 * 4);
+    assert_eq!(&pixels[0..4], &[255, 0, 0, 255]);
+    assert_eq!(&pixels[12..16], &[0, 0, 0, 0]);
+}
+
+#[test]
+fn shapes_snapshot() {
+    let canvas = software_canvas(200, 150);
+    let fill = canvas.solid_brush(Color::rgb(40, 120, 200)).unwrap();
+    let outline = canvas.solid_brush(Color::BLACK).unwrap();
+    let dashed = canvas
+        .stroke_style()
+        .dash_style(DashStyle::Dash)
+        .build()
+        .unwrap();
+
+    let draw = canvas.begin_draw().unwrap();
+    draw.clear(Color::WHITE);
+    draw.fill_rect(Rect::new(10.0, 10.0, 80.0, 50.0), &fill);
+    draw.draw_rect(Rect::new(10.0, 10.0, 80.0, 50.0), &outline, 2.0, None);
+    draw.fill_rounded_rect(Rect::new(110.0, 10.0, 80.0, 50.0), 10.0, 10.0, &fill);
+    draw.fill_ellipse((50.0, 110.0), 35.0, 25.0, &fill);
+    draw.draw_ellipse((150.0, 110.0), 35.0, 25.0, &outline, 3.0, Some(&dashed));
+    draw.draw_line((0.0, 75.0), (200.0, 75.0), &outline, 1.0, None);
+    draw.end().unwrap();
+
+    assert_snapshot(&canvas, "shapes");
+}
+
+#[test]
+fn geometry_snapshot() {
+    let canvas = software_canvas(200, 200);
+    let stops = [
+        GradientStop::new(0.0, Color::rgb(255, 240, 120)),
+        GradientStop::new(1.0, Color::rgb(240, 140, 20)),
+    ];
+    let gradient = canvas
+        .linear_gradient_brush((0.0, -60.0), (0.0, 60.0), &stops, ExtendMode::Clamp)
+        .unwrap();
+    let outline = canvas.solid_brush(Color::rgb(80, 40, 0)).unwrap();
+
+    let star = canvas
+        .path()
+        .unwrap()
+        .begin_figure((0.0, -60.0), true)
+        .line_to((35.0, 48.0))
+        .line_to((-57.0, -18.0))
+        .line_to((57.0, -18.0))
+        .line_to((-35.0, 48.0))
+        .end_figure(true)
+        .build()
+        .unwrap();
+
+    let draw = canvas.begin_draw().unwrap();
+    draw.clear(Color::WHITE);
+    draw.set_transform(
+        &Transform::rotation(15.0, Point::default()).then(&Transform::translation(100.0, 100.0)),
+    );
+    draw.fill_geometry(&star, &gradient);
+    draw.draw_geometry(&star, &outline, 3.0, None);
+    draw.end().unwrap();
+
+    assert_snapshot(&canvas, "geometry");
+}
+
+#[test]
+fn layer_snapshot() {
+    let canvas = software_canvas(100, 100);
+    let fill = canvas.solid_brush(Color::rgb(200, 30, 30)).unwrap();
+    let mask = canvas.ellipse_geometry((50.0, 50.0), 40.0, 40.0).unwrap();
+    let layer = canvas.layer().unwrap();
+
+    let params = LayerParameters {
+        mask: Some(&mask),
+        opacity: 0.5,
+        ..Default::default()
+    };
+
+    let draw = canvas.begin_draw().unwrap();
+    draw.clear(Color::WHITE);
+    draw.push_layer(&layer, &params);
+    draw.fill_rect(Rect::new(0.0, 0.0, 100.0, 100.0), &fill);
+    draw.pop_layer();
+    draw.end().unwrap();
+
+    assert_snapshot(&canvas, "layer");
+}
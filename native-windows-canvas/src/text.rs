@@ -0,0 +1,335 @@
+use crate::error::check;
+use crate::{release, Brush, Canvas, CanvasError, Point};
+use native_windows_gui::d2d::locale_name;
+use std::{mem, ops::Range, ptr};
+use winapi::shared::minwindef::{BOOL, FALSE};
+use winapi::um::dwrite::*;
+use winapi::um::unknwnbase::IUnknown;
+
+/// The horizontal alignment of the text in its layout box
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextAlign {
+    Leading,
+    Trailing,
+    Center,
+    Justified,
+}
+
+/// The vertical alignment of the paragraphs in their layout box
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParagraphAlign {
+    Near,
+    Far,
+    Center,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FontStyle {
+    Normal,
+    Oblique,
+    Italic,
+}
+
+impl FontStyle {
+    fn dwrite(self) -> DWRITE_FONT_STYLE {
+        match self {
+            FontStyle::Normal => DWRITE_FONT_STYLE_NORMAL,
+            FontStyle::Oblique => DWRITE_FONT_STYLE_OBLIQUE,
+            FontStyle::Italic => DWRITE_FONT_STYLE_ITALIC,
+        }
+    }
+}
+
+/**
+    The font and paragraph properties used to draw or to lay out text.
+    Text formats do not depend on the render target and can be shared between canvas.
+*/
+pub struct TextFormat {
+    pub format: *mut IDWriteTextFormat,
+}
+
+impl Drop for TextFormat {
+    fn drop(&mut self) {
+        unsafe { release(self.format) }
+    }
+}
+
+/// The size of a laid out text, in device independent pixels
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TextMetrics {
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+    pub line_count: u32,
+}
+
+/// The result of a hit test on a text layout
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextHit {
+    /// Position of the character under the point, in UTF-16 code units
+    pub position: u32,
+
+    /// True if the point is on the trailing side of the character
+    pub trailing: bool,
+
+    /// True if the point is inside the text
+    pub inside: bool,
+}
+
+/**
+    A text formatted and laid out in a box. Ranges of the text can be styled independently.
+
+    Ranges are expressed in UTF-16 code units, like the positions returned by `hit_test`.
+*/
+pub struct TextLayout {
+    pub layout: *mut IDWriteTextLayout,
+}
+
+impl TextLayout {
+    pub fn metrics(&self) -> TextMetrics {
+        let mut m: DWRITE_TEXT_METRICS = unsafe { mem::zeroed() };
+        unsafe { (&*self.layout).GetMetrics(&mut m) };
+
+        TextMetrics {
+            left: m.left,
+            top: m.top,
+            width: m.width,
+            height: m.height,
+            line_count: m.lineCount,
+        }
+    }
+
+    /// Set the size of the layout box
+    pub fn set_max_size(&self, width: f32, height: f32) {
+        unsafe {
+            (&*self.layout).SetMaxWidth(width);
+            (&*self.layout).SetMaxHeight(height);
+        }
+    }
+
+    /// Set the font weight of a range. The weight goes from 100 (thin) to 900 (heavy). 400 is normal and 700 is bold.
+    pub fn set_weight(&self, range: Range<u32>, weight: u32) {
+        unsafe { (&*self.layout).SetFontWeight(weight, text_range(range)) };
+    }
+
+    pub fn set_style(&self, range: Range<u32>, style: FontStyle) {
+        unsafe { (&*self.layout).SetFontStyle(style.dwrite(), text_range(range)) };
+    }
+
+    pub fn set_size(&self, range: Range<u32>, size: f32) {
+        unsafe { (&*self.layout).SetFontSize(size, text_range(range)) };
+    }
+
+    pub fn set_underline(&self, range: Range<u32>, underline: bool) {
+        unsafe { (&*self.layout).SetUnderline(underline as BOOL, text_range(range)) };
+    }
+
+    pub fn set_strikethrough(&self, range: Range<u32>, strikethrough: bool) {
+        unsafe { (&*self.layout).SetStrikethrough(strikethrough as BOOL, text_range(range)) };
+    }
+
+    /// Paint a range with `brush` instead of the brush passed to `draw_text_layout`
+    pub fn set_brush(&self, range: Range<u32>, brush: &Brush) {
+        unsafe {
+            (&*self.layout).SetDrawingEffect(brush.brush as *mut IUnknown, text_range(range));
+        }
+    }
+
+    /// Find the character under `point`. The point is relative to the origin of the layout.
+    pub fn hit_test<P: Into<Point>>(&self, point: P) -> TextHit {
+        let point = point.into();
+        let (mut trailing, mut inside) = (FALSE, FALSE);
+        let mut metrics: DWRITE_HIT_TEST_METRICS = unsafe { mem::zeroed() };
+        unsafe {
+            (&*self.layout).HitTestPoint(
+                point.x,
+                point.y,
+                &mut trailing,
+                &mut inside,
+                &mut metrics,
+            );
+        }
+
+        TextHit {
+            position: metrics.textPosition,
+            trailing: trailing != FALSE,
+            inside: inside != FALSE,
+        }
+    }
+}
+
+impl Drop for TextLayout {
+    fn drop(&mut self) {
+        unsafe { release(self.layout) }
+    }
+}
+
+impl Canvas {
+    /**
+        Create a text format.
+
+        ```rust
+        use native_windows_canvas as nwc;
+
+        fn title_format(canvas: &nwc::Canvas) -> Result<nwc::TextFormat, nwc::CanvasError> {
+            canvas.text_format()
+                .family("Segoe UI")
+                .size(24.0)
+                .weight(700)
+                .align(nwc::TextAlign::Center)
+                .build()
+        }
+        ```
+    */
+    pub fn text_format(&self) -> TextFormatBuilder<'_> {
+        TextFormatBuilder {
+            canvas: self,
+            family: "Segoe UI",
+            size: 14.0,
+            weight: 400,
+            style: FontStyle::Normal,
+            align: TextAlign::Leading,
+            paragraph_align: ParagraphAlign::Near,
+            word_wrap: true,
+        }
+    }
+
+    /// Lay out `text` in a box of `max_width` by `max_height` using `format`
+    pub fn text_layout(
+        &self,
+        text: &str,
+        format: &TextFormat,
+        max_width: f32,
+        max_height: f32,
+    ) -> Result<TextLayout, CanvasError> {
+        let text: Vec<u16> = text.encode_utf16().collect();
+        let mut layout = ptr::null_mut();
+        let hr = unsafe {
+            (&*self.factories().write).CreateTextLayout(
+                text.as_ptr(),
+                text.len() as u32,
+                format.format,
+                max_width,
+                max_height,
+                &mut layout,
+            )
+        };
+        check(hr, "Failed to create a text layout")?;
+
+        Ok(TextLayout { layout })
+    }
+}
+
+pub struct TextFormatBuilder<'a> {
+    canvas: &'a Canvas,
+    family: &'a str,
+    size: f32,
+    weight: u32,
+    style: FontStyle,
+    align: TextAlign,
+    paragraph_align: ParagraphAlign,
+    word_wrap: bool,
+}
+
+impl<'a> TextFormatBuilder<'a> {
+    pub fn family(mut self, family: &'a str) -> TextFormatBuilder<'a> {
+        self.family = family;
+        self
+    }
+
+    /// The font size in device independent pixels
+    pub fn size(mut self, size: f32) -> TextFormatBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    /// The font weight. From 100 (thin) to 900 (heavy). 400 is normal and 700 is bold.
+    pub fn weight(mut self, weight: u32) -> TextFormatBuilder<'a> {
+        self.weight = weight;
+        self
+    }
+
+    pub fn style(mut self, style: FontStyle) -> TextFormatBuilder<'a> {
+        self.style = style;
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> TextFormatBuilder<'a> {
+        self.align = align;
+        self
+    }
+
+    pub fn paragraph_align(mut self, align: ParagraphAlign) -> TextFormatBuilder<'a> {
+        self.paragraph_align = align;
+        self
+    }
+
+    /// If false, the text is not broken into lines to fit the width of the layout box
+    pub fn word_wrap(mut self, wrap: bool) -> TextFormatBuilder<'a> {
+        self.word_wrap = wrap;
+        self
+    }
+
+    pub fn build(self) -> Result<TextFormat, CanvasError> {
+        let family: Vec<u16> = self.family.encode_utf16().chain(Some(0)).collect();
+        let locale = locale_name();
+
+        let mut format = ptr::null_mut();
+        let hr = unsafe {
+            (&*self.canvas.factories().write).CreateTextFormat(
+                family.as_ptr(),
+                ptr::null_mut(),
+                self.weight,
+                self.style.dwrite(),
+                DWRITE_FONT_STRETCH_NORMAL,
+                self.size,
+                locale.as_ptr(),
+                &mut format,
+            )
+        };
+        check(hr, "Failed to create a text format")?;
+
+        let format = TextFormat { format };
+
+        let align = match self.align {
+            TextAlign::Leading => DWRITE_TEXT_ALIGNMENT_LEADING,
+            TextAlign::Trailing => DWRITE_TEXT_ALIGNMENT_TRAILING,
+            TextAlign::Center => DWRITE_TEXT_ALIGNMENT_CENTER,
+            TextAlign::Justified => DWRITE_TEXT_ALIGNMENT_JUSTIFIED,
+        };
+
+        let paragraph_align = match self.paragraph_align {
+            ParagraphAlign::Near => DWRITE_PARAGRAPH_ALIGNMENT_NEAR,
+            ParagraphAlign::Far => DWRITE_PARAGRAPH_ALIGNMENT_FAR,
+            ParagraphAlign::Center => DWRITE_PARAGRAPH_ALIGNMENT_CENTER,
+        };
+
+        let wrap = match self.word_wrap {
+            true => DWRITE_WORD_WRAPPING_WRAP,
+            false => DWRITE_WORD_WRAPPING_NO_WRAP,
+        };
+
+        unsafe {
+            let f = &*format.format;
+            check(
+                f.SetTextAlignment(align),
+                "Failed to set the text alignment",
+            )?;
+            check(
+                f.SetParagraphAlignment(paragraph_align),
+                "Failed to set the paragraph alignment",
+            )?;
+            check(f.SetWordWrapping(wrap), "Failed to set the word wrapping")?;
+        }
+
+        Ok(format)
+    }
+}
+
+fn text_range(range: Range<u32>) -> DWRITE_TEXT_RANGE {
+    DWRITE_TEXT_RANGE {
+        startPosition: range.start,
+        length: range.end.saturating_sub(range.start),
+    }
+}
@@ -0,0 +1,386 @@
+/*!
+    Plain value types used by the canvas API. They convert to their Direct2D counterpart when a command is recorded.
+*/
+use winapi::um::d2d1::{
+    D2D1_COLOR_F, D2D1_ELLIPSE, D2D1_MATRIX_3X2_F, D2D1_POINT_2F, D2D1_RECT_F, D2D1_ROUNDED_RECT,
+};
+
+/// A color with straight (non premultiplied) alpha. Each component is in the `0.0..=1.0` range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const BLACK: Color = Color {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 1.0,
+    };
+    pub const WHITE: Color = Color {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 1.0,
+    };
+    pub const TRANSPARENT: Color = Color {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 0.0,
+    };
+
+    /// An opaque color from 8 bits components
+    pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color::rgba(r, g, b, 255)
+    }
+
+    /// A color from 8 bits components
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color {
+            r: r as f32 / 255.0,
+            g: g as f32 / 255.0,
+            b: b as f32 / 255.0,
+            a: a as f32 / 255.0,
+        }
+    }
+
+    /// Return the same color with a different alpha
+    pub fn with_alpha(self, a: f32) -> Color {
+        Color { a, ..self }
+    }
+}
+
+impl From<[u8; 3]> for Color {
+    fn from(c: [u8; 3]) -> Color {
+        Color::rgb(c[0], c[1], c[2])
+    }
+}
+
+impl From<[u8; 4]> for Color {
+    fn from(c: [u8; 4]) -> Color {
+        Color::rgba(c[0], c[1], c[2], c[3])
+    }
+}
+
+impl From<Color> for D2D1_COLOR_F {
+    fn from(c: Color) -> D2D1_COLOR_F {
+        D2D1_COLOR_F {
+            r: c.r,
+            g: c.g,
+            b: c.b,
+            a: c.a,
+        }
+    }
+}
+
+/// A point in device independent pixels
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Point {
+        Point { x, y }
+    }
+}
+
+impl From<(f32, f32)> for Point {
+    fn from((x, y): (f32, f32)) -> Point {
+        Point { x, y }
+    }
+}
+
+impl From<Point> for D2D1_POINT_2F {
+    fn from(p: Point) -> D2D1_POINT_2F {
+        D2D1_POINT_2F { x: p.x, y: p.y }
+    }
+}
+
+/// A rectangle in device independent pixels
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Rect {
+    /// A rectangle from its position and its size
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect {
+            left: x,
+            top: y,
+            right: x + width,
+            bottom: y + height,
+        }
+    }
+
+    /// A rectangle that covers everything. Used as the default bounds of a layer.
+    pub fn infinite() -> Rect {
+        Rect {
+            left: -f32::MAX,
+            top: -f32::MAX,
+            right: f32::MAX,
+            bottom: f32::MAX,
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+
+    pub fn center(&self) -> Point {
+        Point {
+            x: (self.left + self.right) / 2.0,
+            y: (self.top + self.bottom) / 2.0,
+        }
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= self.left && p.x < self.right && p.y >= self.top && p.y < self.bottom
+    }
+}
+
+impl From<Rect> for D2D1_RECT_F {
+    fn from(r: Rect) -> D2D1_RECT_F {
+        D2D1_RECT_F {
+            left: r.left,
+            top: r.top,
+            right: r.right,
+            bottom: r.bottom,
+        }
+    }
+}
+
+impl From<D2D1_RECT_F> for Rect {
+    fn from(r: D2D1_RECT_F) -> Rect {
+        Rect {
+            left: r.left,
+            top: r.top,
+            right: r.right,
+            bottom: r.bottom,
+        }
+    }
+}
+
+pub(crate) fn rounded_rect(rect: Rect, radius_x: f32, radius_y: f32) -> D2D1_ROUNDED_RECT {
+    D2D1_ROUNDED_RECT {
+        rect: rect.into(),
+        radiusX: radius_x,
+        radiusY: radius_y,
+    }
+}
+
+pub(crate) fn ellipse(center: Point, radius_x: f32, radius_y: f32) -> D2D1_ELLIPSE {
+    D2D1_ELLIPSE {
+        point: center.into(),
+        radiusX: radius_x,
+        radiusY: radius_y,
+    }
+}
+
+/**
+    A 2D affine transformation. Points are transformed as row vectors: `[x, y, 1] * M`.
+
+    Transformations are combined with `then`:
+
+    ```rust
+    use native_windows_canvas::{Point, Transform};
+
+    let t = Transform::scale(2.0, 2.0, Point::default()).then(&Transform::translation(10.0, 0.0));
+    assert_eq!(t.transform_point(Point::new(1.0, 1.0)), Point::new(12.0, 2.0));
+    ```
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    pub m11: f32,
+    pub m12: f32,
+    pub m21: f32,
+    pub m22: f32,
+    pub dx: f32,
+    pub dy: f32,
+}
+
+impl Transform {
+    pub fn identity() -> Transform {
+        Transform {
+            m11: 1.0,
+            m12: 0.0,
+            m21: 0.0,
+            m22: 1.0,
+            dx: 0.0,
+            dy: 0.0,
+        }
+    }
+
+    pub fn translation(x: f32, y: f32) -> Transform {
+        Transform {
+            dx: x,
+            dy: y,
+            ..Transform::identity()
+        }
+    }
+
+    /// Scale by `(sx, sy)` around `center`
+    pub fn scale(sx: f32, sy: f32, center: Point) -> Transform {
+        Transform {
+            m11: sx,
+            m12: 0.0,
+            m21: 0.0,
+            m22: sy,
+            dx: center.x - sx * center.x,
+            dy: center.y - sy * center.y,
+        }
+    }
+
+    /// Clockwise rotation of `angle` degrees around `center`
+    pub fn rotation(angle: f32, center: Point) -> Transform {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Transform {
+            m11: cos,
+            m12: sin,
+            m21: -sin,
+            m22: cos,
+            dx: center.x - cos * center.x + sin * center.y,
+            dy: center.y - sin * center.x - cos * center.y,
+        }
+    }
+
+    /// Skew of `angle_x` and `angle_y` degrees around `center`
+    pub fn skew(angle_x: f32, angle_y: f32, center: Point) -> Transform {
+        let tan_x = angle_x.to_radians().tan();
+        let tan_y = angle_y.to_radians().tan();
+        Transform {
+            m11: 1.0,
+            m12: tan_y,
+            m21: tan_x,
+            m22: 1.0,
+            dx: -center.y * tan_x,
+            dy: -center.x * tan_y,
+        }
+    }
+
+    /// Return the transformation that applies `self` and then `other`
+    pub fn then(&self, other: &Transform) -> Transform {
+        Transform {
+            m11: self.m11 * other.m11 + self.m12 * other.m21,
+            m12: self.m11 * other.m12 + self.m12 * other.m22,
+            m21: self.m21 * other.m11 + self.m22 * other.m21,
+            m22: self.m21 * other.m12 + self.m22 * other.m22,
+            dx: self.dx * other.m11 + self.dy * other.m21 + other.dx,
+            dy: self.dx * other.m12 + self.dy * other.m22 + other.dy,
+        }
+    }
+
+    /// Return the inverse of the transformation or `None` if it cannot be inverted
+    pub fn invert(&self) -> Option<Transform> {
+        let det = self.m11 * self.m22 - self.m12 * self.m21;
+        if det.abs() <= f32::EPSILON {
+            return None;
+        }
+
+        Some(Transform {
+            m11: self.m22 / det,
+            m12: -self.m12 / det,
+            m21: -self.m21 / det,
+            m22: self.m11 / det,
+            dx: (self.m21 * self.dy - self.m22 * self.dx) / det,
+            dy: (self.m12 * self.dx - self.m11 * self.dy) / det,
+        })
+    }
+
+    pub fn transform_point(&self, p: Point) -> Point {
+        Point {
+            x: p.x * self.m11 + p.y * self.m21 + self.dx,
+            y: p.x * self.m12 + p.y * self.m22 + self.dy,
+        }
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::identity()
+    }
+}
+
+impl From<Transform> for D2D1_MATRIX_3X2_F {
+    fn from(t: Transform) -> D2D1_MATRIX_3X2_F {
+        D2D1_MATRIX_3X2_F {
+            matrix: [[t.m11, t.m12], [t.m21, t.m22], [t.dx, t.dy]],
+        }
+    }
+}
+
+impl From<D2D1_MATRIX_3X2_F> for Transform {
+    fn from(m: D2D1_MATRIX_3X2_F) -> Transform {
+        let [[m11, m12], [m21, m22], [dx, dy]] = m.matrix;
+        Transform {
+            m11,
+            m12,
+            m21,
+            m22,
+            dx,
+            dy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Point, b: Point) {
+        assert!(
+            (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn rotation_around_center() {
+        let t = Transform::rotation(90.0, Point::new(10.0, 10.0));
+        assert_near(
+            t.transform_point(Point::new(20.0, 10.0)),
+            Point::new(10.0, 20.0),
+        );
+        assert_near(
+            t.transform_point(Point::new(10.0, 10.0)),
+            Point::new(10.0, 10.0),
+        );
+    }
+
+    #[test]
+    fn then_applies_self_first() {
+        let t =
+            Transform::translation(5.0, 0.0).then(&Transform::scale(2.0, 3.0, Point::default()));
+        assert_near(
+            t.transform_point(Point::new(1.0, 1.0)),
+            Point::new(12.0, 3.0),
+        );
+    }
+
+    #[test]
+    fn invert() {
+        let t = Transform::rotation(30.0, Point::new(3.0, 4.0))
+            .then(&Transform::translation(7.0, -2.0));
+        let p = Point::new(11.0, -5.0);
+        assert_near(t.invert().unwrap().transform_point(t.transform_point(p)), p);
+        assert!(Transform::scale(0.0, 1.0, Point::default())
+            .invert()
+            .is_none());
+    }
+}
@@ -0,0 +1,935 @@
+/*!
+    The names of the native-windows-gui API used by the derive macros. They are used to report unknown events and
+    unknown parameters at expansion time with a span instead of a confusing error in the generated code.
+
+    Types missing from these tables (ex: custom controls or custom layouts) are not validated.
+    The tests of this module compare the tables with the sources of native-windows-gui.
+*/
+
+/// The methods of the builders, by builder type. `flags` and `ty` are handled by the derive macro.
+static BUILDERS: &[(&str, &[&str])] = &[
+    ("AnimatedImage", &["source_bin", "source_file"]),
+    (
+        "AnimationFrame",
+        &[
+            "ex_flags", "flags", "parent", "play", "position", "size", "source",
+        ],
+    ),
+    (
+        "AnimationTimer",
+        &["active", "interval", "lifetime", "max_tick", "parent"],
+    ),
+    (
+        "Bitmap",
+        &[
+            "size",
+            "source_bin",
+            "source_embed",
+            "source_embed_id",
+            "source_embed_str",
+            "source_file",
+            "source_set",
+            "source_system",
+            "strict",
+        ],
+    ),
+    (
+        "Button",
+        &[
+            "bitmap", "enabled", "ex_flags", "flags", "focus", "font", "icon", "parent",
+            "position", "size", "text",
+        ],
+    ),
+    (
+        "CheckBox",
+        &[
+            "background_color",
+            "check_state",
+            "enabled",
+            "ex_flags",
+            "flags",
+            "focus",
+            "font",
+            "parent",
+            "position",
+            "size",
+            "text",
+        ],
+    ),
+    ("ColorDialog", &["saved_color"]),
+    (
+        "ComboBox",
+        &[
+            "collection",
+            "enabled",
+            "ex_flags",
+            "flags",
+            "focus",
+            "font",
+            "item_height",
+            "owner_draw",
+            "parent",
+            "position",
+            "selected_index",
+            "size",
+            "v_align",
+        ],
+    ),
+    (
+        "ComboBoxEx",
+        &[
+            "collection",
+            "editable",
+            "enabled",
+            "ex_flags",
+            "filter",
+            "flags",
+            "focus",
+            "font",
+            "image_list",
+            "item_image",
+            "parent",
+            "position",
+            "selected_index",
+            "size",
+            "text",
+        ],
+    ),
+    (
+        "Cursor",
+        &[
+            "size",
+            "source_bin",
+            "source_embed",
+            "source_embed_id",
+            "source_embed_str",
+            "source_file",
+            "source_system",
+            "strict",
+        ],
+    ),
+    (
+        "DatePicker",
+        &[
+            "date", "ex_flags", "flags", "focus", "font", "format", "parent", "position", "range",
+            "size",
+        ],
+    ),
+    (
+        "DynLayout",
+        &["child", "child_item", "child_layout", "parent"],
+    ),
+    ("EmbedResource", &["module"]),
+    (
+        "ExternCanvas",
+        &[
+            "ex_flags", "flags", "icon", "parent", "position", "size", "title",
+        ],
+    ),
+    (
+        "FileDialog",
+        &[
+            "action",
+            "default_folder",
+            "filters",
+            "multiselect",
+            "title",
+        ],
+    ),
+    (
+        "FlexboxLayout",
+        &[
+            "align_content",
+            "align_items",
+            "aspect_ratio",
+            "auto_size",
+            "auto_spacing",
+            "border",
+            "child",
+            "child_align_self",
+            "child_flex_basis",
+            "child_flex_grow",
+            "child_flex_shrink",
+            "child_layout",
+            "child_margin",
+            "child_max_size",
+            "child_min_size",
+            "child_position",
+            "child_size",
+            "direction",
+            "flex_direction",
+            "flex_wrap",
+            "justify_content",
+            "max_size",
+            "min_size",
+            "overflow",
+            "padding",
+            "parent",
+            "style",
+        ],
+    ),
+    (
+        "Font",
+        &[
+            "charset",
+            "family",
+            "italic",
+            "pitch",
+            "quality",
+            "size",
+            "size_absolute",
+            "strikeout",
+            "underline",
+            "weight",
+        ],
+    ),
+    ("FontDialog", &[]),
+    (
+        "Frame",
+        &["enabled", "ex_flags", "flags", "parent", "position", "size"],
+    ),
+    (
+        "GridLayout",
+        &[
+            "child",
+            "child_item",
+            "child_layout",
+            "columns",
+            "margin",
+            "max_column",
+            "max_row",
+            "max_size",
+            "min_size",
+            "parent",
+            "rows",
+            "spacing",
+        ],
+    ),
+    (
+        "HotKeyInput",
+        &[
+            "enabled", "ex_flags", "flags", "focus", "font", "parent", "position", "rules", "size",
+            "value",
+        ],
+    ),
+    (
+        "Icon",
+        &[
+            "size",
+            "source_bin",
+            "source_embed",
+            "source_embed_id",
+            "source_embed_str",
+            "source_file",
+            "source_set",
+            "source_system",
+            "strict",
+        ],
+    ),
+    ("ImageDecoder", &[]),
+    ("ImageEncoder", &[]),
+    (
+        "ImageFrame",
+        &[
+            "animation",
+            "background_color",
+            "bitmap",
+            "ex_flags",
+            "flags",
+            "icon",
+            "looping",
+            "parent",
+            "position",
+            "scaling",
+            "size",
+        ],
+    ),
+    ("ImageList", &["dpi_aware", "grow", "initial", "size"]),
+    (
+        "ImageSet",
+        &["source_bin", "source_bins", "source_file", "source_files"],
+    ),
+    (
+        "IpAddressInput",
+        &[
+            "address", "enabled", "ex_flags", "flags", "focus", "font", "parent", "position",
+            "size",
+        ],
+    ),
+    (
+        "Label",
+        &[
+            "background_color",
+            "ex_flags",
+            "flags",
+            "font",
+            "h_align",
+            "parent",
+            "position",
+            "size",
+            "text",
+            "v_align",
+        ],
+    ),
+    (
+        "LinkLabel",
+        &[
+            "enabled", "ex_flags", "flags", "font", "parent", "position", "size", "text",
+        ],
+    ),
+    (
+        "ListBox",
+        &[
+            "collection",
+            "enabled",
+            "ex_flags",
+            "flags",
+            "focus",
+            "font",
+            "multi_selection",
+            "owner_draw",
+            "parent",
+            "position",
+            "selected_index",
+            "size",
+        ],
+    ),
+    (
+        "ListView",
+        &[
+            "background_color",
+            "double_buffer",
+            "ex_flags",
+            "ex_window_flags",
+            "flags",
+            "focus",
+            "item_count",
+            "list_style",
+            "parent",
+            "position",
+            "size",
+            "text_color",
+        ],
+    ),
+    ("Menu", &["disabled", "parent", "popup", "text"]),
+    ("MenuItem", &["check", "disabled", "parent", "text"]),
+    ("MenuSeparator", &["parent"]),
+    ("MessageWindow", &[]),
+    (
+        "MonthCalendar",
+        &[
+            "bold_days",
+            "date",
+            "enabled",
+            "ex_flags",
+            "first_day_of_week",
+            "flags",
+            "focus",
+            "font",
+            "locale",
+            "max_selection",
+            "months",
+            "multi_select",
+            "parent",
+            "position",
+            "range",
+            "selection_range",
+            "size",
+            "today",
+        ],
+    ),
+    ("Notice", &["parent"]),
+    (
+        "NumberSelect",
+        &[
+            "decimals",
+            "enabled",
+            "flags",
+            "font",
+            "max_float",
+            "max_int",
+            "min_float",
+            "min_int",
+            "parent",
+            "position",
+            "size",
+            "step_float",
+            "step_int",
+            "value_float",
+            "value_int",
+        ],
+    ),
+    (
+        "Plotters",
+        &[
+            "crosshair",
+            "ex_flags",
+            "interactive",
+            "parent",
+            "position",
+            "size",
+            "tooltip",
+            "x_range",
+            "y_range",
+        ],
+    ),
+    (
+        "ProgressBar",
+        &[
+            "ex_flags",
+            "flags",
+            "marquee",
+            "marquee_update",
+            "parent",
+            "pos",
+            "position",
+            "range",
+            "size",
+            "state",
+            "step",
+        ],
+    ),
+    (
+        "RadioButton",
+        &[
+            "background_color",
+            "check_state",
+            "ex_flags",
+            "flags",
+            "focus",
+            "font",
+            "parent",
+            "position",
+            "size",
+            "text",
+        ],
+    ),
+    (
+        "RichLabel",
+        &[
+            "background_color",
+            "ex_flags",
+            "flags",
+            "font",
+            "h_align",
+            "line_height",
+            "parent",
+            "position",
+            "size",
+            "text",
+        ],
+    ),
+    (
+        "RichTextBox",
+        &[
+            "ex_flags", "flags", "focus", "font", "limit", "parent", "position", "readonly",
+            "size", "text",
+        ],
+    ),
+    (
+        "ScrollBar",
+        &[
+            "enabled", "ex_flags", "flags", "focus", "parent", "pos", "position", "range", "size",
+        ],
+    ),
+    (
+        "StackLayout",
+        &[
+            "align",
+            "child",
+            "child_item",
+            "child_layout",
+            "direction",
+            "justify",
+            "margin",
+            "parent",
+            "spacing",
+        ],
+    ),
+    ("StatusBar", &["font", "parent", "text"]),
+    ("Tab", &["image_index", "parent", "text"]),
+    (
+        "TabsContainer",
+        &[
+            "ex_flags",
+            "flags",
+            "font",
+            "image_list",
+            "parent",
+            "position",
+            "size",
+        ],
+    ),
+    (
+        "TextBox",
+        &[
+            "ex_flags", "flags", "focus", "font", "limit", "parent", "position", "readonly",
+            "size", "text",
+        ],
+    ),
+    (
+        "TextInput",
+        &[
+            "align",
+            "auto_complete",
+            "background_color",
+            "ex_flags",
+            "flags",
+            "focus",
+            "font",
+            "limit",
+            "parent",
+            "password",
+            "placeholder_text",
+            "position",
+            "readonly",
+            "size",
+            "text",
+        ],
+    ),
+    ("Timer", &["interval", "parent", "stopped"]),
+    (
+        "Tooltip",
+        &[
+            "decoration",
+            "default_decoration",
+            "register",
+            "register_callback",
+        ],
+    ),
+    (
+        "TrackBar",
+        &[
+            "background_color",
+            "ex_flags",
+            "flags",
+            "focus",
+            "parent",
+            "pos",
+            "position",
+            "range",
+            "selected_range",
+            "size",
+        ],
+    ),
+    (
+        "TrayNotification",
+        &[
+            "balloon_icon",
+            "callback",
+            "flags",
+            "icon",
+            "info",
+            "info_title",
+            "parent",
+            "realtime",
+            "tip",
+            "visible",
+        ],
+    ),
+    (
+        "TreeView",
+        &[
+            "drag_and_drop",
+            "enabled",
+            "ex_flags",
+            "flags",
+            "focus",
+            "font",
+            "image_list",
+            "parent",
+            "position",
+            "size",
+            "tri_state",
+        ],
+    ),
+    (
+        "Window",
+        &[
+            "accept_files",
+            "center",
+            "ex_flags",
+            "flags",
+            "icon",
+            "maximized",
+            "minimized",
+            "parent",
+            "position",
+            "size",
+            "title",
+            "topmost",
+        ],
+    ),
+    (
+        "WrapLayout",
+        &[
+            "align",
+            "child",
+            "child_item",
+            "child_layout",
+            "justify",
+            "margin",
+            "parent",
+            "spacing",
+        ],
+    ),
+];
+
+/// The methods of the layout items, by layout type. `layout` selects the layout of the item.
+static LAYOUT_ITEMS: &[(&str, &[&str])] = &[
+    (
+        "DynLayout",
+        &[
+            "anchors", "geometry", "layout", "max_size", "min_size", "mv", "sz",
+        ],
+    ),
+    (
+        "GridLayout",
+        &[
+            "col", "col_span", "h_align", "layout", "row", "row_span", "v_align",
+        ],
+    ),
+    (
+        "StackLayout",
+        &["align", "layout", "preferred_size", "stretch"],
+    ),
+    ("WrapLayout", &["layout", "preferred_size"]),
+];
+
+/// The variants of `Event`
+static EVENTS: &[&str] = &[
+    "Unknown",
+    "OnMousePress",
+    "OnMouseMove",
+    "OnMouseWheel",
+    "OnContextMenu",
+    "OnInit",
+    "OnPaint",
+    "OnChar",
+    "OnKeyPress",
+    "OnKeyRelease",
+    "OnSysKeyPress",
+    "OnSysKeyRelease",
+    "OnKeyEnter",
+    "OnKeyEsc",
+    "OnMinMaxInfo",
+    "OnResize",
+    "OnResizeBegin",
+    "OnResizeEnd",
+    "OnWindowMaximize",
+    "OnWindowMinimize",
+    "OnMove",
+    "OnVerticalScroll",
+    "OnHorizontalScroll",
+    "OnFileDrop",
+    "OnButtonClick",
+    "OnButtonDoubleClick",
+    "OnLabelClick",
+    "OnLabelDoubleClick",
+    "OnImageFrameClick",
+    "OnImageFrameDoubleClick",
+    "OnTextInput",
+    "OnComboBoxClosed",
+    "OnComboBoxDropdown",
+    "OnComboxBoxSelection",
+    "OnComboBoxTextCommit",
+    "OnDatePickerDropdown",
+    "OnDatePickerClosed",
+    "OnDatePickerChanged",
+    "OnMonthCalendarSelectionChanged",
+    "OnMonthCalendarSelect",
+    "OnMonthCalendarViewChanged",
+    "OnLinkClick",
+    "OnIpAddressFieldChanged",
+    "OnHotKeyChanged",
+    "OnAnimationStart",
+    "OnAnimationStop",
+    "OnListBoxDoubleClick",
+    "OnListBoxSelect",
+    "TabsContainerChanged",
+    "TabsContainerChanging",
+    "TrackBarUpdated",
+    "OnMenuOpen",
+    "OnMenuEnter",
+    "OnMenuExit",
+    "OnMenuHover",
+    "OnMenuItemSelected",
+    "OnTooltipText",
+    "OnTreeViewClick",
+    "OnTreeViewDoubleClick",
+    "OnTreeViewRightClick",
+    "OnTreeViewBeginItemEdit",
+    "OnTreeViewEndItemEdit",
+    "OnTreeFocusLost",
+    "OnTreeFocus",
+    "OnTreeItemDelete",
+    "OnTreeItemExpanded",
+    "OnTreeItemChanged",
+    "OnTreeItemSelectionChanged",
+    "OnTreeItemExpanding",
+    "OnTreeItemMoved",
+    "OnListViewClear",
+    "OnListViewItemRemoved",
+    "OnListViewItemInsert",
+    "OnListViewItemActivated",
+    "OnListViewClick",
+    "OnListViewRightClick",
+    "OnListViewDoubleClick",
+    "OnListViewColumnClick",
+    "OnListViewItemChanged",
+    "OnListViewFocus",
+    "OnListViewFocusLost",
+    "OnTrayNotificationShow",
+    "OnTrayNotificationHide",
+    "OnTrayNotificationTimeout",
+    "OnTrayNotificationUserClose",
+    "OnTimerTick",
+    "OnTimerStop",
+    "OnNotice",
+    "OnPlottersViewChanged",
+    "OnPlottersHover",
+    "OnWindowClose",
+];
+
+/// The variants of `MousePressEvent`. They can be used as an event name in `nwg_events`.
+pub static MOUSE_PRESS_EVENTS: &[&str] = &[
+    "MousePressLeftUp",
+    "MousePressLeftDown",
+    "MousePressRightUp",
+    "MousePressRightDown",
+];
+
+/// The arguments that can be passed to a callback. `RC_SELF` is an alias of `SELF`.
+/// `INDEX` is the index of the control in a collection of controls.
+pub static CALLBACK_ARGS: &[&str] = &[
+    "SELF", "CTRL", "HANDLE", "EVT", "EVT_DATA", "RC_SELF", "INDEX",
+];
+
+fn find(
+    table: &'static [(&str, &'static [&'static str])],
+    ty: &syn::Ident,
+) -> Option<&'static [&'static str]> {
+    table
+        .iter()
+        .find(|(name, _)| ty == name)
+        .map(|(_, params)| *params)
+}
+
+/// Returns an error if `name` is not a variant of `Event` or `MousePressEvent`
+pub fn check_event(name: &syn::Ident) -> syn::Result<()> {
+    let known = EVENTS
+        .iter()
+        .chain(MOUSE_PRESS_EVENTS.iter())
+        .any(|evt| name == evt);
+    if known {
+        Ok(())
+    } else {
+        let msg = format!("unknown event `{}`", name);
+        Err(syn::Error::new(name.span(), msg))
+    }
+}
+
+/// Returns an error if a name of `names` is not a method of the builder of `ty`
+pub fn check_builder_params(ty: &syn::Ident, names: &[syn::Ident]) -> syn::Result<()> {
+    match find(BUILDERS, ty) {
+        Some(params) => check_params(ty, names, params.iter().copied()),
+        None => Ok(()),
+    }
+}
+
+/// Returns an error if a name of `names` is not a parameter of the items of the layout `ty`
+pub fn check_layout_item_params(ty: &syn::Ident, names: &[syn::Ident]) -> syn::Result<()> {
+    if ty == "FlexboxLayout" {
+        // The flexbox parameters are the `child_*` methods of the builder
+        let builder = find(BUILDERS, ty).unwrap_or(&[]);
+        let params = builder
+            .iter()
+            .filter_map(|p| p.strip_prefix("child_"))
+            .chain(Some("layout"));
+        return check_params(ty, names, params);
+    }
+
+    match find(LAYOUT_ITEMS, ty) {
+        Some(params) => check_params(ty, names, params.iter().copied()),
+        None => Ok(()),
+    }
+}
+
+fn check_params<'a, I>(ty: &syn::Ident, names: &[syn::Ident], params: I) -> syn::Result<()>
+where
+    I: Iterator<Item = &'a str> + Clone,
+{
+    for name in names {
+        if !params.clone().any(|p| name == p) {
+            let expected: Vec<&str> = params.collect();
+            let msg = format!(
+                "unknown parameter `{}` for `{}`. Expected one of: {}",
+                name,
+                ty,
+                expected.join(", ")
+            );
+            return Err(syn::Error::new(name.span(), msg));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::Path;
+
+    /// The methods with a `self` receiver of every inherent impl, by type name
+    type Methods = BTreeMap<String, Vec<String>>;
+
+    fn collect_items(items: &[syn::Item], methods: &mut Methods) {
+        for item in items {
+            match item {
+                syn::Item::Impl(i) if i.trait_.is_none() => {
+                    let ty = match &*i.self_ty {
+                        syn::Type::Path(p) => p.path.segments.last().unwrap().ident.to_string(),
+                        _ => continue,
+                    };
+
+                    let names = methods.entry(ty).or_default();
+                    for item in i.items.iter() {
+                        if let syn::ImplItem::Method(m) = item {
+                            let public = matches!(m.vis, syn::Visibility::Public(_));
+                            let name = m.sig.ident.to_string();
+                            if public && m.sig.receiver().is_some() && !names.contains(&name) {
+                                names.push(name);
+                            }
+                        }
+                    }
+                }
+                syn::Item::Mod(m) => {
+                    if let Some((_, items)) = &m.content {
+                        collect_items(items, methods);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_dir(dir: &Path, methods: &mut Methods) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect_dir(&path, methods);
+            } else if path.extension().map(|e| e == "rs").unwrap_or(false) {
+                // The builders of `ControlBase` are internal
+                if path.ends_with("control_base.rs") {
+                    continue;
+                }
+
+                let file = syn::parse_file(&fs::read_to_string(&path).unwrap()).unwrap();
+                collect_items(&file.items, methods);
+            }
+        }
+    }
+
+    fn gui_sources() -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../native-windows-gui/src")
+    }
+
+    fn gui_methods() -> Methods {
+        let mut methods = Methods::new();
+        collect_dir(Path::new(gui_sources()), &mut methods);
+        methods
+    }
+
+    fn sorted(names: &[&str]) -> Vec<String> {
+        let mut names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn builders_match_the_gui_sources() {
+        let methods = gui_methods();
+
+        let mut expected = Vec::new();
+        for (ty, names) in methods.iter() {
+            if let Some(ty) = ty.strip_suffix("Builder") {
+                let mut names: Vec<String> = names
+                    .iter()
+                    .filter(|n| *n != "build" && *n != "build_partial")
+                    .cloned()
+                    .collect();
+                names.sort();
+                expected.push((ty.to_string(), names));
+            }
+        }
+
+        let table: Vec<(String, Vec<String>)> = BUILDERS
+            .iter()
+            .map(|(ty, names)| (ty.to_string(), sorted(names)))
+            .collect();
+
+        let types =
+            |t: &[(String, Vec<String>)]| t.iter().map(|(ty, _)| ty.clone()).collect::<Vec<_>>();
+        assert_eq!(types(&table), types(&expected));
+
+        for ((ty, names), (_, expected)) in table.iter().zip(expected.iter()) {
+            assert_eq!(names, expected, "{}Builder", ty);
+        }
+    }
+
+    #[test]
+    fn layout_items_match_the_gui_sources() {
+        let methods = gui_methods();
+        let extra: &[(&str, &[&str])] = &[
+            ("DynLayout", &["layout", "mv", "sz"]),
+            (
+                "GridLayout",
+                &["col", "col_span", "layout", "row", "row_span"],
+            ),
+            ("StackLayout", &["layout"]),
+            ("WrapLayout", &["layout"]),
+        ];
+
+        for ((ty, names), (_, extra)) in LAYOUT_ITEMS.iter().zip(extra) {
+            let item = format!("{}Item", ty);
+            let mut expected: Vec<String> = methods[&item].clone();
+            expected.extend(extra.iter().map(|n| n.to_string()));
+            expected.sort();
+
+            // The derive macro does not expose the preferred size of grid items
+            if *ty == "GridLayout" {
+                expected.retain(|n| n != "preferred_size");
+            }
+
+            assert_eq!(sorted(names), expected, "{}", item);
+        }
+    }
+
+    #[test]
+    fn events_match_the_gui_sources() {
+        let path = Path::new(gui_sources()).join("events.rs");
+        let file = syn::parse_file(&fs::read_to_string(path).unwrap()).unwrap();
+
+        let variants = |name: &str| -> Vec<String> {
+            let mut variants: Vec<String> = file
+                .items
+                .iter()
+                .find_map(|item| match item {
+                    syn::Item::Enum(e) if e.ident == name => Some(e),
+                    _ => None,
+                })
+                .unwrap()
+                .variants
+                .iter()
+                .map(|v| v.ident.to_string())
+                .collect();
+            variants.sort();
+            variants
+        };
+
+        assert_eq!(sorted(EVENTS), variants("Event"));
+        assert_eq!(sorted(MOUSE_PRESS_EVENTS), variants("MousePressEvent"));
+    }
+}
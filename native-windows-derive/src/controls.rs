@@ -1,11 +1,9 @@
 use crate::shared::Parameters;
 
-pub fn parameters(field: &syn::Field, attr_id: &'static str) -> (Vec<syn::Ident>, Vec<syn::Expr>) {
-    let member = match field.ident.as_ref() {
-        Some(m) => m,
-        None => unreachable!(),
-    };
-
+pub fn parameters(
+    field: &syn::Field,
+    attr_id: &'static str,
+) -> syn::Result<(Vec<syn::Ident>, Vec<syn::Expr>)> {
     let nwg_control = |attr: &&syn::Attribute| {
         attr.path
             .get_ident()
@@ -18,10 +16,7 @@ pub fn parameters(field: &syn::Field, attr_id: &'static str) -> (Vec<syn::Ident>
         None => unreachable!(),
     };
 
-    let ctrl: Parameters = match syn::parse2(attr.tokens.clone()) {
-        Ok(a) => a,
-        Err(e) => panic!("Failed to parse field #{}: {}", member, e),
-    };
+    let ctrl: Parameters = syn::parse2(attr.tokens.clone())?;
 
     let params = ctrl.params;
     let mut names = Vec::with_capacity(params.len());
@@ -36,46 +31,38 @@ pub fn parameters(field: &syn::Field, attr_id: &'static str) -> (Vec<syn::Ident>
         exprs.push(p.e);
     }
 
-    (names, exprs)
+    Ok((names, exprs))
 }
 
-pub fn expand_flags(member_name: &syn::Ident, ty: &syn::Ident, flags: syn::Expr) -> syn::Expr {
-    let flags_type = format!("{}Flags", ty);
-
+pub fn expand_flags(ty: &syn::Ident, flags: syn::Expr) -> syn::Result<syn::Expr> {
     let flags_value = match &flags {
-        syn::Expr::Lit(expr_lit) => match &expr_lit.lit {
-            syn::Lit::Str(value) => value,
-            other => panic!(
-                "Compressed flags must str, got {:?} for control {}",
-                other, member_name
-            ),
-        },
-        other => panic!(
-            "Compressed flags must str, got {:?} for control {}",
-            other, member_name
-        ),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(value),
+            ..
+        }) => value,
+        other => {
+            let msg = "compressed flags must be a string. Ex: `flags: \"VISIBLE|DISABLED\"`";
+            return Err(syn::Error::new_spanned(other, msg));
+        }
     };
 
-    let flags = flags_value.value();
-    let splitted: Vec<&str> = flags.split('|').collect();
+    // The flags keep the span of the string so that unknown flags are reported on the attribute
+    let span = flags_value.span();
+    let flags_type = syn::Ident::new(&format!("{}Flags", ty), span);
 
-    let flags_count = splitted.len() - 1;
-    let mut final_flags: String = String::with_capacity(100);
-    for (i, value) in splitted.into_iter().enumerate() {
-        final_flags.push_str(&flags_type);
-        final_flags.push_str("::");
-        final_flags.push_str(value);
-
-        if i != flags_count {
-            final_flags.push('|');
+    let mut values: Vec<syn::Ident> = Vec::new();
+    for value in flags_value.value().split('|') {
+        match syn::parse_str::<syn::Ident>(value.trim()) {
+            Ok(mut ident) => {
+                ident.set_span(span);
+                values.push(ident);
+            }
+            Err(_) => {
+                let msg = format!("`{}` is not a valid flag name", value.trim());
+                return Err(syn::Error::new(span, msg));
+            }
         }
     }
 
-    match syn::parse_str(&final_flags) {
-        Ok(e) => e,
-        Err(e) => panic!(
-            "Failed to parse flags value for control {}: {}",
-            member_name, e
-        ),
-    }
+    syn::parse2(quote! { #(#flags_type::#values)|* })
 }
@@ -1,3 +1,5 @@
+use crate::api::{check_event, CALLBACK_ARGS, MOUSE_PRESS_EVENTS};
+use crate::shared::collection_item;
 use proc_macro2 as pm2;
 use quote::ToTokens;
 use std::collections::HashMap;
@@ -86,10 +88,40 @@ impl Parse for CallbackDefinitions {
     }
 }
 
+/// The control that sent an event
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum EventMember {
+    /// A control field. Ex: `evt_ui.button`
+    Control(syn::Expr),
+
+    /// A collection of controls. `element` is the control of an item `c` of the collection. Ex: `c` or `&c.member`
+    Collection {
+        collection: syn::Expr,
+        element: syn::Expr,
+    },
+}
+
+impl ToTokens for EventMember {
+    /// Generates the condition matching the sender of the event. A collection also binds the index of the sender to `_index`.
+    fn to_tokens(&self, tokens: &mut pm2::TokenStream) {
+        let tk = match self {
+            EventMember::Control(member) => quote! { &_handle == &#member },
+            EventMember::Collection {
+                collection,
+                element,
+            } => quote! {
+                let Some(_index) = #collection.iter().position(|c| &_handle == #element)
+            },
+        };
+
+        tk.to_tokens(tokens);
+    }
+}
+
 /// Parsed callbacks for a event type
 #[derive(Debug)]
 struct EventCallback {
-    member: syn::Expr,
+    member: EventMember,
     path: syn::Path,
     args: Punctuated<syn::Expr, Token![,]>,
 }
@@ -121,18 +153,10 @@ impl ControlEvents {
     }
 
     pub fn add_top_level_handle(&mut self, field: &syn::Field) {
-        let attrs = &field.attrs;
-        if attrs.len() == 0 {
-            return;
-        }
-
-        let member = field
-            .ident
-            .as_ref()
-            .expect("Cannot find member name when generating control");
-
-        if top_level_window(field) {
-            self.handles.push(member.clone());
+        if let Some(member) = field.ident.as_ref() {
+            if top_level_window(field) {
+                self.handles.push(member.clone());
+            }
         }
     }
 
@@ -142,30 +166,24 @@ impl ControlEvents {
         })
     }
 
-    pub fn parse(&mut self, field: &syn::Field) {
-        let attrs = &field.attrs;
-        if attrs.len() == 0 {
-            return;
-        }
+    pub fn parse(&mut self, field: &syn::Field) -> syn::Result<()> {
+        let member = match field.ident.as_ref() {
+            Some(m) => m,
+            None => return Ok(()),
+        };
 
-        let member = field
-            .ident
-            .as_ref()
-            .expect("Cannot find member name when generating control");
-        let attr = match find_events_attr(&attrs) {
+        let attr = match find_events_attr(&field.attrs) {
             Some(a) => a,
             None => {
-                return;
+                return Ok(());
             }
         };
 
-        let callback_definitions: CallbackDefinitions = match syn::parse2(attr.tokens.clone()) {
-            Ok(a) => a,
-            Err(e) => panic!("Failed to parse events for #{}: {}", member, e),
-        };
+        let callback_definitions: CallbackDefinitions = syn::parse2(attr.tokens.clone())?;
+        let collection = collection_item(&field.ty).is_some();
 
         for callback_def in callback_definitions.params.iter() {
-            let mapped_event = map_event_enum(&callback_def.callback_id);
+            let mapped_event = map_event_enum(&callback_def.callback_id)?;
             let evt_callbacks = self
                 .callbacks
                 .entry(mapped_event)
@@ -173,23 +191,48 @@ impl ControlEvents {
 
             for cb_fn in callback_def.callbacks.iter() {
                 let callback = EventCallback {
-                    member: Self::parse_member(&callback_def.field_name, &member),
+                    member: Self::parse_member(&callback_def.field_name, member, collection)?,
                     path: cb_fn.path.clone(),
-                    args: map_callback_args(&member, &cb_fn.args, &self.callback_args_cache),
+                    args: map_callback_args(
+                        member,
+                        collection,
+                        &cb_fn.args,
+                        &self.callback_args_cache,
+                    )?,
                 };
 
                 evt_callbacks.push(callback);
             }
         }
+
+        Ok(())
     }
 
-    fn parse_member(base: &Option<syn::Expr>, id: &syn::Ident) -> syn::Expr {
-        let tokens = match base {
-            Some(b) => quote! { evt_ui.#id.#b },
-            None => quote! {  evt_ui.#id },
+    fn parse_member(
+        base: &Option<syn::Expr>,
+        id: &syn::Ident,
+        collection: bool,
+    ) -> syn::Result<EventMember> {
+        let tokens = match (base, collection) {
+            (Some(b), false) => quote! { evt_ui.#id.#b },
+            (None, false) => quote! {  evt_ui.#id },
+            (Some(b), true) => quote! { &c.#b },
+            (None, true) => quote! { c },
         };
 
-        syn::parse2(tokens).expect("Failed to generate event match code")
+        let member = syn::parse2(tokens).map_err(|_| {
+            let msg = format!("expected a member of `{}`", id);
+            syn::Error::new_spanned(base, msg)
+        })?;
+
+        if collection {
+            Ok(EventMember::Collection {
+                collection: syn::parse2(quote! { evt_ui.#id })?,
+                element: member,
+            })
+        } else {
+            Ok(EventMember::Control(member))
+        }
     }
 }
 
@@ -255,18 +298,18 @@ impl<'a> ToTokens for EventCallbackCol<'a> {
                 let member = &cb[0].member;
                 let path = &cb[0].path;
                 let args = &cb[0].args;
-                quote! { if &_handle == &#member { #path(#args) } }
+                quote! { if #member { #path(#args) } }
             }
             _ => {
                 // Group callbacks by members
-                let mut members_callbacks: HashMap<&syn::Expr, Vec<(&syn::Path, &Args)>> =
+                let mut members_callbacks: HashMap<&EventMember, Vec<(&syn::Path, &Args)>> =
                     HashMap::new();
                 for c in cb.iter() {
                     let mc = members_callbacks.entry(&c.member).or_insert(Vec::new());
                     mc.push((&c.path, &c.args));
                 }
 
-                let members: Vec<&&syn::Expr> = members_callbacks.keys().collect();
+                let members: Vec<&&EventMember> = members_callbacks.keys().collect();
                 let values: Vec<PathArgs> =
                     members_callbacks.values().map(|c| PathArgs(c)).collect();
 
@@ -276,8 +319,8 @@ impl<'a> ToTokens for EventCallbackCol<'a> {
                 let values = &values[1..];
 
                 quote! {
-                    if &_handle == &#member0 { #value0 }
-                    #(else if &_handle == &#members { #values })*
+                    if #member0 { #value0 }
+                    #(else if #members { #values })*
                 }
             }
         };
@@ -331,25 +374,30 @@ fn top_level_window(field: &syn::Field) -> bool {
 
 fn map_callback_args(
     member: &syn::Ident,
+    collection: bool,
     args: &Option<Punctuated<syn::Ident, Token![,]>>,
     cache: &HashMap<usize, syn::Expr>,
-) -> Punctuated<syn::Expr, Token![,]> {
+) -> syn::Result<Punctuated<syn::Expr, Token![,]>> {
     let mut p = Punctuated::new();
-    if args.is_none() {
-        p.push(cache[&0].clone());
-        return p;
-    }
+    let args = match args {
+        Some(args) => args,
+        None => {
+            p.push(cache[&0].clone());
+            return Ok(p);
+        }
+    };
 
-    let values = ["SELF", "CTRL", "HANDLE", "EVT", "EVT_DATA", "RC_SELF"];
-    for a in args.as_ref().unwrap().iter() {
-        let pos = values.iter().position(|v| &a == &v);
+    for a in args.iter() {
+        let pos = CALLBACK_ARGS.iter().position(|v| a == v);
         match pos {
             Some(0) | Some(5) => {
                 p.push(cache[&0].clone());
             }
+            Some(1) if collection => {
+                p.push(syn::parse2(quote! { &evt_ui.#member[_index] })?);
+            }
             Some(1) => {
-                let param = format!("&evt_ui.{}", member);
-                p.push(syn::parse_str(&param).unwrap());
+                p.push(syn::parse2(quote! { &evt_ui.#member })?);
             }
             Some(2) => {
                 p.push(cache[&2].clone());
@@ -360,28 +408,40 @@ fn map_callback_args(
             Some(4) => {
                 p.push(cache[&4].clone());
             }
+            Some(6) if collection => {
+                p.push(syn::parse2(quote! { _index })?);
+            }
+            Some(6) => {
+                let msg = "`INDEX` can only be used in the events of a collection of controls";
+                return Err(syn::Error::new(a.span(), msg));
+            }
             Some(_) => {
                 unreachable!();
             }
-            None => panic!(
-                "Unknown callback argument: {}. Should be one of those values: {:?}",
-                a, values
-            ),
+            None => {
+                let msg = format!(
+                    "unknown callback argument `{}`. Expected one of: {}",
+                    a,
+                    CALLBACK_ARGS.join(", ")
+                );
+                return Err(syn::Error::new(a.span(), msg));
+            }
         }
     }
 
-    p
+    Ok(p)
 }
 
-fn map_event_enum(ident: &syn::Ident) -> syn::Pat {
-    let evt = ident.to_string();
-    let pat = match &evt as &str {
-        "MousePressLeftUp" | "MousePressLeftDown" | "MousePressRightUp" | "MousePressRightDown" => {
-            format!("Event::OnMousePress(MousePressEvent::{})", evt)
-        }
-        "OnMousePress" => "Event::OnMousePress(_)".into(),
-        _ => format!("Event::{}", evt),
+fn map_event_enum(ident: &syn::Ident) -> syn::Result<syn::Pat> {
+    check_event(ident)?;
+
+    let pat = if MOUSE_PRESS_EVENTS.iter().any(|evt| ident == evt) {
+        quote! { Event::OnMousePress(MousePressEvent::#ident) }
+    } else if ident == "OnMousePress" {
+        quote! { Event::OnMousePress(_) }
+    } else {
+        quote! { Event::#ident }
     };
 
-    syn::parse_str(&pat).unwrap()
+    syn::parse2(pat)
 }
@@ -1,11 +1,14 @@
+use crate::api::{check_builder_params, check_layout_item_params};
 use crate::shared::Parameters;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct GridLayoutChild {
-    pub col: u32,
-    pub row: u32,
-    pub col_span: u32,
-    pub row_span: u32,
+    pub col: syn::Expr,
+    pub row: syn::Expr,
+    pub col_span: syn::Expr,
+    pub row_span: syn::Expr,
+    pub h_align: Option<syn::Expr>,
+    pub v_align: Option<syn::Expr>,
 }
 
 #[derive(Clone, Debug)]
@@ -14,48 +17,82 @@ pub struct FlexboxLayoutChild {
     pub param_values: Vec<syn::Expr>,
 }
 
+/// How the item of a layout child is created
+#[derive(Clone, Debug)]
+pub enum ItemConstructor {
+    /// `DynLayoutItem::new(control, mv, sz)`
+    Dyn { mv: syn::Expr, sz: syn::Expr },
+
+    /// `<Layout as DeriveLayout>::layout_item(control)`
+    Derive(syn::Ident),
+}
+
+/// The child of a layout that takes an item created by `constructor` and configured with its methods
+#[derive(Clone, Debug)]
+pub struct ItemLayoutChild {
+    pub constructor: ItemConstructor,
+    pub param_names: Vec<syn::Ident>,
+    pub param_values: Vec<syn::Expr>,
+}
+
 #[derive(Debug)]
 pub enum LayoutChild {
-    Init {
-        field_name: String,
-        params: Parameters,
-    },
+    Init { params: Parameters },
     Grid(GridLayoutChild),
     Flexbox(FlexboxLayoutChild),
+    Item(ItemLayoutChild),
 }
 
 impl LayoutChild {
-    pub fn prepare(field: &syn::Field) -> Option<LayoutChild> {
-        let field_name = field
-            .ident
-            .as_ref()
-            .map(|i| i.to_string())
-            .unwrap_or("Unnamed".to_string());
-
-        let map_attr = |attr: &syn::Attribute| LayoutChild::Init {
-            field_name,
-            params: syn::parse2(attr.tokens.clone()).unwrap(),
+    pub fn prepare(field: &syn::Field) -> syn::Result<Option<LayoutChild>> {
+        let attr = field.attrs.iter().find(|attr| {
+            attr.path
+                .get_ident()
+                .map(|id| id == "nwg_layout_item")
+                .unwrap_or(false)
+        });
+
+        let attr = match attr {
+            Some(attr) => attr,
+            None => return Ok(None),
         };
 
-        field
-            .attrs
-            .iter()
-            .find(|attr| {
-                attr.path
-                    .get_ident()
-                    .map(|id| id == "nwg_layout_item")
-                    .unwrap_or(false)
-            })
-            .map(map_attr)
+        let params: Parameters = syn::parse2(attr.tokens.clone())?;
+        if !params.params.iter().any(|p| p.ident == "layout") {
+            let msg = "missing `layout` parameter. Ex: `#[nwg_layout_item(layout: my_layout)]`";
+            return Err(syn::Error::new_spanned(attr, msg));
+        }
+
+        Ok(Some(LayoutChild::Init { params }))
     }
 
-    pub fn parse(&mut self, parent_type: &syn::Ident) {
-        if parent_type == "GridLayout" {
-            *self = Self::parse_grid_layout_params(self);
+    pub fn parse(&mut self, parent_type: &syn::Ident) -> syn::Result<()> {
+        if let LayoutChild::Init { params, .. } = self {
+            let names: Vec<syn::Ident> = params.params.iter().map(|p| p.ident.clone()).collect();
+            check_layout_item_params(parent_type, &names)?;
+        }
+
+        *self = if parent_type == "GridLayout" {
+            Self::parse_grid_layout_params(self)
         } else if parent_type == "FlexboxLayout" {
-            *self = Self::parse_flexbox_layout_params(self);
+            Self::parse_flexbox_layout_params(self)
+        } else if parent_type == "DynLayout" {
+            Self::parse_dyn_layout_params(self)
         } else {
-            panic!("Unknown parent type: {:?}", parent_type);
+            let constructor = ItemConstructor::Derive(parent_type.clone());
+            Self::parse_item_layout_params(self, constructor, &["layout"])
+        };
+
+        Ok(())
+    }
+
+    /// The `layout` parameter of an `Init` child
+    pub fn layout_param(&self) -> Option<&syn::Expr> {
+        match self {
+            LayoutChild::Init { params: p, .. } => {
+                p.params.iter().find(|p| p.ident == "layout").map(|p| &p.e)
+            }
+            _ => None,
         }
     }
 
@@ -82,22 +119,28 @@ impl LayoutChild {
     }
 
     fn parse_grid_layout_params(child: &mut LayoutChild) -> LayoutChild {
-        let [mut col, mut row, mut col_span, mut row_span] = [0, 0, 1, 1];
+        let zero: syn::Expr = syn::parse_str("0").unwrap();
+        let one: syn::Expr = syn::parse_str("1").unwrap();
+        let (mut col, mut row) = (zero.clone(), zero);
+        let (mut col_span, mut row_span) = (one.clone(), one);
+        let (mut h_align, mut v_align) = (None, None);
 
         match child {
             LayoutChild::Init { params: p, .. } => {
                 for p in p.params.iter() {
                     let attr_name = p.ident.to_string();
                     match &attr_name as &str {
-                        "col" => col = Self::int_value(&p.e),
-                        "row" => row = Self::int_value(&p.e),
-                        "col_span" => col_span = Self::int_value(&p.e),
-                        "row_span" => row_span = Self::int_value(&p.e),
+                        "col" => col = p.e.clone(),
+                        "row" => row = p.e.clone(),
+                        "col_span" => col_span = p.e.clone(),
+                        "row_span" => row_span = p.e.clone(),
+                        "h_align" => h_align = Some(p.e.clone()),
+                        "v_align" => v_align = Some(p.e.clone()),
                         _ => {}
                     }
                 }
             }
-            _ => panic!("Called parse on a non-Init child layout"),
+            _ => unreachable!("Called parse on a non-Init child layout"),
         };
 
         LayoutChild::Grid(GridLayoutChild {
@@ -105,6 +148,8 @@ impl LayoutChild {
             col_span,
             row,
             row_span,
+            h_align,
+            v_align,
         })
     }
 
@@ -124,7 +169,7 @@ impl LayoutChild {
                     param_values.push(p.e.clone());
                 }
             }
-            _ => panic!("Called parse on a non-Init child layout"),
+            _ => unreachable!("Called parse on a non-Init child layout"),
         }
 
         LayoutChild::Flexbox(FlexboxLayoutChild {
@@ -133,14 +178,54 @@ impl LayoutChild {
         })
     }
 
-    fn int_value(expr: &syn::Expr) -> u32 {
-        match expr {
-            syn::Expr::Lit(lit) => match &lit.lit {
-                syn::Lit::Int(i) => i.base10_parse().unwrap(),
-                _ => panic!("Layout item members must be int literal."),
-            },
-            _ => panic!("Layout item members must be int literal."),
+    fn parse_dyn_layout_params(child: &mut LayoutChild) -> LayoutChild {
+        let zero: syn::Expr = syn::parse_str("(0, 0)").unwrap();
+        let (mut mv, mut sz) = (zero.clone(), zero);
+
+        match child {
+            LayoutChild::Init { params: p, .. } => {
+                for p in p.params.iter() {
+                    if &p.ident == "mv" {
+                        mv = p.e.clone();
+                    } else if &p.ident == "sz" {
+                        sz = p.e.clone();
+                    }
+                }
+            }
+            _ => unreachable!("Called parse on a non-Init child layout"),
+        }
+
+        let constructor = ItemConstructor::Dyn { mv, sz };
+        Self::parse_item_layout_params(child, constructor, &["layout", "mv", "sz"])
+    }
+
+    fn parse_item_layout_params(
+        child: &mut LayoutChild,
+        constructor: ItemConstructor,
+        skip: &[&str],
+    ) -> LayoutChild {
+        let mut param_names = Vec::with_capacity(2);
+        let mut param_values = Vec::with_capacity(2);
+
+        match child {
+            LayoutChild::Init { params: p, .. } => {
+                for p in p.params.iter() {
+                    if skip.iter().any(|name| &p.ident == name) {
+                        continue;
+                    }
+
+                    param_names.push(p.ident.clone());
+                    param_values.push(p.e.clone());
+                }
+            }
+            _ => unreachable!("Called parse on a non-Init child layout"),
         }
+
+        LayoutChild::Item(ItemLayoutChild {
+            constructor,
+            param_names,
+            param_values,
+        })
     }
 }
 
@@ -148,12 +233,10 @@ impl LayoutChild {
 // Main layout
 //
 
-pub fn layout_parameters(field: &syn::Field) -> (Vec<syn::Ident>, Vec<syn::Expr>) {
-    let member = match field.ident.as_ref() {
-        Some(m) => m,
-        None => unreachable!(),
-    };
-
+pub fn layout_parameters(
+    field: &syn::Field,
+    ty: &syn::Ident,
+) -> syn::Result<(Vec<syn::Ident>, Vec<syn::Expr>)> {
     let nwg_layout = |attr: &&syn::Attribute| {
         attr.path
             .get_ident()
@@ -166,10 +249,7 @@ pub fn layout_parameters(field: &syn::Field) -> (Vec<syn::Ident>, Vec<syn::Expr>
         None => unreachable!(),
     };
 
-    let layout: Parameters = match syn::parse2(attr.tokens.clone()) {
-        Ok(a) => a,
-        Err(e) => panic!("Failed to parse field #{}: {}", member, e),
-    };
+    let layout: Parameters = syn::parse2(attr.tokens.clone())?;
 
     let params = layout.params;
     let mut names = Vec::with_capacity(params.len());
@@ -180,5 +260,7 @@ pub fn layout_parameters(field: &syn::Field) -> (Vec<syn::Ident>, Vec<syn::Expr>
         exprs.push(p.e);
     }
 
-    (names, exprs)
+    check_builder_params(ty, &names)?;
+
+    Ok((names, exprs))
 }
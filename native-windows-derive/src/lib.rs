@@ -11,6 +11,7 @@ extern crate quote;
 
 use proc_macro_crate::crate_name;
 
+mod api;
 mod controls;
 mod events;
 mod layouts;
@@ -155,6 +156,25 @@ nwg::TextInput::builder()
     .build(&mut data.text_edit);
 ```
 
+## Collections of controls
+
+`nwg_control` can also be used on a `Vec` or an array of controls. Every element of the collection is built with the
+parameters of the attribute. The parameters can use `INDEX`, the index of the element being built, to compute a different value
+for every control.
+
+A `Vec` is resized to the value of the `count` parameter before the controls are built. Without `count`, the elements already
+in the `Vec` are built. Collections of controls cannot be used as a parent and are never selected by the auto parent detection.
+
+```
+#[nwg_control(count: 5, text: &format!("Button {}", INDEX), position: (10, 10 + 35 * INDEX as i32))]
+#[nwg_layout_item(layout: grid, col: 0, row: INDEX as u32)]
+#[nwg_events(OnButtonClick: [TestApp::button_click(SELF, INDEX)])]
+buttons: Vec<nwg::Button>,
+
+#[nwg_control]
+labels: [nwg::Label; 3],
+```
+
 ## Resources
 
 Use the `nwg_resource` to generate a resource from a struct field. It works the exact same way as `nwg_controls`.
@@ -187,6 +207,7 @@ That's very limiting. For example, if the same callback is used by two different
  - **HANDLE**: Sends the handle of the control. `&ControlHandle`
  - **EVT**: Sends the event that was triggered. `&Event`
  - **EVT_DATA**: Sends the data of the event that was triggered. `&EventData`
+ - **INDEX**: Sends the index of the control in its collection. `usize`. Only available for collections of controls.
 
 It's also possible to not use any parameters, ex: `TestApp::callback1()`.
 
@@ -220,6 +241,37 @@ Use the `nwg_layout` attribute to instance a layout from a struct field and `nwg
 Under the hood, both these attribute work the same way as `nwg_control`. `nwg_layout` uses the builder attribute for a the layout struct and
 `nwg_layout_item` uses the parameters of the item type of the parent (ex: `GridLayoutItem` for `GridLayout`).
 
+`GridLayout` items accept `col`, `row`, `col_span`, `row_span`, `h_align` and `v_align`. The alignments must be a `GridAlign` value.
+The cells and spans are `u32` expressions.
+
+`StackLayout` items accept `stretch`, `align` and `preferred_size`. `WrapLayout` items accept `preferred_size`.
+The parameters are the methods of `StackLayoutItem` and `WrapLayoutItem`.
+
+```
+#[nwg_layout(parent: window, direction: nwg::StackDirection::Horizontal)]
+buttons_layout: nwg::StackLayout,
+
+#[nwg_control(text: "Ok")]
+#[nwg_layout_item(layout: buttons_layout, stretch: 1)]
+ok_button: nwg::Button,
+```
+
+`DynLayout` items accept `mv` and `sz` (the arguments of `DynLayoutItem::new`) and the methods of `DynLayoutItem`
+(ex: `anchors`, `min_size`, `max_size`).
+
+```
+#[nwg_layout(parent: window)]
+dyn_layout: nwg::DynLayout,
+
+#[nwg_control(text: "Ok")]
+#[nwg_layout_item(layout: dyn_layout, anchors: nwg::DynPin::RIGHT | nwg::DynPin::BOTTOM, min_size: (80, 25))]
+ok_button: nwg::Button,
+```
+
+Any other layout type can be used with NWD by implementing the `nwg::DeriveLayout` trait. The layout must have a `builder()` function
+returning a builder with a `child_item` method and a `build` method, like the layouts of NWG. The item of a control is created by
+`DeriveLayout::layout_item` and the parameters of `nwg_layout_item` are called as methods on the item.
+
 NWD cannot guess the parent of layout items.
 
 ## Partials
@@ -259,7 +311,15 @@ struct Ui {
 pub fn derive_ui(input: pm::TokenStream) -> pm::TokenStream {
     let base = parse_macro_input!(input as DeriveInput);
     let names = parse_base_names(&base);
-    let ui_data = parse_ui_data(&base).expect("NWG derive can only be implemented on structs");
+    let ui_data = match parse_ui_data(&base) {
+        Some(data) => data,
+        None => {
+            let msg = "NWG derive can only be implemented on structs";
+            return syn::Error::new(base.ident.span(), msg)
+                .to_compile_error()
+                .into();
+        }
+    };
 
     let module_name = &names.n_module;
     let struct_name = &names.n_struct;
@@ -274,7 +334,10 @@ pub fn derive_ui(input: pm::TokenStream) -> pm::TokenStream {
     let generics = quote! { #lt #generic_params #gt }; // <'a: 'b, T: Trait1, const C>
     let generic_names = quote! { #lt #generic_names #gt }; // <'a, T, C>
 
-    let ui = NwgUi::build(&ui_data, false);
+    let ui = match NwgUi::build(&ui_data, false) {
+        Ok(ui) => ui,
+        Err(e) => return e.to_compile_error().into(),
+    };
     let controls = ui.controls();
     let resources = ui.resources();
     let partials = ui.partials();
@@ -410,8 +473,19 @@ pub fn derive_partial(input: pm::TokenStream) -> pm::TokenStream {
     let generics = quote! { #lt #generic_params #gt }; // <'a: 'b, T: Trait1, const C>
     let generic_names = quote! { #lt #generic_names #gt }; // <'a, T, C>
 
-    let ui_data = parse_ui_data(&base).expect("NWG derive can only be implemented on structs");
-    let ui = NwgUi::build(&ui_data, true);
+    let ui_data = match parse_ui_data(&base) {
+        Some(data) => data,
+        None => {
+            let msg = "NWG derive can only be implemented on structs";
+            return syn::Error::new(base.ident.span(), msg)
+                .to_compile_error()
+                .into();
+        }
+    };
+    let ui = match NwgUi::build(&ui_data, true) {
+        Ok(ui) => ui,
+        Err(e) => return e.to_compile_error().into(),
+    };
     let controls = ui.controls();
     let resources = ui.resources();
     let partials = ui.partials();
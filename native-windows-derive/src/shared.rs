@@ -43,3 +43,37 @@ impl Parse for Parameters {
         Ok(parameters)
     }
 }
+
+/// The kind of a field holding several controls
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Collection {
+    /// `Vec<T>`
+    Vec,
+
+    /// `[T; N]`
+    Array,
+}
+
+/// Returns the kind and the element type of a `Vec<T>` or `[T; N]` field
+pub fn collection_item(ty: &syn::Type) -> Option<(Collection, &syn::Type)> {
+    match ty {
+        syn::Type::Array(array) => Some((Collection::Array, &array.elem)),
+        syn::Type::Path(p) => {
+            let seg = p.path.segments.last()?;
+            if seg.ident != "Vec" {
+                return None;
+            }
+
+            match &seg.arguments {
+                syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                    match &args.args[0] {
+                        syn::GenericArgument::Type(item) => Some((Collection::Vec, item)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
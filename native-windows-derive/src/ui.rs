@@ -1,6 +1,10 @@
+use crate::api::check_builder_params;
 use crate::events::ControlEvents;
-use crate::layouts::{layout_parameters, FlexboxLayoutChild, GridLayoutChild, LayoutChild};
-use crate::shared::Parameters;
+use crate::layouts::{
+    layout_parameters, FlexboxLayoutChild, GridLayoutChild, ItemConstructor, ItemLayoutChild,
+    LayoutChild,
+};
+use crate::shared::{collection_item, Collection, Parameters};
 use quote::ToTokens;
 
 const TOP_LEVEL: &'static [&'static str] = &["Window", "MessageWindow", "ExternCanvas"];
@@ -19,6 +23,10 @@ struct NwgControl<'a> {
 
     ty: syn::Ident,
 
+    // Set if the field is a `Vec` or an array of controls. `count` resizes a `Vec` before building the controls.
+    collection: Option<Collection>,
+    count: Option<syn::Expr>,
+
     layout: Option<LayoutChild>,
     layout_index: usize,
 
@@ -39,7 +47,7 @@ impl<'a> NwgControl<'a> {
         })
     }
 
-    fn parse_type(field: &syn::Field) -> syn::Ident {
+    fn parse_type(field: &syn::Field) -> syn::Result<syn::Ident> {
         // Check for `ty` in nwg_control
         let nwg_control = |attr: &&syn::Attribute| {
             attr.path
@@ -53,62 +61,41 @@ impl<'a> NwgControl<'a> {
             None => unreachable!(),
         };
 
-        let params: Parameters = match syn::parse2(attr.tokens.clone()) {
-            Ok(p) => p,
-            Err(e) => panic!(
-                "Failed to parse field #{}: {}",
-                field.ident.as_ref().unwrap(),
-                e
-            ),
-        };
-
-        match params.params.iter().find(|p| p.ident == "ty").map(|p| &p.e) {
-            Some(syn::Expr::Path(p)) => match p.path.segments.last().map(|seg| seg.ident.clone()) {
-                Some(ty) => {
-                    return ty;
-                }
-                None => {}
-            },
-            _ => {}
+        let params: Parameters = syn::parse2(attr.tokens.clone())?;
+        if let Some(ty) = explicit_type(&params)? {
+            return Ok(ty);
         }
 
-        // Use field type
-        match &field.ty {
-            syn::Type::Path(p) => match p.path.segments.last() {
-                Some(seg) => seg.ident.clone(),
-                None => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_control attribute.", field.ident)
-            },
-            _ => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_control attribute.", field.ident)
-        }
+        // Use field type, or the type of the elements of a collection
+        let ty = match collection_item(&field.ty) {
+            Some((_, item)) => item,
+            None => &field.ty,
+        };
+
+        type_ident(ty, "nwg_control").cloned()
     }
 
-    fn expand_flags(&mut self) {
+    fn expand_flags(&mut self) -> syn::Result<()> {
         let flags_index = self.names.iter().position(|n| n == "flags");
         if let Some(i) = flags_index {
             let old_flags = self.values[i].clone();
-            self.values[i] = crate::controls::expand_flags(&self.id, &self.ty, old_flags);
+            self.values[i] = crate::controls::expand_flags(&self.ty, old_flags)?;
         }
-    }
 
-    fn expand_parent(&mut self) {
-        let parent_index = self.names.iter().position(|n| n == "parent");
-        if parent_index.is_none() {
-            return;
-        }
+        Ok(())
+    }
 
-        let i = parent_index.unwrap();
-        let parent_expr: syn::Expr = match &self.values[i] {
-            syn::Expr::Path(p) => {
-                let id = &p.path.segments.last().unwrap().ident;
-                self.parent_id = Some(id.to_string());
-                syn::parse_str(&format!("&data.{}", id)).unwrap()
-            }
-            _ => {
-                panic!("Bad expression type for parent of field {}", self.id);
-            }
+    fn expand_parent(&mut self, parents: &ParentFields) -> syn::Result<()> {
+        let parent_index = match self.names.iter().position(|n| n == "parent") {
+            Some(i) => i,
+            None => return Ok(()),
         };
 
-        self.values[i] = parent_expr;
+        let id = parents.parent_ident(&self.values[parent_index])?;
+        self.parent_id = Some(id.to_string());
+        self.values[parent_index] = syn::parse2(quote! { &data.#id })?;
+
+        Ok(())
     }
 }
 
@@ -129,7 +116,7 @@ impl<'a> NwgResource<'a> {
         })
     }
 
-    fn parse_type(field: &syn::Field) -> syn::Ident {
+    fn parse_type(field: &syn::Field) -> syn::Result<syn::Ident> {
         // Check for `ty` in nwg_resource
         let nwg_resource = |attr: &&syn::Attribute| {
             attr.path
@@ -143,33 +130,13 @@ impl<'a> NwgResource<'a> {
             None => unreachable!(),
         };
 
-        let params: Parameters = match syn::parse2(attr.tokens.clone()) {
-            Ok(p) => p,
-            Err(e) => panic!(
-                "Failed to parse field #{}: {}",
-                field.ident.as_ref().unwrap(),
-                e
-            ),
-        };
-
-        match params.params.iter().find(|p| p.ident == "ty").map(|p| &p.e) {
-            Some(syn::Expr::Path(p)) => match p.path.segments.last().map(|seg| seg.ident.clone()) {
-                Some(ty) => {
-                    return ty;
-                }
-                None => {}
-            },
-            _ => {}
+        let params: Parameters = syn::parse2(attr.tokens.clone())?;
+        if let Some(ty) = explicit_type(&params)? {
+            return Ok(ty);
         }
 
         // Use field type
-        match &field.ty {
-            syn::Type::Path(p) => match p.path.segments.last() {
-                Some(seg) => seg.ident.clone(),
-                None => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_resource attribute.", field.ident)
-            },
-            _ => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_resource attribute.", field.ident)
-        }
+        type_ident(&field.ty, "nwg_resource").cloned()
     }
 }
 
@@ -191,36 +158,21 @@ impl<'a> NwgLayout<'a> {
         })
     }
 
-    fn parse_type(field: &syn::Field) -> &syn::Ident {
+    fn parse_type(field: &syn::Field) -> syn::Result<&syn::Ident> {
         // TODO: extract type from nwg_layout first
-
-        match &field.ty {
-            syn::Type::Path(p) => match p.path.segments.last() {
-                Some(seg) => &seg.ident,
-                None => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_control attribute.", field.ident)
-            },
-            _ => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_control attribute.", field.ident)
-        }
+        type_ident(&field.ty, "nwg_layout")
     }
 
-    fn expand_parent(&mut self) {
-        let parent_index = self.names.iter().position(|n| n == "parent");
-        if parent_index.is_none() {
-            return;
-        }
-
-        let i = parent_index.unwrap();
-        let parent_expr: syn::Expr = match &self.values[i] {
-            syn::Expr::Path(p) => {
-                let id = &p.path.segments.last().unwrap().ident;
-                syn::parse_str(&format!("&ui.{}", id)).unwrap()
-            }
-            _ => {
-                panic!("Bad expression type for parent of field {}", self.id);
-            }
+    fn expand_parent(&mut self, parents: &ParentFields) -> syn::Result<()> {
+        let parent_index = match self.names.iter().position(|n| n == "parent") {
+            Some(i) => i,
+            None => return Ok(()),
         };
 
-        self.values[i] = parent_expr;
+        let id = parents.parent_ident(&self.values[parent_index])?;
+        self.values[parent_index] = syn::parse2(quote! { &ui.#id })?;
+
+        Ok(())
     }
 }
 
@@ -240,17 +192,11 @@ impl<'a> NwgPartial<'a> {
         })
     }
 
-    fn parse_type(field: &syn::Field) -> &syn::Ident {
-        match &field.ty {
-            syn::Type::Path(p) => match p.path.segments.last() {
-                Some(seg) => &seg.ident,
-                None => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_partial attribute.", field.ident)
-            },
-            _ => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_partial attribute.", field.ident)
-        }
+    fn parse_type(field: &syn::Field) -> syn::Result<&syn::Ident> {
+        type_ident(&field.ty, "nwg_partial")
     }
 
-    fn parse_parent(field: &syn::Field) -> Option<syn::Ident> {
+    fn parse_parent(field: &syn::Field, parents: &ParentFields) -> syn::Result<Option<syn::Ident>> {
         let nwg_partial = |attr: &&syn::Attribute| {
             attr.path
                 .get_ident()
@@ -263,14 +209,16 @@ impl<'a> NwgPartial<'a> {
             None => unreachable!(),
         };
 
-        let params: Parameters = match syn::parse2(attr.tokens.clone()) {
-            Ok(p) => p,
-            Err(e) => panic!(
-                "Failed to parse field #{}: {}",
-                field.ident.as_ref().unwrap(),
-                e
-            ),
-        };
+        let params: Parameters = syn::parse2(attr.tokens.clone())?;
+        for p in params.params.iter() {
+            if p.ident != "parent" {
+                let msg = format!(
+                    "unknown parameter `{}` for a partial. Expected: parent",
+                    p.ident
+                );
+                return Err(syn::Error::new(p.ident.span(), msg));
+            }
+        }
 
         let parent_value = params
             .params
@@ -278,11 +226,88 @@ impl<'a> NwgPartial<'a> {
             .find(|p| p.ident == "parent")
             .map(|p| &p.e);
         match parent_value {
-            Some(v) => match v {
-                syn::Expr::Path(p) => p.path.segments.last().map(|seg| seg.ident.clone()),
-                _ => None,
-            },
-            None => None,
+            Some(v) => parents.parent_ident(v).map(|id| Some(id.clone())),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Returns the type set with the `ty` parameter of an attribute
+fn explicit_type(params: &Parameters) -> syn::Result<Option<syn::Ident>> {
+    match params.params.iter().find(|p| p.ident == "ty").map(|p| &p.e) {
+        Some(syn::Expr::Path(p)) => Ok(p.path.segments.last().map(|seg| seg.ident.clone())),
+        Some(other) => Err(syn::Error::new_spanned(other, "expected a type name")),
+        None => Ok(None),
+    }
+}
+
+/// Returns the name of the type of a field
+fn type_ident<'a>(ty: &'a syn::Type, attr_name: &str) -> syn::Result<&'a syn::Ident> {
+    if let syn::Type::Path(p) = ty {
+        if let Some(seg) = p.path.segments.last() {
+            return Ok(&seg.ident);
+        }
+    }
+
+    let msg = match attr_name {
+        "nwg_control" | "nwg_resource" => format!(
+            "impossible to parse the type of this field. Try specifying it with `#[{}(ty: Type)]`",
+            attr_name
+        ),
+        _ => "impossible to parse the type of this field".to_string(),
+    };
+
+    Err(syn::Error::new_spanned(ty, msg))
+}
+
+/// The control fields of the ui, used to validate the `parent` parameters
+struct ParentFields {
+    controls: Vec<syn::Ident>,
+    collections: Vec<syn::Ident>,
+}
+
+impl ParentFields {
+    fn new<'a>(fields: impl Iterator<Item = &'a syn::Field>) -> ParentFields {
+        let mut parents = ParentFields {
+            controls: Vec::new(),
+            collections: Vec::new(),
+        };
+
+        for field in fields.filter(|field| NwgControl::valid(field)) {
+            let id = field.ident.clone().unwrap();
+            match collection_item(&field.ty) {
+                Some(_) => parents.collections.push(id),
+                None => parents.controls.push(id),
+            }
+        }
+
+        parents
+    }
+
+    /// Returns the name of the field referenced by a `parent` parameter. The parent must be a control of the ui.
+    fn parent_ident<'a>(&self, value: &'a syn::Expr) -> syn::Result<&'a syn::Ident> {
+        let id = match value {
+            syn::Expr::Path(p) => p.path.get_ident(),
+            _ => None,
+        };
+
+        match id {
+            Some(id) if self.controls.contains(id) => Ok(id),
+            Some(id) if self.collections.contains(id) => {
+                let msg = format!(
+                    "`{}` is a collection of controls and cannot be a parent",
+                    id
+                );
+                Err(syn::Error::new(id.span(), msg))
+            }
+            Some(id) => {
+                let msg = format!("`{}` is not a control of this struct", id);
+                Err(syn::Error::new(id.span(), msg))
+            }
+            None => Err(syn::Error::new_spanned(
+                value,
+                "the parent must be the name of a control field",
+            )),
         }
     }
 }
@@ -302,10 +327,30 @@ impl<'a> ToTokens for NwgUiControls<'a> {
                 let member = item.id;
                 let names = &item.names;
                 let values = &item.values;
+
+                if item.collection.is_none() {
+                    let control_tk = quote! {
+                        #ty::builder()
+                            #(.#names(#values))*
+                            .build(&mut data.#member)?;
+                    };
+
+                    control_tk.to_tokens(tokens);
+                    return;
+                }
+
+                let count = item.count.iter();
                 let control_tk = quote! {
-                    #ty::builder()
-                        #(.#names(#values))*
-                        .build(&mut data.#member)?;
+                    #(data.#member = (0..#count).map(|_| Default::default()).collect();)*
+
+                    for _index in 0..data.#member.len() {
+                        #[allow(non_snake_case, unused_variables)]
+                        let INDEX = _index;
+
+                        #ty::builder()
+                            #(.#names(#values))*
+                            .build(&mut data.#member[_index])?;
+                    }
                 };
 
                 control_tk.to_tokens(tokens);
@@ -382,19 +427,59 @@ impl<'a> ToTokens for NwgUiLayouts<'a> {
             fn to_tokens(&self, tokens: &mut pm2::TokenStream) {
                 let c = &self.0;
                 let id = &c.id;
+                let member = match c.collection {
+                    Some(_) => quote! { ui.#id[_index] },
+                    None => quote! { ui.#id },
+                };
 
                 let item_tk = match &c.layout {
-                    Some(LayoutChild::Grid( GridLayoutChild {col, row, col_span, row_span} )) =>
+                    Some(LayoutChild::Grid(GridLayoutChild {
+                        col,
+                        row,
+                        col_span,
+                        row_span,
+                        h_align,
+                        v_align,
+                    })) => {
+                        let h_align = h_align.iter();
+                        let v_align = v_align.iter();
                         quote! {
-                            child_item(GridLayoutItem::new(&ui.#id, #col, #row, #col_span, #row_span))
-                        },
-                    Some(LayoutChild::Flexbox( FlexboxLayoutChild { param_names, param_values } )) =>
+                            child_item(
+                                GridLayoutItem::new(&#member, #col, #row, #col_span, #row_span)
+                                    #(.h_align(#h_align))*
+                                    #(.v_align(#v_align))*
+                            )
+                        }
+                    }
+                    Some(LayoutChild::Flexbox(FlexboxLayoutChild {
+                        param_names,
+                        param_values,
+                    })) => quote! {
+                        child(&#member)
+                        #(.#param_names(#param_values))*
+                    },
+                    Some(LayoutChild::Item(ItemLayoutChild {
+                        constructor,
+                        param_names,
+                        param_values,
+                    })) => {
+                        let item = match constructor {
+                            ItemConstructor::Dyn { mv, sz } => {
+                                quote! { DynLayoutItem::new(&#member, #mv, #sz) }
+                            }
+                            ItemConstructor::Derive(ty) => {
+                                quote! { <#ty as DeriveLayout>::layout_item(&#member) }
+                            }
+                        };
+
                         quote! {
-                            child(&ui.#id)
-                            #(.#param_names(#param_values))*
-                        },
-                    Some(LayoutChild::Init{ field_name, .. }) => panic!("Unmatched layout item for field \"{}\", Did you forget the `layout` parameter?", field_name),
-                    None => panic!("Unfiltered layout item")
+                            child_item(
+                                #item
+                                    #(.#param_names(#param_values))*
+                            )
+                        }
+                    }
+                    Some(LayoutChild::Init { .. }) | None => unreachable!("Unmatched layout item"),
                 };
 
                 item_tk.to_tokens(tokens);
@@ -414,11 +499,41 @@ impl<'a> ToTokens for NwgUiLayouts<'a> {
                 let values = &self.layout.values;
                 let children = &self.children;
 
+                if children.iter().all(|c| c.0.collection.is_none()) {
+                    let layout_tk = quote! {
+                        #ty::builder()
+                            #(.#names(#values))*
+                            #(.#children)*
+                            .build(&ui.#id)?;
+                    };
+                    layout_tk.to_tokens(tokens);
+                    return;
+                }
+
+                // Collections add their items in a loop
+                let children = children.iter().map(|child| match child.0.collection {
+                    Some(_) => {
+                        let collection = child.0.id;
+                        quote! {
+                            for _index in 0..ui.#collection.len() {
+                                #[allow(non_snake_case, unused_variables)]
+                                let INDEX = _index;
+                                layout_builder = layout_builder.#child;
+                            }
+                        }
+                    }
+                    None => quote! { layout_builder = layout_builder.#child; },
+                });
+
                 let layout_tk = quote! {
-                    #ty::builder()
-                        #(.#names(#values))*
-                        #(.#children)*
-                        .build(&ui.#id)?;
+                    {
+                        let mut layout_builder = #ty::builder()
+                            #(.#names(#values))*;
+
+                        #(#children)*
+
+                        layout_builder.build(&ui.#id)?;
+                    }
                 };
                 layout_tk.to_tokens(tokens);
             }
@@ -498,10 +613,12 @@ pub struct NwgUi<'a> {
 }
 
 impl<'a> NwgUi<'a> {
-    pub fn build(data: &'a syn::DataStruct, partial: bool) -> NwgUi<'a> {
+    pub fn build(data: &'a syn::DataStruct, partial: bool) -> syn::Result<NwgUi<'a>> {
+        const NAMED_FIELDS: &str = "Ui structure must have named fields";
         let named_fields = match &data.fields {
             syn::Fields::Named(n) => &n.named,
-            _ => panic!("Ui structure must have named fields"),
+            syn::Fields::Unnamed(u) => return Err(syn::Error::new_spanned(u, NAMED_FIELDS)),
+            syn::Fields::Unit => return Err(syn::Error::new(data.struct_token.span, NAMED_FIELDS)),
         };
 
         let mut controls = Vec::with_capacity(named_fields.len());
@@ -513,18 +630,44 @@ impl<'a> NwgUi<'a> {
         let partial_parent_expr: syn::Expr = syn::parse_str("parent_ref.unwrap()").unwrap();
         let parent_ident = syn::Ident::new("parent", pm2::Span::call_site());
 
+        // The fields that can be referenced by a `parent` parameter
+        let parents = ParentFields::new(named_fields.iter());
+
         // First pass: parse controls, layouts, and events
         for (field_pos, field) in named_fields.iter().enumerate() {
+            let id = field.ident.as_ref().unwrap();
+
             if NwgControl::valid(field) {
-                let id = field.ident.as_ref().unwrap();
-                let ty = NwgControl::parse_type(field);
-                let (names, values) = crate::controls::parameters(field, "nwg_control");
+                let ty = NwgControl::parse_type(field)?;
+                let (mut names, mut values) = crate::controls::parameters(field, "nwg_control")?;
+                let collection = collection_item(&field.ty).map(|(kind, _)| kind);
+
+                let count = match names.iter().position(|n| n == "count") {
+                    Some(i) if collection == Some(Collection::Vec) => {
+                        names.remove(i);
+                        Some(values.remove(i))
+                    }
+                    Some(i) => {
+                        let msg = "`count` can only be used on a `Vec` of controls";
+                        return Err(syn::Error::new(names[i].span(), msg));
+                    }
+                    None => None,
+                };
+
+                check_builder_params(&ty, &names)?;
+
+                if collection.is_some() && TOP_LEVEL.iter().any(|top| ty == top) {
+                    let msg = format!("a collection of `{}` is not supported", ty);
+                    return Err(syn::Error::new_spanned(&field.ty, msg));
+                }
 
                 let f = NwgControl {
                     id,
                     parent_id: None,
                     ty,
-                    layout: LayoutChild::prepare(field),
+                    collection,
+                    count,
+                    layout: LayoutChild::prepare(field)?,
                     layout_index: 0,
                     names,
                     values,
@@ -532,15 +675,19 @@ impl<'a> NwgUi<'a> {
                 };
 
                 events.add_top_level_handle(field);
-                events.parse(field);
+                events.parse(field)?;
 
                 controls.push(f);
+            } else if LayoutChild::prepare(field)?.is_some() {
+                let msg =
+                    "`nwg_layout_item` can only be used on a field with a `nwg_control` attribute";
+                return Err(syn::Error::new(id.span(), msg));
             }
 
             if NwgResource::valid(field) {
-                let id = field.ident.as_ref().unwrap();
-                let ty = NwgResource::parse_type(field);
-                let (names, values) = crate::controls::parameters(field, "nwg_resource");
+                let ty = NwgResource::parse_type(field)?;
+                let (names, values) = crate::controls::parameters(field, "nwg_resource")?;
+                check_builder_params(&ty, &names)?;
 
                 let f = NwgResource {
                     id,
@@ -551,9 +698,8 @@ impl<'a> NwgUi<'a> {
 
                 resources.push(f);
             } else if NwgLayout::valid(field) {
-                let id = field.ident.as_ref().unwrap();
-                let ty = NwgLayout::parse_type(field);
-                let (names, values) = layout_parameters(field);
+                let ty = NwgLayout::parse_type(field)?;
+                let (names, values) = layout_parameters(field, ty)?;
 
                 let layout = NwgLayout {
                     id,
@@ -565,13 +711,13 @@ impl<'a> NwgUi<'a> {
                 layouts.push(layout);
             } else if NwgPartial::valid(field) {
                 let partial = NwgPartial {
-                    id: field.ident.as_ref().unwrap(),
-                    ty: NwgPartial::parse_type(field),
-                    parent: NwgPartial::parse_parent(field),
+                    id,
+                    ty: NwgPartial::parse_type(field)?,
+                    parent: NwgPartial::parse_parent(field, &parents)?,
                 };
 
                 events.add_partial(&partial.id);
-                events.parse(field);
+                events.parse(field)?;
 
                 partials.push(partial);
             }
@@ -582,13 +728,14 @@ impl<'a> NwgUi<'a> {
             // Add the parent value of the layout object if it was not already defined
             let has_attr_parent = layouts[i].names.iter().any(|n| n == "parent");
             if has_attr_parent {
-                layouts[i].expand_parent();
+                layouts[i].expand_parent(&parents)?;
             } else {
                 if partial {
                     layouts[i].names.push(parent_ident.clone());
                     layouts[i].values.push(partial_parent_expr.clone());
                 } else {
-                    panic!("Auto detection of layout parent outside of partial is not yet implemented!");
+                    let msg = "missing `parent` parameter. Ex: `#[nwg_layout(parent: window)]`";
+                    return Err(syn::Error::new(layouts[i].id.span(), msg));
                 }
             }
 
@@ -598,13 +745,28 @@ impl<'a> NwgUi<'a> {
                     let layout = &layouts[i];
 
                     if child_layout.parent_matches(&layout.id) {
-                        child_layout.parse(&layout.ty);
+                        child_layout.parse(&layout.ty)?;
                         control.layout_index = i;
                     }
                 }
             }
         }
 
+        // Layout items that do not match any layout
+        for control in controls.iter() {
+            if let Some(layout) = control.layout.as_ref().and_then(|l| l.layout_param()) {
+                let msg = match layout {
+                    syn::Expr::Path(p) if p.path.get_ident().is_some() => format!(
+                        "`{}` is not a layout of this struct",
+                        p.path.get_ident().unwrap()
+                    ),
+                    _ => "the layout must be the name of a layout field".to_string(),
+                };
+
+                return Err(syn::Error::new_spanned(layout, msg));
+            }
+        }
+
         for i in 0..(controls.len()) {
             let top_level = TOP_LEVEL.iter().any(|top| &controls[i].ty == top);
             if top_level {
@@ -613,13 +775,13 @@ impl<'a> NwgUi<'a> {
 
             let has_attr_parent = controls[i].names.iter().any(|n| n == "parent");
             if has_attr_parent {
-                controls[i].expand_parent();
+                controls[i].expand_parent(&parents)?;
             } else {
                 // Rewind the controls set the parent to the nearest control that supports children
                 let parent = controls[0..i]
                     .iter()
                     .rev()
-                    .find(|i| AUTO_PARENT.iter().any(|top| i.ty == top));
+                    .find(|i| i.collection.is_none() && AUTO_PARENT.iter().any(|top| i.ty == top));
 
                 if let Some(parent) = parent {
                     let parent_id = Some(parent.id.to_string());
@@ -638,27 +800,37 @@ impl<'a> NwgUi<'a> {
         }
 
         // Parent Weight
-        fn compute_weight(controls: &[NwgControl], index: usize, weight: &mut [u16; 2]) {
-            match &controls[index].parent_id {
-                Some(p) => {
-                    if let Some(parent_index) = controls.iter().position(|c| &c.id == &p) {
-                        compute_weight(controls, parent_index, weight);
-                        weight[0] += 1;
+        fn compute_weight(
+            controls: &[NwgControl],
+            index: usize,
+            depth: usize,
+            weight: &mut [u16; 2],
+        ) -> syn::Result<()> {
+            if let Some(p) = &controls[index].parent_id {
+                if let Some(parent_index) = controls.iter().position(|c| c.id == p) {
+                    if depth > controls.len() {
+                        let id = controls[index].id;
+                        let msg = format!("the parent of `{}` is one of its own children", id);
+                        return Err(syn::Error::new(id.span(), msg));
                     }
+
+                    compute_weight(controls, parent_index, depth + 1, weight)?;
+                    weight[0] += 1;
                 }
-                None => {}
             }
+
+            Ok(())
         }
 
         for i in 0..(controls.len()) {
             let mut weight = controls[i].weight;
-            compute_weight(&controls, i, &mut weight);
+            compute_weight(&controls, i, 0, &mut weight)?;
             controls[i].weight = weight;
         }
 
         // Helpers
         for control in controls.iter_mut() {
-            control.expand_flags();
+            control.expand_flags()?;
         }
 
         // Sort by weight
@@ -668,13 +840,13 @@ impl<'a> NwgUi<'a> {
             a.cmp(&b)
         });
 
-        NwgUi {
+        Ok(NwgUi {
             controls,
             resources,
             layouts,
             partials,
             events,
-        }
+        })
     }
 
     pub fn controls(&self) -> NwgUiControls {
@@ -0,0 +1,10 @@
+//! Compile error tests for the derive macros. The expected diagnostics are in `tests/ui/*.stderr`.
+//! Run `TRYBUILD=overwrite cargo test --test ui` to update them after changing an error message.
+
+// native-windows-gui only builds on Windows
+#[cfg(windows)]
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
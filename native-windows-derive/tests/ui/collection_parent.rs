@@ -0,0 +1,16 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_control(count: 3)]
+    frames: Vec<nwg::Frame>,
+
+    #[nwg_control(parent: frames)]
+    button: nwg::Button,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control(flags: nwg::WindowFlags::VISIBLE)]
+    window: nwg::Window,
+}
+
+fn main() {}
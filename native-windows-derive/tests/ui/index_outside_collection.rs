@@ -0,0 +1,18 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_control]
+    #[nwg_events(OnButtonClick: [App::clicked(SELF, INDEX)])]
+    button: nwg::Button,
+}
+
+impl App {
+    fn clicked(&self, _index: usize) {}
+}
+
+fn main() {}
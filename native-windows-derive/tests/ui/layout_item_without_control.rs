@@ -0,0 +1,16 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window)]
+    layout: nwg::GridLayout,
+
+    #[nwg_layout_item(layout: layout)]
+    font: nwg::Font,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_layout]
+    layout: nwg::GridLayout,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(NwgUi)]
+pub enum App {
+    Window(nwg::Window),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_control(parent: frame2)]
+    frame1: nwg::Frame,
+
+    #[nwg_control(parent: frame1)]
+    frame2: nwg::Frame,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App(nwg::Window);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    #[nwg_events(OnInit: [App::init(SELF, CONTROL)])]
+    window: nwg::Window,
+}
+
+impl App {
+    fn init(&self, _window: &nwg::Window) {}
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    #[nwg_events(OnWindowClosed: [nwg::stop_thread_dispatch()])]
+    window: nwg::Window,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use native_windows_derive::NwgUi;
+use native_windows_gui as nwg;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window)]
+    layout: nwg::StackLayout,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: layout, strech: 1)]
+    button: nwg::Button,
+}
+
+fn main() {}
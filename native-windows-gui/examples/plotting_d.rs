@@ -48,9 +48,10 @@ pub struct PlottingExample {
     #[nwg_layout(parent: window, min_size: [400, 250])]
     layout: nwg::GridLayout,
 
-    #[nwg_control(parent: window)]
+    #[nwg_control(parent: window, crosshair: true, tooltip: true, x_range: -100.0..100.0, y_range: -200.0..200.0)]
     #[nwg_events(
-        OnMouseMove: [PlottingExample::update_interactive],
+        OnPlottersViewChanged: [PlottingExample::update_interactive],
+        OnPlottersHover: [PlottingExample::update_interactive],
     )]
     #[nwg_layout_item(layout: layout, col: 0, row: 0, col_span: 3)]
     graph: nwg::Plotters,
@@ -76,7 +77,7 @@ pub struct PlottingExample {
     label1: nwg::Label,
 
     #[nwg_control(parent: options_frame, selected_index: Some(0), collection: EXAMPLES.to_vec())]
-    #[nwg_events(OnListBoxSelect: [PlottingExample::check_animate, PlottingExample::draw_graph])]
+    #[nwg_events(OnListBoxSelect: [PlottingExample::check_animate, PlottingExample::check_interactive, PlottingExample::draw_graph])]
     #[nwg_layout_item(layout: options_layout, size: Size { width: Auto, height: Points(200.0) })]
     example_list: nwg::ListBox<&'static str>,
 }
@@ -323,14 +324,25 @@ impl PlottingExample {
     }
 
     fn interactive_chart(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let root = self.graph.draw().unwrap();
+        // Zoom with the mouse wheel and drag the chart to move it
+        let view = self.graph.view();
+        let root = self.graph.draw()?;
 
         let mut chart = ChartBuilder::on(&root)
             .caption("y=x*2", ("sans-serif", 50).into_font())
             .margin(50)
             .x_label_area_size(30)
             .y_label_area_size(30)
-            .build_cartesian_2d(-100..100, -200..200)?;
+            .build_cartesian_2d(view.x_range(), view.y_range())?;
+
+        // Tell the control where the data is so it can map the mouse position to it
+        self.graph
+            .set_plot_area(chart.plotting_area().get_pixel_range());
+
+        let points: Vec<(f64, f64)> = (-10..=10)
+            .map(|x| (x as f64 * 10.0, x as f64 * 20.0))
+            .collect();
+        self.graph.set_series("y = x*2", points.clone());
 
         chart
             .configure_mesh()
@@ -342,35 +354,18 @@ impl PlottingExample {
             .draw()?;
 
         chart
-            .draw_series(LineSeries::new(
-                [-100, -50, 0, 50, 100].iter().map(|&x| (x, x * 2)),
-                &RED,
-            ))?
+            .draw_series(LineSeries::new(points.iter().cloned(), &RED))?
             .label("y = x*2")
             .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
 
-        chart
-            .configure_series_labels()
-            .border_style(&BLACK)
-            .draw()?;
-
-        // As far as I know, there's no way to fetch the margin in pixels within a chart, so you have to use trial and error
-        // 80 & 130 seems to be good enough for this case
-        let (x, _) = nwg::GlobalCursor::local_position(&self.graph, None);
-        let max_x = self.graph.size().0 as i32;
-        let percent = (x - 80) as f32 / (max_x - 130) as f32;
-        let value = (((percent - 0.5) * 200.0) as i32).clamp(-100, 100);
-
-        chart.draw_series(PointSeries::of_element(
-            [value].iter().map(|&x| (x, x * 2)),
-            5,
-            ShapeStyle::from(&RED).filled(),
-            &|coord, size, style| {
-                EmptyElement::at(coord)
-                    + Circle::new((0, 0), size, style)
-                    + Text::new(format!("{:?}", coord), (0, 15), ("sans-serif", 15))
-            },
-        ))?;
+        // The crosshair and the tooltip are drawn by the control, only the value under the mouse is drawn here
+        if let Some((x, y)) = self.graph.hover() {
+            chart.draw_series(std::iter::once(Text::new(
+                format!("({:.1}, {:.1})", x, y),
+                (view.x_min, view.y_max),
+                ("sans-serif", 15),
+            )))?;
+        }
 
         chart
             .configure_series_labels()
@@ -457,6 +452,12 @@ impl PlottingExample {
         }
     }
 
+    fn check_interactive(&self) {
+        let index = self.example_list.selection().unwrap_or(0);
+        self.graph.set_interactive(index == 5);
+        self.graph.reset_view();
+    }
+
     fn update_interactive(&self) {
         let index = self.example_list.selection().unwrap_or(0);
         if index == 5 {
@@ -0,0 +1,305 @@
+use super::{ControlBase, ControlHandle};
+use crate::win32::base_helper::{check_hwnd, to_utf16};
+use crate::win32::window_helper as wh;
+use crate::NwgError;
+use winapi::shared::minwindef::{LPARAM, WPARAM};
+use winapi::um::commctrl::{ACS_AUTOPLAY, ACS_CENTER, ACS_TIMER, ACS_TRANSPARENT};
+use winapi::um::winuser::{WS_DISABLED, WS_VISIBLE};
+
+#[cfg(feature = "embed-resource")]
+use crate::EmbedResource;
+
+const NOT_BOUND: &'static str = "AnimationFrame is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: AnimationFrame handle is not HWND!";
+
+bitflags! {
+    /**
+        The animation frame flags
+
+        * NONE:        No flags. Equivalent to a invisible animation frame.
+        * VISIBLE:     The animation frame is immediatly visible after creation
+        * DISABLED:    The animation frame cannot be interacted with by the user.
+        * CENTER:      Center the animation in the control instead of resizing the control to the animation size
+        * TRANSPARENT: Draw the background of the animation with the background color of the parent
+        * AUTO_PLAY:   Play the animation in a loop as soon as it is opened
+        * TIMER:       Play the animation in the UI thread with a timer instead of a background thread
+    */
+    pub struct AnimationFrameFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const CENTER = ACS_CENTER;
+        const TRANSPARENT = ACS_TRANSPARENT;
+        const AUTO_PLAY = ACS_AUTOPLAY;
+        const TIMER = ACS_TIMER;
+    }
+}
+
+/**
+An animation frame plays a silent AVI clip. It is mostly used to display busy indicators (ex: a file copy animation).
+Only uncompressed or RLE compressed AVI clips without sound are supported.
+
+Requires the `animation-frame` feature.
+
+**Builder parameters:**
+  * `parent`:   **Required.** The animation frame parent container.
+  * `size`:     The animation frame size.
+  * `position`: The animation frame position.
+  * `flags`:    A combination of the AnimationFrameFlags values.
+  * `ex_flags`: A combination of win32 window extended flags. Unlike `flags`, ex_flags must be used straight from winapi
+  * `source`:   The path to the AVI file to open
+  * `play`:     Play the animation in a loop after the control is created
+
+**Control events:**
+  * `OnAnimationStart`: When the animation starts playing
+  * `OnAnimationStop`: When the animation stops playing
+  * `MousePress(_)`: Generic mouse press events on the animation frame
+  * `OnMouseMove`: Generic mouse mouse event
+
+```rust
+use native_windows_gui as nwg;
+fn build_busy(anim: &mut nwg::AnimationFrame, window: &nwg::Window) {
+    nwg::AnimationFrame::builder()
+        .source(Some("busy.avi"))
+        .play(true)
+        .parent(window)
+        .build(anim);
+}
+```
+*/
+#[derive(Default, PartialEq, Eq)]
+pub struct AnimationFrame {
+    pub handle: ControlHandle,
+}
+
+impl AnimationFrame {
+    pub fn builder<'a>() -> AnimationFrameBuilder<'a> {
+        AnimationFrameBuilder {
+            size: (100, 100),
+            position: (0, 0),
+            flags: None,
+            ex_flags: 0,
+            source: None,
+            play: false,
+            parent: None,
+        }
+    }
+
+    /// Open an AVI clip from a file. Replaces the current clip.
+    pub fn open_file<'a>(&self, path: &'a str) -> Result<(), NwgError> {
+        use winapi::um::commctrl::ACM_OPENW;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let path = to_utf16(path);
+        match wh::send_message(handle, ACM_OPENW, 0, path.as_ptr() as LPARAM) {
+            0 => Err(NwgError::resource_create(
+                "Failed to open the AVI clip of an AnimationFrame",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Open an AVI clip from the resources of a module. The clip must be stored as an `AVI` resource.
+    #[cfg(feature = "embed-resource")]
+    pub fn open_resource(&self, embed: &EmbedResource, id: usize) -> Result<(), NwgError> {
+        use winapi::um::commctrl::ACM_OPENW;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        match wh::send_message(handle, ACM_OPENW, embed.hinst as WPARAM, id as LPARAM) {
+            0 => Err(NwgError::resource_create(
+                "Failed to open the AVI clip of an AnimationFrame",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Close the current clip. The control becomes blank.
+    pub fn close(&self) {
+        use winapi::um::commctrl::ACM_OPENW;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, ACM_OPENW, 0, 0);
+    }
+
+    /**
+        Play the frames `from` to `to` of the clip. `to` set to `None` plays until the last frame.
+        `repeat` is the number of times the frames are played. `None` repeats the animation indefinitely.
+    */
+    pub fn play(&self, from: u16, to: Option<u16>, repeat: Option<u32>) {
+        use winapi::shared::minwindef::MAKELONG;
+        use winapi::um::commctrl::ACM_PLAY;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let repeat = repeat.map(|r| r as i32).unwrap_or(-1);
+        let to = to.unwrap_or(0xFFFF);
+        wh::send_message(
+            handle,
+            ACM_PLAY,
+            repeat as WPARAM,
+            MAKELONG(from, to) as LPARAM,
+        );
+    }
+
+    /// Stop the animation. The current frame stays displayed.
+    pub fn stop(&self) {
+        use winapi::um::commctrl::ACM_STOP;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, ACM_STOP, 0, 0);
+    }
+
+    /// Display a single frame of the clip
+    pub fn seek(&self, frame: u16) {
+        self.play(frame, Some(frame), Some(1));
+    }
+
+    /// Return `true` if the animation is playing
+    pub fn playing(&self) -> bool {
+        use winapi::um::commctrl::ACM_ISPLAYING;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, ACM_ISPLAYING, 0, 0) != 0
+    }
+
+    /// Return true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Return the size of the animation frame in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Set the size of the animation frame in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Return the position of the animation frame in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Set the position of the animation frame in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        winapi::um::commctrl::ANIMATE_CLASS
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE | ACS_CENTER | ACS_TRANSPARENT
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        use winapi::um::winuser::WS_CHILD;
+        WS_CHILD
+    }
+}
+
+control_cursor!(AnimationFrame, NOT_BOUND, BAD_HANDLE);
+
+impl Drop for AnimationFrame {
+    fn drop(&mut self) {
+        self.handle.destroy();
+    }
+}
+
+pub struct AnimationFrameBuilder<'a> {
+    size: (i32, i32),
+    position: (i32, i32),
+    flags: Option<AnimationFrameFlags>,
+    ex_flags: u32,
+    source: Option<&'a str>,
+    play: bool,
+    parent: Option<ControlHandle>,
+}
+
+impl<'a> AnimationFrameBuilder<'a> {
+    pub fn flags(mut self, flags: AnimationFrameFlags) -> AnimationFrameBuilder<'a> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn ex_flags(mut self, flags: u32) -> AnimationFrameBuilder<'a> {
+        self.ex_flags = flags;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> AnimationFrameBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> AnimationFrameBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn source(mut self, source: Option<&'a str>) -> AnimationFrameBuilder<'a> {
+        self.source = source;
+        self
+    }
+
+    pub fn play(mut self, play: bool) -> AnimationFrameBuilder<'a> {
+        self.play = play;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> AnimationFrameBuilder<'a> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut AnimationFrame) -> Result<(), NwgError> {
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("AnimationFrame")),
+        }?;
+
+        *out = Default::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .ex_flags(self.ex_flags)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        if let Some(source) = self.source {
+            out.open_file(source)?;
+        }
+
+        if self.play {
+            out.play(0, None, None);
+        }
+
+        Ok(())
+    }
+}
@@ -113,6 +113,7 @@ impl AnimationThread {
         if let Some(Some(t)) = state.timers.get_mut(id as usize) {
             t.active = true;
             t.birthtime = Instant::now();
+            t.last_tick = Instant::now();
             t.current_tick = 0;
         }
     }
@@ -266,6 +266,8 @@ impl Button {
     }
 }
 
+control_cursor!(Button, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for Button {
     fn drop(&mut self) {
         self.handle.destroy();
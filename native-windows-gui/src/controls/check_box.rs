@@ -310,6 +310,8 @@ impl CheckBox {
     }
 }
 
+control_cursor!(CheckBox, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for CheckBox {
     fn drop(&mut self) {
         use crate::unbind_raw_event_handler;
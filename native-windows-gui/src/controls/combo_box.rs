@@ -1,10 +1,11 @@
-use super::{ControlBase, ControlHandle};
+use super::{ControlBase, ControlHandle, OwnerDrawItem};
 use crate::win32::base_helper::{check_hwnd, from_utf16, to_utf16};
 use crate::win32::window_helper as wh;
 use crate::{unbind_raw_event_handler, Font, NwgError, RawEventHandler, VTextAlign};
 use std::cell::{Ref, RefCell, RefMut};
 use std::fmt::Display;
 use std::mem;
+use std::rc::Rc;
 use winapi::shared::minwindef::{LPARAM, WPARAM};
 use winapi::shared::windef::HWND;
 use winapi::um::winuser::{WS_DISABLED, WS_TABSTOP, WS_VISIBLE};
@@ -46,6 +47,8 @@ Requires the `combobox` feature.
   * `collection`:     The default collection of the combobox
   * `selected_index`: The default selected index. None means no values are selected.
   * `focus`:          The control receive focus after being created
+  * `owner_draw`:     If the items are drawn by the application. Requires the item type to implement `OwnerDrawItem`
+  * `item_height`:    The height of the items of an owner drawn combobox
 
 **Control events:**
   * `OnComboBoxClosed`: When the combobox dropdown is closed
@@ -73,8 +76,9 @@ fn build_combobox(combo: &mut nwg::ComboBox<&'static str>, window: &nwg::Window)
 #[derive(Default)]
 pub struct ComboBox<D: Display + Default> {
     pub handle: ControlHandle,
-    collection: RefCell<Vec<D>>,
+    collection: Rc<RefCell<Vec<D>>>,
     handler0: RefCell<Option<RawEventHandler>>,
+    handler1: RefCell<Option<RawEventHandler>>,
 }
 
 impl<D: Display + Default> ComboBox<D> {
@@ -89,6 +93,8 @@ impl<D: Display + Default> ComboBox<D> {
             font: None,
             collection: None,
             selected_index: None,
+            owner_draw: None,
+            item_height: None,
             parent: None,
         }
     }
@@ -285,6 +291,33 @@ impl<D: Display + Default> ComboBox<D> {
         col
     }
 
+    /// Set the height of the items in the combobox dropdown and in the selection field.
+    /// Mostly useful with owner drawn combobox.
+    pub fn set_item_height(&self, height: u32) {
+        use winapi::um::winuser::CB_SETITEMHEIGHT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let (_, height) = unsafe { crate::win32::high_dpi::logical_to_physical(0, height as i32) };
+
+        wh::send_message(handle, CB_SETITEMHEIGHT, 0, height as LPARAM);
+        wh::send_message(
+            handle,
+            CB_SETITEMHEIGHT,
+            -1isize as WPARAM,
+            height as LPARAM,
+        );
+    }
+
+    /// Redraw the items of an owner drawn combobox. Should be called after items in `collection_mut` are modified.
+    pub fn redraw(&self) {
+        use winapi::um::winuser::InvalidateRect;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            InvalidateRect(handle, ::std::ptr::null(), 1);
+        }
+    }
+
     /// Return the number of items in the control. NOT the inner rust collection
     pub fn len(&self) -> usize {
         use winapi::um::winuser::CB_GETCOUNT;
@@ -547,6 +580,45 @@ impl<D: Display + Default> ComboBox<D> {
     }
 }
 
+control_cursor!([D: Display + Default] ComboBox<D>, NOT_BOUND, BAD_HANDLE);
+
+impl<D: Display + Default + OwnerDrawItem + 'static> ComboBox<D> {
+    /// Draw the items of the combobox using `OwnerDrawItem::draw`.
+    /// The `WM_DRAWITEM` message is sent to the parent of the control, so the hook is bound to the parent.
+    fn hook_owner_draw(&mut self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::basetsd::UINT_PTR;
+        use winapi::um::winuser::{DRAWITEMSTRUCT, WM_DRAWITEM};
+
+        if self.handle.blank() {
+            panic!("{}", NOT_BOUND);
+        }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let collection = self.collection.clone();
+
+        let handler = bind_raw_event_handler_inner(
+            &parent_handle,
+            handle as UINT_PTR,
+            move |_hwnd, msg, _w, l| {
+                match msg {
+                    WM_DRAWITEM => unsafe {
+                        let draw = l as *const DRAWITEMSTRUCT;
+                        if super::owner_draw::draw_item(handle, &collection, draw) {
+                            return Some(1);
+                        }
+                    },
+                    _ => {}
+                }
+
+                None
+            },
+        );
+
+        *self.handler1.borrow_mut() = Some(handler.unwrap());
+    }
+}
+
 impl<D: Display + Default> Drop for ComboBox<D> {
     fn drop(&mut self) {
         let handler = self.handler0.borrow();
@@ -554,6 +626,11 @@ impl<D: Display + Default> Drop for ComboBox<D> {
             drop(unbind_raw_event_handler(h));
         }
 
+        let handler = self.handler1.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
         self.handle.destroy();
     }
 }
@@ -568,6 +645,8 @@ pub struct ComboBoxBuilder<'a, D: Display + Default> {
     font: Option<&'a Font>,
     collection: Option<Vec<D>>,
     selected_index: Option<usize>,
+    owner_draw: Option<fn(&mut ComboBox<D>)>,
+    item_height: Option<u32>,
     parent: Option<ControlHandle>,
 }
 
@@ -622,12 +701,19 @@ impl<'a, D: Display + Default> ComboBoxBuilder<'a, D> {
         self
     }
 
+    pub fn item_height(mut self, height: u32) -> ComboBoxBuilder<'a, D> {
+        self.item_height = Some(height);
+        self
+    }
+
     pub fn v_align(self, _align: VTextAlign) -> ComboBoxBuilder<'a, D> {
         // Disabled for now because of a bug. Keep the method for backward compatibility
         self
     }
 
     pub fn build(self, out: &mut ComboBox<D>) -> Result<(), NwgError> {
+        use winapi::um::winuser::{CBS_HASSTRINGS, CBS_OWNERDRAWFIXED};
+
         let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
 
         let parent = match self.parent {
@@ -635,12 +721,17 @@ impl<'a, D: Display + Default> ComboBoxBuilder<'a, D> {
             None => Err(NwgError::no_parent("ComboBox")),
         }?;
 
+        let mut forced_flags = out.forced_flags();
+        if self.owner_draw.is_some() {
+            forced_flags |= CBS_OWNERDRAWFIXED | CBS_HASSTRINGS;
+        }
+
         // Drop the old object
         *out = ComboBox::default();
 
         out.handle = ControlBase::build_hwnd()
             .class_name(out.class_name())
-            .forced_flags(out.forced_flags())
+            .forced_flags(forced_flags)
             .flags(flags)
             .ex_flags(self.ex_flags)
             .size(self.size)
@@ -654,6 +745,14 @@ impl<'a, D: Display + Default> ComboBoxBuilder<'a, D> {
             out.set_font(Font::global_default().as_ref());
         }
 
+        if let Some(hook) = self.owner_draw {
+            hook(out);
+        }
+
+        if let Some(height) = self.item_height {
+            out.set_item_height(height);
+        }
+
         if self.collection.is_some() {
             out.set_collection(self.collection.unwrap());
         }
@@ -672,6 +771,16 @@ impl<'a, D: Display + Default> ComboBoxBuilder<'a, D> {
     }
 }
 
+impl<'a, D: Display + Default + OwnerDrawItem + 'static> ComboBoxBuilder<'a, D> {
+    pub fn owner_draw(mut self, owner_draw: bool) -> ComboBoxBuilder<'a, D> {
+        self.owner_draw = match owner_draw {
+            true => Some(ComboBox::<D>::hook_owner_draw),
+            false => None,
+        };
+        self
+    }
+}
+
 impl<D: Display + Default> PartialEq for ComboBox<D> {
     fn eq(&self, other: &Self) -> bool {
         self.handle == other.handle
@@ -0,0 +1,764 @@
+use super::{ControlBase, ControlHandle};
+use crate::win32::base_helper::{check_hwnd, from_utf16, to_utf16};
+use crate::win32::window_helper as wh;
+use crate::{unbind_raw_event_handler, Font, NwgError, RawEventHandler};
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::fmt::Display;
+use std::mem;
+use std::rc::Rc;
+use winapi::shared::minwindef::{LPARAM, WPARAM};
+use winapi::shared::windef::HWND;
+use winapi::um::commctrl::COMBOBOXEXITEMW;
+use winapi::um::winuser::{WS_DISABLED, WS_TABSTOP, WS_VISIBLE};
+
+#[cfg(feature = "image-list")]
+use crate::ImageList;
+
+const NOT_BOUND: &'static str = "ComboBoxEx is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: ComboBoxEx handle is not HWND!";
+
+bitflags! {
+    /**
+        The ComboBoxEx flags
+
+        * NONE:     No flags. Equivalent to a invisible combobox.
+        * VISIBLE:  The combobox is immediatly visible after creation
+        * DISABLED: The combobox cannot be interacted with by the user. It also has a grayed out look.
+        * TAB_STOP: The control can be selected using tab navigation
+    */
+    pub struct ComboBoxExFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const TAB_STOP = WS_TABSTOP;
+    }
+}
+
+/**
+    The function used by a `ComboBoxEx` to filter its collection as the user types.
+    Matching with `Prefix` and `Substring` is not case sensitive.
+*/
+#[derive(Clone, Copy, Debug)]
+pub enum ComboBoxFilter {
+    /// Every item of the collection is displayed
+    NoFilter,
+
+    /// Only display the items that starts with the text
+    Prefix,
+
+    /// Only display the items that contains the text
+    Substring,
+
+    /// Custom matcher. Receives the item display value and the text typed by the user.
+    /// Returns `true` if the item should be displayed.
+    Custom(fn(&str, &str) -> bool),
+}
+
+impl ComboBoxFilter {
+    /// Check if `item` should be displayed when the user has typed `text`
+    pub fn matches(&self, item: &str, text: &str) -> bool {
+        match self {
+            ComboBoxFilter::NoFilter => true,
+            ComboBoxFilter::Prefix => item.to_lowercase().starts_with(&text.to_lowercase()),
+            ComboBoxFilter::Substring => item.to_lowercase().contains(&text.to_lowercase()),
+            ComboBoxFilter::Custom(f) => f(item, text),
+        }
+    }
+}
+
+impl Default for ComboBoxFilter {
+    fn default() -> ComboBoxFilter {
+        ComboBoxFilter::Prefix
+    }
+}
+
+/**
+A ComboBoxEx is an editable combobox that filters its collection as the user types and that can display an image
+next to its items. Unlike `ComboBox`, the text typed by the user does not have to match an item of the collection.
+When the user commits a text that is not in the collection (by pressing ENTER or by moving the focus away)
+the control raises `OnComboBoxTextCommit`.
+
+The height of the control includes the height of the dropdown.
+
+Requires the `combobox` feature. The image methods also require the `image-list` feature.
+
+**Builder parameters:**
+  * `parent`:         **Required.** The combobox parent container.
+  * `size`:           The combobox size.
+  * `position`:       The combobox position.
+  * `enabled`:        If the combobox can be used by the user. It also has a grayed out look if disabled.
+  * `flags`:          A combination of the ComboBoxExFlags values.
+  * `ex_flags`:       A combination of win32 window extended flags. Unlike `flags`, ex_flags must be used straight from winapi
+  * `font`:           The font used for the combobox text
+  * `collection`:     The default collection of the combobox
+  * `selected_index`: The default selected index. None means no values are selected.
+  * `text`:           The default text of the combobox
+  * `editable`:       If the user can type in the combobox. Defaults to `true`
+  * `filter`:         How the collection is filtered as the user types. Defaults to `ComboBoxFilter::Prefix`
+  * `image_list`:     The image list containing the item images
+  * `item_image`:     A function returning the index of the image of an item in the image list
+  * `focus`:          The control receive focus after being created
+
+**Control events:**
+  * `OnComboBoxClosed`: When the combobox dropdown is closed
+  * `OnComboBoxDropdown`: When the combobox dropdown is opened
+  * `OnComboxBoxSelection`: When a new value in a combobox is choosen
+  * `OnComboBoxTextCommit`: When the user commits a text that is not an item of the collection. See `EventData::on_combo_box_text_commit`
+  * `OnTextInput`: When the text of the combobox is changed by the user
+  * `MousePress(_)`: Generic mouse press events on the checkbox
+  * `OnMouseMove`: Generic mouse mouse event
+  * `OnMouseWheel`: Generic mouse wheel event
+
+```rust
+use native_windows_gui as nwg;
+fn build_search(combo: &mut nwg::ComboBoxEx<&'static str>, window: &nwg::Window) {
+    nwg::ComboBoxEx::builder()
+        .collection(vec!["apple", "apricot", "banana", "blueberry"])
+        .filter(nwg::ComboBoxFilter::Substring)
+        .parent(window)
+        .build(combo);
+}
+```
+*/
+#[derive(Default)]
+pub struct ComboBoxEx<D: Display + Default> {
+    pub handle: ControlHandle,
+    collection: Rc<RefCell<Vec<D>>>,
+    filter: Rc<Cell<ComboBoxFilter>>,
+    item_image: Option<fn(&D) -> Option<usize>>,
+    handler0: RefCell<Option<RawEventHandler>>,
+}
+
+impl<D: Display + Default> ComboBoxEx<D> {
+    pub fn builder<'a>() -> ComboBoxExBuilder<'a, D> {
+        ComboBoxExBuilder {
+            size: (150, 200),
+            position: (0, 0),
+            enabled: true,
+            focus: false,
+            flags: None,
+            ex_flags: 0,
+            font: None,
+            collection: None,
+            selected_index: None,
+            text: None,
+            editable: true,
+            filter: Default::default(),
+            item_image: None,
+            #[cfg(feature = "image-list")]
+            image_list: None,
+            parent: None,
+        }
+    }
+
+    /// Add a new item to the combobox. The item is only displayed if it matches the current text.
+    pub fn push(&self, item: D) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let display = format!("{}", item);
+        let text = unsafe { wh::get_window_text(handle) };
+        let mut col = self.collection.borrow_mut();
+
+        if text.is_empty() || self.filter.get().matches(&display, &text) {
+            let image = self.item_image.and_then(|f| f(&item));
+            insert_item(handle, col.len(), &display, image);
+        }
+
+        col.push(item);
+    }
+
+    /// Remove the item at the selected index in the collection and returns it.
+    /// Panic of the index is out of bounds
+    pub fn remove(&self, index: usize) -> D {
+        let item = self.collection.borrow_mut().remove(index);
+        self.sync();
+        item
+    }
+
+    /// Return the index in the collection of the currently selected item. Return `None` if no item is selected.
+    pub fn selection(&self) -> Option<usize> {
+        use winapi::um::winuser::{CB_ERR, CB_GETCURSEL};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        match wh::send_message(handle, CB_GETCURSEL, 0, 0) {
+            CB_ERR => None,
+            position => Some(item_index(handle, position as usize)),
+        }
+    }
+
+    /// Return the display value of the currently selected item
+    /// Return `None` if no item is selected. This reads the visual value.
+    pub fn selection_string(&self) -> Option<String> {
+        use winapi::shared::ntdef::WCHAR;
+        use winapi::um::winuser::{CB_ERR, CB_GETCURSEL, CB_GETLBTEXT, CB_GETLBTEXTLEN};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let index = wh::send_message(handle, CB_GETCURSEL, 0, 0);
+        if index == CB_ERR {
+            None
+        } else {
+            let index = index as usize;
+            let length = (wh::send_message(handle, CB_GETLBTEXTLEN, index, 0) as usize) + 1; // +1 for the null character
+            let mut buffer: Vec<WCHAR> = vec![0; length];
+            wh::send_message(handle, CB_GETLBTEXT, index, buffer.as_mut_ptr() as LPARAM);
+
+            Some(from_utf16(&buffer))
+        }
+    }
+
+    /// Set the currently selected item using its index in the collection.
+    /// This clears the filter so that every item is displayed.
+    /// If the value is None, remove the selected value
+    pub fn set_selection(&self, index: Option<usize>) {
+        use winapi::um::winuser::CB_SETCURSEL;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        {
+            let col = self.collection.borrow();
+            populate(handle, &col, ComboBoxFilter::NoFilter, "", self.item_image);
+        }
+
+        let index = index.unwrap_or(-1isize as usize);
+        wh::send_message(handle, CB_SETCURSEL, index, 0);
+    }
+
+    /// Return the text in the combobox edit field
+    pub fn text(&self) -> String {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_text(handle) }
+    }
+
+    /// Set the text in the combobox edit field. This does not filter the collection.
+    pub fn set_text<'a>(&self, v: &'a str) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_text(handle, v) }
+    }
+
+    /// Return the filter used when the user types in the combobox
+    pub fn filter(&self) -> ComboBoxFilter {
+        self.filter.get()
+    }
+
+    /// Set the filter used when the user types in the combobox. Does not update the view until the text is changed.
+    pub fn set_filter(&self, filter: ComboBoxFilter) {
+        self.filter.set(filter);
+    }
+
+    /// Show or hide the dropdown of the combox
+    pub fn dropdown(&self, v: bool) {
+        use winapi::um::winuser::CB_SHOWDROPDOWN;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, CB_SHOWDROPDOWN, v as usize, 0);
+    }
+
+    /// Sets the image list of the combobox. See the `item_image` builder parameter to select the image of the items.
+    #[cfg(feature = "image-list")]
+    pub fn set_image_list(&self, list: Option<&ImageList>) {
+        use std::ptr;
+        use winapi::um::commctrl::CBEM_SETIMAGELIST;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let list_handle = list.map(|l| l.handle).unwrap_or(ptr::null_mut());
+
+        wh::send_message(handle, CBEM_SETIMAGELIST, 0, list_handle as _);
+    }
+
+    /// Returns the image list of the combobox or None if there is none.
+    /// The returned image list is not owned
+    #[cfg(feature = "image-list")]
+    pub fn image_list(&self) -> Option<ImageList> {
+        use winapi::um::commctrl::{CBEM_GETIMAGELIST, HIMAGELIST};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let handle = wh::send_message(handle, CBEM_GETIMAGELIST, 0, 0) as HIMAGELIST;
+        if handle.is_null() {
+            None
+        } else {
+            Some(ImageList::borrowed(handle))
+        }
+    }
+
+    /// Update the visual of the control with the inner collection and the current text.
+    /// This rebuild every item in the combobox and can take some time on big collections.
+    pub fn sync(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let text = unsafe { wh::get_window_text(handle) };
+        let col = self.collection.borrow();
+        populate(handle, &col, self.filter.get(), &text, self.item_image);
+    }
+
+    /// Set the item collection of the combobox. Return the old collection
+    pub fn set_collection(&self, mut col: Vec<D>) -> Vec<D> {
+        {
+            let mut col_ref = self.collection.borrow_mut();
+            mem::swap::<Vec<D>>(&mut col_ref, &mut col);
+        }
+
+        self.sync();
+
+        col
+    }
+
+    /// Clears the control and free the underlying collection. Same as `set_collection(Vec::new())`
+    pub fn clear(&self) {
+        self.set_collection(Vec::new());
+    }
+
+    /// Return the number of items displayed in the control. NOT the inner rust collection
+    pub fn len(&self) -> usize {
+        use winapi::um::winuser::CB_GETCOUNT;
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, CB_GETCOUNT, 0, 0) as usize
+    }
+
+    //
+    // Common control functions
+    //
+
+    /// Return the font of the control
+    pub fn font(&self) -> Option<Font> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let font_handle = wh::get_window_font(handle);
+        if font_handle.is_null() {
+            None
+        } else {
+            Some(Font {
+                handle: font_handle,
+            })
+        }
+    }
+
+    /// Set the font of the control
+    pub fn set_font(&self, font: Option<&Font>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            wh::set_window_font(handle, font.map(|f| f.handle), true);
+        }
+    }
+
+    /// Return true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Set the keyboard focus on the button.
+    pub fn set_focus(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            wh::set_focus(handle);
+        }
+    }
+
+    /// Return true if the control user can interact with the control, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Return true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Return the size of the button in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Set the size of the button in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Return the position of the button in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Set the position of the button in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Get read-only access to the inner collection of the combobox
+    /// This call refcell.borrow under the hood. Be sure to drop the value before
+    /// calling other combobox methods
+    pub fn collection(&self) -> Ref<'_, Vec<D>> {
+        self.collection.borrow()
+    }
+
+    /// Get mutable access to the inner collection of the combobox. Does not update the visual
+    /// control. Call `sync` to update the view. This call refcell.borrow_mut under the hood.
+    /// Be sure to drop the value before calling other combobox methods
+    pub fn collection_mut(&self) -> RefMut<'_, Vec<D>> {
+        self.collection.borrow_mut()
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        winapi::um::commctrl::WC_COMBOBOXEX
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE | WS_TABSTOP
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        use winapi::um::winuser::WS_CHILD;
+        WS_CHILD
+    }
+}
+
+control_cursor!([D: Display + Default] ComboBoxEx<D>, NOT_BOUND, BAD_HANDLE);
+
+impl<D: Display + Default + 'static> ComboBoxEx<D> {
+    /// Filter the collection when the user types in the combobox.
+    /// The `CBN_EDITCHANGE` notification is sent to the parent of the control, so the hook is bound to the parent.
+    fn hook_edit_change(&self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::{basetsd::UINT_PTR, minwindef::HIWORD};
+        use winapi::um::winuser::{CBN_EDITCHANGE, CB_SHOWDROPDOWN, WM_COMMAND};
+
+        if self.handle.blank() {
+            panic!("{}", NOT_BOUND);
+        }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+
+        let collection = self.collection.clone();
+        let filter = self.filter.clone();
+        let item_image = self.item_image;
+        let filtering = Cell::new(false);
+
+        let handler = bind_raw_event_handler_inner(
+            &parent_handle,
+            handle as UINT_PTR,
+            move |_hwnd, msg, w, l| {
+                if msg != WM_COMMAND || l as HWND != handle {
+                    return None;
+                }
+
+                if HIWORD(w as u32) != CBN_EDITCHANGE || filtering.get() {
+                    return None;
+                }
+
+                let col = match collection.try_borrow() {
+                    Ok(col) => col,
+                    Err(_) => return None,
+                };
+
+                filtering.set(true);
+
+                let text = unsafe { wh::get_window_text(handle) };
+                let count = populate(handle, &col, filter.get(), &text, item_image);
+                let show = count > 0 && !text.is_empty();
+                wh::send_message(handle, CB_SHOWDROPDOWN, show as usize, 0);
+
+                // Opening the dropdown replaces the edit text with the closest item. Put back what the user typed.
+                unsafe {
+                    wh::set_window_text(handle, &text);
+                }
+                set_caret_at_end(handle, &text);
+
+                filtering.set(false);
+
+                None
+            },
+        );
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+}
+
+impl<D: Display + Default> Drop for ComboBoxEx<D> {
+    fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        self.handle.destroy();
+    }
+}
+
+/// Fill the dropdown with the items of `collection` that match `text`. Returns the number of items displayed.
+fn populate<D: Display>(
+    handle: HWND,
+    collection: &[D],
+    filter: ComboBoxFilter,
+    text: &str,
+    item_image: Option<fn(&D) -> Option<usize>>,
+) -> usize {
+    use winapi::um::commctrl::CBEM_DELETEITEM;
+    use winapi::um::winuser::CB_GETCOUNT;
+
+    let count = wh::send_message(handle, CB_GETCOUNT, 0, 0) as usize;
+    for i in (0..count).rev() {
+        wh::send_message(handle, CBEM_DELETEITEM, i as WPARAM, 0);
+    }
+
+    let mut shown = 0;
+    for (index, item) in collection.iter().enumerate() {
+        let display = format!("{}", item);
+        if !text.is_empty() && !filter.matches(&display, text) {
+            continue;
+        }
+
+        let image = item_image.and_then(|f| f(item));
+        insert_item(handle, index, &display, image);
+        shown += 1;
+    }
+
+    shown
+}
+
+/// Add an item at the end of the dropdown. `index` is the index of the item in the rust collection.
+fn insert_item(handle: HWND, index: usize, display: &str, image: Option<usize>) {
+    use winapi::um::commctrl::{
+        CBEIF_IMAGE, CBEIF_LPARAM, CBEIF_SELECTEDIMAGE, CBEIF_TEXT, CBEM_INSERTITEMW,
+    };
+
+    let mut text = to_utf16(display);
+    let mut item: COMBOBOXEXITEMW = unsafe { mem::zeroed() };
+    item.mask = CBEIF_TEXT | CBEIF_LPARAM;
+    item.iItem = -1;
+    item.pszText = text.as_mut_ptr();
+    item.lParam = index as LPARAM;
+
+    if let Some(image) = image {
+        item.mask |= CBEIF_IMAGE | CBEIF_SELECTEDIMAGE;
+        item.iImage = image as i32;
+        item.iSelectedImage = image as i32;
+    }
+
+    wh::send_message(
+        handle,
+        CBEM_INSERTITEMW,
+        0,
+        &item as *const COMBOBOXEXITEMW as LPARAM,
+    );
+}
+
+/// Return the index in the rust collection of the item displayed at `position`
+fn item_index(handle: HWND, position: usize) -> usize {
+    use winapi::um::commctrl::{CBEIF_LPARAM, CBEM_GETITEMW};
+
+    let mut item: COMBOBOXEXITEMW = unsafe { mem::zeroed() };
+    item.mask = CBEIF_LPARAM;
+    item.iItem = position as isize;
+
+    wh::send_message(
+        handle,
+        CBEM_GETITEMW,
+        0,
+        &mut item as *mut COMBOBOXEXITEMW as LPARAM,
+    );
+
+    item.lParam as usize
+}
+
+fn set_caret_at_end(handle: HWND, text: &str) {
+    use winapi::shared::minwindef::MAKELONG;
+    use winapi::um::commctrl::CBEM_GETCOMBOCONTROL;
+    use winapi::um::winuser::CB_SETEDITSEL;
+
+    let combo = wh::send_message(handle, CBEM_GETCOMBOCONTROL, 0, 0) as HWND;
+    let end = text.encode_utf16().count() as u16;
+    wh::send_message(combo, CB_SETEDITSEL, 0, MAKELONG(end, end) as LPARAM);
+}
+
+pub struct ComboBoxExBuilder<'a, D: Display + Default> {
+    size: (i32, i32),
+    position: (i32, i32),
+    enabled: bool,
+    focus: bool,
+    flags: Option<ComboBoxExFlags>,
+    ex_flags: u32,
+    font: Option<&'a Font>,
+    collection: Option<Vec<D>>,
+    selected_index: Option<usize>,
+    text: Option<&'a str>,
+    editable: bool,
+    filter: ComboBoxFilter,
+    item_image: Option<fn(&D) -> Option<usize>>,
+    #[cfg(feature = "image-list")]
+    image_list: Option<&'a ImageList>,
+    parent: Option<ControlHandle>,
+}
+
+impl<'a, D: Display + Default + 'static> ComboBoxExBuilder<'a, D> {
+    pub fn flags(mut self, flags: ComboBoxExFlags) -> ComboBoxExBuilder<'a, D> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn ex_flags(mut self, flags: u32) -> ComboBoxExBuilder<'a, D> {
+        self.ex_flags = flags;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> ComboBoxExBuilder<'a, D> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> ComboBoxExBuilder<'a, D> {
+        self.position = pos;
+        self
+    }
+
+    pub fn font(mut self, font: Option<&'a Font>) -> ComboBoxExBuilder<'a, D> {
+        self.font = font;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> ComboBoxExBuilder<'a, D> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn collection(mut self, collection: Vec<D>) -> ComboBoxExBuilder<'a, D> {
+        self.collection = Some(collection);
+        self
+    }
+
+    pub fn selected_index(mut self, index: Option<usize>) -> ComboBoxExBuilder<'a, D> {
+        self.selected_index = index;
+        self
+    }
+
+    pub fn text(mut self, text: &'a str) -> ComboBoxExBuilder<'a, D> {
+        self.text = Some(text);
+        self
+    }
+
+    pub fn editable(mut self, editable: bool) -> ComboBoxExBuilder<'a, D> {
+        self.editable = editable;
+        self
+    }
+
+    pub fn filter(mut self, filter: ComboBoxFilter) -> ComboBoxExBuilder<'a, D> {
+        self.filter = filter;
+        self
+    }
+
+    pub fn item_image(mut self, f: fn(&D) -> Option<usize>) -> ComboBoxExBuilder<'a, D> {
+        self.item_image = Some(f);
+        self
+    }
+
+    #[cfg(feature = "image-list")]
+    pub fn image_list(mut self, list: Option<&'a ImageList>) -> ComboBoxExBuilder<'a, D> {
+        self.image_list = list;
+        self
+    }
+
+    pub fn enabled(mut self, e: bool) -> ComboBoxExBuilder<'a, D> {
+        self.enabled = e;
+        self
+    }
+
+    pub fn focus(mut self, focus: bool) -> ComboBoxExBuilder<'a, D> {
+        self.focus = focus;
+        self
+    }
+
+    pub fn build(self, out: &mut ComboBoxEx<D>) -> Result<(), NwgError> {
+        use winapi::um::winuser::{CBS_DROPDOWN, CBS_DROPDOWNLIST};
+
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("ComboBoxEx")),
+        }?;
+
+        let mut forced_flags = out.forced_flags();
+        match self.editable {
+            true => forced_flags |= CBS_DROPDOWN,
+            false => forced_flags |= CBS_DROPDOWNLIST,
+        }
+
+        // Drop the old object
+        *out = ComboBoxEx::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(forced_flags)
+            .flags(flags)
+            .ex_flags(self.ex_flags)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        out.filter.set(self.filter);
+        out.item_image = self.item_image;
+        out.hook_edit_change();
+
+        if self.font.is_some() {
+            out.set_font(self.font);
+        } else {
+            out.set_font(Font::global_default().as_ref());
+        }
+
+        #[cfg(feature = "image-list")]
+        {
+            if self.image_list.is_some() {
+                out.set_image_list(self.image_list);
+            }
+        }
+
+        if let Some(col) = self.collection {
+            out.set_collection(col);
+        }
+
+        if self.selected_index.is_some() {
+            out.set_selection(self.selected_index);
+        }
+
+        if let Some(text) = self.text {
+            out.set_text(text);
+        }
+
+        out.set_enabled(self.enabled);
+
+        if self.focus {
+            out.set_focus();
+        }
+
+        Ok(())
+    }
+}
+
+impl<D: Display + Default> PartialEq for ComboBoxEx<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
@@ -0,0 +1,35 @@
+/**
+    Implements `set_cursor` and `cursor` for a control with a HWND handle. The two messages are the panic
+    messages of the control when it is not bound and when its handle is not a HWND.
+
+    ```ignore
+    control_cursor!(Button, NOT_BOUND, BAD_HANDLE);
+    control_cursor!([D: Display + Default] ListBox<D>, NOT_BOUND, BAD_HANDLE);
+    ```
+*/
+macro_rules! control_cursor {
+    ([$($generics:tt)*] $control:ty, $not_bound:expr, $bad_handle:expr) => {
+        impl<$($generics)*> $control {
+            /// Sets the cursor displayed when the mouse is over the control. `None` restores the default cursor of the control.
+            /// The control takes the ownership of the cursor and drops it when it is replaced or when the control is destroyed.
+            pub fn set_cursor(&self, cursor: Option<$crate::Cursor>) {
+                let handle = $crate::win32::base_helper::check_hwnd(&self.handle, $not_bound, $bad_handle);
+                unsafe { $crate::win32::window_helper::set_window_cursor(handle, cursor) }
+            }
+
+            /// Returns the cursor set with `set_cursor`. The returned cursor does not own its handle
+            /// and is only valid until the cursor of the control is replaced or the control is destroyed.
+            pub fn cursor(&self) -> Option<$crate::Cursor> {
+                let handle = $crate::win32::base_helper::check_hwnd(&self.handle, $not_bound, $bad_handle);
+                unsafe { $crate::win32::window_helper::get_window_cursor(handle) }.map(|handle| $crate::Cursor {
+                    handle: handle as _,
+                    owned: false,
+                })
+            }
+        }
+    };
+
+    ($control:ty, $not_bound:expr, $bad_handle:expr) => {
+        control_cursor!([] $control, $not_bound, $bad_handle);
+    };
+}
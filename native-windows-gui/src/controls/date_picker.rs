@@ -411,6 +411,8 @@ impl DatePicker {
     }
 }
 
+control_cursor!(DatePicker, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for DatePicker {
     fn drop(&mut self) {
         self.handle.destroy();
@@ -242,6 +242,8 @@ impl ExternCanvas {
     }
 }
 
+control_cursor!(ExternCanvas, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for ExternCanvas {
     fn drop(&mut self) {
         self.handle.destroy();
@@ -140,6 +140,8 @@ impl Frame {
     }
 }
 
+control_cursor!(Frame, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for Frame {
     fn drop(&mut self) {
         self.handle.destroy();
@@ -152,6 +152,30 @@ impl<D: Display + Default> PartialEq<ComboBox<D>> for ControlHandle {
     }
 }
 
+#[cfg(feature = "combobox")]
+use super::ComboBoxEx;
+
+#[cfg(feature = "combobox")]
+impl<D: Display + Default> From<&ComboBoxEx<D>> for ControlHandle {
+    fn from(control: &ComboBoxEx<D>) -> Self {
+        control.handle
+    }
+}
+
+#[cfg(feature = "combobox")]
+impl<D: Display + Default> PartialEq<ControlHandle> for ComboBoxEx<D> {
+    fn eq(&self, other: &ControlHandle) -> bool {
+        self.handle == *other
+    }
+}
+
+#[cfg(feature = "combobox")]
+impl<D: Display + Default> PartialEq<ComboBoxEx<D>> for ControlHandle {
+    fn eq(&self, other: &ComboBoxEx<D>) -> bool {
+        *self == other.handle
+    }
+}
+
 #[cfg(feature = "listbox")]
 use super::ListBox;
 
@@ -191,6 +215,36 @@ use super::DatePicker;
 #[cfg(feature = "datetime-picker")]
 handles!(DatePicker);
 
+#[cfg(feature = "month-calendar")]
+use super::MonthCalendar;
+
+#[cfg(feature = "month-calendar")]
+handles!(MonthCalendar);
+
+#[cfg(feature = "link-label")]
+use super::LinkLabel;
+
+#[cfg(feature = "link-label")]
+handles!(LinkLabel);
+
+#[cfg(feature = "ip-address-input")]
+use super::IpAddressInput;
+
+#[cfg(feature = "ip-address-input")]
+handles!(IpAddressInput);
+
+#[cfg(feature = "hotkey-input")]
+use super::HotKeyInput;
+
+#[cfg(feature = "hotkey-input")]
+handles!(HotKeyInput);
+
+#[cfg(feature = "animation-frame")]
+use super::AnimationFrame;
+
+#[cfg(feature = "animation-frame")]
+handles!(AnimationFrame);
+
 #[cfg(feature = "progress-bar")]
 use super::ProgressBar;
 
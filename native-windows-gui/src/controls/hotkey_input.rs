@@ -0,0 +1,409 @@
+use super::{ControlBase, ControlHandle};
+use crate::win32::base_helper::check_hwnd;
+use crate::win32::window_helper as wh;
+use crate::{Font, NwgError};
+use winapi::shared::minwindef::{LPARAM, WPARAM};
+use winapi::um::commctrl::{
+    HKCOMB_A, HKCOMB_C, HKCOMB_CA, HKCOMB_NONE, HKCOMB_S, HKCOMB_SA, HKCOMB_SC, HKCOMB_SCA,
+    HOTKEYF_ALT, HOTKEYF_CONTROL, HOTKEYF_EXT, HOTKEYF_SHIFT,
+};
+use winapi::um::winuser::{WS_DISABLED, WS_TABSTOP, WS_VISIBLE};
+
+const NOT_BOUND: &'static str = "HotKeyInput is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: HotKeyInput handle is not HWND!";
+
+bitflags! {
+    /**
+        The hotkey input flags
+
+        * NONE:     No flags. Equivalent to a invisible hotkey input.
+        * VISIBLE:  The hotkey input is immediatly visible after creation
+        * DISABLED: The hotkey input cannot be interacted with by the user. It also has a grayed out look.
+        * TAB_STOP: The control can be selected using tab navigation
+    */
+    pub struct HotKeyInputFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const TAB_STOP = WS_TABSTOP;
+    }
+}
+
+bitflags! {
+    /**
+        The modifier keys of a hotkey
+
+        * SHIFT:    The SHIFT key
+        * CONTROL:  The CTRL key
+        * ALT:      The ALT key
+        * EXTENDED: The key is an extended key (ex: the right CTRL or the arrow keys of the numeric keypad)
+    */
+    pub struct HotKeyModifiers: u8 {
+        const NONE = 0;
+        const SHIFT = HOTKEYF_SHIFT;
+        const CONTROL = HOTKEYF_CONTROL;
+        const ALT = HOTKEYF_ALT;
+        const EXTENDED = HOTKEYF_EXT;
+    }
+}
+
+bitflags! {
+    /**
+        The combinations of modifier keys that a hotkey input can reject. See `HotKeyInput::set_rules`
+
+        * UNMODIFIED:    Keys without modifiers
+        * SHIFT:         SHIFT
+        * CONTROL:       CTRL
+        * ALT:           ALT
+        * SHIFT_CONTROL: SHIFT+CTRL
+        * SHIFT_ALT:     SHIFT+ALT
+        * CONTROL_ALT:   CTRL+ALT
+        * SHIFT_CONTROL_ALT: SHIFT+CTRL+ALT
+    */
+    pub struct HotKeyCombinations: u32 {
+        const UNMODIFIED = HKCOMB_NONE as u32;
+        const SHIFT = HKCOMB_S as u32;
+        const CONTROL = HKCOMB_C as u32;
+        const ALT = HKCOMB_A as u32;
+        const SHIFT_CONTROL = HKCOMB_SC as u32;
+        const SHIFT_ALT = HKCOMB_SA as u32;
+        const CONTROL_ALT = HKCOMB_CA as u32;
+        const SHIFT_CONTROL_ALT = HKCOMB_SCA as u32;
+    }
+}
+
+/**
+    A keyboard shortcut. `key` is a virtual key code (see the `nwg::keys` module).
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HotKey {
+    pub key: u32,
+    pub modifiers: HotKeyModifiers,
+}
+
+/**
+A hotkey input lets the user record a keyboard shortcut by pressing it. The control displays the shortcut (ex: "Ctrl + Shift + A").
+The control does not register the shortcut with the system.
+
+Requires the `hotkey-input` feature.
+
+**Builder parameters:**
+  * `parent`:   **Required.** The hotkey input parent container.
+  * `size`:     The hotkey input size.
+  * `position`: The hotkey input position.
+  * `enabled`:  If the hotkey input can be used by the user. It also has a grayed out look if disabled.
+  * `flags`:    A combination of the HotKeyInputFlags values.
+  * `ex_flags`: A combination of win32 window extended flags. Unlike `flags`, ex_flags must be used straight from winapi
+  * `font`:     The font used for the hotkey input text
+  * `value`:    The default hotkey
+  * `rules`:    The rejected combinations of modifiers and their replacement. See `set_rules`
+  * `focus`:    The control receive focus after being created
+
+**Control events:**
+  * `OnHotKeyChanged`: When the user changes the hotkey
+  * `MousePress(_)`: Generic mouse press events on the hotkey input
+  * `OnMouseMove`: Generic mouse mouse event
+  * `OnMouseWheel`: Generic mouse wheel event
+
+```rust
+use native_windows_gui as nwg;
+fn build_hotkey(hotkey: &mut nwg::HotKeyInput, window: &nwg::Window) {
+    nwg::HotKeyInput::builder()
+        .rules(nwg::HotKeyCombinations::UNMODIFIED | nwg::HotKeyCombinations::SHIFT, nwg::HotKeyModifiers::CONTROL)
+        .parent(window)
+        .build(hotkey);
+}
+```
+*/
+#[derive(Default, PartialEq, Eq)]
+pub struct HotKeyInput {
+    pub handle: ControlHandle,
+}
+
+impl HotKeyInput {
+    pub fn builder<'a>() -> HotKeyInputBuilder<'a> {
+        HotKeyInputBuilder {
+            size: (130, 25),
+            position: (0, 0),
+            enabled: true,
+            focus: false,
+            flags: None,
+            ex_flags: 0,
+            font: None,
+            value: None,
+            rules: None,
+            parent: None,
+        }
+    }
+
+    /// Return the hotkey in the control. Returns `None` if the control is empty.
+    pub fn value(&self) -> Option<HotKey> {
+        use winapi::shared::minwindef::{HIBYTE, LOBYTE, LOWORD};
+        use winapi::um::commctrl::HKM_GETHOTKEY;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let value = LOWORD(wh::send_message(handle, HKM_GETHOTKEY, 0, 0) as u32);
+        match LOBYTE(value) {
+            0 => None,
+            key => Some(HotKey {
+                key: key as u32,
+                modifiers: HotKeyModifiers::from_bits_truncate(HIBYTE(value)),
+            }),
+        }
+    }
+
+    /// Set the hotkey in the control. If `None` is passed, the control is cleared.
+    pub fn set_value(&self, value: Option<HotKey>) {
+        use winapi::shared::minwindef::MAKEWORD;
+        use winapi::um::commctrl::HKM_SETHOTKEY;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let value = match value {
+            Some(v) => MAKEWORD(v.key as u8, v.modifiers.bits()),
+            None => 0,
+        };
+
+        wh::send_message(handle, HKM_SETHOTKEY, value as WPARAM, 0);
+    }
+
+    /**
+        Set the combinations of modifiers that the user cannot enter. When the user enters a rejected combination,
+        the control uses the `replacement` modifiers instead.
+
+        For example, to force the shortcuts to use CTRL: `set_rules(UNMODIFIED | SHIFT, HotKeyModifiers::CONTROL)`
+    */
+    pub fn set_rules(&self, rejected: HotKeyCombinations, replacement: HotKeyModifiers) {
+        use winapi::um::commctrl::HKM_SETRULES;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(
+            handle,
+            HKM_SETRULES,
+            rejected.bits() as WPARAM,
+            replacement.bits() as LPARAM,
+        );
+    }
+
+    /// Return the font of the control
+    pub fn font(&self) -> Option<Font> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let font_handle = wh::get_window_font(handle);
+        if font_handle.is_null() {
+            None
+        } else {
+            Some(Font {
+                handle: font_handle,
+            })
+        }
+    }
+
+    /// Set the font of the control
+    pub fn set_font(&self, font: Option<&Font>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            wh::set_window_font(handle, font.map(|f| f.handle), true);
+        }
+    }
+
+    /// Return true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Set the keyboard focus on the control.
+    pub fn set_focus(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            wh::set_focus(handle);
+        }
+    }
+
+    /// Return true if the control user can interact with the control, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Return true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Return the size of the hotkey input in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Set the size of the hotkey input in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Return the position of the hotkey input in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Set the position of the hotkey input in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        winapi::um::commctrl::HOTKEY_CLASS
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE | WS_TABSTOP
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        use winapi::um::winuser::WS_CHILD;
+        WS_CHILD
+    }
+}
+
+control_cursor!(HotKeyInput, NOT_BOUND, BAD_HANDLE);
+
+impl Drop for HotKeyInput {
+    fn drop(&mut self) {
+        self.handle.destroy();
+    }
+}
+
+pub struct HotKeyInputBuilder<'a> {
+    size: (i32, i32),
+    position: (i32, i32),
+    enabled: bool,
+    focus: bool,
+    flags: Option<HotKeyInputFlags>,
+    ex_flags: u32,
+    font: Option<&'a Font>,
+    value: Option<HotKey>,
+    rules: Option<(HotKeyCombinations, HotKeyModifiers)>,
+    parent: Option<ControlHandle>,
+}
+
+impl<'a> HotKeyInputBuilder<'a> {
+    pub fn flags(mut self, flags: HotKeyInputFlags) -> HotKeyInputBuilder<'a> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn ex_flags(mut self, flags: u32) -> HotKeyInputBuilder<'a> {
+        self.ex_flags = flags;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> HotKeyInputBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> HotKeyInputBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn enabled(mut self, e: bool) -> HotKeyInputBuilder<'a> {
+        self.enabled = e;
+        self
+    }
+
+    pub fn focus(mut self, focus: bool) -> HotKeyInputBuilder<'a> {
+        self.focus = focus;
+        self
+    }
+
+    pub fn font(mut self, font: Option<&'a Font>) -> HotKeyInputBuilder<'a> {
+        self.font = font;
+        self
+    }
+
+    pub fn value(mut self, value: Option<HotKey>) -> HotKeyInputBuilder<'a> {
+        self.value = value;
+        self
+    }
+
+    pub fn rules(
+        mut self,
+        rejected: HotKeyCombinations,
+        replacement: HotKeyModifiers,
+    ) -> HotKeyInputBuilder<'a> {
+        self.rules = Some((rejected, replacement));
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> HotKeyInputBuilder<'a> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut HotKeyInput) -> Result<(), NwgError> {
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("HotKeyInput")),
+        }?;
+
+        *out = Default::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .ex_flags(self.ex_flags)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        if self.font.is_some() {
+            out.set_font(self.font);
+        } else {
+            out.set_font(Font::global_default().as_ref());
+        }
+
+        if let Some((rejected, replacement)) = self.rules {
+            out.set_rules(rejected, replacement);
+        }
+
+        if self.value.is_some() {
+            out.set_value(self.value);
+        }
+
+        out.set_enabled(self.enabled);
+
+        if self.focus {
+            out.set_focus();
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,18 @@
 use super::{ControlBase, ControlHandle};
 use crate::win32::{base_helper::check_hwnd, resources_helper as rh, window_helper as wh};
 use crate::{unbind_raw_event_handler, Bitmap, Icon, NwgError, RawEventHandler};
-use std::cell::RefCell;
-use winapi::shared::windef::HBRUSH;
+use std::{cell::RefCell, mem, ptr, rc::Rc};
+use winapi::shared::windef::{HBITMAP, HBRUSH, HDC, HWND};
 use winapi::um::wingdi::DeleteObject;
+use winapi::um::winnt::HANDLE;
 use winapi::um::winuser::{WS_DISABLED, WS_VISIBLE};
 
+#[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+use crate::{AnimatedImage, AnimationTimer};
+
+#[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+use std::time::Duration;
+
 const NOT_BOUND: &'static str = "ImageFrame is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: ImageFrame handle is not HWND!";
 
@@ -16,9 +23,181 @@ bitflags! {
     }
 }
 
+/**
+    How an image frame places its image in the control
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageFrameScaling {
+    /// The image keeps its size and is centered in the control. This is the default.
+    Center,
+    /// The image is resized to the size of the control, ignoring its aspect ratio
+    Stretch,
+    /// The image is resized to fit in the control, keeping its aspect ratio
+    Fit,
+    /// The image is resized to cover the whole control, keeping its aspect ratio. The parts outside of the control are cropped.
+    Fill,
+}
+
+impl ImageFrameScaling {
+    /// Return the position and the size of an image of size `image` drawn in a control of size `area`
+    fn image_rect(self, image: (i32, i32), area: (i32, i32)) -> (i32, i32, i32, i32) {
+        let (image_width, image_height) = image;
+        let (area_width, area_height) = area;
+
+        let (width, height) = match self {
+            ImageFrameScaling::Center => image,
+            ImageFrameScaling::Stretch => area,
+            ImageFrameScaling::Fit | ImageFrameScaling::Fill => {
+                let scale_x = area_width as f64 / image_width.max(1) as f64;
+                let scale_y = area_height as f64 / image_height.max(1) as f64;
+                let scale = match self {
+                    ImageFrameScaling::Fit => scale_x.min(scale_y),
+                    _ => scale_x.max(scale_y),
+                };
+
+                (
+                    (image_width as f64 * scale).round() as i32,
+                    (image_height as f64 * scale).round() as i32,
+                )
+            }
+        };
+
+        (
+            (area_width - width) / 2,
+            (area_height - height) / 2,
+            width,
+            height,
+        )
+    }
+}
+
+impl Default for ImageFrameScaling {
+    fn default() -> ImageFrameScaling {
+        ImageFrameScaling::Center
+    }
+}
+
+/// The image displayed by an image frame
+enum FrameImage {
+    Bitmap(HANDLE),
+    Icon(HANDLE),
+}
+
+#[derive(Default)]
+struct ImageFrameState {
+    scaling: ImageFrameScaling,
+
+    #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+    player: ImageFramePlayer,
+}
+
+impl ImageFrameState {
+    /// The static control can only center its image, so the frame paints itself for the other modes and for animations
+    fn custom_paint(&self) -> bool {
+        #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+        {
+            if self.player.animation.is_some() {
+                return true;
+            }
+        }
+
+        self.scaling != ImageFrameScaling::Center
+    }
+}
+
+#[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+#[derive(Default)]
+struct ImageFramePlayer {
+    animation: Option<AnimatedImage>,
+    frame: usize,
+    playing: bool,
+    looping: bool,
+    timer: AnimationTimer,
+}
+
+#[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+impl ImageFramePlayer {
+    fn frame_count(&self) -> usize {
+        self.animation
+            .as_ref()
+            .map(|a| a.frame_count())
+            .unwrap_or(0)
+    }
+
+    /// Frames with a delay shorter than 20ms are played at 100ms, like web browsers do
+    fn frame_delay(&self) -> Duration {
+        let delay = self
+            .animation
+            .as_ref()
+            .and_then(|a| a.delay(self.frame))
+            .unwrap_or_default();
+
+        match delay < Duration::from_millis(20) {
+            true => Duration::from_millis(100),
+            false => delay,
+        }
+    }
+
+    fn is_timer(&self, id: u32) -> bool {
+        match self.timer.handle {
+            ControlHandle::Timer(_, timer_id) => timer_id == id,
+            _ => false,
+        }
+    }
+
+    fn play(&mut self, hwnd: HWND) {
+        let count = self.frame_count();
+        if count < 2 {
+            return;
+        }
+
+        if self.timer.handle.blank() {
+            AnimationTimer::builder()
+                .parent(ControlHandle::Hwnd(hwnd))
+                .build(&mut self.timer)
+                .expect("Failed to build the image frame animation timer");
+        }
+
+        if !self.looping && self.frame + 1 == count {
+            self.frame = 0;
+        }
+
+        self.timer.set_interval(self.frame_delay());
+        self.timer.start();
+        self.playing = true;
+    }
+
+    fn pause(&mut self) {
+        if self.playing {
+            self.timer.stop();
+            self.playing = false;
+        }
+    }
+
+    /// Show the next frame. The animation stops on the last frame if it does not loop.
+    fn advance(&mut self) {
+        let count = self.frame_count();
+        if self.frame + 1 < count {
+            self.frame += 1;
+        } else if self.looping && count > 1 {
+            self.frame = 0;
+        } else {
+            self.pause();
+            return;
+        }
+
+        self.timer.set_interval(self.frame_delay());
+    }
+}
+
 /**
 An image frame is a control that displays a `Bitmap` or a `Icon` image resource.
 
+With the `image-decoder` and `animation-timer` features, an image frame can also play an `AnimatedImage` (GIF or APNG).
+Each frame is displayed for the delay stored in the image.
+
+By default the image is centered in the control and keeps its size. See `ImageFrameScaling` for the other modes.
+
 ImageFrame is not behind any features.
 
 **Builder parameters:**
@@ -30,6 +209,9 @@ ImageFrame is not behind any features.
   * `background_color`: The background color of the image frame. Used if the image is smaller than the control
   * `bitmap`:           A bitmap to display. If this value is set, icon is ignored.
   * `icon`:             An icon to display
+  * `scaling`:          How the image is placed in the control. Defaults to `ImageFrameScaling::Center`
+  * `animation`:        An animated image to play. If this value is set, bitmap and icon are ignored. Requires the `image-decoder` and `animation-timer` features.
+  * `looping`:          If the animation restarts after the last frame. Defaults to `true`
 
 **Control events:**
   * `OnImageFrameClick`: When the image frame is clicked once by the user
@@ -46,12 +228,25 @@ fn build_frame(button: &mut nwg::ImageFrame, window: &nwg::Window, ico: &nwg::Ic
         .build(button);
 }
 ```
+
+```rust
+use native_windows_gui as nwg;
+fn build_spinner(frame: &mut nwg::ImageFrame, window: &nwg::Window, spinner: &nwg::AnimatedImage) {
+    nwg::ImageFrame::builder()
+        .parent(window)
+        .animation(Some(spinner))
+        .scaling(nwg::ImageFrameScaling::Fit)
+        .build(frame);
+}
+```
 */
 #[derive(Default)]
 pub struct ImageFrame {
     pub handle: ControlHandle,
     background_brush: Option<HBRUSH>,
+    state: Rc<RefCell<ImageFrameState>>,
     handler0: RefCell<Option<RawEventHandler>>,
+    handler1: RefCell<Option<RawEventHandler>>,
 }
 
 impl ImageFrame {
@@ -65,16 +260,24 @@ impl ImageFrame {
             icon: None,
             parent: None,
             background_color: None,
+            scaling: ImageFrameScaling::Center,
+
+            #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+            animation: None,
+
+            #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+            looping: true,
         }
     }
 
-    /// Sets the bitmap image of the image frame. Replace the current bitmap or icon.
+    /// Sets the bitmap image of the image frame. Replace the current bitmap, icon or animation.
     /// Set `image` to `None` to remove the image
     pub fn set_bitmap<'a>(&self, image: Option<&'a Bitmap>) {
         use winapi::shared::minwindef::{LPARAM, WPARAM};
         use winapi::um::winuser::{IMAGE_BITMAP, STM_SETIMAGE};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.clear_animation();
 
         let image_handle = image.map(|i| i.handle as LPARAM).unwrap_or(0);
         let prev_img = wh::send_message(handle, STM_SETIMAGE, IMAGE_BITMAP as WPARAM, image_handle);
@@ -85,13 +288,14 @@ impl ImageFrame {
         }
     }
 
-    /// Sets the bitmap image of the image frame. Replace the current bitmap or icon.
+    /// Sets the icon image of the image frame. Replace the current bitmap, icon or animation.
     /// Set `image` to `None` to remove the image
     pub fn set_icon<'a>(&self, image: Option<&'a Icon>) {
         use winapi::shared::minwindef::{LPARAM, WPARAM};
         use winapi::um::winuser::{IMAGE_ICON, STM_SETIMAGE};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.clear_animation();
 
         let image_handle = image.map(|i| i.handle as LPARAM).unwrap_or(0);
         let prev_img = wh::send_message(handle, STM_SETIMAGE, IMAGE_ICON as WPARAM, image_handle);
@@ -105,32 +309,43 @@ impl ImageFrame {
     /// Returns the current image in the image frame.
     /// If the image frame has a bitmap, the value will be returned in `bitmap`
     /// If the image frame has a icon, the value will be returned in `icon`
+    /// If the image frame plays an animation, the current frame will be returned in `bitmap`
     pub fn image<'a>(&self, bitmap: &mut Option<Bitmap>, icon: &mut Option<Icon>) {
-        use winapi::shared::minwindef::WPARAM;
-        use winapi::shared::windef::HBITMAP;
-        use winapi::um::winnt::HANDLE;
-        use winapi::um::winuser::{IMAGE_BITMAP, IMAGE_ICON, STM_GETIMAGE};
-
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
-        let bitmap_handle = wh::send_message(handle, STM_GETIMAGE, IMAGE_BITMAP as WPARAM, 0);
-        let icon_handle = wh::send_message(handle, STM_GETIMAGE, IMAGE_ICON as WPARAM, 0);
 
         *bitmap = None;
         *icon = None;
 
-        if bitmap_handle != 0 && rh::is_bitmap(bitmap_handle as HBITMAP) {
-            *bitmap = Some(Bitmap {
-                handle: bitmap_handle as HANDLE,
-                owned: false,
-            });
-        } else if icon_handle != 0 {
-            *icon = Some(Icon {
-                handle: icon_handle as HANDLE,
-                owned: false,
-            });
+        match current_image(handle, &self.state.borrow()) {
+            Some(FrameImage::Bitmap(handle)) => {
+                *bitmap = Some(Bitmap {
+                    handle,
+                    owned: false,
+                })
+            }
+            Some(FrameImage::Icon(handle)) => {
+                *icon = Some(Icon {
+                    handle,
+                    owned: false,
+                })
+            }
+            None => {}
         }
     }
 
+    /// Return how the image is placed in the control
+    pub fn scaling(&self) -> ImageFrameScaling {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().scaling
+    }
+
+    /// Set how the image is placed in the control
+    pub fn set_scaling(&self, scaling: ImageFrameScaling) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow_mut().scaling = scaling;
+        invalidate(handle);
+    }
+
     /// Return true if the control user can interact with the control, return false otherwise
     pub fn enabled(&self) -> bool {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
@@ -197,6 +412,70 @@ impl ImageFrame {
         WS_CHILD | SS_NOTIFY | SS_CENTERIMAGE
     }
 
+    /// Stop and remove the current animation, if any
+    fn clear_animation(&self) {
+        #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+        {
+            let mut state = self.state.borrow_mut();
+            state.player.pause();
+            state.player.animation = None;
+            state.player.frame = 0;
+        }
+    }
+
+    /// Paint the image when it is scaled or animated, and advance the animation
+    fn hook_painting(&self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::{basetsd::UINT_PTR, minwindef::LRESULT};
+        use winapi::um::winuser::{WM_ERASEBKGND, WM_PAINT, WM_SIZE};
+
+        if self.handle.blank() {
+            panic!("{}", NOT_BOUND);
+        }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+
+        let state = self.state.clone();
+        let handler = bind_raw_event_handler_inner(
+            &self.handle,
+            handle as UINT_PTR,
+            move |hwnd, msg, _w, _l| {
+                match msg {
+                    WM_PAINT => {
+                        let state = state.borrow();
+                        if state.custom_paint() {
+                            unsafe { paint(hwnd, &state) };
+                            return Some(0);
+                        }
+                    }
+                    WM_ERASEBKGND => {
+                        if state.borrow().custom_paint() {
+                            return Some(1 as LRESULT);
+                        }
+                    }
+                    WM_SIZE => {
+                        if state.borrow().custom_paint() {
+                            invalidate(hwnd);
+                        }
+                    }
+                    #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+                    wh::NWG_TIMER_TICK => {
+                        let mut state = state.borrow_mut();
+                        if state.player.is_timer(_w as u32) {
+                            state.player.advance();
+                            invalidate(hwnd);
+                            return Some(0);
+                        }
+                    }
+                    _ => {}
+                }
+
+                None
+            },
+        );
+
+        *self.handler1.borrow_mut() = Some(handler.unwrap());
+    }
+
     /// Change the label background color to transparent.
     /// Change the checkbox background color.
     fn hook_background_color(&mut self, c: [u8; 3]) {
@@ -236,6 +515,227 @@ impl ImageFrame {
     }
 }
 
+#[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+impl ImageFrame {
+    /**
+        Sets an animated image and starts playing it from the first frame. Replace the current bitmap, icon or animation.
+        Set `image` to `None` to remove the image.
+
+        The animation is shared with `image`, no pixels are copied.
+    */
+    pub fn set_animation<'a>(&self, image: Option<&'a AnimatedImage>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.set_bitmap(None);
+
+        if let Some(image) = image {
+            let mut state = self.state.borrow_mut();
+            state.player.animation = Some(image.clone());
+            state.player.frame = 0;
+            state.player.play(handle);
+        }
+
+        invalidate(handle);
+    }
+
+    /// Return the animated image played by the image frame, if any
+    pub fn animation(&self) -> Option<AnimatedImage> {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().player.animation.clone()
+    }
+
+    /// Resume the animation. If the animation does not loop and ended, it restarts from the first frame.
+    /// Does nothing if the image frame has no animation or if the animation has a single frame.
+    pub fn play(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow_mut().player.play(handle);
+        invalidate(handle);
+    }
+
+    /// Pause the animation on the current frame
+    pub fn pause(&self) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow_mut().player.pause();
+    }
+
+    /// Return true if the animation is playing
+    pub fn playing(&self) -> bool {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().player.playing
+    }
+
+    /// Return true if the animation restarts after the last frame
+    pub fn looping(&self) -> bool {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().player.looping
+    }
+
+    /// Set if the animation restarts after the last frame. If not, the animation stops on the last frame.
+    pub fn set_looping(&self, looping: bool) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow_mut().player.looping = looping;
+    }
+
+    /// Return the index of the animation frame currently displayed
+    pub fn frame(&self) -> usize {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().player.frame
+    }
+
+    /// Display a frame of the animation. The index is clamped to the last frame.
+    /// If the animation is playing, it continues from this frame.
+    pub fn set_frame(&self, index: usize) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut state = self.state.borrow_mut();
+        let player = &mut state.player;
+        player.frame = index.min(player.frame_count().saturating_sub(1));
+        if player.playing {
+            player.timer.set_interval(player.frame_delay());
+            player.timer.start();
+        }
+
+        invalidate(handle);
+    }
+}
+
+control_cursor!(ImageFrame, NOT_BOUND, BAD_HANDLE);
+
+fn invalidate(hwnd: HWND) {
+    use winapi::um::winuser::InvalidateRect;
+    unsafe {
+        InvalidateRect(hwnd, ptr::null(), 0);
+    }
+}
+
+/// Return the image displayed by the image frame: the current animation frame or the image of the static control
+fn current_image(hwnd: HWND, state: &ImageFrameState) -> Option<FrameImage> {
+    use winapi::shared::minwindef::WPARAM;
+    use winapi::um::winuser::{IMAGE_BITMAP, IMAGE_ICON, STM_GETIMAGE};
+
+    #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+    {
+        let player = &state.player;
+        if let Some(animation) = player.animation.as_ref() {
+            return animation
+                .frame(player.frame)
+                .map(|b| FrameImage::Bitmap(b.handle));
+        }
+    }
+
+    #[cfg(not(all(feature = "image-decoder", feature = "animation-timer")))]
+    let _ = state;
+
+    let bitmap_handle = wh::send_message(hwnd, STM_GETIMAGE, IMAGE_BITMAP as WPARAM, 0);
+    let icon_handle = wh::send_message(hwnd, STM_GETIMAGE, IMAGE_ICON as WPARAM, 0);
+
+    if bitmap_handle != 0 && rh::is_bitmap(bitmap_handle as HBITMAP) {
+        Some(FrameImage::Bitmap(bitmap_handle as HANDLE))
+    } else if icon_handle != 0 {
+        Some(FrameImage::Icon(icon_handle as HANDLE))
+    } else {
+        None
+    }
+}
+
+/// Paint the image frame in a buffer to avoid flickering during animations
+unsafe fn paint(hwnd: HWND, state: &ImageFrameState) {
+    use winapi::shared::minwindef::{LPARAM, WPARAM};
+    use winapi::shared::windef::RECT;
+    use winapi::um::wingdi::{
+        BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, SelectObject, SRCCOPY,
+    };
+    use winapi::um::winuser::{
+        BeginPaint, EndPaint, FillRect, GetClientRect, GetParent, GetSysColorBrush, SendMessageW,
+        COLOR_BTNFACE, PAINTSTRUCT, WM_CTLCOLORSTATIC,
+    };
+
+    let mut paint: PAINTSTRUCT = mem::zeroed();
+    let dc = BeginPaint(hwnd, &mut paint);
+
+    let mut rect: RECT = mem::zeroed();
+    GetClientRect(hwnd, &mut rect);
+    let area = (rect.right, rect.bottom);
+
+    let buffer_dc = CreateCompatibleDC(dc);
+    let buffer = CreateCompatibleBitmap(dc, area.0, area.1);
+    let old_buffer = SelectObject(buffer_dc, buffer as _);
+
+    // Same background as the static control: the parent picks the brush
+    let mut brush = SendMessageW(
+        GetParent(hwnd),
+        WM_CTLCOLORSTATIC,
+        buffer_dc as WPARAM,
+        hwnd as LPARAM,
+    ) as HBRUSH;
+    if brush.is_null() {
+        brush = GetSysColorBrush(COLOR_BTNFACE);
+    }
+    FillRect(buffer_dc, &rect, brush);
+
+    match current_image(hwnd, state) {
+        Some(FrameImage::Bitmap(bitmap)) => draw_bitmap(buffer_dc, bitmap, state.scaling, area),
+        Some(FrameImage::Icon(icon)) => draw_icon(buffer_dc, icon, state.scaling, area),
+        None => {}
+    }
+
+    BitBlt(dc, 0, 0, area.0, area.1, buffer_dc, 0, 0, SRCCOPY);
+
+    SelectObject(buffer_dc, old_buffer);
+    DeleteObject(buffer as _);
+    DeleteDC(buffer_dc);
+
+    EndPaint(hwnd, &paint);
+}
+
+unsafe fn draw_bitmap(dc: HDC, bitmap: HANDLE, scaling: ImageFrameScaling, area: (i32, i32)) {
+    use winapi::um::wingdi::{
+        AlphaBlend, CreateCompatibleDC, DeleteDC, SelectObject, SetBrushOrgEx, SetStretchBltMode,
+        StretchBlt, AC_SRC_ALPHA, AC_SRC_OVER, BLENDFUNCTION, HALFTONE, SRCCOPY,
+    };
+
+    let ((width, height), _) = match rh::bitmap_info(bitmap as HBITMAP) {
+        Ok(info) => info,
+        Err(_) => return,
+    };
+    let (width, height) = (width as i32, height as i32);
+    let (x, y, w, h) = scaling.image_rect((width, height), area);
+
+    let source_dc = CreateCompatibleDC(dc);
+    let old_source = SelectObject(source_dc, bitmap as _);
+
+    if rh::bitmap_has_alpha(bitmap as HBITMAP) {
+        let blend = BLENDFUNCTION {
+            BlendOp: AC_SRC_OVER,
+            BlendFlags: 0,
+            SourceConstantAlpha: 255,
+            AlphaFormat: AC_SRC_ALPHA,
+        };
+        AlphaBlend(dc, x, y, w, h, source_dc, 0, 0, width, height, blend);
+    } else {
+        SetStretchBltMode(dc, HALFTONE as _);
+        SetBrushOrgEx(dc, 0, 0, ptr::null_mut());
+        StretchBlt(dc, x, y, w, h, source_dc, 0, 0, width, height, SRCCOPY);
+    }
+
+    SelectObject(source_dc, old_source);
+    DeleteDC(source_dc);
+}
+
+unsafe fn draw_icon(dc: HDC, icon: HANDLE, scaling: ImageFrameScaling, area: (i32, i32)) {
+    use winapi::um::winuser::DrawIconEx;
+
+    // Not declared by winapi
+    const DI_NORMAL: u32 = 0x0003;
+
+    let (width, height) = match rh::icon_size(icon) {
+        Ok(size) => size,
+        Err(_) => return,
+    };
+
+    let (x, y, w, h) = scaling.image_rect((width as i32, height as i32), area);
+    DrawIconEx(dc, x, y, icon as _, w, h, 0, ptr::null_mut(), DI_NORMAL);
+}
+
 impl Drop for ImageFrame {
     fn drop(&mut self) {
         let handler = self.handler0.borrow();
@@ -243,6 +743,11 @@ impl Drop for ImageFrame {
             drop(unbind_raw_event_handler(h));
         }
 
+        let handler = self.handler1.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
         if let Some(bg) = self.background_brush {
             unsafe {
                 DeleteObject(bg as _);
@@ -262,6 +767,13 @@ pub struct ImageFrameBuilder<'a> {
     icon: Option<&'a Icon>,
     parent: Option<ControlHandle>,
     background_color: Option<[u8; 3]>,
+    scaling: ImageFrameScaling,
+
+    #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+    animation: Option<&'a AnimatedImage>,
+
+    #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+    looping: bool,
 }
 
 impl<'a> ImageFrameBuilder<'a> {
@@ -305,6 +817,23 @@ impl<'a> ImageFrameBuilder<'a> {
         self
     }
 
+    pub fn scaling(mut self, scaling: ImageFrameScaling) -> ImageFrameBuilder<'a> {
+        self.scaling = scaling;
+        self
+    }
+
+    #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+    pub fn animation(mut self, animation: Option<&'a AnimatedImage>) -> ImageFrameBuilder<'a> {
+        self.animation = animation;
+        self
+    }
+
+    #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+    pub fn looping(mut self, looping: bool) -> ImageFrameBuilder<'a> {
+        self.looping = looping;
+        self
+    }
+
     pub fn build(self, out: &mut ImageFrame) -> Result<(), NwgError> {
         use winapi::um::winuser::{SS_BITMAP, SS_ICON};
 
@@ -342,6 +871,17 @@ impl<'a> ImageFrameBuilder<'a> {
             out.hook_background_color(self.background_color.unwrap());
         }
 
+        out.state.borrow_mut().scaling = self.scaling;
+        out.hook_painting();
+
+        #[cfg(all(feature = "image-decoder", feature = "animation-timer"))]
+        {
+            out.state.borrow_mut().player.looping = self.looping;
+            if self.animation.is_some() {
+                out.set_animation(self.animation);
+            }
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,350 @@
+use super::{ControlBase, ControlHandle};
+use crate::win32::base_helper::check_hwnd;
+use crate::win32::window_helper as wh;
+use crate::{Font, NwgError};
+use winapi::shared::minwindef::{DWORD, LPARAM, WPARAM};
+use winapi::um::winuser::{WS_DISABLED, WS_TABSTOP, WS_VISIBLE};
+
+const NOT_BOUND: &'static str = "IpAddressInput is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: IpAddressInput handle is not HWND!";
+
+bitflags! {
+    /**
+        The ip address input flags
+
+        * NONE:     No flags. Equivalent to a invisible ip address input.
+        * VISIBLE:  The ip address input is immediatly visible after creation
+        * DISABLED: The ip address input cannot be interacted with by the user. It also has a grayed out look.
+        * TAB_STOP: The control can be selected using tab navigation
+    */
+    pub struct IpAddressInputFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const TAB_STOP = WS_TABSTOP;
+    }
+}
+
+/**
+An ip address input is an edit field made of four numeric fields to enter an IPv4 address.
+
+Requires the `ip-address-input` feature.
+
+**Builder parameters:**
+  * `parent`:   **Required.** The ip address input parent container.
+  * `size`:     The ip address input size.
+  * `position`: The ip address input position.
+  * `enabled`:  If the ip address input can be used by the user. It also has a grayed out look if disabled.
+  * `flags`:    A combination of the IpAddressInputFlags values.
+  * `ex_flags`: A combination of win32 window extended flags. Unlike `flags`, ex_flags must be used straight from winapi
+  * `font`:     The font used for the ip address input text
+  * `address`:  The default address. None leaves the fields blank.
+  * `focus`:    The control receive focus after being created
+
+**Control events:**
+  * `OnIpAddressFieldChanged`: When the user changes a field or moves from one field to another. See `EventData::on_ip_address_field_changed`
+  * `OnTextInput`: When the address is changed
+  * `MousePress(_)`: Generic mouse press events on the ip address input
+  * `OnMouseMove`: Generic mouse mouse event
+  * `OnMouseWheel`: Generic mouse wheel event
+
+```rust
+use native_windows_gui as nwg;
+fn build_ip(ip: &mut nwg::IpAddressInput, window: &nwg::Window) {
+    nwg::IpAddressInput::builder()
+        .address(Some([192, 168, 0, 1]))
+        .parent(window)
+        .build(ip);
+}
+```
+*/
+#[derive(Default, PartialEq, Eq)]
+pub struct IpAddressInput {
+    pub handle: ControlHandle,
+}
+
+impl IpAddressInput {
+    pub fn builder<'a>() -> IpAddressInputBuilder<'a> {
+        IpAddressInputBuilder {
+            size: (130, 25),
+            position: (0, 0),
+            enabled: true,
+            focus: false,
+            flags: None,
+            ex_flags: 0,
+            font: None,
+            address: None,
+            parent: None,
+        }
+    }
+
+    /// Return the address in the control. Blank fields are returned as 0.
+    pub fn address(&self) -> [u8; 4] {
+        use winapi::um::commctrl::{
+            FIRST_IPADDRESS, FOURTH_IPADDRESS, IPM_GETADDRESS, SECOND_IPADDRESS, THIRD_IPADDRESS,
+        };
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut address: DWORD = 0;
+        wh::send_message(
+            handle,
+            IPM_GETADDRESS,
+            0,
+            &mut address as *mut DWORD as LPARAM,
+        );
+
+        let address = address as LPARAM;
+        [
+            FIRST_IPADDRESS(address),
+            SECOND_IPADDRESS(address),
+            THIRD_IPADDRESS(address),
+            FOURTH_IPADDRESS(address),
+        ]
+    }
+
+    /// Set the address in the control. If `None` is passed, the fields are cleared.
+    pub fn set_address(&self, address: Option<[u8; 4]>) {
+        use winapi::um::commctrl::{IPM_CLEARADDRESS, IPM_SETADDRESS, MAKEIPADDRESS};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        match address {
+            Some([a, b, c, d]) => {
+                let address = MAKEIPADDRESS(a as DWORD, b as DWORD, c as DWORD, d as DWORD);
+                wh::send_message(handle, IPM_SETADDRESS, 0, address);
+            }
+            None => {
+                wh::send_message(handle, IPM_CLEARADDRESS, 0, 0);
+            }
+        }
+    }
+
+    /// Return `true` if every field of the control is blank
+    pub fn blank(&self) -> bool {
+        use winapi::um::commctrl::IPM_ISBLANK;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, IPM_ISBLANK, 0, 0) != 0
+    }
+
+    /// Set the accepted range of values of a field. `field` is in the 0-3 range. The range is inclusive.
+    pub fn set_field_range(&self, field: usize, min: u8, max: u8) {
+        use winapi::um::commctrl::{IPM_SETRANGE, MAKEIPRANGE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, IPM_SETRANGE, field as WPARAM, MAKEIPRANGE(min, max));
+    }
+
+    /// Set the keyboard focus on a field of the control. `field` is in the 0-3 range.
+    pub fn set_field_focus(&self, field: usize) {
+        use winapi::um::commctrl::IPM_SETFOCUS;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, IPM_SETFOCUS, field as WPARAM, 0);
+    }
+
+    /// Return the font of the control
+    pub fn font(&self) -> Option<Font> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let font_handle = wh::get_window_font(handle);
+        if font_handle.is_null() {
+            None
+        } else {
+            Some(Font {
+                handle: font_handle,
+            })
+        }
+    }
+
+    /// Set the font of the control
+    pub fn set_font(&self, font: Option<&Font>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            wh::set_window_font(handle, font.map(|f| f.handle), true);
+        }
+    }
+
+    /// Return true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Set the keyboard focus on the first field of the control.
+    pub fn set_focus(&self) {
+        self.set_field_focus(0);
+    }
+
+    /// Return true if the control user can interact with the control, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Return true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Return the size of the ip address input in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Set the size of the ip address input in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Return the position of the ip address input in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Set the position of the ip address input in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        winapi::um::commctrl::WC_IPADDRESS
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE | WS_TABSTOP
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        use winapi::um::winuser::WS_CHILD;
+        WS_CHILD
+    }
+}
+
+control_cursor!(IpAddressInput, NOT_BOUND, BAD_HANDLE);
+
+impl Drop for IpAddressInput {
+    fn drop(&mut self) {
+        self.handle.destroy();
+    }
+}
+
+pub struct IpAddressInputBuilder<'a> {
+    size: (i32, i32),
+    position: (i32, i32),
+    enabled: bool,
+    focus: bool,
+    flags: Option<IpAddressInputFlags>,
+    ex_flags: u32,
+    font: Option<&'a Font>,
+    address: Option<[u8; 4]>,
+    parent: Option<ControlHandle>,
+}
+
+impl<'a> IpAddressInputBuilder<'a> {
+    pub fn flags(mut self, flags: IpAddressInputFlags) -> IpAddressInputBuilder<'a> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn ex_flags(mut self, flags: u32) -> IpAddressInputBuilder<'a> {
+        self.ex_flags = flags;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> IpAddressInputBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> IpAddressInputBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn enabled(mut self, e: bool) -> IpAddressInputBuilder<'a> {
+        self.enabled = e;
+        self
+    }
+
+    pub fn focus(mut self, focus: bool) -> IpAddressInputBuilder<'a> {
+        self.focus = focus;
+        self
+    }
+
+    pub fn font(mut self, font: Option<&'a Font>) -> IpAddressInputBuilder<'a> {
+        self.font = font;
+        self
+    }
+
+    pub fn address(mut self, address: Option<[u8; 4]>) -> IpAddressInputBuilder<'a> {
+        self.address = address;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> IpAddressInputBuilder<'a> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut IpAddressInput) -> Result<(), NwgError> {
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("IpAddressInput")),
+        }?;
+
+        *out = Default::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .ex_flags(self.ex_flags)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        if self.font.is_some() {
+            out.set_font(self.font);
+        } else {
+            out.set_font(Font::global_default().as_ref());
+        }
+
+        if self.address.is_some() {
+            out.set_address(self.address);
+        }
+
+        out.set_enabled(self.enabled);
+
+        if self.focus {
+            out.set_focus();
+        }
+
+        Ok(())
+    }
+}
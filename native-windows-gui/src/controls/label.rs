@@ -398,6 +398,8 @@ impl Label {
     }
 }
 
+control_cursor!(Label, NOT_BOUND, BAD_HANDLE);
+
 impl PartialEq for Label {
     fn eq(&self, other: &Self) -> bool {
         self.handle == other.handle
@@ -0,0 +1,383 @@
+use super::{ControlBase, ControlHandle};
+use crate::win32::base_helper::{check_hwnd, from_utf16, to_utf16};
+use crate::win32::window_helper as wh;
+use crate::{Font, NwgError};
+use std::mem;
+use winapi::shared::minwindef::LPARAM;
+use winapi::um::commctrl::{
+    LITEM, LWS_IGNORERETURN, LWS_NOPREFIX, LWS_RIGHT, LWS_TRANSPARENT, LWS_USEVISUALSTYLE,
+};
+use winapi::um::winuser::{WS_DISABLED, WS_TABSTOP, WS_VISIBLE};
+
+const NOT_BOUND: &'static str = "LinkLabel is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: LinkLabel handle is not HWND!";
+
+bitflags! {
+    /**
+        The link label flags
+
+        * NONE:             No flags. Equivalent to a invisible link label.
+        * VISIBLE:          The link label is immediatly visible after creation
+        * DISABLED:         The link label cannot be interacted with by the user. It also has a grayed out look.
+        * TAB_STOP:         The links can be selected using tab navigation
+        * TRANSPARENT:      The background of the control is transparent
+        * IGNORE_RETURN:    Pressing ENTER on a focused link does not raise `OnLinkClick`
+        * NO_PREFIX:        The `&` character is displayed as is instead of underlining the next character
+        * USE_VISUAL_STYLE: Draw the links using the colors of the current visual style
+        * RIGHT:            The text is right aligned
+    */
+    pub struct LinkLabelFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const TAB_STOP = WS_TABSTOP;
+        const TRANSPARENT = LWS_TRANSPARENT;
+        const IGNORE_RETURN = LWS_IGNORERETURN;
+        const NO_PREFIX = LWS_NOPREFIX;
+        const USE_VISUAL_STYLE = LWS_USEVISUALSTYLE;
+        const RIGHT = LWS_RIGHT;
+    }
+}
+
+/**
+A link label (SysLink) displays text with clickable hyperlinks. The links are defined in the text using
+`<a>` tags: `Read the <a href="https://example.com" id="docs">documentation</a>`. The `href` and the `id` attributes are optional.
+
+The control does not open the links itself. Handle `OnLinkClick` to react to a click.
+
+Requires the `link-label` feature.
+
+**Builder parameters:**
+  * `parent`:   **Required.** The link label parent container.
+  * `text`:     The link label text, with the links markup.
+  * `size`:     The link label size.
+  * `position`: The link label position.
+  * `enabled`:  If the link label can be used by the user. It also has a grayed out look if disabled.
+  * `flags`:    A combination of the LinkLabelFlags values.
+  * `ex_flags`: A combination of win32 window extended flags. Unlike `flags`, ex_flags must be used straight from winapi
+  * `font`:     The font used for the link label text
+
+**Control events:**
+  * `OnLinkClick`: When a link is clicked. See `EventData::on_link_click`
+  * `MousePress(_)`: Generic mouse press events on the link label
+  * `OnMouseMove`: Generic mouse mouse event
+  * `OnMouseWheel`: Generic mouse wheel event
+
+```rust
+use native_windows_gui as nwg;
+fn build_link(link: &mut nwg::LinkLabel, window: &nwg::Window) {
+    nwg::LinkLabel::builder()
+        .text("Visit the <a href=\"https://github.com\">project page</a>")
+        .parent(window)
+        .build(link);
+}
+```
+*/
+#[derive(Default, PartialEq, Eq)]
+pub struct LinkLabel {
+    pub handle: ControlHandle,
+}
+
+impl LinkLabel {
+    pub fn builder<'a>() -> LinkLabelBuilder<'a> {
+        LinkLabelBuilder {
+            text: "",
+            size: (130, 25),
+            position: (0, 0),
+            enabled: true,
+            flags: None,
+            ex_flags: 0,
+            font: None,
+            parent: None,
+        }
+    }
+
+    /// Return the url of the link at `index`. Returns an empty string if the link has no `href`.
+    pub fn link_url(&self, index: usize) -> String {
+        use winapi::um::commctrl::LIF_URL;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let item = get_item(handle, index, LIF_URL);
+        from_utf16(&item.szUrl)
+    }
+
+    /// Set the url of the link at `index`
+    pub fn set_link_url<'a>(&self, index: usize, url: &'a str) {
+        use winapi::um::commctrl::{LIF_ITEMINDEX, LIF_URL, LM_SETITEM};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LITEM = unsafe { mem::zeroed() };
+        item.mask = LIF_ITEMINDEX | LIF_URL;
+        item.iLink = index as i32;
+
+        let url = to_utf16(url);
+        let len = url.len().min(item.szUrl.len() - 1);
+        item.szUrl[..len].copy_from_slice(&url[..len]);
+
+        wh::send_message(handle, LM_SETITEM, 0, &item as *const LITEM as LPARAM);
+    }
+
+    /// Return the id of the link at `index`. Returns an empty string if the link has no `id`.
+    pub fn link_id(&self, index: usize) -> String {
+        use winapi::um::commctrl::LIF_ITEMID;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let item = get_item(handle, index, LIF_ITEMID);
+        from_utf16(&item.szID)
+    }
+
+    /// Return `true` if the link at `index` can be clicked
+    pub fn link_enabled(&self, index: usize) -> bool {
+        use winapi::um::commctrl::{LIF_STATE, LIS_ENABLED};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let item = get_item(handle, index, LIF_STATE);
+        item.state & LIS_ENABLED == LIS_ENABLED
+    }
+
+    /// Enable or disable the link at `index`
+    pub fn set_link_enabled(&self, index: usize, enabled: bool) {
+        use winapi::um::commctrl::{LIF_ITEMINDEX, LIF_STATE, LIS_ENABLED, LM_SETITEM};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LITEM = unsafe { mem::zeroed() };
+        item.mask = LIF_ITEMINDEX | LIF_STATE;
+        item.iLink = index as i32;
+        item.stateMask = LIS_ENABLED;
+        item.state = if enabled { LIS_ENABLED } else { 0 };
+
+        wh::send_message(handle, LM_SETITEM, 0, &item as *const LITEM as LPARAM);
+    }
+
+    /// Mark the link at `index` as visited
+    pub fn set_link_visited(&self, index: usize, visited: bool) {
+        use winapi::um::commctrl::{LIF_ITEMINDEX, LIF_STATE, LIS_VISITED, LM_SETITEM};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LITEM = unsafe { mem::zeroed() };
+        item.mask = LIF_ITEMINDEX | LIF_STATE;
+        item.iLink = index as i32;
+        item.stateMask = LIS_VISITED;
+        item.state = if visited { LIS_VISITED } else { 0 };
+
+        wh::send_message(handle, LM_SETITEM, 0, &item as *const LITEM as LPARAM);
+    }
+
+    /// Return the height required to display the whole text of the control at its current width
+    pub fn ideal_height(&self) -> u32 {
+        use winapi::um::commctrl::LM_GETIDEALHEIGHT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let height = wh::send_message(handle, LM_GETIDEALHEIGHT, 0, 0) as i32;
+        let (_, height) = unsafe { crate::win32::high_dpi::physical_to_logical(0, height) };
+        height as u32
+    }
+
+    /// Return the font of the control
+    pub fn font(&self) -> Option<Font> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let font_handle = wh::get_window_font(handle);
+        if font_handle.is_null() {
+            None
+        } else {
+            Some(Font {
+                handle: font_handle,
+            })
+        }
+    }
+
+    /// Set the font of the control
+    pub fn set_font(&self, font: Option<&Font>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            wh::set_window_font(handle, font.map(|f| f.handle), true);
+        }
+    }
+
+    /// Return true if the control user can interact with the control, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Return true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Return the size of the link label in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Set the size of the link label in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Return the position of the link label in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Set the position of the link label in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Return the link label text, including the links markup
+    pub fn text(&self) -> String {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_text(handle) }
+    }
+
+    /// Set the link label text. The links are defined with `<a>` tags.
+    pub fn set_text<'a>(&self, v: &'a str) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_text(handle, v) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        winapi::um::commctrl::WC_LINK
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE | WS_TABSTOP
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        use winapi::um::winuser::WS_CHILD;
+        WS_CHILD
+    }
+}
+
+control_cursor!(LinkLabel, NOT_BOUND, BAD_HANDLE);
+
+impl Drop for LinkLabel {
+    fn drop(&mut self) {
+        self.handle.destroy();
+    }
+}
+
+fn get_item(handle: winapi::shared::windef::HWND, index: usize, mask: u32) -> LITEM {
+    use winapi::um::commctrl::{LIF_ITEMINDEX, LIS_ENABLED, LIS_FOCUSED, LIS_VISITED, LM_GETITEM};
+
+    let mut item: LITEM = unsafe { mem::zeroed() };
+    item.mask = LIF_ITEMINDEX | mask;
+    item.iLink = index as i32;
+    item.stateMask = LIS_ENABLED | LIS_FOCUSED | LIS_VISITED;
+
+    wh::send_message(handle, LM_GETITEM, 0, &mut item as *mut LITEM as LPARAM);
+
+    item
+}
+
+pub struct LinkLabelBuilder<'a> {
+    text: &'a str,
+    size: (i32, i32),
+    position: (i32, i32),
+    enabled: bool,
+    flags: Option<LinkLabelFlags>,
+    ex_flags: u32,
+    font: Option<&'a Font>,
+    parent: Option<ControlHandle>,
+}
+
+impl<'a> LinkLabelBuilder<'a> {
+    pub fn flags(mut self, flags: LinkLabelFlags) -> LinkLabelBuilder<'a> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn ex_flags(mut self, flags: u32) -> LinkLabelBuilder<'a> {
+        self.ex_flags = flags;
+        self
+    }
+
+    pub fn text(mut self, text: &'a str) -> LinkLabelBuilder<'a> {
+        self.text = text;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> LinkLabelBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> LinkLabelBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn enabled(mut self, e: bool) -> LinkLabelBuilder<'a> {
+        self.enabled = e;
+        self
+    }
+
+    pub fn font(mut self, font: Option<&'a Font>) -> LinkLabelBuilder<'a> {
+        self.font = font;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> LinkLabelBuilder<'a> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut LinkLabel) -> Result<(), NwgError> {
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("LinkLabel")),
+        }?;
+
+        *out = Default::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .ex_flags(self.ex_flags)
+            .size(self.size)
+            .position(self.position)
+            .text(self.text)
+            .parent(Some(parent))
+            .build()?;
+
+        if self.font.is_some() {
+            out.set_font(self.font);
+        } else {
+            out.set_font(Font::global_default().as_ref());
+        }
+
+        out.set_enabled(self.enabled);
+
+        Ok(())
+    }
+}
@@ -1,14 +1,19 @@
-use super::{ControlBase, ControlHandle};
+use super::{ControlBase, ControlHandle, OwnerDrawItem};
 use crate::win32::base_helper::{check_hwnd, from_utf16, to_utf16};
+use crate::win32::high_dpi;
 use crate::win32::window_helper as wh;
-use crate::{Font, NwgError};
+use crate::{unbind_raw_event_handler, Font, NwgError, RawEventHandler};
 use std::cell::{Ref, RefCell, RefMut};
 use std::fmt::Display;
 use std::mem;
 use std::ops::Range;
+use std::rc::Rc;
 use winapi::shared::minwindef::{LPARAM, WPARAM};
 use winapi::shared::windef::HWND;
-use winapi::um::winuser::{LBS_MULTIPLESEL, LBS_NOSEL, WS_DISABLED, WS_TABSTOP, WS_VISIBLE};
+use winapi::um::winuser::{
+    LBS_EXTENDEDSEL, LBS_MULTICOLUMN, LBS_MULTIPLESEL, LBS_NOSEL, WS_DISABLED, WS_TABSTOP,
+    WS_VISIBLE,
+};
 
 const NOT_BOUND: &'static str = "ListBox is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: ListBox handle is not HWND!";
@@ -21,6 +26,8 @@ bitflags! {
         * VISIBLE:  The listbox is immediatly visible after creation
         * DISABLED: The listbox cannot be interacted with by the user. It also has a grayed out look.
         * MULTI_SELECT: It is possible for the user to select more than 1 item at a time
+        * EXTENDED_SELECT: Multiple items can be selected using the SHIFT and CTRL keys or the mouse
+        * MULTI_COLUMN: The items are displayed in multiple columns that are scrolled horizontally. See `ListBox::set_column_width`
         * NO_SELECT: It is impossible for the user to select the listbox items
         * TAB_STOP: The control can be selected using tab navigation
     */
@@ -29,6 +36,8 @@ bitflags! {
         const VISIBLE = WS_VISIBLE;
         const DISABLED = WS_DISABLED;
         const MULTI_SELECT = LBS_MULTIPLESEL;
+        const EXTENDED_SELECT = LBS_EXTENDEDSEL;
+        const MULTI_COLUMN = LBS_MULTICOLUMN;
         const NO_SELECT = LBS_NOSEL;
         const TAB_STOP = WS_TABSTOP;
     }
@@ -51,6 +60,7 @@ Requires the `list-box` feature.
   * `collection`:      The default collections of the listbox
   * `selected_index`:  The default selected index in the listbox collection
   * `multi_selection`: The collections of indices to set as selected in a multi selection listbox
+  * `owner_draw`:      If the items are drawn by the application. Requires the item type to implement `OwnerDrawItem`
 
 **Control events:**
   * `OnListBoxSelect`: When the current listbox selection is changed
@@ -72,11 +82,46 @@ fn build_listbox(listb: &mut nwg::ListBox<&'static str>, window: &nwg::Window, f
 }
 ```
 
+Owner drawn list boxes let the items draw themselves through the `OwnerDrawItem` trait.
+Each item can have its own height.
+
+```rust
+use native_windows_gui as nwg;
+use std::fmt;
+
+#[derive(Default)]
+struct Entry { title: String, subtitle: String }
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.title) }
+}
+
+impl nwg::OwnerDrawItem for Entry {
+    fn item_height(&self) -> u32 { 40 }
+
+    fn draw(&self, data: &nwg::OwnerDrawData) {
+        data.draw_background();
+        data.draw_text(&self.title, (4, -10));
+        data.draw_text(&self.subtitle, (4, 10));
+        data.draw_focus();
+    }
+}
+
+fn build_listbox(listb: &mut nwg::ListBox<Entry>, window: &nwg::Window) {
+    nwg::ListBox::builder()
+        .owner_draw(true)
+        .parent(window)
+        .build(listb);
+}
+```
+
 */
 #[derive(Default)]
 pub struct ListBox<D: Display + Default> {
     pub handle: ControlHandle,
-    collection: RefCell<Vec<D>>,
+    collection: Rc<RefCell<Vec<D>>>,
+    item_height: Option<fn(&D) -> u32>,
+    handler0: RefCell<Option<RawEventHandler>>,
 }
 
 impl<D: Display + Default> ListBox<D> {
@@ -92,6 +137,7 @@ impl<D: Display + Default> ListBox<D> {
             collection: None,
             selected_index: None,
             multi_selection: Vec::new(),
+            owner_draw: None,
             parent: None,
         }
     }
@@ -104,10 +150,11 @@ impl<D: Display + Default> ListBox<D> {
         let display = format!("{}", item);
         let display_os = to_utf16(&display);
 
-        unsafe {
-            wh::send_message(handle, LB_ADDSTRING, 0, mem::transmute(display_os.as_ptr()));
-        }
+        let index = unsafe {
+            wh::send_message(handle, LB_ADDSTRING, 0, mem::transmute(display_os.as_ptr()))
+        };
 
+        self.update_item_height(handle, index, &item);
         self.collection.borrow_mut().push(item);
     }
 
@@ -122,20 +169,22 @@ impl<D: Display + Default> ListBox<D> {
         let display = format!("{}", item);
         let display_os = to_utf16(&display);
 
-        let mut col = self.collection.borrow_mut();
-        if index == std::usize::MAX {
-            col.push(item);
-        } else {
-            col.insert(index, item);
-        }
-
-        unsafe {
+        let inserted_index = unsafe {
             wh::send_message(
                 handle,
                 LB_INSERTSTRING,
                 index,
                 mem::transmute(display_os.as_ptr()),
-            );
+            )
+        };
+
+        self.update_item_height(handle, inserted_index, &item);
+
+        let mut col = self.collection.borrow_mut();
+        if index == std::usize::MAX {
+            col.push(item);
+        } else {
+            col.insert(index, item);
         }
     }
 
@@ -343,9 +392,11 @@ impl<D: Display + Default> ListBox<D> {
             let display = format!("{}", item);
             let display_os = to_utf16(&display);
 
-            unsafe {
-                wh::send_message(handle, LB_ADDSTRING, 0, mem::transmute(display_os.as_ptr()));
-            }
+            let index = unsafe {
+                wh::send_message(handle, LB_ADDSTRING, 0, mem::transmute(display_os.as_ptr()))
+            };
+
+            self.update_item_height(handle, index, item);
         }
     }
 
@@ -361,9 +412,11 @@ impl<D: Display + Default> ListBox<D> {
             let display = format!("{}", item);
             let display_os = to_utf16(&display);
 
-            unsafe {
-                wh::send_message(handle, LB_ADDSTRING, 0, mem::transmute(display_os.as_ptr()));
-            }
+            let index = unsafe {
+                wh::send_message(handle, LB_ADDSTRING, 0, mem::transmute(display_os.as_ptr()))
+            };
+
+            self.update_item_height(handle, index, item);
         }
 
         let mut col_ref = self.collection.borrow_mut();
@@ -377,6 +430,25 @@ impl<D: Display + Default> ListBox<D> {
         self.set_collection(Vec::new());
     }
 
+    /// Set the width of the columns of a list box created with the `MULTI_COLUMN` flag
+    pub fn set_column_width(&self, width: u32) {
+        use winapi::um::winuser::LB_SETCOLUMNWIDTH;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let (width, _) = unsafe { high_dpi::logical_to_physical(width as i32, 0) };
+        wh::send_message(handle, LB_SETCOLUMNWIDTH, width as WPARAM, 0);
+    }
+
+    /// Redraw the items of an owner drawn list box. Should be called after items in `collection_mut` are modified.
+    pub fn redraw(&self) {
+        use winapi::um::winuser::InvalidateRect;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            InvalidateRect(handle, ::std::ptr::null(), 1);
+        }
+    }
+
     /// Return the number of items in the control. NOT the inner rust collection
     pub fn len(&self) -> usize {
         use winapi::um::winuser::LB_GETCOUNT;
@@ -509,10 +581,74 @@ impl<D: Display + Default> ListBox<D> {
         use winapi::um::winuser::LB_RESETCONTENT;
         wh::send_message(handle, LB_RESETCONTENT, 0, 0);
     }
+
+    /// Set the height of the item at `index` in an owner drawn list box. Does nothing for regular list box.
+    fn update_item_height(&self, handle: HWND, index: isize, item: &D) {
+        use winapi::um::winuser::{LB_ERR, LB_SETITEMHEIGHT};
+
+        let measure = match self.item_height {
+            Some(m) => m,
+            None => return,
+        };
+
+        let height = measure(item);
+        if index == LB_ERR || height == 0 {
+            return;
+        }
+
+        let (_, height) = unsafe { high_dpi::logical_to_physical(0, height as i32) };
+        wh::send_message(handle, LB_SETITEMHEIGHT, index as WPARAM, height as LPARAM);
+    }
+}
+
+control_cursor!([D: Display + Default] ListBox<D>, NOT_BOUND, BAD_HANDLE);
+
+impl<D: Display + Default + OwnerDrawItem + 'static> ListBox<D> {
+    /// Draw the items of the list box using `OwnerDrawItem::draw`.
+    /// The `WM_DRAWITEM` message is sent to the parent of the control, so the hook is bound to the parent.
+    fn hook_owner_draw(&mut self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::basetsd::UINT_PTR;
+        use winapi::um::winuser::{DRAWITEMSTRUCT, WM_DRAWITEM};
+
+        if self.handle.blank() {
+            panic!("{}", NOT_BOUND);
+        }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let collection = self.collection.clone();
+
+        self.item_height = Some(D::item_height);
+
+        let handler = bind_raw_event_handler_inner(
+            &parent_handle,
+            handle as UINT_PTR,
+            move |_hwnd, msg, _w, l| {
+                match msg {
+                    WM_DRAWITEM => unsafe {
+                        let draw = l as *const DRAWITEMSTRUCT;
+                        if super::owner_draw::draw_item(handle, &collection, draw) {
+                            return Some(1);
+                        }
+                    },
+                    _ => {}
+                }
+
+                None
+            },
+        );
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
 }
 
 impl<D: Display + Default> Drop for ListBox<D> {
     fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
         self.handle.destroy();
     }
 }
@@ -528,6 +664,7 @@ pub struct ListBoxBuilder<'a, D: Display + Default> {
     collection: Option<Vec<D>>,
     selected_index: Option<usize>,
     multi_selection: Vec<usize>,
+    owner_draw: Option<fn(&mut ListBox<D>)>,
     parent: Option<ControlHandle>,
 }
 
@@ -588,6 +725,8 @@ impl<'a, D: Display + Default> ListBoxBuilder<'a, D> {
     }
 
     pub fn build(self, out: &mut ListBox<D>) -> Result<(), NwgError> {
+        use winapi::um::winuser::LBS_OWNERDRAWVARIABLE;
+
         let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
 
         let parent = match self.parent {
@@ -595,11 +734,16 @@ impl<'a, D: Display + Default> ListBoxBuilder<'a, D> {
             None => Err(NwgError::no_parent("ListBox")),
         }?;
 
+        let mut forced_flags = out.forced_flags();
+        if self.owner_draw.is_some() {
+            forced_flags |= LBS_OWNERDRAWVARIABLE;
+        }
+
         *out = Default::default();
 
         out.handle = ControlBase::build_hwnd()
             .class_name(out.class_name())
-            .forced_flags(out.forced_flags())
+            .forced_flags(forced_flags)
             .flags(flags)
             .ex_flags(self.ex_flags)
             .size(self.size)
@@ -613,11 +757,15 @@ impl<'a, D: Display + Default> ListBoxBuilder<'a, D> {
             out.set_font(Font::global_default().as_ref());
         }
 
+        if let Some(hook) = self.owner_draw {
+            hook(out);
+        }
+
         if let Some(col) = self.collection {
             out.set_collection(col);
         }
 
-        if flags & LBS_MULTIPLESEL == LBS_MULTIPLESEL {
+        if flags & (LBS_MULTIPLESEL | LBS_EXTENDEDSEL) != 0 {
             for i in self.multi_selection {
                 out.multi_add_selection(i);
             }
@@ -637,6 +785,16 @@ impl<'a, D: Display + Default> ListBoxBuilder<'a, D> {
     }
 }
 
+impl<'a, D: Display + Default + OwnerDrawItem + 'static> ListBoxBuilder<'a, D> {
+    pub fn owner_draw(mut self, owner_draw: bool) -> ListBoxBuilder<'a, D> {
+        self.owner_draw = match owner_draw {
+            true => Some(ListBox::<D>::hook_owner_draw),
+            false => None,
+        };
+        self
+    }
+}
+
 impl<D: Display + Default> PartialEq for ListBox<D> {
     fn eq(&self, other: &Self) -> bool {
         self.handle == other.handle
@@ -327,10 +327,81 @@ impl ListView {
 
         match wh::send_message(handle, LVM_GETIMAGELIST, list_type.to_raw() as _, 0) {
             0 => None,
-            handle => Some(ImageList {
-                handle: handle as _,
-                owned: false,
-            }),
+            handle => Some(ImageList::borrowed(handle as _)),
+        }
+    }
+
+    /// Sets the overlay drawn over the image of an item. `overlay` is the overlay slot set with `ImageList::set_overlay`.
+    /// Use `0` to remove the overlay. Does nothing if the index is out of bounds
+    #[cfg(feature = "image-list")]
+    pub fn set_item_overlay(&self, row_index: usize, overlay: u8) {
+        use winapi::um::commctrl::{
+            INDEXTOOVERLAYMASK, LVIS_OVERLAYMASK, LVITEMW, LVM_SETITEMSTATE,
+        };
+
+        if !self.has_item(row_index, 0) {
+            return;
+        }
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.stateMask = LVIS_OVERLAYMASK;
+        item.state = INDEXTOOVERLAYMASK(overlay as u32);
+
+        wh::send_message(
+            handle,
+            LVM_SETITEMSTATE,
+            row_index,
+            &mut item as *mut _ as isize,
+        );
+    }
+
+    /// Returns the overlay slot drawn over the image of an item, or `0` if there is none or if the index is out of bounds
+    #[cfg(feature = "image-list")]
+    pub fn item_overlay(&self, row_index: usize) -> u8 {
+        use winapi::um::commctrl::{LVIS_OVERLAYMASK, LVM_GETITEMSTATE};
+
+        if !self.has_item(row_index, 0) {
+            return 0;
+        }
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let state = wh::send_message(
+            handle,
+            LVM_GETITEMSTATE,
+            row_index,
+            LVIS_OVERLAYMASK as isize,
+        );
+
+        ((state as u32 & LVIS_OVERLAYMASK) >> 8) as u8
+    }
+
+    /// Creates a drag image of an item, to use with `ImageList::begin_drag`. The returned image list is owned.
+    /// Returns `None` if the index is out of bounds
+    #[cfg(feature = "image-list")]
+    pub fn create_drag_image(&self, row_index: usize) -> Option<ImageList> {
+        use winapi::shared::windef::POINT;
+        use winapi::um::commctrl::LVM_CREATEDRAGIMAGE;
+
+        if !self.has_item(row_index, 0) {
+            return None;
+        }
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let mut origin = POINT { x: 0, y: 0 };
+        match wh::send_message(
+            handle,
+            LVM_CREATEDRAGIMAGE,
+            row_index,
+            &mut origin as *mut POINT as isize,
+        ) {
+            0 => None,
+            list => {
+                let mut list = ImageList::borrowed(list as _);
+                list.owned = true;
+                Some(list)
+            }
         }
     }
 
@@ -1228,6 +1299,8 @@ impl ListView {
     }
 }
 
+control_cursor!(ListView, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for ListView {
     fn drop(&mut self) {
         use winapi::um::wingdi::DeleteObject;
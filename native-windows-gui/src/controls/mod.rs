@@ -1,3 +1,6 @@
+#[macro_use]
+mod control_cursor;
+
 mod button;
 mod check_box;
 mod control_base;
@@ -41,12 +44,33 @@ mod notice;
 #[cfg(feature = "combobox")]
 mod combo_box;
 
+#[cfg(feature = "combobox")]
+mod combo_box_ex;
+
 #[cfg(feature = "listbox")]
 mod list_box;
 
+#[cfg(any(feature = "listbox", feature = "combobox"))]
+mod owner_draw;
+
 #[cfg(feature = "datetime-picker")]
 mod date_picker;
 
+#[cfg(feature = "month-calendar")]
+mod month_calendar;
+
+#[cfg(feature = "link-label")]
+mod link_label;
+
+#[cfg(feature = "ip-address-input")]
+mod ip_address_input;
+
+#[cfg(feature = "hotkey-input")]
+mod hotkey_input;
+
+#[cfg(feature = "animation-frame")]
+mod animation_frame;
+
 #[cfg(feature = "progress-bar")]
 mod progress_bar;
 
@@ -83,18 +107,25 @@ mod scroll_bar;
 #[cfg(feature = "plotting")]
 mod plotters;
 
+#[cfg(feature = "plotting")]
+mod plotters_view;
+
 mod handle_from_control;
+mod preferred_size;
 
 pub use button::{Button, ButtonBuilder, ButtonFlags};
 pub use check_box::{CheckBox, CheckBoxBuilder, CheckBoxFlags, CheckBoxState};
 pub use control_base::{ControlBase, HwndBuilder, OtherBuilder, TimerBuilder as BaseTimerBuilder};
 pub use control_handle::ControlHandle;
-pub use image_frame::{ImageFrame, ImageFrameBuilder, ImageFrameFlags};
+pub use image_frame::{ImageFrame, ImageFrameBuilder, ImageFrameFlags, ImageFrameScaling};
 pub use label::{Label, LabelBuilder, LabelFlags};
 pub use radio_button::{RadioButton, RadioButtonBuilder, RadioButtonFlags, RadioButtonState};
-pub use text_input::{TextInput, TextInputBuilder, TextInputFlags};
+pub use text_input::{AutoCompleteFlags, TextInput, TextInputBuilder, TextInputFlags};
 pub use window::{Window, WindowBuilder, WindowFlags};
 
+pub(crate) use preferred_size::control_preferred_size;
+pub use preferred_size::PreferredSize;
+
 #[cfg(feature = "textbox")]
 pub use text_box::{TextBox, TextBoxBuilder, TextBoxFlags};
 
@@ -133,12 +164,37 @@ pub use notice::{Notice, NoticeBuilder, NoticeSender};
 #[cfg(feature = "combobox")]
 pub use combo_box::{ComboBox, ComboBoxBuilder, ComboBoxFlags};
 
+#[cfg(feature = "combobox")]
+pub use combo_box_ex::{ComboBoxEx, ComboBoxExBuilder, ComboBoxExFlags, ComboBoxFilter};
+
 #[cfg(feature = "listbox")]
 pub use list_box::{ListBox, ListBoxBuilder, ListBoxFlags};
 
+#[cfg(any(feature = "listbox", feature = "combobox"))]
+pub use owner_draw::{OwnerDrawData, OwnerDrawItem, OwnerDrawState};
+
 #[cfg(feature = "datetime-picker")]
 pub use date_picker::{DatePicker, DatePickerBuilder, DatePickerFlags, DatePickerValue};
 
+#[cfg(feature = "month-calendar")]
+pub use month_calendar::{
+    MonthCalendar, MonthCalendarBuilder, MonthCalendarFlags, MonthCalendarView,
+};
+
+#[cfg(feature = "link-label")]
+pub use link_label::{LinkLabel, LinkLabelBuilder, LinkLabelFlags};
+
+#[cfg(feature = "ip-address-input")]
+pub use ip_address_input::{IpAddressInput, IpAddressInputBuilder, IpAddressInputFlags};
+
+#[cfg(feature = "hotkey-input")]
+pub use hotkey_input::{
+    HotKey, HotKeyCombinations, HotKeyInput, HotKeyInputBuilder, HotKeyInputFlags, HotKeyModifiers,
+};
+
+#[cfg(feature = "animation-frame")]
+pub use animation_frame::{AnimationFrame, AnimationFrameBuilder, AnimationFrameFlags};
+
 #[cfg(feature = "progress-bar")]
 pub use progress_bar::{ProgressBar, ProgressBarBuilder, ProgressBarFlags, ProgressBarState};
 
@@ -147,8 +203,8 @@ pub use tabs::{Tab, TabBuilder, TabsContainer, TabsContainerBuilder, TabsContain
 
 #[cfg(feature = "tree-view")]
 pub use treeview::{
-    ExpandState, TreeInsert, TreeItem, TreeItemAction, TreeItemState, TreeView, TreeViewBuilder,
-    TreeViewFlags,
+    ExpandState, TreeCheckState, TreeInsert, TreeItem, TreeItemAction, TreeItemState, TreeView,
+    TreeViewBuilder, TreeViewFlags,
 };
 
 #[cfg(all(feature = "tree-view-iterator", feature = "tree-view"))]
@@ -185,4 +241,7 @@ pub use scroll_bar::{ScrollBar, ScrollBarBuilder, ScrollBarFlags};
 #[cfg(feature = "plotting")]
 pub use self::plotters::{
     Plotters, PlottersBackend, PlottersBuilder, PlottersDrawingArea, PlottersError,
+    SoftwarePlotters,
 };
+#[cfg(feature = "plotting")]
+pub use self::plotters_view::{PlottersPoint, PlottersView};
@@ -0,0 +1,886 @@
+use super::{ControlBase, ControlHandle, DatePickerValue};
+use crate::win32::base_helper::check_hwnd;
+use crate::win32::window_helper as wh;
+use crate::{unbind_raw_event_handler, Font, NwgError, RawEventHandler};
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+use winapi::shared::minwindef::{LPARAM, WPARAM};
+use winapi::um::commctrl::{
+    MCS_NOTODAY, MCS_NOTODAYCIRCLE, MCS_NOTRAILINGDATES, MCS_SHORTDAYSOFWEEK, MCS_WEEKNUMBERS,
+    MONTHDAYSTATE,
+};
+use winapi::um::minwinbase::SYSTEMTIME;
+use winapi::um::winuser::{WS_DISABLED, WS_TABSTOP, WS_VISIBLE};
+
+#[cfg(feature = "winnls")]
+use crate::Locale;
+
+const NOT_BOUND: &'static str = "MonthCalendar is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: MonthCalendar handle is not HWND!";
+
+bitflags! {
+    /**
+        The MonthCalendar flags
+
+        * NONE:               No flags. Equivalent to a invisible calendar.
+        * VISIBLE:            The calendar is immediatly visible after creation
+        * DISABLED:           The calendar cannot be interacted with by the user. It also has a grayed out look.
+        * TAB_STOP:           The control can be selected using tab navigation
+        * WEEK_NUMBERS:       Display the week numbers to the left of each row of days
+        * NO_TODAY:           Do not display the "today" date at the bottom of the control
+        * NO_TODAY_CIRCLE:    Do not circle the "today" date
+        * NO_TRAILING_DATES:  Do not display the dates from the previous and next months
+        * SHORT_DAYS_OF_WEEK: Use the short names of the days of the week in the header
+    */
+    pub struct MonthCalendarFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const TAB_STOP = WS_TABSTOP;
+        const WEEK_NUMBERS = MCS_WEEKNUMBERS;
+        const NO_TODAY = MCS_NOTODAY;
+        const NO_TODAY_CIRCLE = MCS_NOTODAYCIRCLE;
+        const NO_TRAILING_DATES = MCS_NOTRAILINGDATES;
+        const SHORT_DAYS_OF_WEEK = MCS_SHORTDAYSOFWEEK;
+    }
+}
+
+/// The view displayed by a month calendar
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MonthCalendarView {
+    /// The days of one or more months
+    Month,
+    /// The months of a year
+    Year,
+    /// The years of a decade
+    Decade,
+    /// The decades of a century
+    Century,
+}
+
+/**
+A month calendar control is an always visible calendar that lets the user select a date or a range of dates.
+If the control is big enough, it displays more than one month. Use the `months` builder parameter to size the control for a number of months.
+
+Days can be displayed in bold (ex: the days with appointments) using `set_bold_days`.
+
+Requires the `month-calendar` feature.
+
+**Builder parameters:**
+  * `parent`:            **Required.** The calendar parent container.
+  * `size`:              The calendar size.
+  * `position`:          The calendar position.
+  * `enabled`:           If the calendar can be used by the user. It also has a grayed out look if disabled.
+  * `flags`:             A combination of the MonthCalendarFlags values.
+  * `ex_flags`:          A combination of win32 window extended flags. Unlike `flags`, ex_flags must be used straight from winapi
+  * `font`:              The font used for the calendar text
+  * `months`:            Resize the control to display `(columns, rows)` months. Overrides `size`.
+  * `date`:              The default selected date
+  * `multi_select`:      If the user can select a range of dates. Must be set during the control creation.
+  * `max_selection`:     The maximum number of days in a range selection. Defaults to 7.
+  * `selection_range`:   The default selected range. Requires `multi_select`.
+  * `range`:             The minimum and the maximum dates that can be displayed. The value is inclusive.
+  * `today`:             The "today" date. Defaults to the system date.
+  * `first_day_of_week`: The first day of the week. 0 (Monday) to 6 (Sunday).
+  * `locale`:            Use the first day of the week of a locale. Requires the `winnls` feature.
+  * `bold_days`:         The days displayed in bold
+  * `focus`:             The control receive focus after being created
+
+**Control events:**
+  * `OnMonthCalendarSelectionChanged`: When the selected date or range of dates changes. Also raised when the user scrolls to another month.
+  * `OnMonthCalendarSelect`: When the user explicitly selects a date
+  * `OnMonthCalendarViewChanged`: When the view changes (ex: from `Month` to `Year`)
+  * `MousePress(_)`: Generic mouse press events on the calendar
+  * `OnMouseMove`: Generic mouse mouse event
+  * `OnMouseWheel`: Generic mouse wheel event
+
+```rust
+use native_windows_gui as nwg;
+fn build_calendar(cal: &mut nwg::MonthCalendar, window: &nwg::Window) {
+    let meeting = nwg::DatePickerValue { year: 2021, month: 3, day: 15 };
+
+    nwg::MonthCalendar::builder()
+        .months((2, 1))
+        .multi_select(true)
+        .max_selection(14)
+        .bold_days(&[meeting])
+        .parent(window)
+        .build(cal);
+}
+```
+*/
+#[derive(Default)]
+pub struct MonthCalendar {
+    pub handle: ControlHandle,
+    bold_days: Rc<RefCell<Vec<DatePickerValue>>>,
+    handler0: RefCell<Option<RawEventHandler>>,
+}
+
+impl MonthCalendar {
+    pub fn builder<'a>() -> MonthCalendarBuilder<'a> {
+        MonthCalendarBuilder {
+            size: (250, 200),
+            position: (0, 0),
+            enabled: true,
+            focus: false,
+            flags: None,
+            ex_flags: 0,
+            font: None,
+            months: None,
+            date: None,
+            multi_select: false,
+            max_selection: None,
+            selection_range: None,
+            range: None,
+            today: None,
+            first_day_of_week: None,
+            bold_days: &[],
+            parent: None,
+        }
+    }
+
+    /// Return the selected date. In a multi select calendar, return the first day of the selected range.
+    pub fn value(&self) -> DatePickerValue {
+        use winapi::um::commctrl::MCM_GETCURSEL;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if self.multi_select() {
+            return self.selection_range()[0];
+        }
+
+        let mut st: SYSTEMTIME = unsafe { mem::zeroed() };
+        wh::send_message(
+            handle,
+            MCM_GETCURSEL,
+            0,
+            &mut st as *mut SYSTEMTIME as LPARAM,
+        );
+        from_systemtime(&st)
+    }
+
+    /// Select a date. In a multi select calendar, this selects a range of a single day.
+    pub fn set_value(&self, date: DatePickerValue) {
+        use winapi::um::commctrl::MCM_SETCURSEL;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if self.multi_select() {
+            return self.set_selection_range([date, date]);
+        }
+
+        let st = to_systemtime(&date);
+        wh::send_message(handle, MCM_SETCURSEL, 0, &st as *const SYSTEMTIME as LPARAM);
+    }
+
+    /// Return `true` if the user can select a range of dates
+    pub fn multi_select(&self) -> bool {
+        use winapi::um::commctrl::MCS_MULTISELECT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let style = wh::get_style(handle);
+        style & MCS_MULTISELECT == MCS_MULTISELECT
+    }
+
+    /// Return the first and the last day of the selected range. Both values are the same in a single select calendar.
+    pub fn selection_range(&self) -> [DatePickerValue; 2] {
+        use winapi::um::commctrl::MCM_GETSELRANGE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if !self.multi_select() {
+            let value = self.value();
+            return [value, value];
+        }
+
+        let mut range: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        wh::send_message(
+            handle,
+            MCM_GETSELRANGE,
+            0,
+            &mut range as *mut [SYSTEMTIME; 2] as LPARAM,
+        );
+
+        [from_systemtime(&range[0]), from_systemtime(&range[1])]
+    }
+
+    /// Select a range of dates. The range must fit in `max_selection`. Does nothing in a single select calendar.
+    pub fn set_selection_range(&self, range: [DatePickerValue; 2]) {
+        use winapi::um::commctrl::MCM_SETSELRANGE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let range = [to_systemtime(&range[0]), to_systemtime(&range[1])];
+        wh::send_message(
+            handle,
+            MCM_SETSELRANGE,
+            0,
+            &range as *const [SYSTEMTIME; 2] as LPARAM,
+        );
+    }
+
+    /// Return the maximum number of days that can be selected in a multi select calendar
+    pub fn max_selection(&self) -> u32 {
+        use winapi::um::commctrl::MCM_GETMAXSELCOUNT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, MCM_GETMAXSELCOUNT, 0, 0) as u32
+    }
+
+    /// Set the maximum number of days that can be selected in a multi select calendar
+    pub fn set_max_selection(&self, count: u32) {
+        use winapi::um::commctrl::MCM_SETMAXSELCOUNT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, MCM_SETMAXSELCOUNT, count as WPARAM, 0);
+    }
+
+    /// Return the minimum and the maximum dates of the calendar. `None` means there is no limit.
+    pub fn range(&self) -> [Option<DatePickerValue>; 2] {
+        use winapi::um::commctrl::{GDTR_MAX, GDTR_MIN, MCM_GETRANGE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut range: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        let set = wh::send_message(
+            handle,
+            MCM_GETRANGE,
+            0,
+            &mut range as *mut [SYSTEMTIME; 2] as LPARAM,
+        ) as WPARAM;
+
+        [
+            match set & GDTR_MIN == GDTR_MIN {
+                true => Some(from_systemtime(&range[0])),
+                false => None,
+            },
+            match set & GDTR_MAX == GDTR_MAX {
+                true => Some(from_systemtime(&range[1])),
+                false => None,
+            },
+        ]
+    }
+
+    /// Set the minimum and the maximum dates of the calendar. The value is inclusive. `None` removes the limit.
+    pub fn set_range(&self, range: [Option<DatePickerValue>; 2]) {
+        use winapi::um::commctrl::{GDTR_MAX, GDTR_MIN, MCM_SETRANGE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut flags = 0;
+        let mut values: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        if let Some(min) = range[0] {
+            flags |= GDTR_MIN;
+            values[0] = to_systemtime(&min);
+        }
+        if let Some(max) = range[1] {
+            flags |= GDTR_MAX;
+            values[1] = to_systemtime(&max);
+        }
+
+        wh::send_message(
+            handle,
+            MCM_SETRANGE,
+            flags as WPARAM,
+            &values as *const [SYSTEMTIME; 2] as LPARAM,
+        );
+    }
+
+    /// Return the "today" date of the calendar
+    pub fn today(&self) -> DatePickerValue {
+        use winapi::um::commctrl::MCM_GETTODAY;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut st: SYSTEMTIME = unsafe { mem::zeroed() };
+        wh::send_message(
+            handle,
+            MCM_GETTODAY,
+            0,
+            &mut st as *mut SYSTEMTIME as LPARAM,
+        );
+        from_systemtime(&st)
+    }
+
+    /// Set the "today" date of the calendar. `None` resets the value to the system date.
+    pub fn set_today(&self, today: Option<DatePickerValue>) {
+        use winapi::um::commctrl::MCM_SETTODAY;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        match today {
+            Some(today) => {
+                let st = to_systemtime(&today);
+                wh::send_message(handle, MCM_SETTODAY, 0, &st as *const SYSTEMTIME as LPARAM);
+            }
+            None => {
+                wh::send_message(handle, MCM_SETTODAY, 0, 0);
+            }
+        }
+    }
+
+    /// Return the first day of the week displayed in the calendar. 0 (Monday) to 6 (Sunday).
+    pub fn first_day_of_week(&self) -> u32 {
+        use winapi::shared::minwindef::LOWORD;
+        use winapi::um::commctrl::MCM_GETFIRSTDAYOFWEEK;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        LOWORD(wh::send_message(handle, MCM_GETFIRSTDAYOFWEEK, 0, 0) as u32) as u32
+    }
+
+    /// Set the first day of the week displayed in the calendar. 0 (Monday) to 6 (Sunday).
+    pub fn set_first_day_of_week(&self, day: u32) {
+        use winapi::um::commctrl::MCM_SETFIRSTDAYOFWEEK;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, MCM_SETFIRSTDAYOFWEEK, 0, day as LPARAM);
+    }
+
+    /// Use the first day of the week of a locale
+    #[cfg(feature = "winnls")]
+    pub fn set_locale(&self, locale: &Locale) {
+        self.set_first_day_of_week(locale.first_day_of_week() as u32);
+    }
+
+    /// Return the current view of the calendar
+    pub fn view(&self) -> MonthCalendarView {
+        use winapi::um::commctrl::{MCMV_CENTURY, MCMV_DECADE, MCMV_YEAR, MCM_GETCURRENTVIEW};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        match wh::send_message(handle, MCM_GETCURRENTVIEW, 0, 0) as u32 {
+            MCMV_YEAR => MonthCalendarView::Year,
+            MCMV_DECADE => MonthCalendarView::Decade,
+            MCMV_CENTURY => MonthCalendarView::Century,
+            _ => MonthCalendarView::Month,
+        }
+    }
+
+    /// Set the current view of the calendar
+    pub fn set_view(&self, view: MonthCalendarView) {
+        use winapi::um::commctrl::{
+            MCMV_CENTURY, MCMV_DECADE, MCMV_MONTH, MCMV_YEAR, MCM_SETCURRENTVIEW,
+        };
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let view = match view {
+            MonthCalendarView::Month => MCMV_MONTH,
+            MonthCalendarView::Year => MCMV_YEAR,
+            MonthCalendarView::Decade => MCMV_DECADE,
+            MonthCalendarView::Century => MCMV_CENTURY,
+        };
+
+        wh::send_message(handle, MCM_SETCURRENTVIEW, 0, view as LPARAM);
+    }
+
+    /// Return the first and the last day of the months fully displayed in the calendar
+    pub fn visible_range(&self) -> [DatePickerValue; 2] {
+        use winapi::um::commctrl::{GMR_VISIBLE, MCM_GETMONTHRANGE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut range: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        wh::send_message(
+            handle,
+            MCM_GETMONTHRANGE,
+            GMR_VISIBLE as WPARAM,
+            &mut range as *mut [SYSTEMTIME; 2] as LPARAM,
+        );
+
+        [from_systemtime(&range[0]), from_systemtime(&range[1])]
+    }
+
+    /// Return the number of months displayed in the calendar
+    pub fn months_count(&self) -> u32 {
+        use winapi::um::commctrl::MCM_GETCALENDARCOUNT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, MCM_GETCALENDARCOUNT, 0, 0) as u32
+    }
+
+    /// Return the size required to display a single month
+    pub fn min_size(&self) -> (u32, u32) {
+        use winapi::shared::windef::RECT;
+        use winapi::um::commctrl::MCM_GETMINREQRECT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut rect: RECT = unsafe { mem::zeroed() };
+        wh::send_message(
+            handle,
+            MCM_GETMINREQRECT,
+            0,
+            &mut rect as *mut RECT as LPARAM,
+        );
+
+        let (w, h) = unsafe {
+            crate::win32::high_dpi::physical_to_logical(
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+            )
+        };
+        (w as u32, h as u32)
+    }
+
+    /// Resize the control to display `columns * rows` months
+    pub fn set_months(&self, columns: u32, rows: u32) {
+        use winapi::shared::windef::RECT;
+        use winapi::um::commctrl::MCM_SIZERECTTOMIN;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let (w, h) = self.min_size();
+        let (w, h) = unsafe {
+            crate::win32::high_dpi::logical_to_physical(
+                (w * columns.max(1)) as i32,
+                (h * rows.max(1)) as i32,
+            )
+        };
+
+        // The control adds a small gap between the months. Let it round the size to a number of months.
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: w + w / 20,
+            bottom: h + h / 20,
+        };
+        wh::send_message(
+            handle,
+            MCM_SIZERECTTOMIN,
+            0,
+            &mut rect as *mut RECT as LPARAM,
+        );
+
+        let (w, h) =
+            unsafe { crate::win32::high_dpi::physical_to_logical(rect.right, rect.bottom) };
+        self.set_size(w as u32, h as u32);
+    }
+
+    /// Return the days displayed in bold
+    pub fn bold_days(&self) -> Vec<DatePickerValue> {
+        self.bold_days.borrow().clone()
+    }
+
+    /// Set the days displayed in bold. The days are kept by the control and displayed when the user scrolls to their month.
+    pub fn set_bold_days(&self, days: &[DatePickerValue]) {
+        use winapi::um::commctrl::{GMR_DAYSTATE, MCM_GETMONTHRANGE, MCM_SETDAYSTATE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        *self.bold_days.borrow_mut() = days.to_vec();
+
+        // Update the months currently displayed. Other months are updated by `MCN_GETDAYSTATE`.
+        let mut range: [SYSTEMTIME; 2] = unsafe { mem::zeroed() };
+        let count = wh::send_message(
+            handle,
+            MCM_GETMONTHRANGE,
+            GMR_DAYSTATE as WPARAM,
+            &mut range as *mut [SYSTEMTIME; 2] as LPARAM,
+        ) as usize;
+
+        let mut state: Vec<MONTHDAYSTATE> = vec![0; count];
+        fill_day_state(&self.bold_days.borrow(), &range[0], &mut state);
+
+        wh::send_message(
+            handle,
+            MCM_SETDAYSTATE,
+            count as WPARAM,
+            state.as_ptr() as LPARAM,
+        );
+    }
+
+    /// Return the font of the control
+    pub fn font(&self) -> Option<Font> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let font_handle = wh::get_window_font(handle);
+        if font_handle.is_null() {
+            None
+        } else {
+            Some(Font {
+                handle: font_handle,
+            })
+        }
+    }
+
+    /// Sets the font of the control
+    pub fn set_font(&self, font: Option<&Font>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            wh::set_window_font(handle, font.map(|f| f.handle), true);
+        }
+    }
+
+    /// Return true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Sets the keyboard focus on the calendar.
+    pub fn set_focus(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe {
+            wh::set_focus(handle);
+        }
+    }
+
+    /// Return true if the control user can interact with the control, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Return true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Return the size of the calendar in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Set the size of the calendar in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Return the position of the calendar in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Set the position of the calendar in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        winapi::um::commctrl::MONTHCAL_CLASS
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE | WS_TABSTOP
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        use winapi::um::commctrl::MCS_DAYSTATE;
+        use winapi::um::winuser::WS_CHILD;
+
+        WS_CHILD | MCS_DAYSTATE
+    }
+
+    /// The calendar asks for the bold days of the months with `MCN_GETDAYSTATE` when the user scrolls.
+    /// The notification is sent to the parent of the control, so the hook is bound to the parent.
+    fn hook_day_state(&self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::basetsd::UINT_PTR;
+        use winapi::um::commctrl::{MCN_GETDAYSTATE, NMDAYSTATE};
+        use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+
+        if self.handle.blank() {
+            panic!("{}", NOT_BOUND);
+        }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let bold_days = self.bold_days.clone();
+
+        let handler = bind_raw_event_handler_inner(
+            &parent_handle,
+            handle as UINT_PTR,
+            move |_hwnd, msg, _w, l| {
+                if msg != WM_NOTIFY {
+                    return None;
+                }
+
+                unsafe {
+                    let nmhdr = &*(l as *const NMHDR);
+                    if nmhdr.hwndFrom != handle || nmhdr.code != MCN_GETDAYSTATE {
+                        return None;
+                    }
+
+                    let data = &*(l as *const NMDAYSTATE);
+                    let state =
+                        ::std::slice::from_raw_parts_mut(data.prgDayState, data.cDayState as usize);
+
+                    if let Ok(days) = bold_days.try_borrow() {
+                        fill_day_state(&days, &data.stStart, state);
+                    }
+                }
+
+                Some(0)
+            },
+        );
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+}
+
+control_cursor!(MonthCalendar, NOT_BOUND, BAD_HANDLE);
+
+impl Drop for MonthCalendar {
+    fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        self.handle.destroy();
+    }
+}
+
+impl PartialEq for MonthCalendar {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+fn to_systemtime(v: &DatePickerValue) -> SYSTEMTIME {
+    SYSTEMTIME {
+        wYear: v.year,
+        wMonth: v.month,
+        wDayOfWeek: 0,
+        wDay: v.day,
+        wHour: 0,
+        wMinute: 0,
+        wSecond: 0,
+        wMilliseconds: 0,
+    }
+}
+
+fn from_systemtime(st: &SYSTEMTIME) -> DatePickerValue {
+    DatePickerValue {
+        year: st.wYear,
+        month: st.wMonth,
+        day: st.wDay,
+    }
+}
+
+/// Fill the day states of the consecutive months starting at `start`. Bit `n` of a month state is day `n+1`.
+fn fill_day_state(days: &[DatePickerValue], start: &SYSTEMTIME, state: &mut [MONTHDAYSTATE]) {
+    let first = (start.wYear as i32) * 12 + (start.wMonth as i32 - 1);
+
+    for s in state.iter_mut() {
+        *s = 0;
+    }
+
+    for day in days {
+        let month = (day.year as i32) * 12 + (day.month as i32 - 1);
+        let index = month - first;
+        if index >= 0 && (index as usize) < state.len() && day.day >= 1 && day.day <= 31 {
+            state[index as usize] |= 1 << (day.day - 1);
+        }
+    }
+}
+
+pub struct MonthCalendarBuilder<'a> {
+    size: (i32, i32),
+    position: (i32, i32),
+    enabled: bool,
+    focus: bool,
+    flags: Option<MonthCalendarFlags>,
+    ex_flags: u32,
+    font: Option<&'a Font>,
+    months: Option<(u32, u32)>,
+    date: Option<DatePickerValue>,
+    multi_select: bool,
+    max_selection: Option<u32>,
+    selection_range: Option<[DatePickerValue; 2]>,
+    range: Option<[Option<DatePickerValue>; 2]>,
+    today: Option<DatePickerValue>,
+    first_day_of_week: Option<u32>,
+    bold_days: &'a [DatePickerValue],
+    parent: Option<ControlHandle>,
+}
+
+impl<'a> MonthCalendarBuilder<'a> {
+    pub fn flags(mut self, flags: MonthCalendarFlags) -> MonthCalendarBuilder<'a> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn ex_flags(mut self, flags: u32) -> MonthCalendarBuilder<'a> {
+        self.ex_flags = flags;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> MonthCalendarBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> MonthCalendarBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn enabled(mut self, e: bool) -> MonthCalendarBuilder<'a> {
+        self.enabled = e;
+        self
+    }
+
+    pub fn font(mut self, font: Option<&'a Font>) -> MonthCalendarBuilder<'a> {
+        self.font = font;
+        self
+    }
+
+    pub fn months(mut self, months: (u32, u32)) -> MonthCalendarBuilder<'a> {
+        self.months = Some(months);
+        self
+    }
+
+    pub fn date(mut self, date: Option<DatePickerValue>) -> MonthCalendarBuilder<'a> {
+        self.date = date;
+        self
+    }
+
+    pub fn multi_select(mut self, multi: bool) -> MonthCalendarBuilder<'a> {
+        self.multi_select = multi;
+        self
+    }
+
+    pub fn max_selection(mut self, count: u32) -> MonthCalendarBuilder<'a> {
+        self.max_selection = Some(count);
+        self
+    }
+
+    pub fn selection_range(
+        mut self,
+        range: Option<[DatePickerValue; 2]>,
+    ) -> MonthCalendarBuilder<'a> {
+        self.selection_range = range;
+        self
+    }
+
+    pub fn range(
+        mut self,
+        range: Option<[Option<DatePickerValue>; 2]>,
+    ) -> MonthCalendarBuilder<'a> {
+        self.range = range;
+        self
+    }
+
+    pub fn today(mut self, today: Option<DatePickerValue>) -> MonthCalendarBuilder<'a> {
+        self.today = today;
+        self
+    }
+
+    pub fn first_day_of_week(mut self, day: Option<u32>) -> MonthCalendarBuilder<'a> {
+        self.first_day_of_week = day;
+        self
+    }
+
+    #[cfg(feature = "winnls")]
+    pub fn locale(mut self, locale: &Locale) -> MonthCalendarBuilder<'a> {
+        self.first_day_of_week = Some(locale.first_day_of_week() as u32);
+        self
+    }
+
+    pub fn bold_days(mut self, days: &'a [DatePickerValue]) -> MonthCalendarBuilder<'a> {
+        self.bold_days = days;
+        self
+    }
+
+    pub fn focus(mut self, focus: bool) -> MonthCalendarBuilder<'a> {
+        self.focus = focus;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> MonthCalendarBuilder<'a> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut MonthCalendar) -> Result<(), NwgError> {
+        use winapi::um::commctrl::MCS_MULTISELECT;
+
+        let mut flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+        if self.multi_select {
+            flags |= MCS_MULTISELECT;
+        }
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("MonthCalendar")),
+        }?;
+
+        *out = Default::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .ex_flags(self.ex_flags)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        out.hook_day_state();
+
+        if self.font.is_some() {
+            out.set_font(self.font);
+        } else {
+            out.set_font(Font::global_default().as_ref());
+        }
+
+        if let Some(day) = self.first_day_of_week {
+            out.set_first_day_of_week(day);
+        }
+
+        if let Some(range) = self.range {
+            out.set_range(range);
+        }
+
+        if self.today.is_some() {
+            out.set_today(self.today);
+        }
+
+        if let Some(count) = self.max_selection {
+            out.set_max_selection(count);
+        }
+
+        if let Some(date) = self.date {
+            out.set_value(date);
+        }
+
+        if let Some(range) = self.selection_range {
+            out.set_selection_range(range);
+        }
+
+        if let Some((columns, rows)) = self.months {
+            out.set_months(columns, rows);
+        }
+
+        out.set_bold_days(self.bold_days);
+
+        out.set_enabled(self.enabled);
+
+        if self.focus {
+            out.set_focus();
+        }
+
+        Ok(())
+    }
+}
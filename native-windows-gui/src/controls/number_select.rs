@@ -270,6 +270,8 @@ impl NumberSelect {
     }
 }
 
+control_cursor!(NumberSelect, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for NumberSelect {
     fn drop(&mut self) {
         if let Some(h) = self.handler.as_ref() {
@@ -0,0 +1,229 @@
+/*!
+    Shared types used by the owner drawn `ListBox` and `ComboBox`.
+*/
+use crate::win32::base_helper::to_utf16;
+use winapi::shared::windef::{HDC, RECT};
+use winapi::um::winuser::{
+    DRAWITEMSTRUCT, ODS_COMBOBOXEDIT, ODS_DISABLED, ODS_FOCUS, ODS_SELECTED,
+};
+
+bitflags! {
+    /**
+        The state of an owner drawn item
+
+        * SELECTED: The item is selected
+        * FOCUS: The item has the keyboard focus
+        * DISABLED: The item is drawn in a disabled control
+        * COMBOBOX_EDIT: The item is drawn in the selection field of a combobox and not in its dropdown list
+    */
+    pub struct OwnerDrawState: u32 {
+        const SELECTED = ODS_SELECTED;
+        const FOCUS = ODS_FOCUS;
+        const DISABLED = ODS_DISABLED;
+        const COMBOBOX_EDIT = ODS_COMBOBOXEDIT;
+    }
+}
+
+/**
+    A trait implemented by the items of an owner drawn `ListBox` or `ComboBox`.
+
+    See `ListBoxBuilder::owner_draw` and `ComboBoxBuilder::owner_draw`.
+
+```rust
+use native_windows_gui as nwg;
+use std::fmt;
+
+#[derive(Default)]
+struct Swatch { name: String, color: [u8; 3] }
+
+impl fmt::Display for Swatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.name) }
+}
+
+impl nwg::OwnerDrawItem for Swatch {
+    fn draw(&self, data: &nwg::OwnerDrawData) {
+        data.draw_background();
+        data.fill_rect([4, 4, 20, 20], self.color);
+        data.draw_text(&self.name, (24, 0));
+        data.draw_focus();
+    }
+}
+```
+*/
+pub trait OwnerDrawItem {
+    /// The height of the item in logical pixels. Only used by controls with a variable item height (ex: `ListBox`).
+    /// Returning `0` keeps the default item height of the control.
+    fn item_height(&self) -> u32 {
+        0
+    }
+
+    /// Draw the item. Called by the control each time the item must be redrawn.
+    fn draw(&self, data: &OwnerDrawData);
+}
+
+/**
+    Opaque type over a `WM_DRAWITEM` message. Passed to `OwnerDrawItem::draw`.
+    The rect coordinates are in physical pixels, relative to the control.
+*/
+pub struct OwnerDrawData {
+    pub(crate) inner: *const DRAWITEMSTRUCT,
+}
+
+impl OwnerDrawData {
+    /// The device context to draw on. The control font is already selected.
+    pub fn hdc(&self) -> HDC {
+        unsafe { (&*self.inner).hDC }
+    }
+
+    /// The bounds of the item to draw as `[left, top, right, bottom]`
+    pub fn rect(&self) -> [i32; 4] {
+        let rc = unsafe { &(&*self.inner).rcItem };
+        [rc.left, rc.top, rc.right, rc.bottom]
+    }
+
+    /// The index of the item in the control collection
+    pub fn index(&self) -> usize {
+        unsafe { (&*self.inner).itemID as usize }
+    }
+
+    /// The state of the item
+    pub fn state(&self) -> OwnerDrawState {
+        OwnerDrawState::from_bits_truncate(unsafe { (&*self.inner).itemState })
+    }
+
+    /// Returns `true` if the item is selected
+    pub fn selected(&self) -> bool {
+        self.state().contains(OwnerDrawState::SELECTED)
+    }
+
+    /// Fill the item with the default background color (the highlight color if the item is selected)
+    /// and set the text color that matches it.
+    pub fn draw_background(&self) {
+        use winapi::um::wingdi::{SetBkMode, SetTextColor, TRANSPARENT};
+        use winapi::um::winuser::{
+            FillRect, GetSysColor, GetSysColorBrush, COLOR_GRAYTEXT, COLOR_HIGHLIGHT,
+            COLOR_HIGHLIGHTTEXT, COLOR_WINDOW, COLOR_WINDOWTEXT,
+        };
+
+        let state = self.state();
+        let (bg, fg) = if state.contains(OwnerDrawState::SELECTED) {
+            (COLOR_HIGHLIGHT, COLOR_HIGHLIGHTTEXT)
+        } else if state.contains(OwnerDrawState::DISABLED) {
+            (COLOR_WINDOW, COLOR_GRAYTEXT)
+        } else {
+            (COLOR_WINDOW, COLOR_WINDOWTEXT)
+        };
+
+        unsafe {
+            let data = &*self.inner;
+            FillRect(data.hDC, &data.rcItem, GetSysColorBrush(bg));
+            SetTextColor(data.hDC, GetSysColor(fg));
+            SetBkMode(data.hDC, TRANSPARENT as _);
+        }
+    }
+
+    /// Fill a rectangle with a solid color. `rect` is `[left, top, right, bottom]` relative to the item bounds.
+    pub fn fill_rect(&self, rect: [i32; 4], color: [u8; 3]) {
+        use winapi::um::wingdi::{CreateSolidBrush, DeleteObject, RGB};
+        use winapi::um::winuser::FillRect;
+
+        let [left, top, _, _] = self.rect();
+        let rc = RECT {
+            left: left + rect[0],
+            top: top + rect[1],
+            right: left + rect[2],
+            bottom: top + rect[3],
+        };
+
+        unsafe {
+            let brush = CreateSolidBrush(RGB(color[0], color[1], color[2]));
+            FillRect(self.hdc(), &rc, brush);
+            DeleteObject(brush as _);
+        }
+    }
+
+    /// Draw a single line of text, vertically centered in the item. `offset` is relative to the item bounds.
+    pub fn draw_text(&self, text: &str, offset: (i32, i32)) {
+        use winapi::um::winuser::{
+            DrawTextW, DT_END_ELLIPSIS, DT_NOPREFIX, DT_SINGLELINE, DT_VCENTER,
+        };
+
+        let [left, top, right, bottom] = self.rect();
+        let mut rc = RECT {
+            left: left + offset.0,
+            top: top + offset.1,
+            right,
+            bottom,
+        };
+
+        let text = to_utf16(text);
+        unsafe {
+            DrawTextW(
+                self.hdc(),
+                text.as_ptr(),
+                -1,
+                &mut rc,
+                DT_SINGLELINE | DT_VCENTER | DT_NOPREFIX | DT_END_ELLIPSIS,
+            );
+        }
+    }
+
+    /// Draw the focus rectangle around the item if the item has the keyboard focus
+    pub fn draw_focus(&self) {
+        use winapi::um::winuser::DrawFocusRect;
+
+        let state = self.state();
+        if state.contains(OwnerDrawState::FOCUS) && !state.contains(OwnerDrawState::COMBOBOX_EDIT) {
+            unsafe {
+                let data = &*self.inner;
+                DrawFocusRect(data.hDC, &data.rcItem);
+            }
+        }
+    }
+}
+
+impl ::std::fmt::Debug for OwnerDrawData {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        write!(
+            f,
+            "OwnerDrawData {{ index: {:?}, rect: {:?}, state: {:?} }}",
+            self.index(),
+            self.rect(),
+            self.state()
+        )
+    }
+}
+
+/// Dispatch a `WM_DRAWITEM` message to the owner drawn item in `collection`.
+/// Returns `false` if the message is not for the control `handle`.
+pub(crate) unsafe fn draw_item<D: OwnerDrawItem>(
+    handle: winapi::shared::windef::HWND,
+    collection: &std::cell::RefCell<Vec<D>>,
+    draw: *const DRAWITEMSTRUCT,
+) -> bool {
+    use winapi::um::winuser::DrawFocusRect;
+
+    let data = &*draw;
+    if data.hwndItem != handle {
+        return false;
+    }
+
+    // itemID is -1 when the control is empty. Only the focus rect must be drawn.
+    if data.itemID == 0xFFFF_FFFF {
+        if data.itemState & ODS_FOCUS == ODS_FOCUS {
+            DrawFocusRect(data.hDC, &data.rcItem);
+        }
+        return true;
+    }
+
+    let col = match collection.try_borrow() {
+        Ok(col) => col,
+        Err(_) => return true,
+    };
+
+    if let Some(item) = col.get(data.itemID as usize) {
+        item.draw(&OwnerDrawData { inner: draw });
+    }
+
+    true
+}
@@ -1,13 +1,17 @@
+use super::plotters_view::{handle_mouse, PlottersInteraction, PlottersPoint, PlottersView};
 use super::{ControlBase, ControlHandle};
 use crate::win32::base_helper::check_hwnd;
 use crate::win32::window_helper as wh;
-use crate::NwgError;
+use crate::{unbind_raw_event_handler, NwgError, RawEventHandler};
 use winapi::um::winuser::{WS_CHILD, WS_CLIPCHILDREN, WS_CLIPSIBLINGS, WS_VISIBLE};
 
+use crate::win32::d2d::{SnapshotResult, SoftwareBitmap};
 pub use crate::win32::plotters_d2d::{PlottersBackend, PlottersError};
 use plotters::coord::Shift;
 use plotters::prelude::DrawingArea;
-use std::ops::Deref;
+use std::cell::RefCell;
+use std::ops::{Deref, Range};
+use std::rc::Rc;
 
 const NOT_BOUND: &'static str = "Plotters control is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: Plotters control handle is not HWND!";
@@ -19,24 +23,35 @@ const BAD_HANDLE: &'static str = "INTERNAL ERROR: Plotters control handle is not
     but it is impossible to do that within the DrawingBackend trait.
 */
 pub struct PlottersDrawingArea<'a> {
-    inner: &'a Plotters,
+    backend: &'a PlottersBackend,
     area: DrawingArea<&'a PlottersBackend, Shift>,
+    interaction: Option<&'a RefCell<PlottersInteraction>>,
 }
 
 impl<'a> PlottersDrawingArea<'a> {
     pub fn new(inner: &'a Plotters) -> Result<PlottersDrawingArea<'a>, PlottersError> {
         let backend = inner.d2d_backend.as_ref().unwrap();
-
         backend.rebuild(inner.handle.hwnd().unwrap())?;
+
+        let mut area = PlottersDrawingArea::begin(backend);
+
+        // The plot area covers the whole control until the draw code calls `Plotters::set_plot_area`
+        let (width, height) = area.dim_in_pixel();
+        inner.interaction.borrow_mut().view.plot_area = [0, 0, width as i32, height as i32];
+        area.interaction = Some(&inner.interaction);
+
+        Ok(area)
+    }
+
+    fn begin(backend: &'a PlottersBackend) -> PlottersDrawingArea<'a> {
         backend.begin_draw();
         backend.clear();
 
-        let area = PlottersDrawingArea {
-            inner: inner,
+        PlottersDrawingArea {
+            backend,
             area: backend.into(),
-        };
-
-        Ok(area)
+            interaction: None,
+        }
     }
 }
 
@@ -50,18 +65,80 @@ impl<'a> Deref for PlottersDrawingArea<'a> {
 
 impl<'a> Drop for PlottersDrawingArea<'a> {
     fn drop(&mut self) {
-        self.inner.d2d_backend.as_ref().unwrap().end_draw();
+        if let Some(interaction) = self.interaction {
+            let state = interaction.borrow();
+            if let Some(pos) = state
+                .hover
+                .filter(|&p| state.crosshair && state.view.contains(p))
+            {
+                let marker = state
+                    .hover_point
+                    .as_ref()
+                    .map(|p| state.view.to_pixel((p.x, p.y)));
+                self.backend
+                    .draw_crosshair(pos, state.view.plot_area, marker);
+            }
+        }
+
+        self.backend.end_draw();
     }
 }
 
 /**
     A canvas-like control that act as a backend for the [plotters](https://docs.rs/plotters/0.3.0/plotters/) library.
     The plotters control use direct2D to render to the canvas.
+
+    An interactive control zooms with the mouse wheel and pans when the mouse is dragged with the left button.
+    The visible data ranges are stored in a `PlottersView` that the draw code reads with `view` to build its chart.
+    The draw code should also report where the data is drawn with `set_plot_area`, so that the mouse position can be
+    mapped to data coordinates. The chart is not redrawn automatically: redraw it on `OnPlottersViewChanged`, and
+    on `OnPlottersHover` if the crosshair is enabled.
+
+    Series registered with `set_series` are used to find the point under the mouse (`hover_point`). If the control
+    was built with a tooltip, the value of that point is shown next to it.
+
+    With the `plotting-export` feature, the chart can be exported to PNG or SVG with the bitmap and SVG backends of plotters.
+
+    Requires the `plotting` feature.
+
+    **Builder parameters:**
+      * `parent`:      **Required.** The plotters parent container.
+      * `size`:        The plotters size.
+      * `position`:    The plotters position.
+      * `ex_flags`:    A combination of win32 window extended flags.
+      * `interactive`: Enable zoom, pan and hover with the mouse.
+      * `crosshair`:   Draw a crosshair under the mouse and a marker on the hovered point.
+      * `tooltip`:     Show the hovered point value in a tooltip. Requires the `tooltip` feature.
+      * `x_range`:     The initial data range on the x axis. `reset_view` restores it.
+      * `y_range`:     The initial data range on the y axis. `reset_view` restores it.
+
+    **Control events:**
+      * `OnPlottersViewChanged`: When the user zoomed or panned the view
+      * `OnPlottersHover`: When the mouse moved over the control or left it
+      * `MousePress(_)`: Generic mouse press events on the control
+      * `OnMouseMove`: Generic mouse mouse event
+      * `OnMouseWheel`: Generic mouse wheel event
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn build_plotters(graph: &mut nwg::Plotters, window: &nwg::Window) {
+        nwg::Plotters::builder()
+            .interactive(true)
+            .crosshair(true)
+            .x_range(0.0..10.0)
+            .y_range(-1.0..1.0)
+            .parent(window)
+            .build(graph);
+    }
+    ```
 */
 #[derive(Default)]
 pub struct Plotters {
     pub handle: ControlHandle,
     d2d_backend: Option<PlottersBackend>,
+    interaction: Rc<RefCell<PlottersInteraction>>,
+    handler0: RefCell<Option<RawEventHandler>>,
 }
 
 impl Plotters {
@@ -70,6 +147,12 @@ impl Plotters {
             size: (500, 500),
             position: (0, 0),
             ex_flags: 0,
+            interactive: false,
+            crosshair: false,
+            #[cfg(feature = "tooltip")]
+            tooltip: false,
+            x_range: 0.0..1.0,
+            y_range: 0.0..1.0,
             parent: None,
         }
     }
@@ -81,6 +164,157 @@ impl Plotters {
         PlottersDrawingArea::new(self)
     }
 
+    /// Return the visible data ranges and the plot area
+    pub fn view(&self) -> PlottersView {
+        self.interaction.borrow().view
+    }
+
+    /// Set the visible data ranges. Does not redraw the chart and does not send `OnPlottersViewChanged`.
+    pub fn set_view(&self, x: Range<f64>, y: Range<f64>) {
+        let mut state = self.interaction.borrow_mut();
+        state.view.x_min = x.start;
+        state.view.x_max = x.end;
+        state.view.y_min = y.start;
+        state.view.y_max = y.end;
+        state.update_hover_point();
+    }
+
+    /// Set the data ranges restored by `reset_view` and make them the visible ranges
+    pub fn set_home_view(&self, x: Range<f64>, y: Range<f64>) {
+        self.set_view(x, y);
+
+        let mut state = self.interaction.borrow_mut();
+        state.home = state.view;
+    }
+
+    /// Restore the data ranges set by the `x_range`/`y_range` builder parameters or by `set_home_view`
+    pub fn reset_view(&self) {
+        let home = self.interaction.borrow().home;
+        self.set_view(home.x_range(), home.y_range());
+    }
+
+    /**
+        Set the pixels covered by the data. Call this from the draw code with the pixel range of the chart plotting area:
+        `graph.set_plot_area(chart.plotting_area().get_pixel_range())`.
+
+        The plot area is reset to the whole control each time `draw` is called.
+    */
+    pub fn set_plot_area(&self, area: (Range<i32>, Range<i32>)) {
+        let (x, y) = area;
+        let mut state = self.interaction.borrow_mut();
+        state.view.plot_area = [x.start, y.start, x.end, y.end];
+        state.update_hover_point();
+    }
+
+    /// Return true if zoom, pan and hover are enabled
+    pub fn interactive(&self) -> bool {
+        self.interaction.borrow().interactive
+    }
+
+    /// Enable or disable zoom, pan and hover
+    pub fn set_interactive(&self, v: bool) {
+        self.interaction.borrow_mut().interactive = v;
+    }
+
+    /// Return true if a crosshair is drawn under the mouse
+    pub fn crosshair(&self) -> bool {
+        self.interaction.borrow().crosshair
+    }
+
+    /// Draw a crosshair under the mouse and a marker on the hovered point. Applies on the next draw.
+    pub fn set_crosshair(&self, v: bool) {
+        self.interaction.borrow_mut().crosshair = v;
+    }
+
+    /// Return the data coordinates under the mouse, or `None` if the mouse is not over the plot area
+    pub fn hover(&self) -> Option<(f64, f64)> {
+        let state = self.interaction.borrow();
+        state
+            .hover
+            .filter(|&p| state.view.contains(p))
+            .map(|p| state.view.to_data(p))
+    }
+
+    /// Return the series point closest to the mouse. See `set_series`.
+    pub fn hover_point(&self) -> Option<PlottersPoint> {
+        self.interaction.borrow().hover_point.clone()
+    }
+
+    /// Register the points of a series so that `hover_point` and the tooltip can find them.
+    /// Replaces the points of a series with the same name.
+    pub fn set_series(&self, name: &str, points: Vec<(f64, f64)>) {
+        let mut state = self.interaction.borrow_mut();
+        match state.series.iter_mut().find(|(n, _)| n == name) {
+            Some(series) => series.1 = points,
+            None => state.series.push((name.to_owned(), points)),
+        }
+
+        state.update_hover_point();
+    }
+
+    /// Remove all the series registered with `set_series`
+    pub fn clear_series(&self) {
+        let mut state = self.interaction.borrow_mut();
+        state.series.clear();
+        state.hover_point = None;
+    }
+
+    /**
+        Export the chart to a PNG file with the plotters bitmap backend. `draw` receives a drawing area of `size` pixels,
+        already filled with white, and the current view. Only the data ranges of the view apply to the exported image.
+
+        Requires the `plotting-export` feature.
+    */
+    #[cfg(feature = "plotting-export")]
+    pub fn export_png<'a, F>(
+        &self,
+        path: &'a str,
+        size: (u32, u32),
+        draw: F,
+    ) -> Result<(), PlottersError>
+    where
+        F: FnOnce(
+            &DrawingArea<plotters::prelude::BitMapBackend, Shift>,
+            &PlottersView,
+        ) -> Result<(), Box<dyn std::error::Error>>,
+    {
+        use plotters::prelude::{BitMapBackend, IntoDrawingArea, WHITE};
+
+        let view = self.view();
+        let root = BitMapBackend::new(path, size).into_drawing_area();
+        root.fill(&WHITE).map_err(export_error)?;
+        draw(&root, &view).map_err(export_error)?;
+        root.present().map_err(export_error)
+    }
+
+    /**
+        Export the chart to a SVG file with the plotters SVG backend. `draw` receives a drawing area of `size` pixels,
+        already filled with white, and the current view. Only the data ranges of the view apply to the exported image.
+
+        Requires the `plotting-export` feature.
+    */
+    #[cfg(feature = "plotting-export")]
+    pub fn export_svg<'a, F>(
+        &self,
+        path: &'a str,
+        size: (u32, u32),
+        draw: F,
+    ) -> Result<(), PlottersError>
+    where
+        F: FnOnce(
+            &DrawingArea<plotters::prelude::SVGBackend, Shift>,
+            &PlottersView,
+        ) -> Result<(), Box<dyn std::error::Error>>,
+    {
+        use plotters::prelude::{IntoDrawingArea, SVGBackend, WHITE};
+
+        let view = self.view();
+        let root = SVGBackend::new(path, size).into_drawing_area();
+        root.fill(&WHITE).map_err(export_error)?;
+        draw(&root, &view).map_err(export_error)?;
+        root.present().map_err(export_error)
+    }
+
     /// Return true if the control currently has the keyboard focus
     pub fn focus(&self) -> bool {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
@@ -164,19 +398,57 @@ impl Plotters {
     pub fn forced_flags(&self) -> u32 {
         WS_CLIPCHILDREN | WS_CLIPSIBLINGS
     }
+
+    /// Bind the handler that zooms, pans and tracks the mouse. It does nothing while the control is not interactive.
+    fn hook_mouse(&self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::basetsd::UINT_PTR;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let interaction = self.interaction.clone();
+        let handler = bind_raw_event_handler_inner(
+            &self.handle,
+            handle as UINT_PTR,
+            move |hwnd, msg, w, l| handle_mouse(&interaction, hwnd, msg, w, l),
+        );
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
 }
 
+control_cursor!(Plotters, NOT_BOUND, BAD_HANDLE);
+
 impl PartialEq for Plotters {
     fn eq(&self, other: &Self) -> bool {
         self.handle == other.handle
     }
 }
 
+impl Drop for Plotters {
+    fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+    }
+}
+
+#[cfg(feature = "plotting-export")]
+fn export_error<E: std::fmt::Display>(e: E) -> PlottersError {
+    PlottersError::Image(e.to_string())
+}
+
 pub struct PlottersBuilder {
     parent: Option<ControlHandle>,
     size: (i32, i32),
     position: (i32, i32),
     ex_flags: u32,
+    interactive: bool,
+    crosshair: bool,
+    #[cfg(feature = "tooltip")]
+    tooltip: bool,
+    x_range: Range<f64>,
+    y_range: Range<f64>,
 }
 
 impl PlottersBuilder {
@@ -185,6 +457,32 @@ impl PlottersBuilder {
         self
     }
 
+    pub fn interactive(mut self, interactive: bool) -> PlottersBuilder {
+        self.interactive = interactive;
+        self
+    }
+
+    pub fn crosshair(mut self, crosshair: bool) -> PlottersBuilder {
+        self.crosshair = crosshair;
+        self
+    }
+
+    #[cfg(feature = "tooltip")]
+    pub fn tooltip(mut self, tooltip: bool) -> PlottersBuilder {
+        self.tooltip = tooltip;
+        self
+    }
+
+    pub fn x_range(mut self, range: Range<f64>) -> PlottersBuilder {
+        self.x_range = range;
+        self
+    }
+
+    pub fn y_range(mut self, range: Range<f64>) -> PlottersBuilder {
+        self.y_range = range;
+        self
+    }
+
     pub fn size(mut self, size: (i32, i32)) -> PlottersBuilder {
         self.size = size;
         self
@@ -218,12 +516,116 @@ impl PlottersBuilder {
         match PlottersBackend::init(handle) {
             Ok(b) => {
                 out.d2d_backend = Some(b);
-                Ok(())
             }
             Err(e) => {
                 *out = Default::default();
-                Err(NwgError::from(e))
+                return Err(NwgError::from(e));
+            }
+        }
+
+        {
+            let mut state = out.interaction.borrow_mut();
+            state.interactive = self.interactive;
+            state.crosshair = self.crosshair;
+        }
+
+        out.set_home_view(self.x_range, self.y_range);
+
+        #[cfg(feature = "tooltip")]
+        {
+            if self.tooltip {
+                let mut tooltip = super::Tooltip::default();
+                super::Tooltip::builder().build(&mut tooltip)?;
+                tooltip.register_tracking(&out.handle);
+                out.interaction.borrow_mut().tooltip = Rc::new(tooltip);
             }
         }
+
+        out.hook_mouse();
+
+        Ok(())
+    }
+}
+
+/**
+    A plotters drawing backend that renders into a bitmap in memory instead of a window.
+
+    It uses the same Direct2D code as the `Plotters` control with the software rasterizer, so charts can be
+    rendered without a display and compared against reference images in tests. COM must be initialized
+    on the current thread (`nwg::init` does it).
+
+    Unlike the control, the size is in pixels and is not scaled by the dpi.
+
+    ```rust,no_run
+    use native_windows_gui as nwg;
+    use plotters::prelude::*;
+
+    fn chart_test() {
+        nwg::init().unwrap();
+
+        let plot = nwg::SoftwarePlotters::new(400, 300).unwrap();
+        {
+            let root = plot.draw().unwrap();
+            root.draw(&Circle::new((200, 150), 50, RED.filled())).unwrap();
+        }
+
+        let result = plot.compare_snapshot("test_rc/snapshots/circle.png", 2).unwrap();
+        assert!(result.is_match(), "{:?}", result);
+    }
+    ```
+*/
+pub struct SoftwarePlotters {
+    backend: PlottersBackend,
+}
+
+impl SoftwarePlotters {
+    /// Create a software backend of `width` by `height` pixels
+    pub fn new(width: u32, height: u32) -> Result<SoftwarePlotters, PlottersError> {
+        let backend = PlottersBackend::init_software(width, height)?;
+        Ok(SoftwarePlotters { backend })
+    }
+
+    /// Prepare the bitmap for drawing. The bitmap is cleared to white.
+    /// The commands are written to the bitmap when the returned object is dropped.
+    pub fn draw<'a>(&'a self) -> Result<PlottersDrawingArea<'a>, PlottersError> {
+        self.backend.rebuild_software()?;
+        Ok(PlottersDrawingArea::begin(&self.backend))
+    }
+
+    /// Return the size of the bitmap in pixels
+    pub fn size(&self) -> (u32, u32) {
+        self.bitmap().size()
+    }
+
+    /// Return the pixels of the bitmap as RGBA with straight alpha, row by row.
+    pub fn to_rgba(&self) -> Result<Vec<u8>, PlottersError> {
+        self.bitmap()
+            .to_rgba()
+            .map_err(|e| PlottersError::Image(e.into()))
+    }
+
+    /// Save the bitmap to a PNG file
+    pub fn save_png(&self, path: &str) -> Result<(), PlottersError> {
+        self.bitmap()
+            .save_png(path)
+            .map_err(|e| PlottersError::Image(e.into()))
+    }
+
+    /**
+        Compare the bitmap against the reference PNG at `path`. Two pixels are equal if none of their channels
+        differ by more than `tolerance`. See `nwg::d2d::compare_snapshot` for how the reference files are created.
+    */
+    pub fn compare_snapshot(
+        &self,
+        path: &str,
+        tolerance: u8,
+    ) -> Result<SnapshotResult, PlottersError> {
+        let pixels = self.to_rgba()?;
+        crate::win32::d2d::compare_snapshot(path, self.size(), &pixels, tolerance)
+            .map_err(|e| PlottersError::Image(e.into()))
+    }
+
+    fn bitmap(&self) -> &SoftwareBitmap {
+        self.backend.software_bitmap().unwrap()
     }
 }
@@ -0,0 +1,397 @@
+/*!
+    View transform and mouse interaction of the plotters control
+*/
+use crate::win32::{high_dpi, window_helper as wh};
+use std::cell::RefCell;
+use std::ops::Range;
+use winapi::shared::minwindef::{LPARAM, LRESULT, UINT, WPARAM};
+use winapi::shared::windef::HWND;
+
+#[cfg(feature = "tooltip")]
+use super::{ControlHandle, Tooltip};
+
+#[cfg(feature = "tooltip")]
+use std::rc::Rc;
+
+/// Maximum distance in logical pixels between the cursor and a series point for the point to be hovered
+const HOVER_DISTANCE: i32 = 24;
+
+/// Zoom factor applied for each notch of the mouse wheel
+const WHEEL_ZOOM: f64 = 1.2;
+
+/**
+    The part of the data that is visible in a `Plotters` control, and where it is drawn.
+
+    Zooming and panning the control change the data ranges. The draw code reads them to build the chart:
+
+    ```rust
+    use native_windows_gui as nwg;
+    use plotters::prelude::*;
+
+    fn draw(graph: &nwg::Plotters) -> Result<(), Box<dyn std::error::Error>> {
+        let view = graph.view();
+        let root = graph.draw()?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(20)
+            .x_label_area_size(30)
+            .y_label_area_size(30)
+            .build_cartesian_2d(view.x_range(), view.y_range())?;
+
+        chart.configure_mesh().draw()?;
+
+        // Tell the control where the data is drawn so the mouse position can be mapped to the data
+        graph.set_plot_area(chart.plotting_area().get_pixel_range());
+
+        Ok(())
+    }
+    ```
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PlottersView {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+
+    /// The pixels covered by the data, relative to the control, in logical pixels: `[left, top, right, bottom]`
+    pub plot_area: [i32; 4],
+}
+
+impl Default for PlottersView {
+    fn default() -> PlottersView {
+        PlottersView {
+            x_min: 0.0,
+            x_max: 1.0,
+            y_min: 0.0,
+            y_max: 1.0,
+            plot_area: [0, 0, 0, 0],
+        }
+    }
+}
+
+impl PlottersView {
+    /// Return the visible data range on the x axis
+    pub fn x_range(&self) -> Range<f64> {
+        self.x_min..self.x_max
+    }
+
+    /// Return the visible data range on the y axis
+    pub fn y_range(&self) -> Range<f64> {
+        self.y_min..self.y_max
+    }
+
+    /// Return true if `pos` (in logical pixels) is inside the plot area
+    pub fn contains(&self, pos: (i32, i32)) -> bool {
+        let [left, top, right, bottom] = self.plot_area;
+        pos.0 >= left && pos.0 < right && pos.1 >= top && pos.1 < bottom
+    }
+
+    /// Map a position in logical pixels to data coordinates. The y axis points up.
+    pub fn to_data(&self, pos: (i32, i32)) -> (f64, f64) {
+        let (width, height) = self.area_size();
+        let [left, top, _, _] = self.plot_area;
+
+        let x = self.x_min + ((pos.0 - left) as f64 / width) * (self.x_max - self.x_min);
+        let y = self.y_max - ((pos.1 - top) as f64 / height) * (self.y_max - self.y_min);
+
+        (x, y)
+    }
+
+    /// Map data coordinates to a position in logical pixels
+    pub fn to_pixel(&self, value: (f64, f64)) -> (i32, i32) {
+        let (width, height) = self.area_size();
+        let [left, top, _, _] = self.plot_area;
+
+        let x = left as f64 + (value.0 - self.x_min) / (self.x_max - self.x_min) * width;
+        let y = top as f64 + (self.y_max - value.1) / (self.y_max - self.y_min) * height;
+
+        (x.round() as i32, y.round() as i32)
+    }
+
+    /// Return the view zoomed by `factor` around `center` (in logical pixels). The data under `center` does not move.
+    /// A factor greater than 1 zooms in.
+    pub fn zoom(&self, factor: f64, center: (i32, i32)) -> PlottersView {
+        let (cx, cy) = self.to_data(center);
+
+        PlottersView {
+            x_min: cx - (cx - self.x_min) / factor,
+            x_max: cx + (self.x_max - cx) / factor,
+            y_min: cy - (cy - self.y_min) / factor,
+            y_max: cy + (self.y_max - cy) / factor,
+            plot_area: self.plot_area,
+        }
+    }
+
+    /// Return the view moved so that the data follows a mouse drag of `delta` logical pixels
+    pub fn pan(&self, delta: (i32, i32)) -> PlottersView {
+        let (width, height) = self.area_size();
+        let dx = delta.0 as f64 / width * (self.x_max - self.x_min);
+        let dy = delta.1 as f64 / height * (self.y_max - self.y_min);
+
+        PlottersView {
+            x_min: self.x_min - dx,
+            x_max: self.x_max - dx,
+            y_min: self.y_min + dy,
+            y_max: self.y_max + dy,
+            plot_area: self.plot_area,
+        }
+    }
+
+    fn area_size(&self) -> (f64, f64) {
+        let [left, top, right, bottom] = self.plot_area;
+        (
+            ((right - left).max(1)) as f64,
+            ((bottom - top).max(1)) as f64,
+        )
+    }
+}
+
+/// The series point closest to the mouse. See `Plotters::hover_point`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlottersPoint {
+    /// The name of the series, as given to `Plotters::set_series`
+    pub series: String,
+
+    /// Index of the point in the series
+    pub index: usize,
+
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Interaction state shared between the plotters control and its raw event handler
+#[derive(Default)]
+pub(crate) struct PlottersInteraction {
+    pub interactive: bool,
+    pub crosshair: bool,
+
+    pub view: PlottersView,
+    pub home: PlottersView,
+
+    /// Last mouse position while the view is dragged
+    pub drag: Option<(i32, i32)>,
+
+    /// Mouse position in logical pixels while the mouse is over the control
+    pub hover: Option<(i32, i32)>,
+    pub hover_point: Option<PlottersPoint>,
+
+    pub series: Vec<(String, Vec<(f64, f64)>)>,
+
+    /// Shared so the tooltip can be updated without borrowing the state
+    #[cfg(feature = "tooltip")]
+    pub tooltip: Rc<Tooltip>,
+}
+
+impl PlottersInteraction {
+    /// Find the series point closest to the hovered position
+    pub fn update_hover_point(&mut self) {
+        let view = self.view;
+        let mut nearest: Option<(i32, PlottersPoint)> = None;
+
+        if let Some(pos) = self.hover.filter(|&p| view.contains(p)) {
+            for (name, points) in self.series.iter() {
+                for (index, &(x, y)) in points.iter().enumerate() {
+                    let (px, py) = view.to_pixel((x, y));
+                    if !view.contains((px, py)) {
+                        continue;
+                    }
+
+                    let distance = (px - pos.0).pow(2) + (py - pos.1).pow(2);
+                    if distance > HOVER_DISTANCE.pow(2) {
+                        continue;
+                    }
+
+                    if nearest.as_ref().map(|n| distance < n.0).unwrap_or(true) {
+                        let series = name.clone();
+                        nearest = Some((
+                            distance,
+                            PlottersPoint {
+                                series,
+                                index,
+                                x,
+                                y,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+
+        self.hover_point = nearest.map(|n| n.1);
+    }
+}
+
+/**
+    Show the value of the hovered point in the tooltip, or hide it.
+
+    The tooltip sends messages to the control, so the interaction state must not be borrowed when this is called.
+*/
+#[cfg(feature = "tooltip")]
+fn update_tooltip(interaction: &RefCell<PlottersInteraction>, hwnd: HWND) {
+    use winapi::shared::windef::POINT;
+    use winapi::um::winuser::ClientToScreen;
+
+    let (tooltip, tip) = {
+        let state = interaction.borrow();
+        let tip = state.hover_point.as_ref().map(|p| {
+            let (x, y) = state.view.to_pixel((p.x, p.y));
+            let text = format!("{}: ({:.3}, {:.3})", p.series, p.x, p.y);
+            (text, (x, y))
+        });
+
+        (state.tooltip.clone(), tip)
+    };
+
+    if tooltip.handle.blank() {
+        return;
+    }
+
+    let owner = ControlHandle::Hwnd(hwnd);
+    match tip {
+        Some((text, (x, y))) => {
+            let (x, y) = unsafe { high_dpi::logical_to_physical(x, y) };
+            let mut pos = POINT {
+                x: x + 12,
+                y: y + 12,
+            };
+            unsafe { ClientToScreen(hwnd, &mut pos) };
+
+            tooltip.track(&owner, &text, (pos.x, pos.y));
+        }
+        None => tooltip.hide_tracking(&owner),
+    }
+}
+
+#[cfg(not(feature = "tooltip"))]
+fn update_tooltip(_interaction: &RefCell<PlottersInteraction>, _hwnd: HWND) {}
+
+/// Raw event handler of an interactive plotters control. Implements zoom, pan and hover.
+pub(crate) fn handle_mouse(
+    interaction: &RefCell<PlottersInteraction>,
+    hwnd: HWND,
+    msg: UINT,
+    w: WPARAM,
+    l: LPARAM,
+) -> Option<LRESULT> {
+    use winapi::shared::minwindef::{HIWORD, LOWORD};
+    use winapi::shared::windef::POINT;
+    use winapi::um::winuser::{
+        ReleaseCapture, ScreenToClient, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
+        WM_CAPTURECHANGED, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MOUSELEAVE, WM_MOUSEMOVE,
+        WM_MOUSEWHEEL,
+    };
+
+    let position = |x: i32, y: i32| unsafe { high_dpi::physical_to_logical(x, y) };
+    let client_position = || {
+        let x = LOWORD(l as u32) as i16 as i32;
+        let y = HIWORD(l as u32) as i16 as i32;
+        position(x, y)
+    };
+
+    match msg {
+        WM_MOUSEWHEEL | WM_LBUTTONDOWN | WM_LBUTTONUP | WM_MOUSEMOVE | WM_MOUSELEAVE
+        | WM_CAPTURECHANGED => {}
+        _ => return None,
+    }
+
+    // SetFocus, SetCapture, ReleaseCapture and the tooltip send messages to the control.
+    // The state is released before calling them, and a message sent while it is borrowed is ignored.
+    let mut state = match interaction.try_borrow_mut() {
+        Ok(state) => state,
+        Err(_) => return None,
+    };
+
+    if !state.interactive {
+        return None;
+    }
+
+    match msg {
+        WM_MOUSEWHEEL => {
+            let mut pos = POINT {
+                x: LOWORD(l as u32) as i16 as i32,
+                y: HIWORD(l as u32) as i16 as i32,
+            };
+            unsafe { ScreenToClient(hwnd, &mut pos) };
+
+            let pos = position(pos.x, pos.y);
+            if !state.view.contains(pos) {
+                return None;
+            }
+
+            let delta = HIWORD(w as u32) as i16 as f64 / 120.0;
+            state.view = state.view.zoom(WHEEL_ZOOM.powf(delta), pos);
+            state.update_hover_point();
+            drop(state);
+
+            update_tooltip(interaction, hwnd);
+            wh::post_message(hwnd, wh::NWG_PLOTTERS_VIEW_CHANGED, 0, 0);
+            return Some(0);
+        }
+        WM_LBUTTONDOWN => {
+            let pos = client_position();
+            if state.view.contains(pos) {
+                state.drag = Some(pos);
+                drop(state);
+
+                unsafe {
+                    wh::set_focus(hwnd);
+                    SetCapture(hwnd);
+                }
+            }
+        }
+        WM_LBUTTONUP => {
+            if state.drag.take().is_some() {
+                drop(state);
+                unsafe { ReleaseCapture() };
+            }
+        }
+        WM_CAPTURECHANGED => {
+            // Another window took the mouse capture: the drag cannot be finished
+            if l as HWND != hwnd {
+                state.drag = None;
+            }
+        }
+        WM_MOUSEMOVE => {
+            let pos = client_position();
+
+            if state.hover.is_none() {
+                let mut track = TRACKMOUSEEVENT {
+                    cbSize: std::mem::size_of::<TRACKMOUSEEVENT>() as u32,
+                    dwFlags: TME_LEAVE,
+                    hwndTrack: hwnd,
+                    dwHoverTime: 0,
+                };
+                unsafe { TrackMouseEvent(&mut track) };
+            }
+
+            if let Some(last) = state.drag {
+                if last != pos {
+                    state.view = state.view.pan((pos.0 - last.0, pos.1 - last.1));
+                    state.drag = Some(pos);
+                    wh::post_message(hwnd, wh::NWG_PLOTTERS_VIEW_CHANGED, 0, 0);
+                }
+            }
+
+            if state.hover != Some(pos) {
+                state.hover = Some(pos);
+                state.update_hover_point();
+                drop(state);
+
+                update_tooltip(interaction, hwnd);
+                wh::post_message(hwnd, wh::NWG_PLOTTERS_HOVER, 0, 0);
+            }
+        }
+        WM_MOUSELEAVE => {
+            state.hover = None;
+            state.hover_point = None;
+            drop(state);
+
+            update_tooltip(interaction, hwnd);
+            wh::post_message(hwnd, wh::NWG_PLOTTERS_HOVER, 0, 0);
+        }
+        _ => {}
+    }
+
+    None
+}
@@ -0,0 +1,156 @@
+use super::{Button, CheckBox, Label, RadioButton, TextInput};
+use crate::win32::base_helper::check_hwnd;
+use crate::win32::{high_dpi, resources_helper as rh, window_helper as wh};
+use winapi::shared::minwindef::LPARAM;
+use winapi::shared::windef::HWND;
+
+const NOT_BOUND: &'static str = "Control is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: Control handle is not HWND!";
+
+/**
+    A control that knows how big it needs to be to display its content.
+    The size is computed from the text, the font and the theme metrics of the control, so it follows
+    translations and DPI changes. Like every size in NWG, the preferred size is in logical pixels.
+
+    Layouts use the preferred size of their children for `GridTrack::auto` tracks, aligned grid cells and
+    the `Auto` flex basis of flexbox items.
+
+    ```rust
+    use native_windows_gui as nwg;
+    use nwg::PreferredSize;
+
+    fn fit_button(button: &nwg::Button) {
+        let (w, h) = button.preferred_size();
+        button.set_size(w, h);
+    }
+    ```
+*/
+pub trait PreferredSize {
+    /// Returns the size the control needs to display its content
+    fn preferred_size(&self) -> (u32, u32);
+}
+
+macro_rules! preferred_size {
+    ($control:ty, $measure:ident) => {
+        impl PreferredSize for $control {
+            fn preferred_size(&self) -> (u32, u32) {
+                let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+                unsafe { $measure(handle) }
+            }
+        }
+    };
+}
+
+preferred_size!(Label, label_size);
+preferred_size!(Button, button_size);
+preferred_size!(CheckBox, button_size);
+preferred_size!(RadioButton, button_size);
+preferred_size!(TextInput, text_input_size);
+
+/// Returns the preferred size of a control from its window class.
+/// Returns `None` if the control does not support it. Images (`ImageFrame`) and empty labels cannot be measured.
+pub(crate) fn control_preferred_size(handle: HWND) -> Option<(u32, u32)> {
+    use winapi::um::winuser::{
+        BS_GROUPBOX, BS_TYPEMASK, ES_MULTILINE, SS_BITMAP, SS_ICON, SS_TYPEMASK,
+    };
+
+    let class_name = unsafe { wh::get_window_class_name(handle) };
+    let style = wh::get_style(handle);
+
+    unsafe {
+        match class_name.to_ascii_lowercase().as_str() {
+            "static" if style & SS_TYPEMASK == SS_BITMAP || style & SS_TYPEMASK == SS_ICON => None,
+            "static" if wh::get_window_text(handle).is_empty() => None,
+            "static" => Some(label_size(handle)),
+            "button" if style & BS_TYPEMASK != BS_GROUPBOX => Some(button_size(handle)),
+            "edit" if style & ES_MULTILINE == 0 => Some(text_input_size(handle)),
+            _ => None,
+        }
+    }
+}
+
+/// The size of the text of a label. Every line of the text is measured.
+unsafe fn label_size(handle: HWND) -> (u32, u32) {
+    let text = wh::get_window_text(handle);
+    let (w, h) = text_extent(handle, &text);
+    logical_size(w, h)
+}
+
+/// Buttons, check boxes and radio buttons report their ideal size with `BCM_GETIDEALSIZE`
+unsafe fn button_size(handle: HWND) -> (u32, u32) {
+    use winapi::shared::windef::SIZE;
+    use winapi::um::commctrl::BCM_GETIDEALSIZE;
+    use winapi::um::winuser::{
+        GetSystemMetrics, BS_3STATE, BS_AUTO3STATE, BS_AUTOCHECKBOX, BS_AUTORADIOBUTTON,
+        BS_CHECKBOX, BS_RADIOBUTTON, BS_TYPEMASK, SM_CXEDGE, SM_CXMENUCHECK, SM_CYEDGE,
+    };
+
+    let mut size = SIZE { cx: 0, cy: 0 };
+    let ok = wh::send_message(
+        handle,
+        BCM_GETIDEALSIZE,
+        0,
+        &mut size as *mut SIZE as LPARAM,
+    );
+    if ok != 0 && size.cx > 0 && size.cy > 0 {
+        return logical_size(size.cx, size.cy);
+    }
+
+    // Without visual styles, the ideal size is computed from the text
+    let text = wh::get_window_text(handle);
+    let (w, h) = text_extent(handle, &text);
+
+    let check = match wh::get_style(handle) & BS_TYPEMASK {
+        BS_CHECKBOX | BS_AUTOCHECKBOX | BS_3STATE | BS_AUTO3STATE | BS_RADIOBUTTON
+        | BS_AUTORADIOBUTTON => true,
+        _ => false,
+    };
+
+    if check {
+        let box_size = GetSystemMetrics(SM_CXMENUCHECK);
+        let spacing = GetSystemMetrics(SM_CXEDGE) * 2;
+        logical_size(box_size + spacing + w, h.max(box_size))
+    } else {
+        let edge_x = GetSystemMetrics(SM_CXEDGE) * 2;
+        let edge_y = GetSystemMetrics(SM_CYEDGE) * 2;
+        logical_size(w + (edge_x * 4), h + (edge_y * 3))
+    }
+}
+
+/// The text of the input, or 10 digits if it is shorter, plus the margins and the borders of the edit control
+unsafe fn text_input_size(handle: HWND) -> (u32, u32) {
+    use winapi::shared::minwindef::{HIWORD, LOWORD};
+    use winapi::um::winuser::{GetSystemMetrics, EM_GETMARGINS, SM_CXEDGE, SM_CYEDGE};
+
+    let text = wh::get_window_text(handle);
+    let (text_w, text_h) = text_extent(handle, &text);
+    let (min_w, _) = text_extent(handle, "0000000000");
+
+    let margins = wh::send_message(handle, EM_GETMARGINS as u32, 0, 0) as u32;
+    let margins = LOWORD(margins) as i32 + HIWORD(margins) as i32;
+
+    let edge_x = GetSystemMetrics(SM_CXEDGE) * 2;
+    let edge_y = GetSystemMetrics(SM_CYEDGE) * 2;
+
+    logical_size(text_w.max(min_w) + margins + edge_x, text_h + edge_y + 2)
+}
+
+/// Measures `text` with the font of the control. Returns the size in physical pixels.
+unsafe fn text_extent(handle: HWND, text: &str) -> (i32, i32) {
+    use winapi::um::winuser::{GetDC, ReleaseDC};
+
+    let dc = GetDC(handle);
+    if dc.is_null() {
+        return (0, 0);
+    }
+
+    let extent = rh::text_extent(dc, wh::get_window_font(handle), text);
+    ReleaseDC(handle, dc);
+
+    extent
+}
+
+unsafe fn logical_size(w: i32, h: i32) -> (u32, u32) {
+    let (w, h) = high_dpi::physical_to_logical(w, h);
+    (w.max(0) as u32, h.max(0) as u32)
+}
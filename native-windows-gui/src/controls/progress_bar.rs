@@ -313,6 +313,8 @@ impl ProgressBar {
     }
 }
 
+control_cursor!(ProgressBar, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for ProgressBar {
     fn drop(&mut self) {
         self.handle.destroy();
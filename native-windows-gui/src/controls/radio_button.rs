@@ -311,6 +311,8 @@ impl RadioButton {
     }
 }
 
+control_cursor!(RadioButton, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for RadioButton {
     fn drop(&mut self) {
         let handler = self.handler0.borrow();
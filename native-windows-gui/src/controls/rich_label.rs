@@ -374,6 +374,8 @@ impl RichLabel {
     }
 }
 
+control_cursor!(RichLabel, NOT_BOUND, BAD_HANDLE);
+
 impl PartialEq for RichLabel {
     fn eq(&self, other: &Self) -> bool {
         self.handle == other.handle
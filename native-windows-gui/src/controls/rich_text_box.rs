@@ -561,6 +561,8 @@ impl RichTextBox {
     }
 }
 
+control_cursor!(RichTextBox, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for RichTextBox {
     fn drop(&mut self) {
         self.handle.destroy();
@@ -344,6 +344,8 @@ impl ScrollBar {
     }
 }
 
+control_cursor!(ScrollBar, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for ScrollBar {
     fn drop(&mut self) {
         use crate::unbind_raw_event_handler;
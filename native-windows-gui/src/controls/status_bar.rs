@@ -167,6 +167,8 @@ impl StatusBar {
     }
 }
 
+control_cursor!(StatusBar, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for StatusBar {
     fn drop(&mut self) {
         let handler = self.handler0.borrow();
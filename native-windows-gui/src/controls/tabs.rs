@@ -138,10 +138,7 @@ impl TabsContainer {
         let handle = wh::send_message(control_handle, TCM_GETIMAGELIST, 0, 0);
         match handle == 0 {
             true => None,
-            false => Some(ImageList {
-                handle: handle as _,
-                owned: false,
-            }),
+            false => Some(ImageList::borrowed(handle as _)),
         }
     }
 
@@ -359,6 +356,8 @@ impl TabsContainer {
     }
 }
 
+control_cursor!(TabsContainer, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for TabsContainer {
     fn drop(&mut self) {
         let handler = self.handler0.borrow();
@@ -690,6 +689,8 @@ impl Tab {
     }
 }
 
+control_cursor!(Tab, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for Tab {
     fn drop(&mut self) {
         self.handle.destroy();
@@ -435,6 +435,8 @@ impl TextBox {
     }
 }
 
+control_cursor!(TextBox, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for TextBox {
     fn drop(&mut self) {
         self.handle.destroy();
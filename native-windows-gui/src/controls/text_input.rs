@@ -40,6 +40,42 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /**
+        The sources and the behaviour of the shell autocomplete of a text input. See `TextInput::set_auto_complete`
+
+        * FILE_SYSTEM:      Complete with the files and the folders of the file system
+        * URL_HISTORY:      Complete with the URLs of the user history
+        * URL_MRU:          Complete with the recently used URLs
+        * URL_ALL:          Same as `URL_HISTORY | URL_MRU`
+        * USE_TAB:          TAB selects the suggestions instead of moving the focus to the next control
+        * FILE_SYS_ONLY:    Only complete with the file system. Cannot be combined with the URL sources.
+        * FILE_SYS_DIRS:    Only complete with the folders of the file system
+        * SUGGEST_FORCE_ON: Always display a dropdown with the suggestions, ignoring the user settings
+        * APPEND_FORCE_ON:  Always append the rest of the best suggestion to the text, ignoring the user settings
+    */
+    pub struct AutoCompleteFlags: u32 {
+        // SHACF_* values from shlwapi.h. They are not defined by winapi.
+        const FILE_SYSTEM = 0x1;
+        const URL_HISTORY = 0x2;
+        const URL_MRU = 0x4;
+        const URL_ALL = 0x2 | 0x4;
+        const USE_TAB = 0x8;
+        const FILE_SYS_ONLY = 0x10;
+        const FILE_SYS_DIRS = 0x20;
+        const SUGGEST_FORCE_ON = 0x10000000;
+        const APPEND_FORCE_ON = 0x40000000;
+    }
+}
+
+// This function is not declared by winapi. shlwapi is linked by the shellapi feature.
+extern "system" {
+    fn SHAutoComplete(
+        hwnd: winapi::shared::windef::HWND,
+        flags: u32,
+    ) -> winapi::shared::ntdef::HRESULT;
+}
+
 /**
 An edit control is a rectangular control window to permit the user to enter and edit text by typing on the keyboard
 This control only allow a single line input. For block of text, use `TextBox`.
@@ -61,6 +97,7 @@ TextInput is not behind any features.
   * `align`:            The alignment of the text in the text input
   * `background_color`: The color of the textinput top and bottom padding. This is not the white background under the text.
   * `focus`:            The control receive focus after being created
+  * `auto_complete`:    Enable the shell autocomplete with the selected sources. See `TextInput::set_auto_complete`
 
 **Control events:**
   * `OnTextInput`: When a TextInput value is changed
@@ -103,6 +140,7 @@ impl TextInput {
             font: None,
             parent: None,
             background_color: None,
+            auto_complete: None,
         }
     }
 
@@ -347,6 +385,20 @@ impl TextInput {
         }
     }
 
+    /// Enable the shell autocomplete on the text input. The suggestions are taken from the sources in `flags`.
+    /// COM must be initialized on the thread (`nwg::init` does it). Once enabled, the autocomplete cannot be removed.
+    pub fn set_auto_complete(&self, flags: AutoCompleteFlags) -> Result<(), NwgError> {
+        use winapi::shared::winerror::SUCCEEDED;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        match SUCCEEDED(unsafe { SHAutoComplete(handle, flags.bits()) }) {
+            true => Ok(()),
+            false => Err(NwgError::control_create(
+                "Failed to enable the autocomplete on a TextInput",
+            )),
+        }
+    }
+
     /// Set the placeholder text displayed in the TextInput
     /// when it is empty and does not have focus
     pub fn set_placeholder_text<'a>(&self, v: Option<&'a str>) {
@@ -502,6 +554,8 @@ impl TextInput {
     }
 }
 
+control_cursor!(TextInput, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for TextInput {
     fn drop(&mut self) {
         use crate::unbind_raw_event_handler;
@@ -536,6 +590,7 @@ pub struct TextInputBuilder<'a> {
     parent: Option<ControlHandle>,
     background_color: Option<[u8; 3]>,
     focus: bool,
+    auto_complete: Option<AutoCompleteFlags>,
 }
 
 impl<'a> TextInputBuilder<'a> {
@@ -604,6 +659,11 @@ impl<'a> TextInputBuilder<'a> {
         self
     }
 
+    pub fn auto_complete(mut self, flags: Option<AutoCompleteFlags>) -> TextInputBuilder<'a> {
+        self.auto_complete = flags;
+        self
+    }
+
     pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> TextInputBuilder<'a> {
         self.parent = Some(p.into());
         self
@@ -667,6 +727,10 @@ impl<'a> TextInputBuilder<'a> {
             out.set_placeholder_text(self.placeholder_text);
         }
 
+        if let Some(flags) = self.auto_complete {
+            out.set_auto_complete(flags)?;
+        }
+
         Ok(())
     }
 }
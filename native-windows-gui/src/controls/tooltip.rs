@@ -392,6 +392,52 @@ impl Tooltip {
         wh::send_message(handle, TTM_ADDTOOLW, 0, tool_ptr as LPARAM);
     }
 
+    /**
+        Register a tracking tooltip under a control. Unlike `register`, the tooltip does not pop up by itself.
+        It is shown at a position chosen by the application with `track` and hidden with `hide_tracking`.
+        `owner` must be a window control and must not also be registered with `register`.
+    */
+    pub fn register_tracking<W: Into<ControlHandle>>(&self, owner: W) {
+        use winapi::um::commctrl::TTM_ADDTOOLW;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let mut text = to_utf16("");
+        let tool = tracking_tool(&owner.into(), text.as_mut_ptr());
+
+        let tool_ptr = &tool as *const _;
+        wh::send_message(handle, TTM_ADDTOOLW, 0, tool_ptr as LPARAM);
+    }
+
+    /// Show the tracking tooltip of `owner` with `text` at `position`, in screen coordinates.
+    /// Use `register_tracking` to associate a control with this tooltip
+    pub fn track<'a, W: Into<ControlHandle>>(&self, owner: W, text: &'a str, position: (i32, i32)) {
+        use winapi::shared::minwindef::MAKELONG;
+        use winapi::um::commctrl::{TTM_TRACKACTIVATE, TTM_TRACKPOSITION, TTM_UPDATETIPTEXTW};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let mut text = to_utf16(text);
+        let tool = tracking_tool(&owner.into(), text.as_mut_ptr());
+        let tool_ptr = &tool as *const _ as LPARAM;
+
+        let (x, y) = position;
+        let position = MAKELONG(x as u16, y as u16) as LPARAM;
+
+        wh::send_message(handle, TTM_UPDATETIPTEXTW, 0, tool_ptr);
+        wh::send_message(handle, TTM_TRACKPOSITION, 0, position);
+        wh::send_message(handle, TTM_TRACKACTIVATE, 1, tool_ptr);
+    }
+
+    /// Hide the tracking tooltip of `owner`
+    pub fn hide_tracking<W: Into<ControlHandle>>(&self, owner: W) {
+        use winapi::um::commctrl::TTM_TRACKACTIVATE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let tool = tracking_tool(&owner.into(), ptr::null_mut());
+
+        let tool_ptr = &tool as *const _;
+        wh::send_message(handle, TTM_TRACKACTIVATE, 0, tool_ptr as LPARAM);
+    }
+
     /// Remove the tooltip from a control
     pub fn unregister<W: Into<ControlHandle>>(&self, owner: W) {
         use winapi::shared::{basetsd::UINT_PTR, windef::RECT};
@@ -452,6 +498,33 @@ impl Drop for Tooltip {
         self.handle.destroy();
     }
 }
+fn tracking_tool(owner: &ControlHandle, text: *mut WCHAR) -> winapi::um::commctrl::TTTOOLINFOW {
+    use winapi::shared::{basetsd::UINT_PTR, windef::RECT};
+    use winapi::um::commctrl::{TTF_ABSOLUTE, TTF_IDISHWND, TTF_TRACK, TTTOOLINFOW};
+
+    if owner.blank() {
+        panic!("{}", NOT_BOUND);
+    }
+    let owner_handle = owner.hwnd().expect(BAD_HANDLE);
+
+    TTTOOLINFOW {
+        cbSize: mem::size_of::<TTTOOLINFOW>() as UINT,
+        uFlags: TTF_IDISHWND | TTF_TRACK | TTF_ABSOLUTE,
+        hwnd: owner_handle,
+        uId: owner_handle as UINT_PTR,
+        rect: RECT {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        },
+        hinst: ptr::null_mut(),
+        lpszText: text,
+        lParam: 0,
+        lpReserved: ptr::null_mut(),
+    }
+}
+
 pub struct TooltipBuilder<'a> {
     title: Option<&'a str>,
     ico: Option<&'a Icon>,
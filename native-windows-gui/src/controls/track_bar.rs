@@ -312,6 +312,8 @@ impl TrackBar {
     }
 }
 
+control_cursor!(TrackBar, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for TrackBar {
     fn drop(&mut self) {
         use crate::unbind_raw_event_handler;
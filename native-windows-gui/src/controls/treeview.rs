@@ -5,15 +5,20 @@ A tree-view control is a window that displays a hierarchical list of items
 use super::{ControlBase, ControlHandle};
 use crate::win32::base_helper::{check_hwnd, from_utf16, to_utf16};
 use crate::win32::window_helper as wh;
-use crate::{Font, NwgError};
+use crate::{unbind_raw_event_handler, Font, NwgError, RawEventHandler};
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::rc::Rc;
 use std::{mem, ptr};
+use winapi::ctypes::c_int;
 use winapi::shared::minwindef::{LPARAM, WPARAM};
-use winapi::um::commctrl::{HTREEITEM, TVIS_EXPANDED, TVIS_SELECTED, TVITEMW, TVS_SHOWSELALWAYS};
+use winapi::shared::windef::HWND;
+use winapi::um::commctrl::{
+    HIMAGELIST, HTREEITEM, TVIS_EXPANDED, TVIS_SELECTED, TVIS_STATEIMAGEMASK, TVITEMW,
+    TVS_CHECKBOXES, TVS_SHOWSELALWAYS,
+};
 use winapi::um::winuser::{WS_DISABLED, WS_TABSTOP, WS_VISIBLE};
 
-#[cfg(feature = "image-list")]
-use winapi::um::commctrl::HIMAGELIST;
-
 #[cfg(feature = "image-list")]
 use crate::ImageList;
 
@@ -27,12 +32,15 @@ bitflags! {
         * VISIBLE:  The tree view is immediatly visible after creation
         * DISABLED: The tree view cannot be interacted with by the user. It also has a grayed out look.
         * TAB_STOP: The tree view can be selected using tab navigation
+        * ALWAYS_SHOW_SELECTION: The selected item stays highlighted when the tree view loses the focus
+        * CHECKBOXES: Display a check box next to each item. See `TreeView::set_item_check_state`
     */
     pub struct TreeViewFlags: u32 {
         const VISIBLE = WS_VISIBLE;
         const DISABLED = WS_DISABLED;
         const TAB_STOP = WS_TABSTOP;
         const ALWAYS_SHOW_SELECTION = TVS_SHOWSELALWAYS;
+        const CHECKBOXES = TVS_CHECKBOXES;
     }
 }
 
@@ -96,6 +104,15 @@ pub enum TreeItemAction {
     },
 }
 
+/// The state of the check box of a tree item. Requires the `CHECKBOXES` flag.
+/// `Partial` requires the `tri_state` builder parameter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TreeCheckState {
+    Unchecked,
+    Checked,
+    Partial,
+}
+
 /// A reference to an item in a TreeView
 #[derive(Debug)]
 pub struct TreeItem {
@@ -127,6 +144,8 @@ Requires the `tree-view` feature
   * `font`:       The font used for the treeview text
   * `parent`:     The treeview parent container.
   * `image_list`: Image list containing the icon to use in the tree-view
+  * `tri_state`:  Use three state check boxes. Checking an item checks its children and updates the check state of its parents.
+  * `drag_and_drop`: Allow the user to move items by dragging them with the mouse. The drag image is created from the image list.
 
 **Control events:**
   * `MousePress(_)`: Generic mouse press events on the tree view
@@ -141,10 +160,36 @@ Requires the `tree-view` feature
   * `OnTreeItemExpanded`: After an item was expanded or collapsed. Sends a `EventData::OnTreeItemUpdate`.
   * `OnTreeItemChanged`: After the state of an item was changed. Sends a `EventData::OnTreeItemUpdate`.
   * `OnTreeItemSelectionChanged`: After the current selection was changed. Sends a `EventData::OnTreeItemChanged`.
+  * `OnTreeItemExpanding`: Before an item is expanded or collapsed. Sends a `EventData::OnTreeItemExpanding` that can cancel the action.
+  * `OnTreeItemMoved`: After an item was moved with drag and drop. Sends a `EventData::OnTreeItemMoved`.
+
+**Lazy loading:**
+Items marked with `set_item_lazy` display an expand button even if they have no children.
+The children can then be inserted in the `OnTreeItemExpanding` event the first time the item is expanded.
+If no children were added, the expand button is removed.
 */
-#[derive(Default, PartialEq, Eq)]
+#[derive(Default)]
 pub struct TreeView {
     pub handle: ControlHandle,
+    check_guard: Rc<Cell<bool>>,
+    drag: Rc<RefCell<Option<TreeDrag>>>,
+    handler0: RefCell<Option<RawEventHandler>>,
+    handler1: RefCell<Option<RawEventHandler>>,
+}
+
+/// State of an item being dragged by the user
+struct TreeDrag {
+    item: HTREEITEM,
+    image: HIMAGELIST,
+}
+
+/// Where a dragged item will be dropped
+#[derive(Copy, Clone)]
+enum TreeDrop {
+    Root,
+    Child(HTREEITEM),
+    Before(HTREEITEM),
+    After(HTREEITEM),
 }
 
 impl TreeView {
@@ -158,6 +203,8 @@ impl TreeView {
             ex_flags: 0,
             font: None,
             parent: None,
+            tri_state: false,
+            drag_and_drop: false,
 
             #[cfg(feature = "image-list")]
             image_list: None,
@@ -186,10 +233,7 @@ impl TreeView {
         if handle.is_null() {
             None
         } else {
-            Some(ImageList {
-                handle,
-                owned: false,
-            })
+            Some(ImageList::borrowed(handle))
         }
     }
 
@@ -252,6 +296,69 @@ impl TreeView {
         }
     }
 
+    /// Sets the overlay drawn over the image of an item. `overlay` is the overlay slot set with `ImageList::set_overlay`.
+    /// Use `0` to remove the overlay. Won't do anything if the item is not in the tree
+    #[cfg(feature = "image-list")]
+    pub fn set_item_overlay(&self, item: &TreeItem, overlay: u8) {
+        use winapi::um::commctrl::{
+            INDEXTOOVERLAYMASK, TVIF_STATE, TVIS_OVERLAYMASK, TVM_SETITEMW,
+        };
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let mut tree_item = blank_item();
+        tree_item.hItem = item.handle;
+        tree_item.mask = TVIF_STATE;
+        tree_item.stateMask = TVIS_OVERLAYMASK;
+        tree_item.state = INDEXTOOVERLAYMASK(overlay as u32);
+
+        wh::send_message(
+            handle,
+            TVM_SETITEMW,
+            0,
+            &mut tree_item as *mut TVITEMW as LPARAM,
+        );
+    }
+
+    /// Returns the overlay slot drawn over the image of an item.
+    /// Returns `0` if the item has no overlay or if it is not in the tree
+    #[cfg(feature = "image-list")]
+    pub fn item_overlay(&self, item: &TreeItem) -> u8 {
+        use winapi::um::commctrl::{TVIF_STATE, TVIS_OVERLAYMASK, TVM_GETITEMW};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let mut tree_item = blank_item();
+        tree_item.hItem = item.handle;
+        tree_item.mask = TVIF_STATE;
+        tree_item.stateMask = TVIS_OVERLAYMASK;
+
+        match wh::send_message(
+            handle,
+            TVM_GETITEMW,
+            0,
+            &mut tree_item as *mut TVITEMW as LPARAM,
+        ) {
+            0 => 0,
+            _ => ((tree_item.state & TVIS_OVERLAYMASK) >> 8) as u8,
+        }
+    }
+
+    /// Creates a drag image of an item, to use with `ImageList::begin_drag`. The returned image list is owned.
+    /// Returns `None` if the item is not in the tree
+    #[cfg(feature = "image-list")]
+    pub fn create_drag_image(&self, item: &TreeItem) -> Option<ImageList> {
+        use winapi::um::commctrl::TVM_CREATEDRAGIMAGE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        match wh::send_message(handle, TVM_CREATEDRAGIMAGE, 0, item.handle as LPARAM) {
+            0 => None,
+            list => {
+                let mut list = ImageList::borrowed(list as _);
+                list.owned = true;
+                Some(list)
+            }
+        }
+    }
+
     /// Sets the text color in the treeview
     pub fn set_text_color(&self, r: u8, g: u8, b: u8) {
         use winapi::um::commctrl::TVM_SETTEXTCOLOR;
@@ -685,6 +792,158 @@ impl TreeView {
         wh::send_message(handle, TVM_GETVISIBLECOUNT, 0, 0) as usize
     }
 
+    /// Returns the state of the check box of an item.
+    /// Returns `None` if the item is not in the tree view or if the tree view does not have check boxes.
+    pub fn item_check_state(&self, item: &TreeItem) -> Option<TreeCheckState> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        check_state(handle, item.handle)
+    }
+
+    /// Sets the state of the check box of an item. This does not update the children or the parents of the item.
+    /// Use `cascade_check_state` after this to propagate the state.
+    pub fn set_item_check_state(&self, item: &TreeItem, state: TreeCheckState) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        self.check_guard.set(true);
+        set_check_state(handle, item.handle, state);
+        self.check_guard.set(false);
+    }
+
+    /**
+        Copy the check state of an item to all of its children, and then update the parents of the item.
+        A parent is `Checked` if all its children are checked, `Unchecked` if none of them are, and `Partial` otherwise.
+
+        This is done automatically when the user clicks on a check box if the tree view was built with `tri_state`.
+    */
+    pub fn cascade_check_state(&self, item: &TreeItem) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        self.check_guard.set(true);
+        cascade_check(handle, item.handle);
+        self.check_guard.set(false);
+    }
+
+    /**
+        Marks an item as having children that are not yet inserted (`cChildren = I_CHILDRENCALLBACK`).
+        The item displays an expand button and the children can be inserted when `OnTreeItemExpanding` is raised.
+    */
+    pub fn set_item_lazy(&self, item: &TreeItem) {
+        use winapi::um::commctrl::{I_CHILDRENCALLBACK, TVIF_CHILDREN, TVM_SETITEMW};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut tree_item = blank_item();
+        tree_item.mask = TVIF_CHILDREN;
+        tree_item.hItem = item.handle;
+        tree_item.cChildren = I_CHILDRENCALLBACK;
+
+        wh::send_message(
+            handle,
+            TVM_SETITEMW,
+            0,
+            &mut tree_item as *mut TVITEMW as LPARAM,
+        );
+    }
+
+    /// Sets the lParam of an item
+    pub fn set_item_param(&self, item: &TreeItem, data: isize) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        set_param(handle, item.handle, data);
+    }
+
+    /**
+        Moves an item and its children under a new parent. `None` moves the item to the root of the tree view.
+
+        The win32 tree view cannot move items, so the items are copied and the old items are removed.
+        This means `OnTreeItemDelete` is raised for the old items. The text, images, check state and lParam of the items are kept.
+
+        Returns the new item or `None` if the new parent is the item itself or one of its children.
+    */
+    pub fn move_item(
+        &self,
+        item: &TreeItem,
+        parent: Option<&TreeItem>,
+        position: TreeInsert,
+    ) -> Option<TreeItem> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let parent = parent.map(|p| p.handle).unwrap_or(ptr::null_mut());
+        move_tree_item(handle, item.handle, parent, insert_position(position))
+            .map(|handle| TreeItem { handle })
+    }
+
+    /// Sorts the children of an item alphabetically. `None` sorts the root items.
+    /// If `recursive` is true, all the descendants are sorted.
+    pub fn sort_children(&self, parent: Option<&TreeItem>, recursive: bool) {
+        use winapi::um::commctrl::{TVI_ROOT, TVM_SORTCHILDREN};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let parent = parent.map(|p| p.handle).unwrap_or(TVI_ROOT);
+
+        wh::send_message(
+            handle,
+            TVM_SORTCHILDREN,
+            recursive as WPARAM,
+            parent as LPARAM,
+        );
+    }
+
+    /**
+        Sorts the children of an item using a comparator. `None` sorts the root items.
+        The comparator is called before the items are reordered, so it can freely query the tree view.
+
+        ```rust
+        use native_windows_gui as nwg;
+        fn sort_by_param(tree: &nwg::TreeView, folder: &nwg::TreeItem) {
+            tree.sort_children_by(Some(folder), |a, b| tree.item_param(a).cmp(&tree.item_param(b)));
+        }
+        ```
+    */
+    pub fn sort_children_by<F>(&self, parent: Option<&TreeItem>, mut compare: F)
+    where
+        F: FnMut(&TreeItem, &TreeItem) -> Ordering,
+    {
+        use winapi::um::commctrl::{TVI_ROOT, TVM_SORTCHILDRENCB, TVSORTCB};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let parent = parent.map(|p| p.handle).unwrap_or(TVI_ROOT);
+
+        let children: Vec<TreeItem> = children(handle, parent)
+            .into_iter()
+            .map(|handle| TreeItem { handle })
+            .collect();
+
+        let mut order: Vec<usize> = (0..children.len()).collect();
+        order.sort_by(|&a, &b| compare(&children[a], &children[b]));
+
+        // TVM_SORTCHILDRENCB only gives the lParam of the items to the callback,
+        // so the lParams are temporarily replaced by the rank of the items.
+        let params: Vec<isize> = children
+            .iter()
+            .map(|c| self.item_param(c).unwrap_or(0))
+            .collect();
+        for (rank, &index) in order.iter().enumerate() {
+            set_param(handle, children[index].handle, rank as isize);
+        }
+
+        let mut sort = TVSORTCB {
+            hParent: parent,
+            lpfnCompare: Some(compare_rank),
+            lParam: 0,
+        };
+
+        wh::send_message(
+            handle,
+            TVM_SORTCHILDRENCB,
+            0,
+            &mut sort as *mut TVSORTCB as LPARAM,
+        );
+
+        for (child, param) in children.iter().zip(params) {
+            set_param(handle, child.handle, param);
+        }
+    }
+
     //
     // Common methods
     //
@@ -841,14 +1100,169 @@ impl TreeView {
 
         wh::send_message(handle, TVM_ENDEDITLABELNOW, f_cancel as WPARAM, 0) != 0
     }
+
+    /**
+        The tree view sends its notifications to its parent, so the hook is bound to the parent.
+        Answers the `cChildren` query of the lazy items, cascades the check state when the user clicks on a check box
+        and starts the drag and drop.
+    */
+    fn hook_parent_notify(&self, tri_state: bool, drag_and_drop: bool) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::basetsd::UINT_PTR;
+        use winapi::um::commctrl::{
+            NMTREEVIEWW, NMTVDISPINFOW, NMTVITEMCHANGE, TVIF_CHILDREN, TVIF_STATE,
+            TVIS_EXPANDEDONCE, TVN_BEGINDRAGW, TVN_GETDISPINFOW, TVN_ITEMCHANGEDW,
+        };
+        use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let check_guard = self.check_guard.clone();
+        let drag = self.drag.clone();
+
+        let handler = bind_raw_event_handler_inner(
+            &parent_handle,
+            handle as UINT_PTR,
+            move |_hwnd, msg, _w, l| {
+                if msg != WM_NOTIFY {
+                    return None;
+                }
+
+                unsafe {
+                    let nmhdr = &*(l as *const NMHDR);
+                    if nmhdr.hwndFrom != handle {
+                        return None;
+                    }
+
+                    match nmhdr.code {
+                        TVN_GETDISPINFOW => {
+                            let data = &mut *(l as *mut NMTVDISPINFOW);
+                            if data.item.mask & TVIF_CHILDREN == TVIF_CHILDREN {
+                                let item = data.item.hItem;
+                                let loaded = item_state_bits(handle, item, TVIS_EXPANDEDONCE) != 0;
+                                let has_children = !children(handle, item).is_empty();
+                                data.item.cChildren = (has_children || !loaded) as c_int;
+                            }
+                            Some(0)
+                        }
+                        TVN_ITEMCHANGEDW if tri_state && !check_guard.get() => {
+                            let data = &*(l as *const NMTVITEMCHANGE);
+                            let old = data.uStateOld & TVIS_STATEIMAGEMASK;
+                            let new = data.uStateNew & TVIS_STATEIMAGEMASK;
+                            if data.uChanged & TVIF_STATE == TVIF_STATE && old != new {
+                                check_guard.set(true);
+
+                                // Clicking a checked box cycles to the partial state. Users expect it to be unchecked.
+                                if check_state(handle, data.hItem) == Some(TreeCheckState::Partial)
+                                {
+                                    set_check_state(handle, data.hItem, TreeCheckState::Unchecked);
+                                }
+                                cascade_check(handle, data.hItem);
+
+                                check_guard.set(false);
+                            }
+                            None
+                        }
+                        TVN_BEGINDRAGW if drag_and_drop => {
+                            let data = &*(l as *const NMTREEVIEWW);
+                            let pt = data.ptDrag;
+                            *drag.borrow_mut() =
+                                Some(begin_drag(handle, data.itemNew.hItem, pt.x, pt.y));
+                            None
+                        }
+                        _ => None,
+                    }
+                }
+            },
+        );
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
+    /// The mouse is captured by the tree view during a drag and drop, so this hook is bound to the tree view itself
+    fn hook_drag(&self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::basetsd::UINT_PTR;
+        use winapi::um::winuser::{
+            ReleaseCapture, VK_ESCAPE, WM_CAPTURECHANGED, WM_KEYDOWN, WM_LBUTTONUP, WM_MOUSEMOVE,
+        };
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let drag = self.drag.clone();
+
+        let handler = bind_raw_event_handler_inner(
+            &self.handle,
+            handle as UINT_PTR,
+            move |_hwnd, msg, w, l| {
+                match msg {
+                    WM_MOUSEMOVE => {
+                        if let Some(d) = drag.borrow().as_ref() {
+                            let (x, y) = mouse_point(l);
+                            drag_over(handle, d, x, y);
+                        }
+                        None
+                    }
+                    WM_LBUTTONUP => {
+                        // Take the drag state before releasing the capture as `WM_CAPTURECHANGED` is sent right away
+                        let d = drag.borrow_mut().take();
+                        if let Some(d) = d {
+                            end_drag(handle, &d);
+                            let (x, y) = mouse_point(l);
+                            let target = drop_target(handle, &d, x, y);
+                            if let Some(target) = target {
+                                drop_item(handle, d.item, target);
+                            }
+                        }
+                        None
+                    }
+                    WM_CAPTURECHANGED => {
+                        let d = drag.borrow_mut().take();
+                        if let Some(d) = d {
+                            end_drag(handle, &d);
+                        }
+                        None
+                    }
+                    WM_KEYDOWN if w as i32 == VK_ESCAPE && drag.borrow().is_some() => {
+                        unsafe {
+                            ReleaseCapture();
+                        }
+                        Some(0)
+                    }
+                    _ => None,
+                }
+            },
+        );
+
+        *self.handler1.borrow_mut() = Some(handler.unwrap());
+    }
 }
 
+control_cursor!(TreeView, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for TreeView {
     fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        let handler = self.handler1.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
         self.handle.destroy();
     }
 }
 
+impl PartialEq for TreeView {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for TreeView {}
+
 /// Builder for a TreeView
 pub struct TreeViewBuilder<'a> {
     size: (i32, i32),
@@ -859,6 +1273,8 @@ pub struct TreeViewBuilder<'a> {
     ex_flags: u32,
     font: Option<&'a Font>,
     parent: Option<ControlHandle>,
+    tri_state: bool,
+    drag_and_drop: bool,
 
     #[cfg(feature = "image-list")]
     image_list: Option<&'a ImageList>,
@@ -905,6 +1321,16 @@ impl<'a> TreeViewBuilder<'a> {
         self
     }
 
+    pub fn tri_state(mut self, tri_state: bool) -> TreeViewBuilder<'a> {
+        self.tri_state = tri_state;
+        self
+    }
+
+    pub fn drag_and_drop(mut self, drag_and_drop: bool) -> TreeViewBuilder<'a> {
+        self.drag_and_drop = drag_and_drop;
+        self
+    }
+
     #[cfg(feature = "image-list")]
     pub fn image_list(mut self, list: Option<&'a ImageList>) -> TreeViewBuilder<'a> {
         self.image_list = list;
@@ -919,12 +1345,15 @@ impl<'a> TreeViewBuilder<'a> {
             None => Err(NwgError::no_parent("TreeView")),
         }?;
 
+        // Check boxes must be enabled after the control creation or they won't be displayed
+        let checkboxes = flags & TVS_CHECKBOXES == TVS_CHECKBOXES || self.tri_state;
+
         *out = Default::default();
 
         out.handle = ControlBase::build_hwnd()
             .class_name(out.class_name())
             .forced_flags(out.forced_flags())
-            .flags(flags)
+            .flags(flags & !TVS_CHECKBOXES)
             .ex_flags(self.ex_flags)
             .size(self.size)
             .position(self.position)
@@ -939,6 +1368,26 @@ impl<'a> TreeViewBuilder<'a> {
 
         builder_set_image_list(&self, out);
 
+        let handle = out.handle.hwnd().unwrap();
+        if checkboxes {
+            wh::set_style(handle, wh::get_style(handle) | TVS_CHECKBOXES);
+        }
+
+        if self.tri_state {
+            use winapi::um::commctrl::{TVM_SETEXTENDEDSTYLE, TVS_EX_PARTIALCHECKBOXES};
+            wh::send_message(
+                handle,
+                TVM_SETEXTENDEDSTYLE,
+                TVS_EX_PARTIALCHECKBOXES as WPARAM,
+                TVS_EX_PARTIALCHECKBOXES as LPARAM,
+            );
+        }
+
+        out.hook_parent_notify(self.tri_state, self.drag_and_drop);
+        if self.drag_and_drop {
+            out.hook_drag();
+        }
+
         if self.focus {
             out.set_focus();
         }
@@ -997,3 +1446,430 @@ fn blank_item() -> TVITEMW {
         lParam: 0,
     }
 }
+
+fn insert_position(position: TreeInsert) -> HTREEITEM {
+    use winapi::um::commctrl::{TVI_FIRST, TVI_LAST, TVI_ROOT, TVI_SORT};
+
+    match position {
+        TreeInsert::First => TVI_FIRST,
+        TreeInsert::Last => TVI_LAST,
+        TreeInsert::Root => TVI_ROOT,
+        TreeInsert::Sort => TVI_SORT,
+        TreeInsert::After(i) => i,
+    }
+}
+
+fn next_item(handle: HWND, action: WPARAM, item: HTREEITEM) -> HTREEITEM {
+    use winapi::um::commctrl::TVM_GETNEXTITEM;
+    wh::send_message(handle, TVM_GETNEXTITEM, action, item as LPARAM) as HTREEITEM
+}
+
+/// Returns the direct children of an item. A null item or `TVI_ROOT` returns the root items.
+fn children(handle: HWND, parent: HTREEITEM) -> Vec<HTREEITEM> {
+    use winapi::um::commctrl::{TVGN_CHILD, TVGN_NEXT, TVGN_ROOT, TVI_ROOT};
+
+    let mut child = match parent.is_null() || parent == TVI_ROOT {
+        true => next_item(handle, TVGN_ROOT, ptr::null_mut()),
+        false => next_item(handle, TVGN_CHILD, parent),
+    };
+
+    let mut children = Vec::new();
+    while !child.is_null() {
+        children.push(child);
+        child = next_item(handle, TVGN_NEXT, child);
+    }
+
+    children
+}
+
+/// Returns `true` if `item` is `ancestor` or one of its descendants
+fn is_descendant(handle: HWND, item: HTREEITEM, ancestor: HTREEITEM) -> bool {
+    use winapi::um::commctrl::TVGN_PARENT;
+
+    let mut item = item;
+    while !item.is_null() {
+        if item == ancestor {
+            return true;
+        }
+        item = next_item(handle, TVGN_PARENT, item);
+    }
+
+    false
+}
+
+fn item_state_bits(handle: HWND, item: HTREEITEM, mask: u32) -> u32 {
+    use winapi::um::commctrl::{TVIF_HANDLE, TVIF_STATE, TVM_GETITEMW};
+
+    let mut tree_item = blank_item();
+    tree_item.mask = TVIF_STATE | TVIF_HANDLE;
+    tree_item.hItem = item;
+    tree_item.stateMask = mask;
+
+    match wh::send_message(
+        handle,
+        TVM_GETITEMW,
+        0,
+        &mut tree_item as *mut TVITEMW as LPARAM,
+    ) {
+        0 => 0,
+        _ => tree_item.state & mask,
+    }
+}
+
+fn check_state(handle: HWND, item: HTREEITEM) -> Option<TreeCheckState> {
+    match item_state_bits(handle, item, TVIS_STATEIMAGEMASK) >> 12 {
+        1 => Some(TreeCheckState::Unchecked),
+        2 => Some(TreeCheckState::Checked),
+        3 => Some(TreeCheckState::Partial),
+        _ => None,
+    }
+}
+
+fn set_check_state(handle: HWND, item: HTREEITEM, state: TreeCheckState) {
+    use winapi::um::commctrl::{INDEXTOSTATEIMAGEMASK, TVIF_STATE, TVM_SETITEMW};
+
+    let index = match state {
+        TreeCheckState::Unchecked => 1,
+        TreeCheckState::Checked => 2,
+        TreeCheckState::Partial => 3,
+    };
+
+    let mut tree_item = blank_item();
+    tree_item.mask = TVIF_STATE;
+    tree_item.hItem = item;
+    tree_item.state = INDEXTOSTATEIMAGEMASK(index);
+    tree_item.stateMask = TVIS_STATEIMAGEMASK;
+
+    wh::send_message(
+        handle,
+        TVM_SETITEMW,
+        0,
+        &mut tree_item as *mut TVITEMW as LPARAM,
+    );
+}
+
+fn cascade_check(handle: HWND, item: HTREEITEM) {
+    use winapi::um::commctrl::TVGN_PARENT;
+
+    fn set_descendants(handle: HWND, item: HTREEITEM, state: TreeCheckState) {
+        for child in children(handle, item) {
+            set_check_state(handle, child, state);
+            set_descendants(handle, child, state);
+        }
+    }
+
+    let state = match check_state(handle, item) {
+        Some(TreeCheckState::Partial) | None => return,
+        Some(state) => state,
+    };
+
+    set_descendants(handle, item, state);
+
+    let mut parent = next_item(handle, TVGN_PARENT, item);
+    while !parent.is_null() {
+        let states: Vec<Option<TreeCheckState>> = children(handle, parent)
+            .into_iter()
+            .map(|child| check_state(handle, child))
+            .collect();
+
+        let state = if states.iter().all(|&s| s == Some(TreeCheckState::Checked)) {
+            TreeCheckState::Checked
+        } else if states.iter().all(|&s| s == Some(TreeCheckState::Unchecked)) {
+            TreeCheckState::Unchecked
+        } else {
+            TreeCheckState::Partial
+        };
+
+        set_check_state(handle, parent, state);
+        parent = next_item(handle, TVGN_PARENT, parent);
+    }
+}
+
+fn set_param(handle: HWND, item: HTREEITEM, data: isize) {
+    use winapi::um::commctrl::{TVIF_PARAM, TVM_SETITEMW};
+
+    let mut tree_item = blank_item();
+    tree_item.mask = TVIF_PARAM;
+    tree_item.hItem = item;
+    tree_item.lParam = data;
+
+    wh::send_message(
+        handle,
+        TVM_SETITEMW,
+        0,
+        &mut tree_item as *mut TVITEMW as LPARAM,
+    );
+}
+
+unsafe extern "system" fn compare_rank(rank1: LPARAM, rank2: LPARAM, _sort: LPARAM) -> c_int {
+    rank1.cmp(&rank2) as c_int
+}
+
+/// Inserts a copy of `item` and its children. Returns the new item or null if the item could not be copied.
+fn copy_tree_item(handle: HWND, item: HTREEITEM, parent: HTREEITEM, after: HTREEITEM) -> HTREEITEM {
+    use winapi::um::commctrl::TVINSERTSTRUCTW_u;
+    use winapi::um::commctrl::{
+        TVIF_CHILDREN, TVIF_HANDLE, TVIF_IMAGE, TVIF_PARAM, TVIF_SELECTEDIMAGE, TVIF_STATE,
+        TVIF_TEXT, TVINSERTSTRUCTW, TVIS_BOLD, TVIS_CUT, TVIS_OVERLAYMASK, TVI_LAST, TVM_GETITEMW,
+        TVM_INSERTITEMW,
+    };
+    const BUFFER_MAX: usize = 260;
+    const COPIED_STATE: u32 = TVIS_STATEIMAGEMASK | TVIS_OVERLAYMASK | TVIS_BOLD | TVIS_CUT;
+
+    let mut text = [0u16; BUFFER_MAX];
+
+    let mut data = blank_item();
+    data.mask = TVIF_HANDLE
+        | TVIF_TEXT
+        | TVIF_IMAGE
+        | TVIF_SELECTEDIMAGE
+        | TVIF_STATE
+        | TVIF_PARAM
+        | TVIF_CHILDREN;
+    data.hItem = item;
+    data.pszText = text.as_mut_ptr();
+    data.cchTextMax = BUFFER_MAX as _;
+    data.stateMask = COPIED_STATE;
+
+    if wh::send_message(handle, TVM_GETITEMW, 0, &mut data as *mut TVITEMW as LPARAM) == 0 {
+        return ptr::null_mut();
+    }
+
+    data.mask &= !TVIF_HANDLE;
+    data.state &= COPIED_STATE;
+
+    let mut u: TVINSERTSTRUCTW_u = unsafe { mem::zeroed() };
+    unsafe {
+        *u.item_mut() = data;
+    }
+
+    let insert = TVINSERTSTRUCTW {
+        hParent: parent,
+        hInsertAfter: after,
+        u,
+    };
+
+    let ptr = &insert as *const TVINSERTSTRUCTW;
+    let new_item = wh::send_message(handle, TVM_INSERTITEMW, 0, ptr as LPARAM) as HTREEITEM;
+    if new_item.is_null() {
+        return new_item;
+    }
+
+    for child in children(handle, item) {
+        copy_tree_item(handle, child, new_item, TVI_LAST);
+    }
+
+    new_item
+}
+
+fn move_tree_item(
+    handle: HWND,
+    item: HTREEITEM,
+    parent: HTREEITEM,
+    after: HTREEITEM,
+) -> Option<HTREEITEM> {
+    use winapi::um::commctrl::TVM_DELETEITEM;
+
+    if is_descendant(handle, parent, item) {
+        return None;
+    }
+
+    let new_item = copy_tree_item(handle, item, parent, after);
+    if new_item.is_null() {
+        return None;
+    }
+
+    wh::send_message(handle, TVM_DELETEITEM, 0, item as LPARAM);
+
+    Some(new_item)
+}
+
+/// Mouse position of a mouse message. Can be negative when the mouse is captured.
+fn mouse_point(l: LPARAM) -> (i32, i32) {
+    use winapi::shared::minwindef::{HIWORD, LOWORD};
+    let l = l as u32;
+    (LOWORD(l) as i16 as i32, HIWORD(l) as i16 as i32)
+}
+
+/// The drag image functions use coordinates relative to the window and not to the client area
+fn drag_point(handle: HWND, x: i32, y: i32) -> (i32, i32) {
+    use winapi::shared::windef::{POINT, RECT};
+    use winapi::um::winuser::{ClientToScreen, GetWindowRect};
+
+    let mut rect: RECT = unsafe { mem::zeroed() };
+    let mut pt = POINT { x, y };
+    unsafe {
+        GetWindowRect(handle, &mut rect);
+        ClientToScreen(handle, &mut pt);
+    }
+
+    (pt.x - rect.left, pt.y - rect.top)
+}
+
+fn begin_drag(handle: HWND, item: HTREEITEM, x: i32, y: i32) -> TreeDrag {
+    use winapi::um::commctrl::{ImageList_BeginDrag, ImageList_DragEnter, TVM_CREATEDRAGIMAGE};
+    use winapi::um::winuser::SetCapture;
+
+    let image = wh::send_message(handle, TVM_CREATEDRAGIMAGE, 0, item as LPARAM) as HIMAGELIST;
+
+    unsafe {
+        if !image.is_null() {
+            let (x, y) = drag_point(handle, x, y);
+            ImageList_BeginDrag(image, 0, 0, 0);
+            ImageList_DragEnter(handle, x, y);
+        }
+
+        SetCapture(handle);
+    }
+
+    TreeDrag { item, image }
+}
+
+fn drag_over(handle: HWND, drag: &TreeDrag, x: i32, y: i32) {
+    use winapi::um::commctrl::{
+        ImageList_DragMove, ImageList_DragShowNolock, TVGN_DROPHILITE, TVM_SELECTITEM,
+        TVM_SETINSERTMARK,
+    };
+
+    let (hilite, mark, after) = match drop_target(handle, drag, x, y) {
+        Some(TreeDrop::Child(item)) => (item, ptr::null_mut(), false),
+        Some(TreeDrop::Before(item)) => (ptr::null_mut(), item, false),
+        Some(TreeDrop::After(item)) => (ptr::null_mut(), item, true),
+        Some(TreeDrop::Root) | None => (ptr::null_mut(), ptr::null_mut(), false),
+    };
+
+    // The drag image must be hidden while the tree view is repainted
+    unsafe {
+        if !drag.image.is_null() {
+            ImageList_DragShowNolock(0);
+        }
+    }
+
+    wh::send_message(handle, TVM_SELECTITEM, TVGN_DROPHILITE, hilite as LPARAM);
+    wh::send_message(handle, TVM_SETINSERTMARK, after as WPARAM, mark as LPARAM);
+
+    unsafe {
+        if !drag.image.is_null() {
+            let (x, y) = drag_point(handle, x, y);
+            ImageList_DragShowNolock(1);
+            ImageList_DragMove(x, y);
+        }
+    }
+}
+
+fn end_drag(handle: HWND, drag: &TreeDrag) {
+    use winapi::um::commctrl::{
+        ImageList_Destroy, ImageList_DragLeave, ImageList_EndDrag, TVGN_DROPHILITE, TVM_SELECTITEM,
+        TVM_SETINSERTMARK,
+    };
+    use winapi::um::winuser::ReleaseCapture;
+
+    unsafe {
+        if !drag.image.is_null() {
+            ImageList_DragLeave(handle);
+            ImageList_EndDrag();
+            ImageList_Destroy(drag.image);
+        }
+
+        ReleaseCapture();
+    }
+
+    wh::send_message(handle, TVM_SELECTITEM, TVGN_DROPHILITE, 0);
+    wh::send_message(handle, TVM_SETINSERTMARK, 0, 0);
+}
+
+/**
+    Returns where the dragged item would be dropped if the mouse was released at (x, y).
+    The upper and lower quarters of an item insert the dragged item as a sibling, the middle inserts it as a child.
+    Returns `None` if the dragged item would be dropped on itself or on one of its children.
+*/
+fn drop_target(handle: HWND, drag: &TreeDrag, x: i32, y: i32) -> Option<TreeDrop> {
+    use winapi::shared::windef::{POINT, RECT};
+    use winapi::um::commctrl::{
+        TVHITTESTINFO, TVHT_ONITEM, TVHT_ONITEMBUTTON, TVHT_ONITEMINDENT, TVHT_ONITEMRIGHT,
+        TVM_GETITEMRECT, TVM_HITTEST,
+    };
+
+    let mut hit = TVHITTESTINFO {
+        pt: POINT { x, y },
+        flags: 0,
+        hItem: ptr::null_mut(),
+    };
+    wh::send_message(
+        handle,
+        TVM_HITTEST,
+        0,
+        &mut hit as *mut TVHITTESTINFO as LPARAM,
+    );
+
+    let on_item = TVHT_ONITEM | TVHT_ONITEMINDENT | TVHT_ONITEMBUTTON | TVHT_ONITEMRIGHT;
+    if hit.hItem.is_null() || hit.flags & on_item == 0 {
+        return Some(TreeDrop::Root);
+    }
+
+    if is_descendant(handle, hit.hItem, drag.item) {
+        return None;
+    }
+
+    // TVM_GETITEMRECT reads the item handle from the start of the rect
+    let mut rect: RECT = unsafe { mem::zeroed() };
+    unsafe {
+        *(&mut rect as *mut RECT as *mut HTREEITEM) = hit.hItem;
+    }
+    wh::send_message(handle, TVM_GETITEMRECT, 0, &mut rect as *mut RECT as LPARAM);
+
+    let quarter = (rect.bottom - rect.top) / 4;
+    if y < rect.top + quarter {
+        Some(TreeDrop::Before(hit.hItem))
+    } else if y >= rect.bottom - quarter {
+        Some(TreeDrop::After(hit.hItem))
+    } else {
+        Some(TreeDrop::Child(hit.hItem))
+    }
+}
+
+/// Moves the dragged item and notifies the parent with a `NWG_TREE_ITEM_MOVED` notification
+fn drop_item(handle: HWND, item: HTREEITEM, target: TreeDrop) {
+    use winapi::shared::basetsd::UINT_PTR;
+    use winapi::um::commctrl::{
+        NMTREEVIEWW, TVGN_CARET, TVGN_PARENT, TVGN_PREVIOUS, TVI_FIRST, TVI_LAST, TVM_SELECTITEM,
+    };
+    use winapi::um::winuser::{GetDlgCtrlID, WM_NOTIFY};
+
+    let (parent, after) = match target {
+        TreeDrop::Root => (ptr::null_mut(), TVI_LAST),
+        TreeDrop::Child(target) => (target, TVI_LAST),
+        TreeDrop::Before(target) => {
+            let previous = next_item(handle, TVGN_PREVIOUS, target);
+            let previous = match previous.is_null() {
+                true => TVI_FIRST,
+                false => previous,
+            };
+            (next_item(handle, TVGN_PARENT, target), previous)
+        }
+        TreeDrop::After(target) => (next_item(handle, TVGN_PARENT, target), target),
+    };
+
+    let new_item = match move_tree_item(handle, item, parent, after) {
+        Some(new_item) => new_item,
+        None => return,
+    };
+
+    wh::send_message(handle, TVM_SELECTITEM, TVGN_CARET, new_item as LPARAM);
+
+    unsafe {
+        let mut notif: NMTREEVIEWW = mem::zeroed();
+        notif.hdr.hwndFrom = handle;
+        notif.hdr.idFrom = GetDlgCtrlID(handle) as UINT_PTR;
+        notif.hdr.code = wh::NWG_TREE_ITEM_MOVED;
+        notif.itemOld.hItem = item;
+        notif.itemNew.hItem = new_item;
+
+        wh::send_message(
+            wh::get_window_parent(handle),
+            WM_NOTIFY,
+            notif.hdr.idFrom,
+            &mut notif as *mut NMTREEVIEWW as LPARAM,
+        );
+    }
+}
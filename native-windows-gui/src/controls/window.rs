@@ -7,7 +7,7 @@ use winapi::um::winuser::{
 use super::{ControlBase, ControlHandle};
 use crate::win32::base_helper::check_hwnd;
 use crate::win32::window_helper as wh;
-use crate::{Icon, NwgError};
+use crate::{Icon, Layout, NwgError};
 
 const NOT_BOUND: &'static str = "Window is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: Window handle is not HWND!";
@@ -224,6 +224,16 @@ impl Window {
         unsafe { wh::set_window_size(handle, x, y, true) }
     }
 
+    /**
+        Resize the window so that its client area has the preferred size of `layout`, then fit the layout.
+        Call this after the text or the font of the controls changed (ex: after a translation) to grow or shrink the window.
+    */
+    pub fn size_to_content<L: Layout>(&self, layout: &L) {
+        let (w, h) = layout.preferred_size();
+        self.set_size(w, h);
+        layout.fit();
+    }
+
     /// Return the position of the button in the parent window
     pub fn position(&self) -> (i32, i32) {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
@@ -264,6 +274,8 @@ impl Window {
     }
 }
 
+control_cursor!(Window, NOT_BOUND, BAD_HANDLE);
+
 impl Drop for Window {
     fn drop(&mut self) {
         self.handle.destroy();
@@ -37,6 +37,10 @@ pub enum NwgError {
     #[cfg(feature = "image-decoder")]
     ImageDecoderError(i32, String),
 
+    /// Error raised by the ImageEncoder feature
+    #[cfg(feature = "image-decoder")]
+    ImageEncoderError(i32, String),
+
     /// Error raised by one of the locale functions
     #[cfg(feature = "winnls")]
     BadLocale(String),
@@ -86,6 +90,11 @@ impl NwgError {
         NwgError::ImageDecoderError(code, e.into())
     }
 
+    #[cfg(feature = "image-decoder")]
+    pub fn image_encoder<S: Into<String>>(code: i32, e: S) -> NwgError {
+        NwgError::ImageEncoderError(code, e.into())
+    }
+
     pub fn no_parent(name: &'static str) -> NwgError {
         NwgError::ControlCreationError(format!("No parent defined for {:?} control", name))
     }
@@ -114,6 +123,9 @@ impl fmt::Display for NwgError {
             #[cfg(feature = "image-decoder")]
             ImageDecoderError(_id, reason) => write!(f, "Image decoder failed: {:?}", reason),
 
+            #[cfg(feature = "image-decoder")]
+            ImageEncoderError(_id, reason) => write!(f, "Image encoder failed: {:?}", reason),
+
             #[cfg(feature = "winnls")]
             BadLocale(reason) => write!(f, "Windows locale functions failed: {:?}", reason),
 
@@ -122,6 +122,9 @@ pub enum Event {
     /// When the current selection of the combobox was changed
     OnComboxBoxSelection,
 
+    /// When the user commits a text that is not an item of a ComboBoxEx. The text is passed in `EventData::OnComboBoxTextCommit`
+    OnComboBoxTextCommit,
+
     /// When the date select dropdown is expanded
     OnDatePickerDropdown,
 
@@ -131,6 +134,31 @@ pub enum Event {
     /// When the value of the date select is changed
     OnDatePickerChanged,
 
+    /// When the selected date or range of dates of a month calendar is changed.
+    /// Also sent when the user scrolls to another month.
+    OnMonthCalendarSelectionChanged,
+
+    /// When the user explicitly selects a date in a month calendar
+    OnMonthCalendarSelect,
+
+    /// When the view of a month calendar is changed (ex: from months to years)
+    OnMonthCalendarViewChanged,
+
+    /// When a link of a LinkLabel is clicked. The link is passed in `EventData::OnLinkClick`
+    OnLinkClick,
+
+    /// When a field of an IpAddressInput is changed. The field is passed in `EventData::OnIpAddressFieldChanged`
+    OnIpAddressFieldChanged,
+
+    /// When the user changes the value of a HotKeyInput
+    OnHotKeyChanged,
+
+    /// When an AnimationFrame starts playing
+    OnAnimationStart,
+
+    /// When an AnimationFrame stops playing
+    OnAnimationStop,
+
     /// When an item on a list box is clicked twice
     OnListBoxDoubleClick,
 
@@ -198,6 +226,12 @@ pub enum Event {
     /// When the selected tree item is changed.
     OnTreeItemSelectionChanged,
 
+    /// Before an item is expanded or collapsed. The action can be cancelled with `EventData::OnTreeItemExpanding`
+    OnTreeItemExpanding,
+
+    /// After an item was moved with drag and drop. The old and the new item are passed in `EventData::OnTreeItemMoved`
+    OnTreeItemMoved,
+
     /// When all the items in a list view are destroyed
     /// Do not add, delete, or rearrange items in the list view while processing this notification code.
     OnListViewClear,
@@ -263,6 +297,13 @@ pub enum Event {
     /// When a notice is... noticed
     OnNotice,
 
+    /// When the user zoomed or panned an interactive Plotters control. Read the new view with `Plotters::view`.
+    OnPlottersViewChanged,
+
+    /// When the mouse moved over an interactive Plotters control or left it.
+    /// Read the data under the cursor with `Plotters::hover` and `Plotters::hover_point`.
+    OnPlottersHover,
+
     /// When a user clicks on the X button of a window
     OnWindowClose,
 }
@@ -300,6 +341,19 @@ pub enum EventData {
     /// The path to one or more files that were dropped in the application
     OnFileDrop(DropFiles),
 
+    /// The text committed by the user in a ComboBoxEx
+    OnComboBoxTextCommit(String),
+
+    /// The index, the id and the url of the clicked link
+    OnLinkClick {
+        index: usize,
+        id: String,
+        url: String,
+    },
+
+    /// The index (0-3) and the new value of the changed field. `value` is -1 if the field is blank.
+    OnIpAddressFieldChanged { field: usize, value: i32 },
+
     /// The handle to the item being deleted. The item is still valid.
     #[cfg(feature = "tree-view")]
     OnTreeItemDelete(crate::TreeItem),
@@ -322,6 +376,17 @@ pub enum EventData {
         new: crate::TreeItem,
     },
 
+    /// The item being expanded or collapsed
+    #[cfg(feature = "tree-view")]
+    OnTreeItemExpanding(TreeItemExpandingData),
+
+    /// The item that was moved (no longer valid) and its copy at the new position
+    #[cfg(feature = "tree-view")]
+    OnTreeItemMoved {
+        old: crate::TreeItem,
+        new: crate::TreeItem,
+    },
+
     /// Row index and column index of the list view item that raised the event
     /// `row_index` `0xFFF...` means the absence of an item
     #[cfg(feature = "list-view")]
@@ -388,6 +453,30 @@ impl EventData {
         }
     }
 
+    /// Unwraps event data into the text committed in a ComboBoxEx. Panics if it's not the right type.
+    pub fn on_combo_box_text_commit(&self) -> &str {
+        match self {
+            EventData::OnComboBoxTextCommit(text) => text,
+            d => panic!("Wrong data type: {:?}", d),
+        }
+    }
+
+    /// Unwraps event data into the index, the id and the url of a clicked link. Panics if it's not the right type.
+    pub fn on_link_click(&self) -> (usize, &str, &str) {
+        match self {
+            EventData::OnLinkClick { index, id, url } => (*index, id, url),
+            d => panic!("Wrong data type: {:?}", d),
+        }
+    }
+
+    /// Unwraps event data into the field index and the field value of an ip address input. Panics if it's not the right type.
+    pub fn on_ip_address_field_changed(&self) -> (usize, i32) {
+        match self {
+            EventData::OnIpAddressFieldChanged { field, value } => (*field, *value),
+            d => panic!("Wrong data type: {:?}", d),
+        }
+    }
+
     /// unwraps event data into the removed tree item
     #[cfg(feature = "tree-view")]
     pub fn on_tree_item_delete(&self) -> &crate::TreeItem {
@@ -415,6 +504,24 @@ impl EventData {
         }
     }
 
+    /// unwraps event data into the item being expanded or collapsed
+    #[cfg(feature = "tree-view")]
+    pub fn on_tree_item_expanding(&self) -> &TreeItemExpandingData {
+        match self {
+            EventData::OnTreeItemExpanding(data) => data,
+            d => panic!("Wrong data type: {:?}", d),
+        }
+    }
+
+    /// unwraps event data into the old item and the new item of a drag and drop
+    #[cfg(feature = "tree-view")]
+    pub fn on_tree_item_moved(&self) -> (&crate::TreeItem, &crate::TreeItem) {
+        match self {
+            EventData::OnTreeItemMoved { old, new } => (old, new),
+            d => panic!("Wrong data type: {:?}", d),
+        }
+    }
+
     /// unwraps event data into f_cancel, new_text.
     /// f_cancel indicates the editing is cancel or not.
     /// new_text is the new input text when editing is not cancel.
@@ -548,6 +655,52 @@ impl fmt::Debug for WindowCloseData {
     }
 }
 
+/// Opaque type over the data of a tree item that is about to be expanded or collapsed
+#[cfg(feature = "tree-view")]
+pub struct TreeItemExpandingData {
+    pub(crate) item: crate::TreeItem,
+    pub(crate) action: crate::ExpandState,
+    pub(crate) cancel: *mut bool,
+}
+
+#[cfg(feature = "tree-view")]
+impl TreeItemExpandingData {
+    /// The item being expanded or collapsed
+    pub fn item(&self) -> &crate::TreeItem {
+        &self.item
+    }
+
+    /// `ExpandState::Expand` or `ExpandState::Collapse`
+    pub fn action(&self) -> crate::ExpandState {
+        self.action
+    }
+
+    /// Prevents the item from being expanded or collapsed
+    pub fn cancel(&self, value: bool) {
+        unsafe {
+            *self.cancel = value;
+        }
+    }
+
+    /// Returns true if the action will be cancelled after the event
+    pub fn cancelled(&self) -> bool {
+        unsafe { *self.cancel }
+    }
+}
+
+#[cfg(feature = "tree-view")]
+impl fmt::Debug for TreeItemExpandingData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TreeItemExpandingData({:?}, {:?}, {})",
+            self.item,
+            self.action,
+            self.cancelled()
+        )
+    }
+}
+
 /// Opaque type over a paint event's data
 #[derive(Debug)]
 pub struct PaintData {
@@ -1,41 +1,158 @@
+use super::{DeriveLayout, Layout, LayoutTarget};
 use crate::controls::ControlHandle;
 use crate::win32::window::bind_raw_event_handler_inner;
 use crate::win32::window_helper as wh;
 use crate::NwgError;
+pub use native_windows_layout::DynAnchors;
+use native_windows_layout::{dyn_preferred_size, solve_dyn, DynItem, LayoutRect};
 use std::cell::RefCell;
 use std::ptr;
 use std::rc::Rc;
 use winapi::shared::windef::HWND;
 
-/// A control item in a DynLayout
+bitflags! {
+    /**
+        The edges of the parent a DynLayout item is pinned to. See `DynAnchors::pinned`.
+
+        * LEFT, TOP, RIGHT, BOTTOM: The item keeps its distance to this edge of the parent
+        * ALL: The item grows with the parent
+    */
+    pub struct DynPin: u32 {
+        const LEFT = 0b0001;
+        const TOP = 0b0010;
+        const RIGHT = 0b0100;
+        const BOTTOM = 0b1000;
+        const ALL = Self::LEFT.bits | Self::TOP.bits | Self::RIGHT.bits | Self::BOTTOM.bits;
+    }
+}
+
+impl From<DynPin> for DynAnchors {
+    fn from(pin: DynPin) -> DynAnchors {
+        DynAnchors::pinned(
+            pin.contains(DynPin::LEFT),
+            pin.contains(DynPin::TOP),
+            pin.contains(DynPin::RIGHT),
+            pin.contains(DynPin::BOTTOM),
+        )
+    }
+}
+
+/// A control or a nested layout in a DynLayout
 #[derive(Debug)]
 pub struct DynLayoutItem {
-    /// The handle to the control in the item
-    control: HWND,
+    /// The control or the layout in the item
+    target: LayoutTarget,
+
+    /// The position and the size of the item when the parent has a size of 0. Set when the item is added to the layout.
     pos_init: (i32, i32),
     size_init: (i32, i32),
-    mv: (i32, i32),
-    sz: (i32, i32),
+
+    /// `false` until the initial geometry of the item is computed
+    captured: bool,
+
+    /// The geometry of the item for the current size of the parent. If `None`, the current geometry of the control is used.
+    geometry: Option<((i32, i32), (u32, u32))>,
+
+    anchors: DynAnchors,
+    min_size: (u32, u32),
+    max_size: (u32, u32),
 }
 
 impl DynLayoutItem {
-    /// Initialize a new layout item
+    /// Initialize a new layout item. The item moves by `mv` percent and grows by `sz` percent of the parent growth.
     pub fn new<W: Into<ControlHandle>>(c: W, mv: (i32, i32), sz: (i32, i32)) -> DynLayoutItem {
+        DynLayoutItem::anchored(c, DynAnchors::percent(mv, sz))
+    }
+
+    /**
+        Initialize a new layout item with anchors. `anchors` can be a `DynAnchors` or a `DynPin`.
+        The initial geometry of the item is the geometry of the control when the item is added to the layout.
+    */
+    pub fn anchored<W: Into<ControlHandle>, A: Into<DynAnchors>>(
+        c: W,
+        anchors: A,
+    ) -> DynLayoutItem {
         let control = c
             .into()
             .hwnd()
             .expect("Child must be a window-like control (HWND handle)");
-        let pos_init = (0, 0);
-        let size_init = (0, 0);
 
         DynLayoutItem {
-            control,
-            pos_init,
-            size_init,
-            mv,
-            sz,
+            target: LayoutTarget::Control(control),
+            pos_init: (0, 0),
+            size_init: (0, 0),
+            captured: false,
+            geometry: None,
+            anchors: anchors.into(),
+            min_size: (0, 0),
+            max_size: (u32::max_value(), u32::max_value()),
         }
     }
+
+    /// Initialize a new layout item from a nested layout. The layout should be built with `build_partial`.
+    /// `pos` and `size` are the initial area of the nested layout.
+    pub fn with_layout<L: Layout + Clone + 'static>(
+        layout: &L,
+        pos: (i32, i32),
+        size: (i32, i32),
+        mv: (i32, i32),
+        sz: (i32, i32),
+    ) -> DynLayoutItem {
+        DynLayoutItem {
+            target: LayoutTarget::Layout(Box::new(layout.clone())),
+            pos_init: pos,
+            size_init: size,
+            captured: true,
+            geometry: None,
+            anchors: DynAnchors::percent(mv, sz),
+            min_size: (0, 0),
+            max_size: (u32::max_value(), u32::max_value()),
+        }
+    }
+
+    /// Sets the position and the size of the control in pixels for the current size of the parent.
+    /// By default, the current geometry of the control is used.
+    pub fn geometry(mut self, position: (i32, i32), size: (u32, u32)) -> DynLayoutItem {
+        self.geometry = Some((position, size));
+        self.captured = false;
+        self
+    }
+
+    /// Sets the anchors of the item. `anchors` can be a `DynAnchors` or a `DynPin`.
+    pub fn anchors<A: Into<DynAnchors>>(mut self, anchors: A) -> DynLayoutItem {
+        self.anchors = anchors.into();
+        self
+    }
+
+    /// Sets the minimum size of the item
+    pub fn min_size(mut self, size: (u32, u32)) -> DynLayoutItem {
+        self.min_size = size;
+        self
+    }
+
+    /// Sets the maximum size of the item
+    pub fn max_size(mut self, size: (u32, u32)) -> DynLayoutItem {
+        self.max_size = size;
+        self
+    }
+
+    /// Computes the initial geometry of the item from its geometry when the parent area is `area`
+    fn capture(&mut self, ((x, y), size): ((i32, i32), (u32, u32))) {
+        let (position, item_size) = match (&self.target, self.geometry) {
+            (_, Some(geometry)) => geometry,
+            (&LayoutTarget::Control(hwnd), None) => unsafe {
+                let (px, py) = wh::get_window_position(hwnd);
+                ((px - x, py - y), wh::get_window_size(hwnd))
+            },
+            (LayoutTarget::Layout(_), None) => return,
+        };
+
+        let item = DynItem::capture((), position, item_size, self.anchors, size);
+        self.pos_init = item.position;
+        self.size_init = item.size;
+        self.geometry = None;
+        self.captured = true;
+    }
 }
 
 /// A layout that lays out widgets
@@ -46,8 +163,34 @@ pub struct DynLayoutInner {
 
     /// The children of the control that fit in the layout
     children: Vec<DynLayoutItem>,
+
+    /// The area of the parent window and the previous control in the tab order given by the parent layout if the layout is nested
+    area: Option<((i32, i32), (u32, u32), Option<HWND>)>,
 }
 
+/**
+A layout that moves and resizes widgets when the parent window is resized, starting from their initial geometry.
+NWG layouts use interior mutability to manage their controls.
+
+Each item has anchors (see `DynAnchors`) that tell how much it moves and grows with the parent,
+and an optional minimum and maximum size. Items can be pinned to the edges of the parent with `DynPin`.
+
+```rust
+    use native_windows_gui as nwg;
+    fn layout(layout: &nwg::DynLayout, window: &nwg::Window, text: &nwg::TextInput, ok: &nwg::Button) {
+        use nwg::{DynLayoutItem, DynPin};
+
+        nwg::DynLayout::builder()
+            .parent(window)
+            .child_item(DynLayoutItem::anchored(text, DynPin::ALL).min_size((100, 25)))
+            .child_item(DynLayoutItem::anchored(ok, DynPin::RIGHT | DynPin::BOTTOM))
+            .build(&layout);
+
+        // Later, the text input stops growing vertically
+        layout.update_child(text, DynPin::LEFT | DynPin::TOP | DynPin::RIGHT);
+    }
+```
+*/
 #[derive(Clone)]
 pub struct DynLayout {
     inner: Rc<RefCell<DynLayoutInner>>,
@@ -58,6 +201,7 @@ impl DynLayout {
         let layout = DynLayoutInner {
             base: ptr::null_mut(),
             children: Vec::new(),
+            area: None,
         };
 
         DynLayoutBuilder { layout }
@@ -78,47 +222,7 @@ impl DynLayout {
         - If the control is not window-like (HWND handle)
     */
     pub fn add_child<W: Into<ControlHandle>>(&self, m: (i32, i32), s: (i32, i32), c: W) {
-        let hwnd = c
-            .into()
-            .hwnd()
-            .expect("Child must be a window-like control (HWND handle)");
-        let pos = unsafe { wh::get_window_position(hwnd) };
-        let size = unsafe { wh::get_window_size(hwnd) };
-
-        let (whost, hhost) = unsafe { wh::get_window_size(self.inner.borrow_mut().base) };
-
-        let xdelta = 0.01 * whost as f32;
-        let ydelta = 0.01 * hhost as f32;
-
-        let mut xpos = pos.0;
-        if m.0 > 0 {
-            xpos -= (xdelta * m.0 as f32) as i32;
-        }
-
-        let mut ypos = pos.1;
-        if m.1 > 0 {
-            ypos -= (ydelta * m.1 as f32) as i32;
-        }
-
-        let mut xsize = size.0 as i32;
-        if s.0 > 0 {
-            xsize -= (xdelta * s.0 as f32) as i32;
-        }
-
-        let mut ysize = size.1 as i32;
-        if s.1 > 0 {
-            ysize -= (ydelta * s.1 as f32) as i32;
-        }
-
-        let item = DynLayoutItem {
-            control: hwnd,
-            pos_init: (xpos, ypos),
-            size_init: (xsize, ysize),
-            mv: m,
-            sz: s,
-        };
-
-        self.add_child_item(item);
+        self.add_child_item(DynLayoutItem::new(c, m, s));
     }
 
     /**
@@ -137,12 +241,16 @@ impl DynLayout {
 
             // No need to check the layout item control because it's checked in `DynLayoutItem::new`
 
+            let mut i = i;
+            if !i.captured {
+                i.capture(inner.parent_area());
+            }
+
             inner.children.push(i);
             inner.base
         };
 
-        let (w, h) = unsafe { wh::get_window_size(base) };
-        self.update_layout(w as u32, h as u32);
+        self.refresh(base);
     }
 
     /**
@@ -168,7 +276,7 @@ impl DynLayout {
             let index = inner
                 .children
                 .iter()
-                .position(|item| item.control == handle);
+                .position(|item| item.target.is_control(handle));
             match index {
                 Some(i) => {
                     inner.children.remove(i);
@@ -181,8 +289,7 @@ impl DynLayout {
             inner.base
         };
 
-        let (w, h) = unsafe { wh::get_window_size(base) };
-        self.update_layout(w as u32, h as u32);
+        self.refresh(base);
     }
 
     /**
@@ -202,7 +309,96 @@ impl DynLayout {
             .into()
             .hwnd()
             .expect("Children is not a window-like control (HWND handle)");
-        inner.children.iter().any(|c| c.control == handle)
+        inner.children.iter().any(|c| c.target.is_control(handle))
+    }
+
+    /**
+        Change the anchors of a children control. `anchors` can be a `DynAnchors` or a `DynPin`.
+        The control keeps its current geometry and follows the new anchors from there.
+
+        This method won't do anything if the control is not in the layout.
+
+        Panic:
+        - If the layout is not initialized
+        - If the control is not window-like (HWND handle)
+    */
+    pub fn update_child<W: Into<ControlHandle>, A: Into<DynAnchors>>(&self, c: W, anchors: A) {
+        let anchors = anchors.into();
+        self.update_child_item(c, |item| item.anchors = anchors);
+    }
+
+    /**
+        Change the minimum and the maximum size of a children control.
+
+        This method won't do anything if the control is not in the layout.
+
+        Panic:
+        - If the layout is not initialized
+        - If the control is not window-like (HWND handle)
+    */
+    pub fn update_child_limits<W: Into<ControlHandle>>(
+        &self,
+        c: W,
+        min_size: (u32, u32),
+        max_size: (u32, u32),
+    ) {
+        self.update_child_item(c, |item| {
+            item.min_size = min_size;
+            item.max_size = max_size;
+        });
+    }
+
+    /**
+        Compute the initial geometry of every children control again from its current geometry.
+        Call this after the application moved or resized the controls of the layout, otherwise the next
+        update of the layout puts them back where they were.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn recapture(&self) {
+        let mut inner = self.inner.borrow_mut();
+        if inner.base.is_null() {
+            panic!("DynLayout is not initialized");
+        }
+
+        let area = inner.parent_area();
+        for item in inner.children.iter_mut() {
+            item.capture(area);
+        }
+    }
+
+    fn update_child_item<W: Into<ControlHandle>, F: FnOnce(&mut DynLayoutItem)>(&self, c: W, f: F) {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("DynLayout is not initialized");
+            }
+
+            let handle = c
+                .into()
+                .hwnd()
+                .expect("Control must be window-like (HWND handle)");
+
+            let area = inner.parent_area();
+            match inner
+                .children
+                .iter_mut()
+                .find(|item| item.target.is_control(handle))
+            {
+                Some(item) => {
+                    f(item);
+                    item.capture(area);
+                }
+                None => {
+                    return;
+                }
+            }
+
+            inner.base
+        };
+
+        self.refresh(base);
     }
 
     /// Resize the layout as if the parent window had the specified size.
@@ -221,21 +417,68 @@ impl DynLayout {
         self.update_layout(w, h);
     }
 
-    /// Resize the layout to fit the parent window size
+    /// Resize the layout to fit the parent window size.
+    /// If the layout is nested in another layout, it is resized to fit the area given by the parent layout.
     ///
     /// Panic:
     ///   - The layout must have been successfully built otherwise this function will panic.
     pub fn fit(&self) {
-        let inner = self.inner.borrow();
-        if inner.base.is_null() {
+        let base = self.inner.borrow().base;
+        if base.is_null() {
             panic!("Layout is not bound to a parent control.")
         }
 
-        let (w, h) = unsafe { wh::get_window_size(inner.base) };
-        self.update_layout(w, h);
+        self.refresh(base);
+    }
+
+    /**
+        Add a nested layout to the layout. The layout should be built with `build_partial`.
+        `pos` and `size` are the area of the nested layout when the parent has its current size.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn add_child_layout<L: Layout + Clone + 'static>(
+        &self,
+        m: (i32, i32),
+        s: (i32, i32),
+        pos: (i32, i32),
+        size: (i32, i32),
+        layout: &L,
+    ) {
+        self.add_child_item(DynLayoutItem::with_layout(layout, pos, size, m, s));
+    }
+
+    /// Returns the size of the smallest area that contains the children of the layout at their initial position
+    pub fn preferred_size(&self) -> (u32, u32) {
+        dyn_preferred_size(&self.inner.borrow().items())
+    }
+
+    /// Update the layout after its children were changed
+    fn refresh(&self, base: HWND) {
+        let area = self.inner.borrow().area;
+        match area {
+            Some((position, (w, h), after)) => {
+                self.update_layout_at(position, w, h, after);
+            }
+            None => {
+                let (w, h) = unsafe { wh::get_window_size(base) };
+                self.update_layout(w, h);
+            }
+        }
     }
 
     fn update_layout(&self, width: u32, height: u32) -> () {
+        self.update_layout_at((0, 0), width, height, None);
+    }
+
+    fn update_layout_at(
+        &self,
+        (x, y): (i32, i32),
+        width: u32,
+        height: u32,
+        after: Option<HWND>,
+    ) -> Option<HWND> {
         use winapi::ctypes::c_int;
         use winapi::um::winuser::{BeginDeferWindowPos, DeferWindowPos, EndDeferWindowPos};
         use winapi::um::winuser::{
@@ -244,54 +487,108 @@ impl DynLayout {
 
         let inner = self.inner.borrow();
         if inner.base.is_null() || inner.children.len() == 0 {
-            return;
+            return after;
         }
 
-        let xdelta = 0.01 * width as f32;
-        let ydelta = 0.01 * height as f32;
+        let area = LayoutRect::new(x, y, width, height);
+        let rects = solve_dyn(&inner.items(), area);
 
         unsafe {
             let hdwp = BeginDeferWindowPos(inner.children.len() as c_int);
 
-            let mut last_handle = None;
-            for item in inner.children.iter() {
-                let mut x = item.pos_init.0;
-                if item.mv.0 > 0 {
-                    x += (xdelta * item.mv.0 as f32) as i32;
+            let mut last_handle = after;
+            for (index, rect) in rects {
+                let LayoutRect {
+                    x,
+                    y,
+                    width,
+                    height,
+                } = rect;
+
+                match &inner.children[index].target {
+                    &LayoutTarget::Control(control) => {
+                        DeferWindowPos(
+                            hdwp,
+                            control,
+                            HWND_TOP,
+                            x,
+                            y,
+                            width as c_int,
+                            height as c_int,
+                            SWP_NOZORDER | SWP_NOREPOSITION | SWP_NOACTIVATE | SWP_NOCOPYBITS,
+                        );
+
+                        wh::set_window_after(control, last_handle);
+                        last_handle = Some(control);
+                    }
+                    LayoutTarget::Layout(layout) => {
+                        last_handle = layout.resize_at((x, y), (width, height), last_handle);
+                    }
                 }
+            }
 
-                let mut y = item.pos_init.1;
-                if item.mv.1 > 0 {
-                    y += (ydelta * item.mv.1 as f32) as i32;
-                }
+            EndDeferWindowPos(hdwp);
 
-                let mut w = item.size_init.0;
-                if item.sz.0 > 0 {
-                    w += (xdelta * item.sz.0 as f32) as i32;
-                }
+            last_handle
+        }
+    }
+}
 
-                let mut h = item.size_init.1;
-                if item.sz.1 > 0 {
-                    h += (ydelta * item.sz.1 as f32) as i32;
-                }
+impl DynLayoutInner {
+    /// The children of the layout as solver items. The id of an item is the index of the child.
+    fn items(&self) -> Vec<DynItem<usize>> {
+        self.children
+            .iter()
+            .enumerate()
+            .map(|(index, item)| DynItem {
+                id: index,
+                position: item.pos_init,
+                size: item.size_init,
+                anchors: item.anchors,
+                min_size: item.min_size,
+                max_size: item.max_size,
+            })
+            .collect()
+    }
 
-                DeferWindowPos(
-                    hdwp,
-                    item.control,
-                    HWND_TOP,
-                    x,
-                    y,
-                    w,
-                    h,
-                    SWP_NOZORDER | SWP_NOREPOSITION | SWP_NOACTIVATE | SWP_NOCOPYBITS,
-                );
+    /// The area of the parent window used by the layout
+    fn parent_area(&self) -> ((i32, i32), (u32, u32)) {
+        match self.area {
+            Some((position, size, _)) => (position, size),
+            None => ((0, 0), unsafe { wh::get_window_size(self.base) }),
+        }
+    }
+}
 
-                wh::set_window_after(item.control, last_handle);
-                last_handle = Some(item.control);
-            }
+impl Layout for DynLayout {
+    fn fit(&self) {
+        DynLayout::fit(self);
+    }
 
-            EndDeferWindowPos(hdwp);
-        }
+    fn resize(&self, w: u32, h: u32) {
+        DynLayout::resize(self, w, h);
+    }
+
+    fn resize_at(
+        &self,
+        position: (i32, i32),
+        size: (u32, u32),
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        self.inner.borrow_mut().area = Some((position, size, after));
+        self.update_layout_at(position, size.0, size.1, after)
+    }
+
+    fn preferred_size(&self) -> (u32, u32) {
+        DynLayout::preferred_size(self)
+    }
+}
+
+impl DeriveLayout for DynLayout {
+    type Item = DynLayoutItem;
+
+    fn layout_item<C: Into<ControlHandle>>(control: C) -> DynLayoutItem {
+        DynLayoutItem::anchored(control, DynAnchors::default())
     }
 }
 
@@ -300,6 +597,7 @@ impl Default for DynLayout {
         let inner = DynLayoutInner {
             base: ptr::null_mut(),
             children: Vec::new(),
+            area: None,
         };
 
         DynLayout {
@@ -329,17 +627,10 @@ impl DynLayoutBuilder {
         s: (i32, i32),
         c: W,
     ) -> DynLayoutBuilder {
-        let hwnd = c.into().hwnd().expect("Child must be HWND");
-        let pos = unsafe { wh::get_window_position(hwnd) };
-        let size = unsafe { wh::get_window_size(hwnd) };
-
-        self.layout.children.push(DynLayoutItem {
-            control: hwnd,
-            pos_init: pos,
-            size_init: (size.0 as i32, size.1 as i32),
-            mv: m,
-            sz: s,
-        });
+        // The builder keeps the geometry of the control as the geometry of the item for a parent of size 0
+        let mut item = DynLayoutItem::new(c, m, s);
+        item.capture(((0, 0), (0, 0)));
+        self.layout.children.push(item);
 
         self
     }
@@ -351,18 +642,34 @@ impl DynLayoutBuilder {
         self
     }
 
-    /// Build the layout object and bind the callback.
-    /// Children must only contains window object otherwise this method will panic.
-    pub fn build(self, layout: &DynLayout) -> Result<(), NwgError> {
-        use winapi::shared::minwindef::{HIWORD, LOWORD};
-        use winapi::um::winuser::WM_SIZE;
+    /// Add a nested layout to the layout. The nested layout should be built with `build_partial`.
+    /// `pos` and `size` are the area of the nested layout when the parent has its initial size.
+    pub fn child_layout<L: Layout + Clone + 'static>(
+        mut self,
+        m: (i32, i32),
+        s: (i32, i32),
+        pos: (i32, i32),
+        size: (i32, i32),
+        layout: &L,
+    ) -> DynLayoutBuilder {
+        self.layout
+            .children
+            .push(DynLayoutItem::with_layout(layout, pos, size, m, s));
+        self
+    }
 
+    /// Build the layout without binding it to the parent window resize event and without an initial layout update.
+    /// Use this to build a layout that is nested in another layout.
+    /// The initial position of the children is relative to the area given by the parent layout.
+    pub fn build_partial(mut self, layout: &DynLayout) -> Result<(), NwgError> {
         if self.layout.base.is_null() {
             return Err(NwgError::layout_create("DynLayout does not have a parent."));
         }
 
-        let (w, h) = unsafe { wh::get_window_size(self.layout.base) };
-        let base_handle = ControlHandle::Hwnd(self.layout.base);
+        let area = self.layout.parent_area();
+        for item in self.layout.children.iter_mut().filter(|i| !i.captured) {
+            item.capture(area);
+        }
 
         // Saves the new layout. TODO: should free the old one too (if any)
         {
@@ -370,6 +677,21 @@ impl DynLayoutBuilder {
             *layout_inner = self.layout;
         }
 
+        Ok(())
+    }
+
+    /// Build the layout object and bind the callback.
+    /// Children must only contains window object otherwise this method will panic.
+    pub fn build(self, layout: &DynLayout) -> Result<(), NwgError> {
+        use winapi::shared::minwindef::{HIWORD, LOWORD};
+        use winapi::um::winuser::WM_SIZE;
+
+        let base = self.layout.base;
+        self.build_partial(layout)?;
+
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        let base_handle = ControlHandle::Hwnd(base);
+
         // Initial layout update
         layout.update_layout(w, h);
 
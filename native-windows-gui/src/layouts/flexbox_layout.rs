@@ -1,4 +1,5 @@
-use crate::controls::ControlHandle;
+use super::Layout;
+use crate::controls::{control_preferred_size, ControlHandle};
 use crate::win32::window::{
     bind_raw_event_handler_inner, unbind_raw_event_handler, RawEventHandler,
 };
@@ -11,9 +12,11 @@ use std::{
 };
 use winapi::shared::windef::HWND;
 
+use native_windows_layout::{
+    changed_placements, flexbox_preferred_size, FlexNode, FlexTree, LayoutRect,
+};
 use stretch::{
-    geometry::{Point, Rect, Size},
-    node::{Node, Stretch},
+    geometry::{Rect, Size},
     number::Number,
     style::*,
 };
@@ -49,6 +52,19 @@ struct FlexboxLayoutInner {
     style: Style,
     children: Vec<FlexboxLayoutChild>,
     parent_layout: Option<FlexboxLayout>,
+
+    /// The area of the parent window and the previous control in the tab order given by a `GridLayout` or a `DynLayout` if the layout is nested
+    area: Option<((i32, i32), (u32, u32), Option<HWND>)>,
+
+    /// The stretch nodes of the layout and of its sublayouts, kept between two updates.
+    /// Sublayouts (with a `parent_layout`) are solved in the tree of the root layout and leave their own tree empty.
+    tree: FlexTree<HWND>,
+
+    /// Set when the style or the children of the layout were modified since the last update
+    dirty: bool,
+
+    /// The control before the children of the layout in the tab order during the last update. Unused by sublayouts.
+    after: Option<HWND>,
 }
 
 /**
@@ -70,6 +86,10 @@ impl FlexboxLayout {
             style: Default::default(),
             children: Vec::new(),
             parent_layout: None,
+            area: None,
+            tree: FlexTree::new(),
+            dirty: true,
+            after: None,
         };
 
         FlexboxLayoutBuilder {
@@ -108,6 +128,7 @@ impl FlexboxLayout {
         }
 
         inner.style = style;
+        inner.dirty = true;
     }
 
     /**
@@ -137,6 +158,7 @@ impl FlexboxLayout {
             };
 
             inner.children.push(FlexboxLayoutChild::Item(item));
+            inner.dirty = true;
         }
 
         self.fit()
@@ -168,6 +190,7 @@ impl FlexboxLayout {
         match index {
             Some(i) => {
                 inner.children.remove(i);
+                inner.dirty = true;
             }
             None => {
                 panic!("Control was not found in layout");
@@ -225,150 +248,121 @@ impl FlexboxLayout {
         - The layout must have been successfully built otherwise this function will panic.
     */
     pub fn children_mut(&self) -> FlexboxLayoutChildrenMut {
-        let inner = self.inner.borrow_mut();
+        let mut inner = self.inner.borrow_mut();
         if inner.base.is_null() {
             panic!("Flexbox layout is not yet initialized!");
         }
 
+        inner.dirty = true;
+
         FlexboxLayoutChildrenMut { inner }
     }
 
     /**
-        Resize the layout to fit the parent window size
+        Resize the layout to fit the parent window size.
+        If the layout is nested in another layout, it is resized to fit the area given by the parent layout.
+
+        Unlike the automatic update when the parent is resized, every control is moved even if its position did not change.
+        A layout that is a child of another `FlexboxLayout` does not have its own tree: the root layout is fitted instead,
+        which moves every control of the root layout.
 
         Panic:
         - The layout must have been successfully built otherwise this function will panic.
     */
     pub fn fit(&self) -> Result<(), stretch::Error> {
-        let inner = self.inner.borrow();
-        if inner.base.is_null() {
+        let (base, parent_layout, area) = {
+            // The content of the controls may have changed since the last update.
+            // The tree of the root layout is invalidated by the `fit` of the root layout.
+            let mut inner = self.inner.borrow_mut();
+            inner.dirty = true;
+            if inner.parent_layout.is_none() {
+                inner.tree.invalidate();
+            }
+            (inner.base, inner.parent_layout.clone(), inner.area)
+        };
+
+        if base.is_null() {
             panic!("FlexboxLayout is not bound to a parent control.")
         }
 
-        if let Some(parent_layout) = &inner.parent_layout {
+        if let Some(parent_layout) = parent_layout {
             parent_layout.fit()
+        } else if let Some((position, (w, h), after)) = area {
+            self.update_layout_after(w, h, position, after).map(|_| ())
         } else {
-            let (w, h) = unsafe { wh::get_window_size(inner.base) };
+            let (w, h) = unsafe { wh::get_window_size(base) };
             self.update_layout(w, h, (0, 0))
         }
     }
 
-    // Utility function to compile tree of children nodes for layout purposes
-    // Also returns the total number of children items to allow cleaner deferred positioning
-    fn build_child_nodes(
-        children: &Vec<FlexboxLayoutChild>,
-        stretch: &mut Stretch,
-    ) -> Result<(usize, Vec<Node>), stretch::Error> {
-        let mut nodes = Vec::new();
-        let mut item_count = 0;
+    /// Returns the size computed by stretch for the layout when the parent size is undefined.
+    /// This is the smallest size that respects the style of the layout and of its children.
+    pub fn preferred_size(&self) -> Result<(u32, u32), stretch::Error> {
+        let inner = self.inner.borrow();
+        flexbox_preferred_size(&inner.style, &FlexboxLayout::flex_nodes(&inner.children))
+    }
 
-        for child in children.iter() {
-            match child {
-                FlexboxLayoutChild::Item(child) => {
-                    nodes.push(stretch.new_node(child.style, Vec::new())?);
-                    item_count += 1;
-                }
-                FlexboxLayoutChild::Flexbox(child) => {
-                    let (child_count, child_nodes) =
-                        FlexboxLayout::build_child_nodes(child.children().children(), stretch)?;
-                    nodes.push(stretch.new_node(child.style(), child_nodes)?);
-                    item_count += child_count;
+    // Converts the children of the layout to the nodes of the flexbox solver
+    fn flex_nodes(children: &Vec<FlexboxLayoutChild>) -> Vec<FlexNode<HWND>> {
+        children
+            .iter()
+            .map(|child| match child {
+                FlexboxLayoutChild::Item(item) => match control_preferred_size(item.control) {
+                    Some(content) => FlexNode::Measured(item.control, item.style, content),
+                    None => FlexNode::Item(item.control, item.style),
+                },
+                FlexboxLayoutChild::Flexbox(layout) => {
+                    let inner = layout.inner.borrow();
+                    FlexNode::Layout(inner.style, FlexboxLayout::flex_nodes(&inner.children))
                 }
-            };
-        }
-
-        Ok((item_count, nodes))
+            })
+            .collect()
     }
 
-    // Applies the calculated item positions for this layout
-    // Uses deferred window positioning to prevent rendering artefacts
-    fn apply_layout_deferred(
-        positioner: &mut wh::DeferredWindowPositioner,
-        stretch: &mut Stretch,
-        nodes: Vec<Node>,
-        children: &Vec<FlexboxLayoutChild>,
-        last_handle: &mut Option<HWND>,
-        offset: (i32, i32),
-    ) -> Result<(), stretch::Error> {
-        use FlexboxLayoutChild as Child;
-
-        for (node, child) in nodes.into_iter().zip(children.iter()) {
-            let layout = stretch.layout(node)?;
-            let Point { x, y } = layout.location;
-            let Size { width, height } = layout.size;
+    // Clears the dirty flag of the layout and of its sublayouts. Returns `true` if any of them was dirty.
+    fn take_dirty(inner: &mut FlexboxLayoutInner) -> bool {
+        let mut dirty = inner.dirty;
+        inner.dirty = false;
 
-            match child {
-                Child::Item(child) => {
-                    positioner
-                        .defer_pos(
-                            child.control,
-                            last_handle.unwrap_or(std::ptr::null_mut()),
-                            x as i32 + offset.0,
-                            y as i32 + offset.1,
-                            width as i32,
-                            height as i32,
-                        )
-                        .ok();
-                    last_handle.replace(child.control);
-                }
-                Child::Flexbox(child) => {
-                    let children_nodes = stretch.children(node)?;
-                    FlexboxLayout::apply_layout_deferred(
-                        positioner,
-                        stretch,
-                        children_nodes,
-                        child.children().children(),
-                        last_handle,
-                        (x as i32, y as i32),
-                    )?;
-                }
+        for child in inner.children.iter() {
+            if let FlexboxLayoutChild::Flexbox(layout) = child {
+                dirty |= FlexboxLayout::take_dirty(&mut layout.inner.borrow_mut());
             }
         }
 
-        Ok(())
+        dirty
     }
 
-    // Applies the calculated item positions for this layout
+    // Applies the calculated item positions for this layout. The items that did not move are not in `placements`.
+    // Uses deferred window positioning to prevent rendering artefacts
+    fn apply_layout_deferred(
+        positioner: &mut wh::DeferredWindowPositioner,
+        placements: &[(HWND, LayoutRect, Option<HWND>)],
+    ) {
+        for &(id, rect, after) in placements.iter() {
+            positioner
+                .defer_pos(
+                    id,
+                    after.unwrap_or(std::ptr::null_mut()),
+                    rect.x,
+                    rect.y,
+                    rect.width as i32,
+                    rect.height as i32,
+                )
+                .ok();
+        }
+    }
+
+    // Applies the calculated item positions for this layout. The items that did not move are not in `placements`.
     // Uses immediate window positioning, which might cause visual artefacts in some cases
-    fn apply_layout_immediate(
-        stretch: &mut Stretch,
-        nodes: Vec<Node>,
-        children: &Vec<FlexboxLayoutChild>,
-        last_handle: &mut Option<HWND>,
-        offset: (i32, i32),
-    ) -> Result<(), stretch::Error> {
-        use FlexboxLayoutChild as Child;
-
-        for (node, child) in nodes.into_iter().zip(children.iter()) {
-            let layout = stretch.layout(node)?;
-            let Point { x, y } = layout.location;
-            let Size { width, height } = layout.size;
-
-            match child {
-                Child::Item(child) => unsafe {
-                    wh::set_window_position(
-                        child.control,
-                        x as i32 + offset.0,
-                        y as i32 + offset.1,
-                    );
-                    wh::set_window_size(child.control, width as u32, height as u32, false);
-                    wh::set_window_after(child.control, *last_handle);
-                    last_handle.replace(child.control);
-                },
-                Child::Flexbox(child) => {
-                    let children_nodes = stretch.children(node)?;
-                    FlexboxLayout::apply_layout_immediate(
-                        stretch,
-                        children_nodes,
-                        child.children().children(),
-                        last_handle,
-                        (x as i32, y as i32),
-                    )?;
-                }
+    fn apply_layout_immediate(placements: &[(HWND, LayoutRect, Option<HWND>)]) {
+        for &(id, rect, after) in placements.iter() {
+            unsafe {
+                wh::set_window_position(id, rect.x, rect.y);
+                wh::set_window_size(id, rect.width, rect.height, false);
+                wh::set_window_after(id, after);
             }
         }
-
-        Ok(())
     }
 
     fn update_layout(
@@ -377,45 +371,81 @@ impl FlexboxLayout {
         height: u32,
         offset: (i32, i32),
     ) -> Result<(), stretch::Error> {
-        let inner = self.inner.borrow();
+        self.update_layout_after(width, height, offset, None)
+            .map(|_| ())
+    }
+
+    /// Update the layout. `after` is the control before the children of the layout in the tab order.
+    /// Returns the last control of the layout in the tab order.
+    fn update_layout_after(
+        &self,
+        width: u32,
+        height: u32,
+        offset: (i32, i32),
+        after: Option<HWND>,
+    ) -> Result<Option<HWND>, stretch::Error> {
+        let mut inner = self.inner.borrow_mut();
         if inner.base.is_null() || inner.children.len() == 0 {
-            return Ok(());
+            return Ok(after);
         }
 
-        let mut stretch = Stretch::new();
-        let (item_count, nodes) = FlexboxLayout::build_child_nodes(&inner.children, &mut stretch)?;
+        // Only the styles that changed since the last update are sent to stretch
+        if FlexboxLayout::take_dirty(&mut inner) {
+            let nodes = FlexboxLayout::flex_nodes(&inner.children);
+            let style = inner.style;
+            inner.tree.update(&style, &nodes)?;
+        }
 
-        let mut style = inner.style.clone();
-        style.size = Size {
-            width: Dimension::Points(width as f32),
-            height: Dimension::Points(height as f32),
-        };
-        let node = stretch.new_node(style, nodes.clone())?;
+        // The tab order of every control must be updated if the layout moved in the tab order
+        if inner.after != after {
+            inner.after = after;
+            inner.tree.invalidate();
+        }
 
-        stretch.compute_layout(node, Size::undefined())?;
+        let area = LayoutRect::new(offset.0, offset.1, width, height);
+        let rects = inner.tree.solve(area)?;
+
+        let last_handle = rects.last().map(|r| r.id).or(after);
+        let placements = changed_placements(&rects, after);
+        if placements.is_empty() {
+            return Ok(last_handle);
+        }
 
         // Keep a fallback case to prevent panics if the layout is too large to be deferred
-        if let Ok(mut positioner) = wh::DeferredWindowPositioner::new(item_count as i32) {
-            let layout_result = FlexboxLayout::apply_layout_deferred(
-                &mut positioner,
-                &mut stretch,
-                nodes,
-                self.children().children(),
-                &mut None,
-                offset,
-            );
+        if let Ok(mut positioner) = wh::DeferredWindowPositioner::new(placements.len() as i32) {
+            FlexboxLayout::apply_layout_deferred(&mut positioner, &placements);
             positioner.end();
-
-            layout_result
         } else {
-            FlexboxLayout::apply_layout_immediate(
-                &mut stretch,
-                nodes,
-                self.children().children(),
-                &mut None,
-                offset,
-            )
+            FlexboxLayout::apply_layout_immediate(&placements);
         }
+
+        Ok(last_handle)
+    }
+}
+
+impl Layout for FlexboxLayout {
+    fn fit(&self) {
+        FlexboxLayout::fit(self).expect("Failed to compute layout");
+    }
+
+    fn resize(&self, w: u32, h: u32) {
+        self.update_layout(w, h, (0, 0))
+            .expect("Failed to compute layout");
+    }
+
+    fn resize_at(
+        &self,
+        position: (i32, i32),
+        size: (u32, u32),
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        self.inner.borrow_mut().area = Some((position, size, after));
+        self.update_layout_after(size.0, size.1, position, after)
+            .expect("Failed to compute layout")
+    }
+
+    fn preferred_size(&self) -> (u32, u32) {
+        FlexboxLayout::preferred_size(self).expect("Failed to compute layout")
     }
 }
 
@@ -804,6 +834,10 @@ impl Default for FlexboxLayout {
             children: Vec::new(),
             style: Default::default(),
             parent_layout: None,
+            area: None,
+            tree: FlexTree::new(),
+            dirty: true,
+            after: None,
         };
 
         FlexboxLayout {
@@ -1,17 +1,31 @@
+use super::{Layout, LayoutTarget};
 use crate::controls::ControlHandle;
 use crate::win32::window::bind_raw_event_handler_inner;
 use crate::win32::window_helper as wh;
 use crate::NwgError;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ptr;
 use std::rc::Rc;
 use winapi::shared::windef::HWND;
 
-/// A control item in a GridLayout
+use native_windows_layout::{Grid, GridCell, LayoutRect};
+pub use native_windows_layout::{GridAlign, GridTrack, GridTrackSize};
+
+/// A control or a nested layout in a GridLayout
 #[derive(Debug)]
 pub struct GridLayoutItem {
-    /// The handle to the control in the item
-    control: HWND,
+    /// The control or the layout in the item
+    target: LayoutTarget,
+
+    /// The size used by `Auto` tracks and aligned items. If this is `None`, the size is measured from
+    /// the content of the control (see `PreferredSize`) or from the children of a nested layout.
+    preferred_size: Option<[u32; 2]>,
+
+    /// The size of the control when it was added to the layout. Used when the content cannot be measured.
+    initial_size: [u32; 2],
+
+    /// The size measured from the content of the control during the last update. Cleared by `GridLayout::fit`.
+    measured_size: Cell<Option<Option<(u32, u32)>>>,
 
     /// The column position of the control in the layout
     pub col: u32,
@@ -24,6 +38,12 @@ pub struct GridLayoutItem {
 
     /// The number row this item should span. Should be 1 for single row item.
     pub row_span: u32,
+
+    /// The horizontal alignment of the control in its cell
+    pub h_align: GridAlign,
+
+    /// The vertical alignment of the control in its cell
+    pub v_align: GridAlign,
 }
 
 impl GridLayoutItem {
@@ -40,12 +60,87 @@ impl GridLayoutItem {
             .hwnd()
             .expect("Child must be a window-like control (HWND handle)");
 
+        let (w, h) = unsafe { wh::get_window_size(control) };
+
+        GridLayoutItem {
+            target: LayoutTarget::Control(control),
+            preferred_size: None,
+            initial_size: [w, h],
+            measured_size: Cell::new(None),
+            col,
+            row,
+            col_span,
+            row_span,
+            h_align: GridAlign::Fill,
+            v_align: GridAlign::Fill,
+        }
+    }
+
+    /// Initialize a new grid layout item holding a nested layout. The layout should be built with `build_partial`.
+    pub fn with_layout<L: Layout + Clone + 'static>(
+        layout: &L,
+        col: u32,
+        row: u32,
+        col_span: u32,
+        row_span: u32,
+    ) -> GridLayoutItem {
         GridLayoutItem {
-            control,
+            target: LayoutTarget::Layout(Box::new(layout.clone())),
+            preferred_size: None,
+            initial_size: [0, 0],
+            measured_size: Cell::new(None),
             col,
             row,
             col_span,
             row_span,
+            h_align: GridAlign::Fill,
+            v_align: GridAlign::Fill,
+        }
+    }
+
+    /// Sets the horizontal alignment of the control in its cell
+    pub fn h_align(mut self, align: GridAlign) -> GridLayoutItem {
+        self.h_align = align;
+        self
+    }
+
+    /// Sets the vertical alignment of the control in its cell
+    pub fn v_align(mut self, align: GridAlign) -> GridLayoutItem {
+        self.v_align = align;
+        self
+    }
+
+    /**
+        Sets the preferred size of the control. By default, the size is measured from the content of the control.
+        Controls that cannot be measured use their size when the item was created.
+    */
+    pub fn preferred_size(mut self, size: [u32; 2]) -> GridLayoutItem {
+        self.preferred_size = Some(size);
+        self
+    }
+
+    fn preferred(&self) -> [u32; 2] {
+        if let Some(size) = self.preferred_size {
+            return size;
+        }
+
+        // Measuring a control sends it messages, so the size is only measured again after `fit`.
+        // Nested layouts are not cached: they measure their own children.
+        let measured = match self.target {
+            LayoutTarget::Control(_) => match self.measured_size.get() {
+                Some(size) => size,
+                None => {
+                    let size = self.target.preferred_size();
+                    self.measured_size.set(Some(size));
+                    size
+                }
+            },
+            LayoutTarget::Layout(_) => self.target.preferred_size(),
+        };
+
+        match measured {
+            Some((w, h)) => [w, h],
+            None => self.initial_size,
         }
     }
 }
@@ -76,6 +171,15 @@ pub struct GridLayoutInner {
 
     /// The spacing between controls
     spacing: u32,
+
+    /// The definition of the columns. Missing columns share the remaining space equally.
+    columns: Vec<GridTrack>,
+
+    /// The definition of the rows. Missing rows share the remaining space equally.
+    rows: Vec<GridTrack>,
+
+    /// The area of the parent window and the previous control in the tab order given by the parent layout if the layout is nested
+    area: Option<((i32, i32), (u32, u32), Option<HWND>)>,
 }
 
 /**
@@ -89,6 +193,11 @@ A GridLayouts has the following properties:
 * max_size - The maximum size of the layout - (default: [u32::max_value(), u32::max_value()])
 * max_column - Number of columns - (default: None),
 * max_row - Number of rows - (default: None),
+* columns - The size of the columns. See `GridTrack` - (default: every column has the same size)
+* rows - The size of the rows. See `GridTrack` - (default: every row has the same size)
+
+Each item can also be aligned in its cell with `GridLayoutItem::h_align` and `GridLayoutItem::v_align`.
+By default, items fill their cell.
 
 ```rust
     use native_windows_gui as nwg;
@@ -102,6 +211,18 @@ A GridLayouts has the following properties:
             .child_item(nwg::GridLayoutItem::new(item2, 1, 0, 2, 1))
             .build(&layout);
     }
+
+    fn form_layout(layout: &nwg::GridLayout, window: &nwg::Window, label: &nwg::Label, input: &nwg::TextInput) {
+        use nwg::{GridAlign, GridLayoutItem, GridTrack};
+
+        nwg::GridLayout::builder()
+            .parent(window)
+            .columns(vec![GridTrack::fixed(100), GridTrack::weight(1)])
+            .rows(vec![GridTrack::auto()])
+            .child_item(GridLayoutItem::new(label, 0, 0, 1, 1).v_align(GridAlign::Center))
+            .child(1, 0, input)
+            .build(&layout);
+    }
 ```
 */
 #[derive(Clone)]
@@ -120,6 +241,9 @@ impl GridLayout {
             max_size: [u32::max_value(), u32::max_value()],
             column_count: None,
             row_count: None,
+            columns: Vec::new(),
+            rows: Vec::new(),
+            area: None,
         };
 
         GridLayoutBuilder { layout }
@@ -134,18 +258,7 @@ impl GridLayout {
         - If the control is not window-like (HWND handle)
     */
     pub fn add_child<W: Into<ControlHandle>>(&self, col: u32, row: u32, c: W) {
-        let h = c
-            .into()
-            .hwnd()
-            .expect("Child must be a window-like control (HWND handle)");
-        let item = GridLayoutItem {
-            control: h,
-            col,
-            row,
-            col_span: 1,
-            row_span: 1,
-        };
-
+        let item = GridLayoutItem::new(c, col, row, 1, 1);
         self.add_child_item(item);
     }
 
@@ -169,8 +282,7 @@ impl GridLayout {
             inner.base
         };
 
-        let (w, h) = unsafe { wh::get_window_size(base) };
-        self.update_layout(w as u32, h as u32);
+        self.refresh(base);
     }
 
     /**
@@ -196,7 +308,7 @@ impl GridLayout {
             let index = inner
                 .children
                 .iter()
-                .position(|item| item.control == handle);
+                .position(|item| item.target.is_control(handle));
             match index {
                 Some(i) => {
                     inner.children.remove(i);
@@ -209,8 +321,7 @@ impl GridLayout {
             inner.base
         };
 
-        let (w, h) = unsafe { wh::get_window_size(base) };
-        self.update_layout(w as u32, h as u32);
+        self.refresh(base);
     }
 
     /**
@@ -243,8 +354,7 @@ impl GridLayout {
             inner.base
         };
 
-        let (w, h) = unsafe { wh::get_window_size(base) };
-        self.update_layout(w as u32, h as u32);
+        self.refresh(base);
     }
 
     /**
@@ -271,7 +381,7 @@ impl GridLayout {
             let index = inner
                 .children
                 .iter()
-                .position(|item| item.control == handle);
+                .position(|item| item.target.is_control(handle));
             match index {
                 Some(i) => {
                     let mut child = inner.children.remove(i);
@@ -287,8 +397,7 @@ impl GridLayout {
             inner.base
         };
 
-        let (w, h) = unsafe { wh::get_window_size(base) };
-        self.update_layout(w as u32, h as u32);
+        self.refresh(base);
     }
 
     /**
@@ -331,8 +440,7 @@ impl GridLayout {
             inner.base
         };
 
-        let (w, h) = unsafe { wh::get_window_size(base) };
-        self.update_layout(w as u32, h as u32);
+        self.refresh(base);
     }
 
     /**
@@ -352,7 +460,7 @@ impl GridLayout {
             .into()
             .hwnd()
             .expect("Children is not a window-like control (HWND handle)");
-        inner.children.iter().any(|c| c.control == handle)
+        inner.children.iter().any(|c| c.target.is_control(handle))
     }
 
     /// Resize the layout as if the parent window had the specified size.
@@ -371,18 +479,48 @@ impl GridLayout {
         self.update_layout(w, h);
     }
 
-    /// Resize the layout to fit the parent window size
+    /// Resize the layout to fit the parent window size.
+    /// If the layout is nested in another layout, it is resized to fit the area given by the parent layout.
+    ///
+    /// The content of the children is measured again. Call this after changing the text or the font of a child
+    /// in an `Auto` track or with an alignment: resizing the window reuses the sizes measured before.
     ///
     /// Panic:
     ///   - The layout must have been successfully built otherwise this function will panic.
     pub fn fit(&self) {
-        let inner = self.inner.borrow();
-        if inner.base.is_null() {
+        let base = {
+            let inner = self.inner.borrow();
+            for item in inner.children.iter() {
+                item.measured_size.set(None);
+            }
+
+            inner.base
+        };
+
+        if base.is_null() {
             panic!("Grid layout is not bound to a parent control.")
         }
 
-        let (w, h) = unsafe { wh::get_window_size(inner.base) };
-        self.update_layout(w, h);
+        self.refresh(base);
+    }
+
+    /**
+        Add a nested layout to the grid layout. The layout should be built with `build_partial`.
+        This is a simplified interface over `add_child_item`
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn add_child_layout<L: Layout + Clone + 'static>(&self, col: u32, row: u32, layout: &L) {
+        let item = GridLayoutItem::with_layout(layout, col, row, 1, 1);
+        self.add_child_item(item);
+    }
+
+    /// Returns the smallest size that can display the children of the layout: the margins, the spacing,
+    /// the fixed and auto tracks and the minimum size of the weighted tracks.
+    pub fn preferred_size(&self) -> (u32, u32) {
+        let inner = self.inner.borrow();
+        inner.grid().preferred_size(&inner.cells())
     }
 
     /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
@@ -421,113 +559,121 @@ impl GridLayout {
         inner.row_count = count;
     }
 
-    fn update_layout(&self, mut width: u32, mut height: u32) -> () {
-        let inner = self.inner.borrow();
-        if inner.base.is_null() || inner.children.len() == 0 {
-            return;
-        }
-
-        let [m_top, m_right, m_bottom, m_left] = inner.margins;
-        let sp = inner.spacing;
+    /// Set the size of the columns in the layout. Columns without a definition have a weight of 1.
+    pub fn columns(&self, columns: Vec<GridTrack>) {
+        let mut inner = self.inner.borrow_mut();
+        inner.columns = columns;
+    }
 
-        let children = &inner.children;
+    /// Set the size of the rows in the layout. Rows without a definition have a weight of 1.
+    pub fn rows(&self, rows: Vec<GridTrack>) {
+        let mut inner = self.inner.borrow_mut();
+        inner.rows = rows;
+    }
 
-        let [min_w, min_h] = inner.min_size;
-        if width < min_w {
-            width = min_w;
-        }
-        if height < min_h {
-            height = min_h;
+    /// Update the layout after its children were changed
+    fn refresh(&self, base: HWND) {
+        let area = self.inner.borrow().area;
+        match area {
+            Some((position, (w, h), after)) => {
+                self.update_layout_at(position, w, h, after);
+            }
+            None => {
+                let (w, h) = unsafe { wh::get_window_size(base) };
+                self.update_layout(w, h);
+            }
         }
+    }
 
-        let [max_w, max_h] = inner.max_size;
-        if width > max_w {
-            width = max_w;
-        }
-        if height > max_h {
-            height = max_h;
-        }
+    fn update_layout(&self, width: u32, height: u32) -> () {
+        self.update_layout_at((0, 0), width, height, None);
+    }
 
-        let column_count = match inner.column_count {
-            Some(c) => c,
-            None => children
-                .iter()
-                .map(|item| item.col + item.col_span)
-                .max()
-                .unwrap_or(1),
-        };
+    fn update_layout_at(
+        &self,
+        (x, y): (i32, i32),
+        width: u32,
+        height: u32,
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() || inner.children.len() == 0 {
+            return after;
+        }
 
-        let row_count = match inner.row_count {
-            Some(c) => c,
-            None => children
-                .iter()
-                .map(|item| item.row + item.row_span)
-                .max()
-                .unwrap_or(1),
+        let area = LayoutRect::new(x, y, width, height);
+        let rects = match inner.grid().solve(&inner.cells(), area) {
+            Some(rects) => rects,
+            None => return after,
         };
 
-        if width < (m_right + m_left) + ((sp * 2) * column_count) {
-            return;
+        let mut last_handle = after;
+        for (index, rect) in rects {
+            last_handle =
+                inner.children[index]
+                    .target
+                    .place(rect.position(), rect.size(), last_handle);
         }
 
-        if height < (m_top + m_bottom) + ((sp * 2) * row_count) {
-            return;
-        }
-
-        // Apply margins
-        width = width - m_right - m_left;
-        height = height - m_top - m_bottom;
-
-        // Apply spacing
-        width = width - ((sp * 2) * column_count);
-        height = height - ((sp * 2) * row_count);
-
-        let item_width = width / column_count;
-        let item_height = height / row_count;
-        let sp2 = sp * 2;
+        last_handle
+    }
+}
 
-        let mut columns = vec![item_width; column_count as usize];
-        let mut rows = vec![item_height; row_count as usize];
-        let extra_width = width - item_width * column_count;
-        if extra_width > 0 {
-            for x in &mut columns[0..(extra_width as usize)] {
-                *x += 1;
-            }
-        }
-        let extra_height = height - item_height * row_count;
-        if extra_height > 0 {
-            for x in &mut rows[0..(extra_height as usize)] {
-                *x += 1;
-            }
+impl GridLayoutInner {
+    /// The parameters of the grid solver
+    fn grid(&self) -> Grid<'_> {
+        Grid {
+            margins: self.margins,
+            spacing: self.spacing,
+            min_size: self.min_size,
+            max_size: self.max_size,
+            column_count: self.column_count,
+            row_count: self.row_count,
+            columns: &self.columns,
+            rows: &self.rows,
         }
+    }
 
-        let mut last_handle = None;
-        for item in inner.children.iter() {
-            let x: u32 = m_left
-                + (sp + (sp2 * item.col))
-                + columns[0..(item.col as usize)].iter().sum::<u32>();
-            let y: u32 =
-                m_top + (sp + (sp2 * item.row)) + rows[0..(item.row as usize)].iter().sum::<u32>();
+    /// The children of the layout as solver cells. The id of a cell is the index of the child.
+    fn cells(&self) -> Vec<GridCell<usize>> {
+        self.children
+            .iter()
+            .enumerate()
+            .map(|(index, item)| GridCell {
+                id: index,
+                col: item.col,
+                row: item.row,
+                col_span: item.col_span,
+                row_span: item.row_span,
+                preferred: item.preferred(),
+                h_align: item.h_align,
+                v_align: item.v_align,
+            })
+            .collect()
+    }
+}
 
-            let local_width: u32 = &columns
-                [(item.col as usize)..((item.col + item.col_span) as usize)]
-                .iter()
-                .sum::<u32>()
-                + (sp2 * (item.col_span - 1));
-            let local_height: u32 = &rows
-                [(item.row as usize)..((item.row + item.row_span) as usize)]
-                .iter()
-                .sum::<u32>()
-                + (sp2 * (item.row_span - 1));
+impl Layout for GridLayout {
+    fn fit(&self) {
+        GridLayout::fit(self);
+    }
 
-            unsafe {
-                wh::set_window_position(item.control, x as i32, y as i32);
-                wh::set_window_size(item.control, local_width, local_height, false);
-                wh::set_window_after(item.control, last_handle)
-            }
+    fn resize(&self, w: u32, h: u32) {
+        GridLayout::resize(self, w, h);
+    }
 
-            last_handle = Some(item.control);
-        }
+    fn resize_at(
+        &self,
+        position: (i32, i32),
+        size: (u32, u32),
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        self.inner.borrow_mut().area = Some((position, size, after));
+        self.update_layout_at(position, size.0, size.1, after)
+    }
+
+    fn preferred_size(&self) -> (u32, u32) {
+        GridLayout::preferred_size(self)
     }
 }
 
@@ -542,6 +688,9 @@ impl Default for GridLayout {
             column_count: None,
             row_count: None,
             spacing: 5,
+            columns: Vec::new(),
+            rows: Vec::new(),
+            area: None,
         };
 
         GridLayout {
@@ -566,15 +715,9 @@ impl GridLayoutBuilder {
     /// This is a shortcut over `child_item` for item with default span.
     /// The handle must be a window object otherwise the function will panic
     pub fn child<W: Into<ControlHandle>>(mut self, col: u32, row: u32, c: W) -> GridLayoutBuilder {
-        let h = c.into().hwnd().expect("Child must be HWND");
-        self.layout.children.push(GridLayoutItem {
-            control: h,
-            col,
-            row,
-            col_span: 1,
-            row_span: 1,
-        });
-
+        self.layout
+            .children
+            .push(GridLayoutItem::new(c, col, row, 1, 1));
         self
     }
 
@@ -585,6 +728,20 @@ impl GridLayoutBuilder {
         self
     }
 
+    /// Add a nested layout to the layout at the position `col` and `row`.
+    /// The nested layout should be built with `build_partial`.
+    pub fn child_layout<L: Layout + Clone + 'static>(
+        mut self,
+        col: u32,
+        row: u32,
+        layout: &L,
+    ) -> GridLayoutBuilder {
+        self.layout
+            .children
+            .push(GridLayoutItem::with_layout(layout, col, row, 1, 1));
+        self
+    }
+
     /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
     pub fn margin(mut self, m: [u32; 4]) -> GridLayoutBuilder {
         self.layout.margins = m;
@@ -621,12 +778,21 @@ impl GridLayoutBuilder {
         self
     }
 
-    /// Build the layout object and bind the callback.
-    /// Children must only contains window object otherwise this method will panic.
-    pub fn build(self, layout: &GridLayout) -> Result<(), NwgError> {
-        use winapi::shared::minwindef::{HIWORD, LOWORD};
-        use winapi::um::winuser::WM_SIZE;
+    /// Set the size of the columns in the layout. Columns without a definition have a weight of 1.
+    pub fn columns(mut self, columns: Vec<GridTrack>) -> GridLayoutBuilder {
+        self.layout.columns = columns;
+        self
+    }
+
+    /// Set the size of the rows in the layout. Rows without a definition have a weight of 1.
+    pub fn rows(mut self, rows: Vec<GridTrack>) -> GridLayoutBuilder {
+        self.layout.rows = rows;
+        self
+    }
 
+    /// Build the layout without binding it to the parent window resize event and without an initial layout update.
+    /// Use this to build a layout that is nested in another layout.
+    pub fn build_partial(self, layout: &GridLayout) -> Result<(), NwgError> {
         if self.layout.base.is_null() {
             return Err(NwgError::layout_create(
                 "Gridlayout does not have a parent.",
@@ -649,15 +815,27 @@ impl GridLayoutBuilder {
             }
         }
 
-        let (w, h) = unsafe { wh::get_window_size(self.layout.base) };
-        let base_handle = ControlHandle::Hwnd(self.layout.base);
-
         // Saves the new layout. TODO: should free the old one too (if any)
         {
             let mut layout_inner = layout.inner.borrow_mut();
             *layout_inner = self.layout;
         }
 
+        Ok(())
+    }
+
+    /// Build the layout object and bind the callback.
+    /// Children must only contains window object otherwise this method will panic.
+    pub fn build(self, layout: &GridLayout) -> Result<(), NwgError> {
+        use winapi::shared::minwindef::{HIWORD, LOWORD};
+        use winapi::um::winuser::WM_SIZE;
+
+        let base = self.layout.base;
+        self.build_partial(layout)?;
+
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        let base_handle = ControlHandle::Hwnd(base);
+
         // Initial layout update
         layout.update_layout(w, h);
 
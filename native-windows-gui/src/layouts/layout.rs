@@ -0,0 +1,154 @@
+use crate::controls::{control_preferred_size, ControlHandle};
+use crate::win32::window_helper as wh;
+use std::fmt;
+use winapi::shared::windef::HWND;
+
+/**
+    The common interface of the NWG layouts.
+
+    A layout that implements this trait can be nested inside a `GridLayout` or a `DynLayout`.
+    A nested layout must be built with `build_partial` so that it does not resize itself when its parent window is resized.
+    The parent layout gives it an area of the window with `resize_at` instead.
+
+    ```rust
+    use native_windows_gui as nwg;
+    fn nested(outer: &nwg::GridLayout, inner: &nwg::GridLayout, window: &nwg::Window, b1: &nwg::Button, b2: &nwg::Button, b3: &nwg::Button) {
+        nwg::GridLayout::builder()
+            .parent(window)
+            .child(0, 0, b1)
+            .child(0, 1, b2)
+            .build_partial(inner);
+
+        nwg::GridLayout::builder()
+            .parent(window)
+            .child_layout(0, 0, inner)
+            .child(1, 0, b3)
+            .build(outer);
+    }
+    ```
+*/
+pub trait Layout {
+    /// Resize the layout to fit the parent window size. A nested layout is resized to fit the area given by its parent layout.
+    fn fit(&self);
+
+    /// Resize the layout as if the parent window had the specified size
+    fn resize(&self, w: u32, h: u32);
+
+    /**
+        Resize the layout to fit an area of the parent window. This is called by the parent layout of a nested layout.
+
+        `after` is the control that comes before the children of the layout in the tab order.
+        Returns the last control of the layout in the tab order, or `after` if the layout has no children.
+    */
+    fn resize_at(
+        &self,
+        position: (i32, i32),
+        size: (u32, u32),
+        after: Option<HWND>,
+    ) -> Option<HWND>;
+
+    /// The smallest size that can display the children of the layout. Used by the parent layout of a nested layout.
+    fn preferred_size(&self) -> (u32, u32);
+}
+
+/**
+    The protocol used by native-windows-derive to add the controls tagged with `#[nwg_layout_item]` to a layout.
+
+    `#[nwg_layout]` calls `builder()` on the layout type, then the builder methods named by the attribute parameters.
+    Every control of the layout is added with `child_item(<L as DeriveLayout>::layout_item(&control))` and the parameters
+    of `#[nwg_layout_item]` are called as methods on the item. Finally, the layout is created with `build`.
+
+    Implement this trait to declare a custom layout in a `#[derive(NwgUi)]` struct.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    #[derive(Default)]
+    struct ColumnLayout { /* ... */ }
+    struct ColumnLayoutItem { control: nwg::ControlHandle, weight: u32 }
+    struct ColumnLayoutBuilder { /* ... */ }
+
+    impl ColumnLayoutItem {
+        fn weight(mut self, weight: u32) -> ColumnLayoutItem { self.weight = weight; self }
+    }
+
+    impl ColumnLayout {
+        fn builder() -> ColumnLayoutBuilder { ColumnLayoutBuilder { } }
+    }
+
+    impl ColumnLayoutBuilder {
+        fn parent<C: Into<nwg::ControlHandle>>(self, _parent: C) -> ColumnLayoutBuilder { self }
+        fn child_item(self, _item: ColumnLayoutItem) -> ColumnLayoutBuilder { self }
+        fn build(self, _layout: &ColumnLayout) -> Result<(), nwg::NwgError> { Ok(()) }
+    }
+
+    impl nwg::DeriveLayout for ColumnLayout {
+        type Item = ColumnLayoutItem;
+
+        fn layout_item<C: Into<nwg::ControlHandle>>(control: C) -> ColumnLayoutItem {
+            ColumnLayoutItem { control: control.into(), weight: 1 }
+        }
+    }
+    ```
+*/
+pub trait DeriveLayout {
+    /// The type of the layout children passed to `child_item`
+    type Item;
+
+    /// Creates the item of a control with the default parameters
+    fn layout_item<C: Into<ControlHandle>>(control: C) -> Self::Item;
+}
+
+/// What is being placed by a layout item: a control or a nested layout
+pub(crate) enum LayoutTarget {
+    Control(HWND),
+    Layout(Box<dyn Layout>),
+}
+
+impl LayoutTarget {
+    /// Returns `true` if the target is the control `handle`
+    pub(crate) fn is_control(&self, handle: HWND) -> bool {
+        match self {
+            LayoutTarget::Control(h) => *h == handle,
+            LayoutTarget::Layout(_) => false,
+        }
+    }
+
+    /**
+        The size needed to display the content of the target. Nested layouts always have a preferred size.
+        Controls that do not implement `PreferredSize` return `None`.
+    */
+    pub(crate) fn preferred_size(&self) -> Option<(u32, u32)> {
+        match self {
+            &LayoutTarget::Control(handle) => control_preferred_size(handle),
+            LayoutTarget::Layout(l) => Some(l.preferred_size()),
+        }
+    }
+
+    /// Move and resize the target. Returns the last control of the target in the tab order.
+    pub(crate) fn place(
+        &self,
+        position: (i32, i32),
+        size: (u32, u32),
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        match self {
+            &LayoutTarget::Control(handle) => unsafe {
+                wh::set_window_position(handle, position.0, position.1);
+                wh::set_window_size(handle, size.0, size.1, false);
+                wh::set_window_after(handle, after);
+                Some(handle)
+            },
+            LayoutTarget::Layout(layout) => layout.resize_at(position, size, after),
+        }
+    }
+}
+
+impl fmt::Debug for LayoutTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutTarget::Control(handle) => write!(f, "Control({:?})", handle),
+            LayoutTarget::Layout(_) => write!(f, "Layout"),
+        }
+    }
+}
@@ -1,4 +1,7 @@
 mod grid_layout;
+mod layout;
+mod stack_layout;
+mod wrap_layout;
 
 #[cfg(feature = "flexbox")]
 mod flexbox_layout;
@@ -6,7 +9,19 @@ mod flexbox_layout;
 #[cfg(feature = "dynamic_layout")]
 mod dyn_layout;
 
-pub use self::grid_layout::{GridLayout, GridLayoutBuilder, GridLayoutInner, GridLayoutItem};
+pub(crate) use self::layout::LayoutTarget;
+pub use self::layout::{DeriveLayout, Layout};
+
+pub use self::grid_layout::{
+    GridAlign, GridLayout, GridLayoutBuilder, GridLayoutInner, GridLayoutItem, GridTrack,
+    GridTrackSize,
+};
+
+pub use self::stack_layout::{
+    StackAlign, StackDirection, StackLayout, StackLayoutBuilder, StackLayoutInner, StackLayoutItem,
+};
+
+pub use self::wrap_layout::{WrapLayout, WrapLayoutBuilder, WrapLayoutInner, WrapLayoutItem};
 
 #[cfg(feature = "flexbox")]
 pub use self::flexbox_layout::{
@@ -15,4 +30,6 @@ pub use self::flexbox_layout::{
 };
 
 #[cfg(feature = "dynamic_layout")]
-pub use self::dyn_layout::{DynLayout, DynLayoutBuilder, DynLayoutInner, DynLayoutItem};
+pub use self::dyn_layout::{
+    DynAnchors, DynLayout, DynLayoutBuilder, DynLayoutInner, DynLayoutItem, DynPin,
+};
@@ -0,0 +1,608 @@
+use super::{DeriveLayout, Layout, LayoutTarget};
+use crate::controls::ControlHandle;
+use crate::win32::window::bind_raw_event_handler_inner;
+use crate::win32::window_helper as wh;
+use crate::NwgError;
+use std::cell::RefCell;
+use std::ptr;
+use std::rc::Rc;
+use winapi::shared::windef::HWND;
+
+use native_windows_layout::{LayoutRect, Stack, StackItem};
+pub use native_windows_layout::{StackAlign, StackDirection};
+
+/// A control or a nested layout in a StackLayout
+#[derive(Debug)]
+pub struct StackLayoutItem {
+    /// The control or the layout in the item
+    target: LayoutTarget,
+
+    /// The size of the item along and across the stack. If this is `None`, the size is measured from
+    /// the content of the control (see `PreferredSize`) or from the children of a nested layout.
+    preferred_size: Option<[u32; 2]>,
+
+    /// The size of the control when it was added to the layout. Used when the content cannot be measured.
+    initial_size: [u32; 2],
+
+    /// The share of the free space of the layout given to the item. An item with a stretch of 0 keeps its preferred size.
+    pub stretch: u32,
+
+    /// The alignment of the item across the stack. If `None`, the alignment of the layout is used.
+    pub align: Option<StackAlign>,
+}
+
+impl StackLayoutItem {
+    /// Initialize a new stack layout item
+    pub fn new<W: Into<ControlHandle>>(c: W) -> StackLayoutItem {
+        let control = c
+            .into()
+            .hwnd()
+            .expect("Child must be a window-like control (HWND handle)");
+
+        let (w, h) = unsafe { wh::get_window_size(control) };
+
+        StackLayoutItem {
+            target: LayoutTarget::Control(control),
+            preferred_size: None,
+            initial_size: [w, h],
+            stretch: 0,
+            align: None,
+        }
+    }
+
+    /// Initialize a new stack layout item holding a nested layout. The layout should be built with `build_partial`.
+    pub fn with_layout<L: Layout + Clone + 'static>(layout: &L) -> StackLayoutItem {
+        StackLayoutItem {
+            target: LayoutTarget::Layout(Box::new(layout.clone())),
+            preferred_size: None,
+            initial_size: [0, 0],
+            stretch: 0,
+            align: None,
+        }
+    }
+
+    /// Sets the share of the free space of the layout given to the item
+    pub fn stretch(mut self, stretch: u32) -> StackLayoutItem {
+        self.stretch = stretch;
+        self
+    }
+
+    /// Sets the alignment of the item across the stack
+    pub fn align(mut self, align: StackAlign) -> StackLayoutItem {
+        self.align = Some(align);
+        self
+    }
+
+    /**
+        Sets the preferred size of the control. By default, the size is measured from the content of the control.
+        Controls that cannot be measured use their size when the item was created.
+    */
+    pub fn preferred_size(mut self, size: [u32; 2]) -> StackLayoutItem {
+        self.preferred_size = Some(size);
+        self
+    }
+
+    fn preferred(&self) -> [u32; 2] {
+        if let Some(size) = self.preferred_size {
+            return size;
+        }
+
+        match self.target.preferred_size() {
+            Some((w, h)) => [w, h],
+            None => self.initial_size,
+        }
+    }
+}
+
+/// A layout that stacks widgets in a single row or column
+/// This is the inner data shared between the callback and the application
+pub struct StackLayoutInner {
+    /// The control that holds the layout
+    base: HWND,
+
+    /// The children of the control that fit in the layout
+    children: Vec<StackLayoutItem>,
+
+    /// The direction of the stack
+    direction: StackDirection,
+
+    /// The top, right, bottom, left space around the layout
+    margins: [u32; 4],
+
+    /// The spacing between controls
+    spacing: u32,
+
+    /// The alignment of the children across the stack
+    align: StackAlign,
+
+    /// The placement of the children along the stack when no child stretches
+    justify: StackAlign,
+
+    /// The area of the parent window and the previous control in the tab order given by the parent layout if the layout is nested
+    area: Option<((i32, i32), (u32, u32), Option<HWND>)>,
+}
+
+/**
+A layout that places widgets in a single column (`StackDirection::Vertical`) or a single row (`StackDirection::Horizontal`).
+Unlike `FlexboxLayout`, it does not require the `flexbox` feature.
+NWG layouts use interior mutability to manage their controls.
+
+Along the stack, every child gets its preferred size (see `PreferredSize`) and the remaining space is shared
+between the children with a stretch factor. If no child stretches, `justify` places the children in the remaining space.
+
+A StackLayout has the following properties:
+* direction - The direction of the stack - (default: StackDirection::Vertical)
+* margin - The top, right, bottom, left margins of the layout - (default: [5, 5, 5, 5])
+* spacing - The spacing between children controls - (default: 5)
+* align - The alignment of the children across the stack - (default: StackAlign::Fill)
+* justify - The placement of the children along the stack - (default: StackAlign::Start)
+
+`StackLayout::vertical` and `StackLayout::horizontal` are shortcuts for the builder with a direction.
+
+```rust
+    use native_windows_gui as nwg;
+    fn layout(layout: &nwg::StackLayout, window: &nwg::Window, text: &nwg::TextInput, ok: &nwg::Button) {
+        use nwg::{StackAlign, StackLayoutItem};
+
+        nwg::StackLayout::vertical()
+            .parent(window)
+            .spacing(10)
+            .child_item(StackLayoutItem::new(text).stretch(1))
+            .child_item(StackLayoutItem::new(ok).align(StackAlign::End))
+            .build(&layout);
+    }
+```
+*/
+#[derive(Clone)]
+pub struct StackLayout {
+    inner: Rc<RefCell<StackLayoutInner>>,
+}
+
+impl StackLayout {
+    pub fn builder() -> StackLayoutBuilder {
+        let layout = StackLayoutInner {
+            base: ptr::null_mut(),
+            children: Vec::new(),
+            direction: StackDirection::Vertical,
+            margins: [5, 5, 5, 5],
+            spacing: 5,
+            align: StackAlign::Fill,
+            justify: StackAlign::Start,
+            area: None,
+        };
+
+        StackLayoutBuilder { layout }
+    }
+
+    /// A builder for a layout that places its children from the top to the bottom
+    pub fn vertical() -> StackLayoutBuilder {
+        StackLayout::builder().direction(StackDirection::Vertical)
+    }
+
+    /// A builder for a layout that places its children from the left to the right
+    pub fn horizontal() -> StackLayoutBuilder {
+        StackLayout::builder().direction(StackDirection::Horizontal)
+    }
+
+    /**
+        Add a children control at the end of the stack.
+        This is a simplified interface over `add_child_item`
+
+        Panic:
+        - If the layout is not initialized
+        - If the control is not window-like (HWND handle)
+    */
+    pub fn add_child<W: Into<ControlHandle>>(&self, c: W) {
+        self.add_child_item(StackLayoutItem::new(c));
+    }
+
+    /**
+        Add a nested layout at the end of the stack. The layout should be built with `build_partial`.
+        This is a simplified interface over `add_child_item`
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn add_child_layout<L: Layout + Clone + 'static>(&self, layout: &L) {
+        self.add_child_item(StackLayoutItem::with_layout(layout));
+    }
+
+    /**
+        Add a children control at the end of the stack.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn add_child_item(&self, i: StackLayoutItem) {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("StackLayout is not initialized");
+            }
+
+            inner.children.push(i);
+            inner.base
+        };
+
+        self.refresh(base);
+    }
+
+    /**
+        Insert a children control in the stack at `index`. The children after `index` are moved down or right.
+
+        Panic:
+        - If the layout is not initialized
+        - If `index` is bigger than the number of children
+    */
+    pub fn insert_child_item(&self, index: usize, i: StackLayoutItem) {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("StackLayout is not initialized");
+            }
+
+            inner.children.insert(index, i);
+            inner.base
+        };
+
+        self.refresh(base);
+    }
+
+    /**
+        Remove the children control in the layout.
+        Note that the child control won't be hidden after being removed from the control.
+
+        This method won't do anything if the control is not in the layout.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn remove_child<W: Into<ControlHandle>>(&self, c: W) {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("StackLayout is not initialized");
+            }
+
+            let handle = c
+                .into()
+                .hwnd()
+                .expect("Control must be window-like (HWND handle)");
+            let index = inner
+                .children
+                .iter()
+                .position(|item| item.target.is_control(handle));
+            match index {
+                Some(i) => {
+                    inner.children.remove(i);
+                }
+                None => {
+                    return;
+                }
+            }
+
+            inner.base
+        };
+
+        self.refresh(base);
+    }
+
+    /**
+        Check if a window control is a children of the layout
+
+        Panic:
+        - If the layout is not initialized
+        - If the child is not a window-like control
+    */
+    pub fn has_child<W: Into<ControlHandle>>(&self, c: W) -> bool {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() {
+            panic!("StackLayout is not initialized");
+        }
+
+        let handle = c
+            .into()
+            .hwnd()
+            .expect("Children is not a window-like control (HWND handle)");
+        inner.children.iter().any(|c| c.target.is_control(handle))
+    }
+
+    /// Resize the layout as if the parent window had the specified size.
+    ///
+    /// Arguments:
+    ///   w: New width of the layout
+    ///   h: New height of the layout
+    ///
+    ///  Panic:
+    ///   - The layout must have been successfully built otherwise this function will panic.
+    pub fn resize(&self, w: u32, h: u32) {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() {
+            panic!("Stack layout is not bound to a parent control.")
+        }
+        self.update_layout(w, h);
+    }
+
+    /// Resize the layout to fit the parent window size.
+    /// If the layout is nested in another layout, it is resized to fit the area given by the parent layout.
+    ///
+    /// Panic:
+    ///   - The layout must have been successfully built otherwise this function will panic.
+    pub fn fit(&self) {
+        let base = self.inner.borrow().base;
+        if base.is_null() {
+            panic!("Stack layout is not bound to a parent control.")
+        }
+
+        self.refresh(base);
+    }
+
+    /// Returns the smallest size that displays every children with its preferred size
+    pub fn preferred_size(&self) -> (u32, u32) {
+        let inner = self.inner.borrow();
+        inner.stack().preferred_size(&inner.items())
+    }
+
+    /// Set the direction of the stack
+    pub fn direction(&self, direction: StackDirection) {
+        let mut inner = self.inner.borrow_mut();
+        inner.direction = direction;
+    }
+
+    /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
+    pub fn margin(&self, m: [u32; 4]) {
+        let mut inner = self.inner.borrow_mut();
+        inner.margins = m;
+    }
+
+    /// Set the size of the space between the children in the layout. Default value is 5.
+    pub fn spacing(&self, sp: u32) {
+        let mut inner = self.inner.borrow_mut();
+        inner.spacing = sp;
+    }
+
+    /// Set the alignment of the children across the stack
+    pub fn align(&self, align: StackAlign) {
+        let mut inner = self.inner.borrow_mut();
+        inner.align = align;
+    }
+
+    /// Set the placement of the children along the stack when no child stretches
+    pub fn justify(&self, justify: StackAlign) {
+        let mut inner = self.inner.borrow_mut();
+        inner.justify = justify;
+    }
+
+    /// Update the layout after its children were changed
+    fn refresh(&self, base: HWND) {
+        let area = self.inner.borrow().area;
+        match area {
+            Some((position, (w, h), after)) => {
+                self.update_layout_at(position, w, h, after);
+            }
+            None => {
+                let (w, h) = unsafe { wh::get_window_size(base) };
+                self.update_layout(w, h);
+            }
+        }
+    }
+
+    fn update_layout(&self, width: u32, height: u32) {
+        self.update_layout_at((0, 0), width, height, None);
+    }
+
+    fn update_layout_at(
+        &self,
+        (x, y): (i32, i32),
+        width: u32,
+        height: u32,
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() || inner.children.len() == 0 {
+            return after;
+        }
+
+        let area = LayoutRect::new(x, y, width, height);
+        let rects = inner.stack().solve(&inner.items(), area);
+
+        let mut last_handle = after;
+        for (index, rect) in rects {
+            last_handle =
+                inner.children[index]
+                    .target
+                    .place(rect.position(), rect.size(), last_handle);
+        }
+
+        last_handle
+    }
+}
+
+impl StackLayoutInner {
+    /// The parameters of the stack solver
+    fn stack(&self) -> Stack {
+        Stack {
+            direction: self.direction,
+            margins: self.margins,
+            spacing: self.spacing,
+            align: self.align,
+            justify: self.justify,
+        }
+    }
+
+    /// The children of the layout as solver items. The id of an item is the index of the child.
+    fn items(&self) -> Vec<StackItem<usize>> {
+        self.children
+            .iter()
+            .enumerate()
+            .map(|(index, item)| StackItem {
+                id: index,
+                preferred: item.preferred(),
+                stretch: item.stretch,
+                align: item.align,
+            })
+            .collect()
+    }
+}
+
+impl Layout for StackLayout {
+    fn fit(&self) {
+        StackLayout::fit(self);
+    }
+
+    fn resize(&self, w: u32, h: u32) {
+        StackLayout::resize(self, w, h);
+    }
+
+    fn resize_at(
+        &self,
+        position: (i32, i32),
+        size: (u32, u32),
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        self.inner.borrow_mut().area = Some((position, size, after));
+        self.update_layout_at(position, size.0, size.1, after)
+    }
+
+    fn preferred_size(&self) -> (u32, u32) {
+        StackLayout::preferred_size(self)
+    }
+}
+
+impl DeriveLayout for StackLayout {
+    type Item = StackLayoutItem;
+
+    fn layout_item<C: Into<ControlHandle>>(control: C) -> StackLayoutItem {
+        StackLayoutItem::new(control)
+    }
+}
+
+impl Default for StackLayout {
+    fn default() -> StackLayout {
+        StackLayout {
+            inner: Rc::new(RefCell::new(StackLayout::builder().layout)),
+        }
+    }
+}
+
+/// Builder for a `StackLayout` struct
+pub struct StackLayoutBuilder {
+    layout: StackLayoutInner,
+}
+
+impl StackLayoutBuilder {
+    /// Set the layout parent. The handle must be a window object otherwise the function will panic
+    pub fn parent<W: Into<ControlHandle>>(mut self, p: W) -> StackLayoutBuilder {
+        self.layout.base = p.into().hwnd().expect("Parent must be HWND");
+        self
+    }
+
+    /// Add a children at the end of the stack.
+    /// This is a shortcut over `child_item` for an item that does not stretch.
+    /// The handle must be a window object otherwise the function will panic
+    pub fn child<W: Into<ControlHandle>>(mut self, c: W) -> StackLayoutBuilder {
+        self.layout.children.push(StackLayoutItem::new(c));
+        self
+    }
+
+    /// Add a children at the end of the stack
+    pub fn child_item(mut self, item: StackLayoutItem) -> StackLayoutBuilder {
+        self.layout.children.push(item);
+        self
+    }
+
+    /// Add a nested layout at the end of the stack.
+    /// The nested layout should be built with `build_partial`.
+    pub fn child_layout<L: Layout + Clone + 'static>(mut self, layout: &L) -> StackLayoutBuilder {
+        self.layout
+            .children
+            .push(StackLayoutItem::with_layout(layout));
+        self
+    }
+
+    /// Set the direction of the stack
+    pub fn direction(mut self, direction: StackDirection) -> StackLayoutBuilder {
+        self.layout.direction = direction;
+        self
+    }
+
+    /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
+    pub fn margin(mut self, m: [u32; 4]) -> StackLayoutBuilder {
+        self.layout.margins = m;
+        self
+    }
+
+    /// Set the size of the space between the children in the layout. Default value is 5.
+    pub fn spacing(mut self, sp: u32) -> StackLayoutBuilder {
+        self.layout.spacing = sp;
+        self
+    }
+
+    /// Set the alignment of the children across the stack. Default value is `StackAlign::Fill`.
+    pub fn align(mut self, align: StackAlign) -> StackLayoutBuilder {
+        self.layout.align = align;
+        self
+    }
+
+    /// Set the placement of the children along the stack when no child stretches. Default value is `StackAlign::Start`.
+    pub fn justify(mut self, justify: StackAlign) -> StackLayoutBuilder {
+        self.layout.justify = justify;
+        self
+    }
+
+    /// Build the layout without binding it to the parent window resize event and without an initial layout update.
+    /// Use this to build a layout that is nested in another layout.
+    pub fn build_partial(self, layout: &StackLayout) -> Result<(), NwgError> {
+        if self.layout.base.is_null() {
+            return Err(NwgError::layout_create(
+                "StackLayout does not have a parent.",
+            ));
+        }
+
+        let mut layout_inner = layout.inner.borrow_mut();
+        *layout_inner = self.layout;
+
+        Ok(())
+    }
+
+    /// Build the layout object and bind the callback.
+    /// Children must only contains window object otherwise this method will panic.
+    pub fn build(self, layout: &StackLayout) -> Result<(), NwgError> {
+        use winapi::shared::minwindef::{HIWORD, LOWORD};
+        use winapi::um::winuser::WM_SIZE;
+
+        let base = self.layout.base;
+        self.build_partial(layout)?;
+
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        let base_handle = ControlHandle::Hwnd(base);
+
+        // Initial layout update
+        layout.update_layout(w, h);
+
+        // Bind the event handler
+        let event_layout = layout.clone();
+        let cb = move |_h, msg, _w, l| {
+            if msg == WM_SIZE {
+                let size = l as u32;
+                let width = LOWORD(size) as i32;
+                let height = HIWORD(size) as i32;
+                let (w, h) = unsafe { crate::win32::high_dpi::physical_to_logical(width, height) };
+                StackLayout::update_layout(&event_layout, w as u32, h as u32);
+            }
+            None
+        };
+
+        /// Keep generating ids so that multiple layouts can be applied to the same parent
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static STACK_LAYOUT_ID: AtomicUsize = AtomicUsize::new(0xAFFF);
+        bind_raw_event_handler_inner(
+            &base_handle,
+            STACK_LAYOUT_ID.fetch_add(1, Ordering::SeqCst),
+            cb,
+        )
+        .unwrap();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,533 @@
+use super::{DeriveLayout, Layout, LayoutTarget};
+use crate::controls::ControlHandle;
+use crate::win32::window::bind_raw_event_handler_inner;
+use crate::win32::window_helper as wh;
+use crate::NwgError;
+use std::cell::RefCell;
+use std::ptr;
+use std::rc::Rc;
+use winapi::shared::windef::HWND;
+
+use native_windows_layout::{LayoutRect, StackAlign, Wrap, WrapItem};
+
+/// A control or a nested layout in a WrapLayout
+#[derive(Debug)]
+pub struct WrapLayoutItem {
+    /// The control or the layout in the item
+    target: LayoutTarget,
+
+    /// The size of the item. If this is `None`, the size is measured from the content of the control
+    /// (see `PreferredSize`) or from the children of a nested layout.
+    preferred_size: Option<[u32; 2]>,
+
+    /// The size of the control when it was added to the layout. Used when the content cannot be measured.
+    initial_size: [u32; 2],
+}
+
+impl WrapLayoutItem {
+    /// Initialize a new wrap layout item
+    pub fn new<W: Into<ControlHandle>>(c: W) -> WrapLayoutItem {
+        let control = c
+            .into()
+            .hwnd()
+            .expect("Child must be a window-like control (HWND handle)");
+
+        let (w, h) = unsafe { wh::get_window_size(control) };
+
+        WrapLayoutItem {
+            target: LayoutTarget::Control(control),
+            preferred_size: None,
+            initial_size: [w, h],
+        }
+    }
+
+    /// Initialize a new wrap layout item holding a nested layout. The layout should be built with `build_partial`.
+    pub fn with_layout<L: Layout + Clone + 'static>(layout: &L) -> WrapLayoutItem {
+        WrapLayoutItem {
+            target: LayoutTarget::Layout(Box::new(layout.clone())),
+            preferred_size: None,
+            initial_size: [0, 0],
+        }
+    }
+
+    /**
+        Sets the preferred size of the control. By default, the size is measured from the content of the control.
+        Controls that cannot be measured use their size when the item was created.
+    */
+    pub fn preferred_size(mut self, size: [u32; 2]) -> WrapLayoutItem {
+        self.preferred_size = Some(size);
+        self
+    }
+
+    fn preferred(&self) -> [u32; 2] {
+        if let Some(size) = self.preferred_size {
+            return size;
+        }
+
+        match self.target.preferred_size() {
+            Some((w, h)) => [w, h],
+            None => self.initial_size,
+        }
+    }
+}
+
+/// A layout that flows widgets in lines
+/// This is the inner data shared between the callback and the application
+pub struct WrapLayoutInner {
+    /// The control that holds the layout
+    base: HWND,
+
+    /// The children of the control that fit in the layout
+    children: Vec<WrapLayoutItem>,
+
+    /// The top, right, bottom, left space around the layout
+    margins: [u32; 4],
+
+    /// The horizontal spacing between controls and the vertical spacing between lines
+    spacing: [u32; 2],
+
+    /// The vertical alignment of the children in their line
+    align: StackAlign,
+
+    /// The placement of the children in their line
+    justify: StackAlign,
+
+    /// The area of the parent window and the previous control in the tab order given by the parent layout if the layout is nested
+    area: Option<((i32, i32), (u32, u32), Option<HWND>)>,
+}
+
+/**
+A layout that places widgets from the left to the right with their preferred size (see `PreferredSize`)
+and starts a new line when a widget does not fit in the current line. Use it for toolbar-like content.
+Unlike `FlexboxLayout`, it does not require the `flexbox` feature.
+NWG layouts use interior mutability to manage their controls.
+
+A WrapLayout has the following properties:
+* margin - The top, right, bottom, left margins of the layout - (default: [5, 5, 5, 5])
+* spacing - The horizontal spacing between children and the vertical spacing between lines - (default: [5, 5])
+* align - The vertical alignment of the children in their line - (default: StackAlign::Center)
+* justify - The placement of the children in their line - (default: StackAlign::Start)
+
+```rust
+    use native_windows_gui as nwg;
+    fn toolbar(layout: &nwg::WrapLayout, window: &nwg::Window, buttons: &[nwg::Button]) {
+        let mut builder = nwg::WrapLayout::builder()
+            .parent(window)
+            .spacing([2, 2]);
+
+        for button in buttons {
+            builder = builder.child(button);
+        }
+
+        builder.build(&layout);
+    }
+```
+*/
+#[derive(Clone)]
+pub struct WrapLayout {
+    inner: Rc<RefCell<WrapLayoutInner>>,
+}
+
+impl WrapLayout {
+    pub fn builder() -> WrapLayoutBuilder {
+        let layout = WrapLayoutInner {
+            base: ptr::null_mut(),
+            children: Vec::new(),
+            margins: [5, 5, 5, 5],
+            spacing: [5, 5],
+            align: StackAlign::Center,
+            justify: StackAlign::Start,
+            area: None,
+        };
+
+        WrapLayoutBuilder { layout }
+    }
+
+    /**
+        Add a children control at the end of the layout.
+        This is a simplified interface over `add_child_item`
+
+        Panic:
+        - If the layout is not initialized
+        - If the control is not window-like (HWND handle)
+    */
+    pub fn add_child<W: Into<ControlHandle>>(&self, c: W) {
+        self.add_child_item(WrapLayoutItem::new(c));
+    }
+
+    /**
+        Add a nested layout at the end of the layout. The layout should be built with `build_partial`.
+        This is a simplified interface over `add_child_item`
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn add_child_layout<L: Layout + Clone + 'static>(&self, layout: &L) {
+        self.add_child_item(WrapLayoutItem::with_layout(layout));
+    }
+
+    /**
+        Add a children control at the end of the layout.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn add_child_item(&self, i: WrapLayoutItem) {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("WrapLayout is not initialized");
+            }
+
+            inner.children.push(i);
+            inner.base
+        };
+
+        self.refresh(base);
+    }
+
+    /**
+        Remove the children control in the layout.
+        Note that the child control won't be hidden after being removed from the control.
+
+        This method won't do anything if the control is not in the layout.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn remove_child<W: Into<ControlHandle>>(&self, c: W) {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("WrapLayout is not initialized");
+            }
+
+            let handle = c
+                .into()
+                .hwnd()
+                .expect("Control must be window-like (HWND handle)");
+            let index = inner
+                .children
+                .iter()
+                .position(|item| item.target.is_control(handle));
+            match index {
+                Some(i) => {
+                    inner.children.remove(i);
+                }
+                None => {
+                    return;
+                }
+            }
+
+            inner.base
+        };
+
+        self.refresh(base);
+    }
+
+    /**
+        Check if a window control is a children of the layout
+
+        Panic:
+        - If the layout is not initialized
+        - If the child is not a window-like control
+    */
+    pub fn has_child<W: Into<ControlHandle>>(&self, c: W) -> bool {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() {
+            panic!("WrapLayout is not initialized");
+        }
+
+        let handle = c
+            .into()
+            .hwnd()
+            .expect("Children is not a window-like control (HWND handle)");
+        inner.children.iter().any(|c| c.target.is_control(handle))
+    }
+
+    /// Resize the layout as if the parent window had the specified size.
+    ///
+    /// Arguments:
+    ///   w: New width of the layout
+    ///   h: New height of the layout
+    ///
+    ///  Panic:
+    ///   - The layout must have been successfully built otherwise this function will panic.
+    pub fn resize(&self, w: u32, h: u32) {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() {
+            panic!("Wrap layout is not bound to a parent control.")
+        }
+        self.update_layout(w, h);
+    }
+
+    /// Resize the layout to fit the parent window size.
+    /// If the layout is nested in another layout, it is resized to fit the area given by the parent layout.
+    ///
+    /// Panic:
+    ///   - The layout must have been successfully built otherwise this function will panic.
+    pub fn fit(&self) {
+        let base = self.inner.borrow().base;
+        if base.is_null() {
+            panic!("Wrap layout is not bound to a parent control.")
+        }
+
+        self.refresh(base);
+    }
+
+    /// Returns the size needed to display every children on a single line
+    pub fn preferred_size(&self) -> (u32, u32) {
+        let inner = self.inner.borrow();
+        inner.wrap().preferred_size(&inner.items())
+    }
+
+    /// Returns the height needed to display every children if the layout is `width` pixels wide
+    pub fn preferred_height(&self, width: u32) -> u32 {
+        let inner = self.inner.borrow();
+        inner.wrap().preferred_height(&inner.items(), width)
+    }
+
+    /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
+    pub fn margin(&self, m: [u32; 4]) {
+        let mut inner = self.inner.borrow_mut();
+        inner.margins = m;
+    }
+
+    /// Set the horizontal spacing between the children and the vertical spacing between the lines
+    pub fn spacing(&self, sp: [u32; 2]) {
+        let mut inner = self.inner.borrow_mut();
+        inner.spacing = sp;
+    }
+
+    /// Set the vertical alignment of the children in their line
+    pub fn align(&self, align: StackAlign) {
+        let mut inner = self.inner.borrow_mut();
+        inner.align = align;
+    }
+
+    /// Set the placement of the children in their line
+    pub fn justify(&self, justify: StackAlign) {
+        let mut inner = self.inner.borrow_mut();
+        inner.justify = justify;
+    }
+
+    /// Update the layout after its children were changed
+    fn refresh(&self, base: HWND) {
+        let area = self.inner.borrow().area;
+        match area {
+            Some((position, (w, h), after)) => {
+                self.update_layout_at(position, w, h, after);
+            }
+            None => {
+                let (w, h) = unsafe { wh::get_window_size(base) };
+                self.update_layout(w, h);
+            }
+        }
+    }
+
+    fn update_layout(&self, width: u32, height: u32) {
+        self.update_layout_at((0, 0), width, height, None);
+    }
+
+    fn update_layout_at(
+        &self,
+        (x, y): (i32, i32),
+        width: u32,
+        height: u32,
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() || inner.children.len() == 0 {
+            return after;
+        }
+
+        let area = LayoutRect::new(x, y, width, height);
+        let rects = inner.wrap().solve(&inner.items(), area);
+
+        let mut last_handle = after;
+        for (index, rect) in rects {
+            last_handle =
+                inner.children[index]
+                    .target
+                    .place(rect.position(), rect.size(), last_handle);
+        }
+
+        last_handle
+    }
+}
+
+impl WrapLayoutInner {
+    /// The parameters of the wrap solver
+    fn wrap(&self) -> Wrap {
+        Wrap {
+            margins: self.margins,
+            spacing: self.spacing,
+            align: self.align,
+            justify: self.justify,
+        }
+    }
+
+    /// The children of the layout as solver items. The id of an item is the index of the child.
+    fn items(&self) -> Vec<WrapItem<usize>> {
+        self.children
+            .iter()
+            .enumerate()
+            .map(|(index, item)| WrapItem::new(index, item.preferred()))
+            .collect()
+    }
+}
+
+impl Layout for WrapLayout {
+    fn fit(&self) {
+        WrapLayout::fit(self);
+    }
+
+    fn resize(&self, w: u32, h: u32) {
+        WrapLayout::resize(self, w, h);
+    }
+
+    fn resize_at(
+        &self,
+        position: (i32, i32),
+        size: (u32, u32),
+        after: Option<HWND>,
+    ) -> Option<HWND> {
+        self.inner.borrow_mut().area = Some((position, size, after));
+        self.update_layout_at(position, size.0, size.1, after)
+    }
+
+    fn preferred_size(&self) -> (u32, u32) {
+        WrapLayout::preferred_size(self)
+    }
+}
+
+impl DeriveLayout for WrapLayout {
+    type Item = WrapLayoutItem;
+
+    fn layout_item<C: Into<ControlHandle>>(control: C) -> WrapLayoutItem {
+        WrapLayoutItem::new(control)
+    }
+}
+
+impl Default for WrapLayout {
+    fn default() -> WrapLayout {
+        WrapLayout {
+            inner: Rc::new(RefCell::new(WrapLayout::builder().layout)),
+        }
+    }
+}
+
+/// Builder for a `WrapLayout` struct
+pub struct WrapLayoutBuilder {
+    layout: WrapLayoutInner,
+}
+
+impl WrapLayoutBuilder {
+    /// Set the layout parent. The handle must be a window object otherwise the function will panic
+    pub fn parent<W: Into<ControlHandle>>(mut self, p: W) -> WrapLayoutBuilder {
+        self.layout.base = p.into().hwnd().expect("Parent must be HWND");
+        self
+    }
+
+    /// Add a children at the end of the layout.
+    /// The handle must be a window object otherwise the function will panic
+    pub fn child<W: Into<ControlHandle>>(mut self, c: W) -> WrapLayoutBuilder {
+        self.layout.children.push(WrapLayoutItem::new(c));
+        self
+    }
+
+    /// Add a children at the end of the layout
+    pub fn child_item(mut self, item: WrapLayoutItem) -> WrapLayoutBuilder {
+        self.layout.children.push(item);
+        self
+    }
+
+    /// Add a nested layout at the end of the layout.
+    /// The nested layout should be built with `build_partial`.
+    pub fn child_layout<L: Layout + Clone + 'static>(mut self, layout: &L) -> WrapLayoutBuilder {
+        self.layout
+            .children
+            .push(WrapLayoutItem::with_layout(layout));
+        self
+    }
+
+    /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
+    pub fn margin(mut self, m: [u32; 4]) -> WrapLayoutBuilder {
+        self.layout.margins = m;
+        self
+    }
+
+    /// Set the horizontal spacing between the children and the vertical spacing between the lines. Default value is [5, 5].
+    pub fn spacing(mut self, sp: [u32; 2]) -> WrapLayoutBuilder {
+        self.layout.spacing = sp;
+        self
+    }
+
+    /// Set the vertical alignment of the children in their line. Default value is `StackAlign::Center`.
+    pub fn align(mut self, align: StackAlign) -> WrapLayoutBuilder {
+        self.layout.align = align;
+        self
+    }
+
+    /// Set the placement of the children in their line. Default value is `StackAlign::Start`.
+    pub fn justify(mut self, justify: StackAlign) -> WrapLayoutBuilder {
+        self.layout.justify = justify;
+        self
+    }
+
+    /// Build the layout without binding it to the parent window resize event and without an initial layout update.
+    /// Use this to build a layout that is nested in another layout.
+    pub fn build_partial(self, layout: &WrapLayout) -> Result<(), NwgError> {
+        if self.layout.base.is_null() {
+            return Err(NwgError::layout_create(
+                "WrapLayout does not have a parent.",
+            ));
+        }
+
+        let mut layout_inner = layout.inner.borrow_mut();
+        *layout_inner = self.layout;
+
+        Ok(())
+    }
+
+    /// Build the layout object and bind the callback.
+    /// Children must only contains window object otherwise this method will panic.
+    pub fn build(self, layout: &WrapLayout) -> Result<(), NwgError> {
+        use winapi::shared::minwindef::{HIWORD, LOWORD};
+        use winapi::um::winuser::WM_SIZE;
+
+        let base = self.layout.base;
+        self.build_partial(layout)?;
+
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        let base_handle = ControlHandle::Hwnd(base);
+
+        // Initial layout update
+        layout.update_layout(w, h);
+
+        // Bind the event handler
+        let event_layout = layout.clone();
+        let cb = move |_h, msg, _w, l| {
+            if msg == WM_SIZE {
+                let size = l as u32;
+                let width = LOWORD(size) as i32;
+                let height = HIWORD(size) as i32;
+                let (w, h) = unsafe { crate::win32::high_dpi::physical_to_logical(width, height) };
+                WrapLayout::update_layout(&event_layout, w as u32, h as u32);
+            }
+            None
+        };
+
+        /// Keep generating ids so that multiple layouts can be applied to the same parent
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static WRAP_LAYOUT_ID: AtomicUsize = AtomicUsize::new(0xBFFF);
+        bind_raw_event_handler_inner(
+            &base_handle,
+            WRAP_LAYOUT_ID.fetch_add(1, Ordering::SeqCst),
+            cb,
+        )
+        .unwrap();
+
+        Ok(())
+    }
+}
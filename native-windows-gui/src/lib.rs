@@ -47,6 +47,10 @@ pub use win32::cursor::GlobalCursor;
 #[cfg(feature = "clipboard")]
 pub use win32::clipboard::{Clipboard, ClipboardData, ClipboardFormat};
 
+/// Direct2D and DirectWrite setup used by the drawing controls and native-windows-canvas
+#[cfg(any(feature = "plotting", feature = "extern-canvas"))]
+pub use win32::d2d;
+
 mod resources;
 pub use resources::*;
 
@@ -0,0 +1,177 @@
+use crate::win32::{animated_image as anim, image_decoder as img, resources_helper as rh};
+use crate::{Bitmap, NwgError};
+use std::{rc::Rc, time::Duration};
+
+struct AnimationFrame {
+    bitmap: Bitmap,
+    delay: Duration,
+}
+
+/**
+An animated image (GIF or APNG) decoded into a list of frames. Animated images are played by the `ImageFrame` control.
+
+Every frame is composed over the previous ones when the image is loaded and is kept in memory as a 32 bits bitmap,
+so long animations can use a lot of memory. Images that are not animated are loaded as a single frame.
+
+An animated image is a reference counted resource. Cloning it does not copy the frames.
+
+Requires the `image-decoder` feature.
+
+**Builder parameters:**
+  * `source_file`:      The source of the image if it is a file.
+  * `source_bin`:       The source of the image if it is a binary blob. For example using `include_bytes!("loading.gif")`.
+
+Example:
+
+```rust
+use native_windows_gui as nwg;
+
+fn load_animation() -> nwg::AnimatedImage {
+    nwg::AnimatedImage::from_file("loading.gif").unwrap()
+}
+
+fn load_animation_builder() -> nwg::AnimatedImage {
+    let mut image = nwg::AnimatedImage::default();
+
+    nwg::AnimatedImage::builder()
+        .source_file(Some("loading.png"))
+        .build(&mut image)
+        .unwrap();
+
+    image
+}
+```
+*/
+#[derive(Clone, Default)]
+pub struct AnimatedImage {
+    frames: Rc<Vec<AnimationFrame>>,
+    size: (u32, u32),
+}
+
+impl AnimatedImage {
+    pub fn builder<'a>() -> AnimatedImageBuilder<'a> {
+        AnimatedImageBuilder {
+            source_file: None,
+            source_bin: None,
+        }
+    }
+
+    /**
+        Single line helper function over the animated image builder api.
+
+        Use a file resource.
+    */
+    pub fn from_file(path: &str) -> Result<AnimatedImage, NwgError> {
+        let mut image = AnimatedImage::default();
+
+        AnimatedImage::builder()
+            .source_file(Some(path))
+            .build(&mut image)?;
+
+        Ok(image)
+    }
+
+    /**
+        Single line helper function over the animated image builder api.
+
+        Use a binary resource.
+    */
+    pub fn from_bin(bin: &[u8]) -> Result<AnimatedImage, NwgError> {
+        let mut image = AnimatedImage::default();
+
+        AnimatedImage::builder()
+            .source_bin(Some(bin))
+            .build(&mut image)?;
+
+        Ok(image)
+    }
+
+    /// Return the size of the animation canvas in pixels
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Return the number of frames in the animation
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Return the bitmap of a frame, or `None` if the index is out of bounds
+    pub fn frame(&self, index: usize) -> Option<&Bitmap> {
+        self.frames.get(index).map(|f| &f.bitmap)
+    }
+
+    /**
+        Return how long a frame is displayed, as stored in the image, or `None` if the index is out of bounds.
+        Note that `ImageFrame` plays frames with a delay shorter than 20ms at 100ms, like web browsers do.
+    */
+    pub fn delay(&self, index: usize) -> Option<Duration> {
+        self.frames.get(index).map(|f| f.delay)
+    }
+
+    /// Return the time it takes to play every frame once
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|f| f.delay).sum()
+    }
+}
+
+pub struct AnimatedImageBuilder<'a> {
+    source_file: Option<&'a str>,
+    source_bin: Option<&'a [u8]>,
+}
+
+impl<'a> AnimatedImageBuilder<'a> {
+    pub fn source_file(mut self, t: Option<&'a str>) -> AnimatedImageBuilder<'a> {
+        self.source_file = t;
+        self
+    }
+
+    pub fn source_bin(mut self, t: Option<&'a [u8]>) -> AnimatedImageBuilder<'a> {
+        self.source_bin = t;
+        self
+    }
+
+    pub fn build(self, out: &mut AnimatedImage) -> Result<(), NwgError> {
+        let file_data;
+        let data = match (self.source_file, self.source_bin) {
+            (Some(path), _) => {
+                file_data = std::fs::read(path).map_err(|e| {
+                    NwgError::resource_create(format!("Could not read {}: {}", path, e))
+                })?;
+                &file_data[..]
+            }
+            (None, Some(bin)) => bin,
+            (None, None) => {
+                return Err(NwgError::resource_create(
+                    "No source provided for AnimatedImage",
+                ))
+            }
+        };
+
+        let (size, composed) = unsafe {
+            let factory = img::create_image_factory()?;
+            let composed = anim::decode_animation(&*factory, data);
+            (&*factory).Release();
+            composed?
+        };
+
+        let mut frames = Vec::with_capacity(composed.len());
+        for frame in composed {
+            let handle = unsafe { rh::bitmap_from_rgba(size, &frame.pixels)? };
+            frames.push(AnimationFrame {
+                bitmap: Bitmap {
+                    handle,
+                    owned: true,
+                },
+                delay: frame.delay,
+            });
+        }
+
+        *out = AnimatedImage {
+            frames: Rc::new(frames),
+            size,
+        };
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-use crate::win32::resources_helper as rh;
-use crate::{NwgError, OemBitmap, OemImage};
+use crate::win32::{high_dpi, resources_helper as rh};
+use crate::{ImageSet, NwgError, OemBitmap, OemImage};
 use std::ptr;
 use winapi::um::winnt::HANDLE;
 use winapi::um::winuser::IMAGE_BITMAP;
@@ -10,7 +10,7 @@ use super::EmbedResource;
 /**
 A wrapper over a bitmap file (*.bmp)
 
-Note that Bitmap object are only used as display resources (ie: it's impossible to read pixels or resized it).
+Note that Bitmap object are mostly used as display resources. Their pixels can be copied with `to_rgba`, but they cannot be resized.
 If those features are needed, see the `image-decoder` feature.
 
 To display a bitmap in an application, see the `ImageFrame` control.
@@ -20,6 +20,9 @@ from any file type supported natively by Windows: `JPEG, PNG, BMP, ICO, DDS, TIF
 
 Bitmaps can be converted to icons using the "copy_as_icon" function.
 
+With `image-decoder`, bitmaps can be saved to any format supported by the `ImageEncoder` using the "save" function.
+Saving to a `.ico` file creates an icon file.
+
 
 **Builder parameters:**
   * `source_file`:      The source of the bitmap if it is a file.
@@ -28,7 +31,8 @@ Bitmaps can be converted to icons using the "copy_as_icon" function.
   * `source_embed`:     The source of the bitmap if it is stored in an embedded file
   * `source_embed_id`:  The number identifier of the icon in the embedded file
   * `source_embed_str`: The string identifier of the icon in the embedded file
-  * `size`:             Optional. Resize the image to this size.
+  * `source_set`:       The source of the bitmap if it is an image set. The variant that best matches `size` at the current DPI is used.
+  * `size`:             Optional. Resize the image to this size. With `source_set`, the size is logical (scaled by the DPI) and defaults to the largest image of the set.
   * `strict`:           Use a system placeholder instead of panicking if the image source do no exists.
 
 Example:
@@ -67,6 +71,7 @@ impl Bitmap {
             source_text: None,
             source_bin: None,
             source_system: None,
+            source_set: None,
 
             #[cfg(feature = "embed-resource")]
             source_embed: None,
@@ -180,12 +185,64 @@ impl Bitmap {
             owned: true,
         }
     }
+
+    /**
+        Return the size of the bitmap in pixels.
+
+        Panics if the bitmap is not initialized
+    */
+    pub fn size(&self) -> (u32, u32) {
+        if self.handle.is_null() {
+            panic!("Bitmap was not initialized");
+        }
+
+        unsafe {
+            rh::bitmap_info(self.handle as _)
+                .map(|(size, _)| size)
+                .unwrap_or((0, 0))
+        }
+    }
+
+    /**
+        Copy the bitmap pixels as RGBA (4 bytes per pixel, straight alpha, rows from top to bottom).
+        Bitmaps without an alpha channel are opaque.
+
+        Panics if the bitmap is not initialized
+    */
+    pub fn to_rgba(&self) -> Result<Vec<u8>, NwgError> {
+        if self.handle.is_null() {
+            panic!("Bitmap was not initialized");
+        }
+
+        unsafe { rh::bitmap_to_rgba(self.handle as _).map(|(_, pixels)| pixels) }
+    }
+
+    /**
+        Save the bitmap to a file. The format is picked from the file extension (png, jpg, bmp, tif, gif, ico).
+        For more options, such as the JPEG quality or metadata, use an `ImageEncoder`.
+
+        Requires the `image-decoder` feature.
+
+        Panics if the bitmap is not initialized
+    */
+    #[cfg(feature = "image-decoder")]
+    pub fn save<'a>(&self, path: &'a str) -> Result<(), NwgError> {
+        use crate::win32::image_decoder as img;
+
+        if self.handle.is_null() {
+            panic!("Bitmap was not initialized");
+        }
+
+        let (size, pixels) = unsafe { rh::bitmap_to_rgba(self.handle as _)? };
+        img::save_rgba(path, size, &pixels)
+    }
 }
 
 pub struct BitmapBuilder<'a> {
     source_text: Option<&'a str>,
     source_bin: Option<&'a [u8]>,
     source_system: Option<OemBitmap>,
+    source_set: Option<&'a ImageSet>,
 
     #[cfg(feature = "embed-resource")]
     source_embed: Option<&'a EmbedResource>,
@@ -216,6 +273,11 @@ impl<'a> BitmapBuilder<'a> {
         self
     }
 
+    pub fn source_set(mut self, t: Option<&'a ImageSet>) -> BitmapBuilder<'a> {
+        self.source_set = t;
+        self
+    }
+
     #[cfg(feature = "embed-resource")]
     pub fn source_embed(mut self, em: Option<&'a EmbedResource>) -> BitmapBuilder<'a> {
         self.source_embed = em;
@@ -273,6 +335,19 @@ impl<'a> BitmapBuilder<'a> {
                 handle,
                 owned: true,
             };
+        } else if let Some(set) = self.source_set {
+            *b = match self.size {
+                Some((w, h)) => {
+                    let (w, h) = unsafe { high_dpi::logical_to_physical(w as i32, h as i32) };
+                    set.bitmap((w as u32, h as u32))?
+                }
+                None => {
+                    let size = set
+                        .largest_size()
+                        .ok_or_else(|| NwgError::resource_create("The image set is empty"))?;
+                    set.bitmap(size)?
+                }
+            };
         } else {
             #[cfg(all(feature = "embed-resource", feature = "image-decoder"))]
             fn build_embed(builder: BitmapBuilder) -> Result<Bitmap, NwgError> {
@@ -1,5 +1,5 @@
 use crate::win32::resources_helper as rh;
-use crate::{NwgError, OemCursor, OemImage};
+use crate::{Bitmap, NwgError, OemCursor, OemImage};
 use std::ptr;
 use winapi::um::winnt::HANDLE;
 use winapi::um::winuser::IMAGE_CURSOR;
@@ -8,10 +8,23 @@ use winapi::um::winuser::IMAGE_CURSOR;
 use super::EmbedResource;
 
 /**
-A wrapper over a cursor file (*.cur)
+A wrapper over a cursor file (*.cur) or an animated cursor file (*.ani)
 
 Cursor resources can be used with the `cursor` feature
 
+A cursor can be displayed over a control with `set_cursor`. The control takes the ownership of the cursor. Cursors can also be created from a bitmap
+with `Cursor::from_bitmap`.
+
+**Builder parameters:**
+  * `source_file`:      The source of the cursor if it is a file. Animated cursors (.ani) are animated.
+  * `source_bin`:       The source of the cursor if it is a binary blob. For example using `include_bytes!("grab.ani")`.
+  * `source_system`:    The source of the cursor if it is a system resource (see OemCursor)
+  * `source_embed`:     The source of the cursor if it is stored in an embedded file
+  * `source_embed_id`:  The number identifier of the cursor in the embedded file
+  * `source_embed_str`: The string identifier of the cursor in the embedded file
+  * `size`:             Optional. Load the cursor image that best matches this size.
+  * `strict`:           Use a system placeholder instead of panicking if the file could not be loaded
+
 Example:
 
 ```rust
@@ -44,6 +57,7 @@ impl Cursor {
     pub fn builder<'a>() -> CursorBuilder<'a> {
         CursorBuilder {
             source_text: None,
+            source_bin: None,
             source_system: None,
             size: None,
 
@@ -88,6 +102,41 @@ impl Cursor {
         Ok(cursor)
     }
 
+    /**
+        Single line helper function over the cursor builder api.
+
+        Use a binary resource. The resource can be a cursor file (.cur) or an animated cursor file (.ani).
+    */
+    pub fn from_bin(bin: &[u8]) -> Result<Cursor, NwgError> {
+        let mut cursor = Cursor::default();
+
+        Cursor::builder().source_bin(Some(bin)).build(&mut cursor)?;
+
+        Ok(cursor)
+    }
+
+    /**
+        Create a cursor from a bitmap. The alpha channel of 32 bits bitmaps is kept.
+        `hotspot` is the pixel of the bitmap that points at the mouse position, for example the center of a crosshair.
+
+        Returns an error if the hotspot is outside of the bitmap. Panics if the bitmap was not initialized.
+    */
+    pub fn from_bitmap(bitmap: &Bitmap, hotspot: (u32, u32)) -> Result<Cursor, NwgError> {
+        if bitmap.handle.is_null() {
+            panic!("Bitmap was not initialized");
+        }
+
+        let handle = unsafe {
+            let (size, pixels) = rh::bitmap_to_rgba(bitmap.handle as _)?;
+            rh::cursor_from_rgba(size, &pixels, hotspot)?
+        };
+
+        Ok(Cursor {
+            handle,
+            owned: true,
+        })
+    }
+
     /**
         Single line helper function over the cursor builder api.
 
@@ -115,6 +164,7 @@ impl Cursor {
 
 pub struct CursorBuilder<'a> {
     source_text: Option<&'a str>,
+    source_bin: Option<&'a [u8]>,
     source_system: Option<OemCursor>,
     size: Option<(u32, u32)>,
 
@@ -136,6 +186,11 @@ impl<'a> CursorBuilder<'a> {
         self
     }
 
+    pub fn source_bin(mut self, t: Option<&'a [u8]>) -> CursorBuilder<'a> {
+        self.source_bin = t;
+        self
+    }
+
     pub fn source_system(mut self, t: Option<OemCursor>) -> CursorBuilder<'a> {
         self.source_system = t;
         self
@@ -176,6 +231,12 @@ impl<'a> CursorBuilder<'a> {
                 handle,
                 owned: true,
             };
+        } else if let Some(src) = self.source_bin {
+            let handle = unsafe { rh::cursor_from_memory(src, self.size)? };
+            *b = Cursor {
+                handle,
+                owned: true,
+            };
         } else if let Some(src) = self.source_system {
             let handle = unsafe { rh::build_oem_image(OemImage::Cursor(src), self.size)? };
             *b = Cursor {
@@ -1,8 +1,9 @@
 use crate::win32::base_helper::{from_utf16, to_utf16};
-use crate::win32::resources_helper as rh;
+use crate::win32::{high_dpi, resources_helper as rh};
 use crate::NwgError;
-use std::ptr;
+use std::{mem, ptr};
 use winapi::shared::windef::HFONT;
+use winapi::um::wingdi::LOGFONTW;
 use winapi::um::winnt::HANDLE;
 
 use std::sync::Mutex;
@@ -12,16 +13,95 @@ lazy_static! {
     static ref DEFAULT_FONT: Mutex<Option<Font>> = {
         Mutex::new(None)
     };
+
+    /// The families of the fonts added with `Font::add_memory_font`, by memory font handle.
+    /// Memory fonts are private to the process and are not listed by the system.
+    static ref MEMORY_FONTS: Mutex<Vec<(usize, Vec<String>)>> = {
+        Mutex::new(Vec::new())
+    };
 }
 
+/**
+    A font loaded from memory with `Font::add_memory_font`. The fonts can be used by family name like any system font.
+*/
 pub struct MemFont(pub HANDLE);
 
+impl MemFont {
+    /// Return the family names of the fonts in the memory font. Use these names with `FontBuilder::family`.
+    pub fn families(&self) -> Vec<String> {
+        MEMORY_FONTS
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(handle, _)| *handle == self.0 as usize)
+            .map(|(_, families)| families.clone())
+            .unwrap_or_default()
+    }
+}
+
+/**
+    The output quality of a font. It defines how the text is antialiased.
+    See https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-logfontw
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FontQuality {
+    /// The appearance of the font does not matter
+    Default = 0,
+    /// The appearance of the font is less important than when `Proof` is used
+    Draft = 1,
+    /// The character quality of the font is more important than exact matching of the font attributes
+    Proof = 2,
+    /// The font is never antialiased
+    NonAntialiased = 3,
+    /// The font is antialiased if it supports it and if the size is not too small or too large
+    Antialiased = 4,
+    /// The text is rendered with ClearType. This is the default.
+    ClearType = 5,
+    /// The text is rendered with ClearType natural widths
+    ClearTypeNatural = 6,
+}
+
+/**
+    The pitch of a font. Fixed pitch fonts (monospaced) use the same width for every character.
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FontPitch {
+    /// Let the font mapper choose
+    Default = 0,
+    /// Select a fixed pitch (monospaced) font
+    Fixed = 1,
+    /// Select a variable pitch font. This is the default.
+    Variable = 2,
+}
+
+/**
+    The measured metrics of a font, in physical pixels.
+    For more information on the values see: https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-textmetricw
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FontMetrics {
+    /// The height of the characters (ascent + descent)
+    pub height: i32,
+    /// The height of the characters above the base line
+    pub ascent: i32,
+    /// The height of the characters below the base line
+    pub descent: i32,
+    /// The space for the accent marks, included in `height`
+    pub internal_leading: i32,
+    /// The space the font designer recommends to add between rows
+    pub external_leading: i32,
+    /// The average width of the characters, usually the width of the letter `x`
+    pub average_char_width: i32,
+    /// The width of the widest character
+    pub max_char_width: i32,
+}
+
 /**
-Represent a font parameters. Returned by the font dialog when the user selected a font.
-Can also be used to create a Font resource using `Font::from_info`
+Represent a font parameters. Returned by the font dialog when the user selected a font, or by `Font::info`.
+Can also be used to create a Font resource using `Font::from_info`, so a font choice can be saved and restored.
 For more information on the parameters see: https://docs.microsoft.com/en-us/windows/win32/api/wingdi/ns-wingdi-logfonta
 */
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FontInfo {
     /// The size of the selected font, in units of 1/10 of a point
     pub point_size: u32,
@@ -55,12 +135,61 @@ pub struct FontInfo {
     pub name: String,
 }
 
+impl FontInfo {
+    pub(crate) fn from_logfont(font: &LOGFONTW, point_size: u32) -> FontInfo {
+        FontInfo {
+            point_size,
+            height: font.lfHeight as i32,
+            width: font.lfWidth as i32,
+            escapement: font.lfEscapement as i32,
+            orientation: font.lfOrientation as i32,
+            weight: font.lfWeight as i32,
+            italic: font.lfItalic == 1,
+            underline: font.lfUnderline == 1,
+            strike_out: font.lfStrikeOut == 1,
+            char_set: font.lfCharSet as u8,
+            out_precision: font.lfOutPrecision as u8,
+            clip_precision: font.lfClipPrecision as u8,
+            quality: font.lfQuality as u8,
+            pitch_and_family: font.lfPitchAndFamily as u8,
+            name: from_utf16(&font.lfFaceName),
+        }
+    }
+
+    pub(crate) fn to_logfont(&self) -> LOGFONTW {
+        let mut font: LOGFONTW = unsafe { mem::zeroed() };
+        font.lfHeight = self.height;
+        font.lfWidth = self.width;
+        font.lfEscapement = self.escapement;
+        font.lfOrientation = self.orientation;
+        font.lfWeight = self.weight;
+        font.lfItalic = self.italic as u8;
+        font.lfUnderline = self.underline as u8;
+        font.lfStrikeOut = self.strike_out as u8;
+        font.lfCharSet = self.char_set;
+        font.lfOutPrecision = self.out_precision;
+        font.lfClipPrecision = self.clip_precision;
+        font.lfQuality = self.quality;
+        font.lfPitchAndFamily = self.pitch_and_family;
+
+        // The face name is limited to 31 characters and the null terminator
+        let name = to_utf16(&self.name);
+        let length = name.len().min(font.lfFaceName.len()) - 1;
+        font.lfFaceName[..length].copy_from_slice(&name[..length]);
+
+        font
+    }
+}
+
 /**
 
 Represent a system font.
 
-Can be used with any controls that draws text. Due to the very limited way win32 can draw text,
-only family, size and weight can be configured.
+Can be used with any controls that draws text. The family, size, weight, style (italic, underline, strikeout),
+quality, character set and pitch can be configured.
+
+A font can be saved with `Font::info` and created again with `Font::from_info`.
+For custom drawn controls, `Font::metrics` and `Font::measure_text` return the size of the text in physical pixels.
 
 Example:
 
@@ -74,11 +203,17 @@ fn build_font() -> nwg::Font {
         .size(16)
         .family("Arial")
         .weight(1000)
+        .italic(true)
+        .quality(nwg::FontQuality::ClearTypeNatural)
         .build(&mut font);
 
     font
 }
 
+fn restore_font(info: &nwg::FontInfo) -> Result<nwg::Font, nwg::NwgError> {
+    nwg::Font::from_info(info)
+}
+
 ```
 
 */
@@ -92,6 +227,96 @@ impl Font {
         FontBuilder::new()
     }
 
+    /**
+        Create a font from a `FontInfo`, for example a font returned by a `FontDialog` or by `Font::info`.
+        If `height` is 0, the height is computed from `point_size` for the screen.
+    */
+    pub fn from_info(info: &FontInfo) -> Result<Font, NwgError> {
+        let mut info = info.clone();
+        if info.height == 0 && info.point_size > 0 {
+            let dpi = unsafe { high_dpi::dpi() };
+            info.height = -((info.point_size as i32 * dpi + 360) / 720);
+        }
+
+        let handle = unsafe { rh::build_font(&info)? };
+        Ok(Font { handle })
+    }
+
+    /**
+        Return the parameters of the font. The `height` is in physical pixels and the `point_size`
+        is computed for the screen.
+    */
+    pub fn info(&self) -> FontInfo {
+        use winapi::um::wingdi::GetObjectW;
+
+        if self.handle.is_null() {
+            panic!("Font was not initialized");
+        }
+
+        let mut font: LOGFONTW = unsafe { mem::zeroed() };
+        let size = mem::size_of::<LOGFONTW>() as i32;
+        unsafe {
+            GetObjectW(self.handle as _, size, &mut font as *mut LOGFONTW as _);
+        }
+
+        let metrics = self.metrics();
+        let dpi = unsafe { high_dpi::dpi() };
+        let point_size = (metrics.height - metrics.internal_leading) * 720 / dpi.max(1);
+
+        FontInfo::from_logfont(&font, point_size.max(0) as u32)
+    }
+
+    /// Return the measured metrics of the font, in physical pixels
+    pub fn metrics(&self) -> FontMetrics {
+        use winapi::um::wingdi::{GetTextMetricsW, SelectObject, TEXTMETRICW};
+        use winapi::um::winuser::{GetDC, ReleaseDC};
+
+        if self.handle.is_null() {
+            panic!("Font was not initialized");
+        }
+
+        unsafe {
+            let dc = GetDC(ptr::null_mut());
+            let old_font = SelectObject(dc, self.handle as _);
+
+            let mut metrics: TEXTMETRICW = mem::zeroed();
+            GetTextMetricsW(dc, &mut metrics);
+
+            SelectObject(dc, old_font);
+            ReleaseDC(ptr::null_mut(), dc);
+
+            FontMetrics {
+                height: metrics.tmHeight,
+                ascent: metrics.tmAscent,
+                descent: metrics.tmDescent,
+                internal_leading: metrics.tmInternalLeading,
+                external_leading: metrics.tmExternalLeading,
+                average_char_width: metrics.tmAveCharWidth,
+                max_char_width: metrics.tmMaxCharWidth,
+            }
+        }
+    }
+
+    /**
+        Return the size of `text` drawn with this font, in physical pixels.
+        Every line is measured separately: the width is the width of the longest line and the height is the sum of the lines heights.
+    */
+    pub fn measure_text(&self, text: &str) -> (u32, u32) {
+        use winapi::um::winuser::{GetDC, ReleaseDC};
+
+        if self.handle.is_null() {
+            panic!("Font was not initialized");
+        }
+
+        unsafe {
+            let dc = GetDC(ptr::null_mut());
+            let (width, height) = rh::text_extent(dc, self.handle, text);
+            ReleaseDC(ptr::null_mut(), dc);
+
+            (width.max(0) as u32, height.max(0) as u32)
+        }
+    }
+
     /// Set the default (application global!) font that will be used when creating controls and return the old one
     pub fn set_global_default(font: Option<Font>) -> Option<Font> {
         let mut global_font = DEFAULT_FONT.lock().unwrap();
@@ -150,6 +375,9 @@ impl Font {
     /**
         Add a font resource from a binary source. Returns a memory font handle if the font was loaded succesfully.
         Send the handle to `remove_memory_font` at the end of your program to free the font from memory.
+
+        The fonts are selected by family name, like the system fonts. Use `MemFont::families` to get the names
+        of the fonts in `bin`. Memory fonts are private to the application, but they are listed by `Font::families`.
     */
     pub fn add_memory_font(bin: &mut [u8]) -> Result<MemFont, ()> {
         use winapi::um::wingdi::AddFontMemResourceEx;
@@ -166,6 +394,12 @@ impl Font {
         };
 
         if num_fonts > 0 {
+            let families = rh::font_families(bin);
+            MEMORY_FONTS
+                .lock()
+                .unwrap()
+                .push((handle as usize, families));
+
             Ok(MemFont(handle))
         } else {
             Err(())
@@ -176,12 +410,17 @@ impl Font {
     pub fn remove_memory_font(font: MemFont) {
         use winapi::um::wingdi::RemoveFontMemResourceEx;
 
+        MEMORY_FONTS
+            .lock()
+            .unwrap()
+            .retain(|(handle, _)| *handle != font.0 as usize);
+
         unsafe {
             RemoveFontMemResourceEx(font.0);
         }
     }
 
-    /// Returns all the font families loaded on the OS.
+    /// Returns all the font families loaded on the OS and the families of the fonts added with `Font::add_memory_font`.
     /// Probably pretty slow, so cache the value if possible
     pub fn families() -> Vec<String> {
         use std::mem;
@@ -223,6 +462,14 @@ impl Font {
             );
         }
 
+        for (_, memory_families) in MEMORY_FONTS.lock().unwrap().iter() {
+            for family in memory_families {
+                if !families.contains(family) {
+                    families.push(family.clone());
+                }
+            }
+        }
+
         families.shrink_to_fit();
         families
     }
@@ -244,19 +491,39 @@ Parameters:
     - size_absolute:  Size of the font. The font mapper transforms this value into device units and matches its absolute value against the character height of the available fonts.
     - weight: Weight of the font. A value betweem 0 and 1000. 0 use the system default, 100 is very thin, 1000 is very bold.
     - family: Family name of the font (ex: Arial). Can be None to use the system default.
+    - italic: If the font is italic. Defaults to false.
+    - underline: If the font is underlined. Defaults to false.
+    - strikeout: If the font is struck out. Defaults to false.
+    - quality: The output quality of the font. Defaults to `FontQuality::ClearType`.
+    - charset: The character set of the font, one of the `*_CHARSET` values of winapi. Defaults to `DEFAULT_CHARSET`.
+    - pitch: The pitch of the font. Defaults to `FontPitch::Variable`.
 */
 pub struct FontBuilder<'a> {
     size: Option<i32>,
     weight: u32,
     family: Option<&'a str>,
+    italic: bool,
+    underline: bool,
+    strikeout: bool,
+    quality: FontQuality,
+    charset: u8,
+    pitch: FontPitch,
 }
 
 impl<'a> FontBuilder<'a> {
     pub fn new() -> FontBuilder<'a> {
+        use winapi::um::wingdi::DEFAULT_CHARSET;
+
         FontBuilder {
             size: None,
             weight: 0,
             family: None,
+            italic: false,
+            underline: false,
+            strikeout: false,
+            quality: FontQuality::ClearType,
+            charset: DEFAULT_CHARSET as u8,
+            pitch: FontPitch::Variable,
         }
     }
 
@@ -280,15 +547,60 @@ impl<'a> FontBuilder<'a> {
         self
     }
 
+    pub fn italic(mut self, italic: bool) -> FontBuilder<'a> {
+        self.italic = italic;
+        self
+    }
+
+    pub fn underline(mut self, underline: bool) -> FontBuilder<'a> {
+        self.underline = underline;
+        self
+    }
+
+    pub fn strikeout(mut self, strikeout: bool) -> FontBuilder<'a> {
+        self.strikeout = strikeout;
+        self
+    }
+
+    pub fn quality(mut self, quality: FontQuality) -> FontBuilder<'a> {
+        self.quality = quality;
+        self
+    }
+
+    pub fn charset(mut self, charset: u8) -> FontBuilder<'a> {
+        self.charset = charset;
+        self
+    }
+
+    pub fn pitch(mut self, pitch: FontPitch) -> FontBuilder<'a> {
+        self.pitch = pitch;
+        self
+    }
+
     pub fn build(self, font: &mut Font) -> Result<(), NwgError> {
-        font.handle = unsafe {
-            rh::build_font(
-                self.size.unwrap_or(0),
-                self.weight,
-                [false, false, false],
-                self.family,
-            )
-        }?;
+        use winapi::um::wingdi::{CLIP_DEFAULT_PRECIS, OUT_DEFAULT_PRECIS};
+
+        let (height, _) = unsafe { high_dpi::logical_to_physical(self.size.unwrap_or(0), 0) };
+
+        let info = FontInfo {
+            point_size: 0,
+            height,
+            width: 0,
+            escapement: 0,
+            orientation: 0,
+            weight: self.weight as i32,
+            italic: self.italic,
+            underline: self.underline,
+            strike_out: self.strikeout,
+            char_set: self.charset,
+            out_precision: OUT_DEFAULT_PRECIS as u8,
+            clip_precision: CLIP_DEFAULT_PRECIS as u8,
+            quality: self.quality as u8,
+            pitch_and_family: self.pitch as u8,
+            name: self.family.unwrap_or("").to_owned(),
+        };
+
+        font.handle = unsafe { rh::build_font(&info) }?;
 
         Ok(())
     }
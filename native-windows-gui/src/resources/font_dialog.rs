@@ -47,26 +47,7 @@ impl FontDialog {
         let data: &InnerFontDialog = &self.data.borrow();
         let font: &LOGFONTW = &data.font;
 
-        let end = font.lfFaceName.iter().position(|&c| c == 0).unwrap_or(0);
-        let name = String::from_utf16(&font.lfFaceName[0..end]).unwrap_or("ERROR".to_string());
-
-        FontInfo {
-            point_size: data.dialog.iPointSize as u32,
-            height: font.lfHeight as i32,
-            width: font.lfWidth as i32,
-            escapement: font.lfEscapement as i32,
-            orientation: font.lfOrientation as i32,
-            weight: font.lfWeight as i32,
-            italic: font.lfItalic == 1,
-            underline: font.lfUnderline == 1,
-            strike_out: font.lfStrikeOut == 1,
-            char_set: font.lfCharSet as u8,
-            out_precision: font.lfOutPrecision as u8,
-            clip_precision: font.lfClipPrecision as u8,
-            quality: font.lfQuality as u8,
-            pitch_and_family: font.lfPitchAndFamily as u8,
-            name,
-        }
+        FontInfo::from_logfont(font, data.dialog.iPointSize as u32)
     }
 }
 
@@ -1,5 +1,5 @@
-use crate::win32::resources_helper as rh;
-use crate::{NwgError, OemIcon, OemImage};
+use crate::win32::{high_dpi, resources_helper as rh};
+use crate::{ImageSet, NwgError, OemIcon, OemImage};
 use std::ptr;
 use winapi::um::winnt::HANDLE;
 use winapi::um::winuser::IMAGE_ICON;
@@ -18,6 +18,9 @@ To display a icon in an application, see the `ImageFrame` control.
 
 Note: Loading an icon from binary source (source_bin) REQUIRES the "image-decoder" feature.
 
+Icons can be converted back to bitmaps using the "copy_as_bitmap" function, and saved to an icon file with "save"
+(REQUIRES the "image-decoder" feature).
+
 **Builder parameters:**
   * `source_file`:      The source of the icon if it is a file.
   * `source_bin`:       The source of the icon if it is a binary blob. For example using `include_bytes!("my_icon.ico")`.
@@ -25,7 +28,8 @@ Note: Loading an icon from binary source (source_bin) REQUIRES the "image-decode
   * `source_embed`:     The source of the icon if it is stored in an embedded file
   * `source_embed_id`:  The number identifier of the icon in the embedded file
   * `source_embed_str`: The string identifier of the icon in the embedded file
-  * `size`:             Optional. Resize the image to this size.
+  * `source_set`:       The source of the icon if it is an image set. The variant that best matches `size` at the current DPI is used.
+  * `size`:             Optional. Resize the image to this size. With `source_set`, the size is logical (scaled by the DPI) and defaults to `(32, 32)`.
   * `strict`:           Use a system placeholder instead of panicking if the image source do no exists.
 
 Example:
@@ -61,6 +65,7 @@ impl Icon {
             source_text: None,
             source_bin: None,
             source_system: None,
+            source_set: None,
 
             #[cfg(feature = "embed-resource")]
             source_embed: None,
@@ -145,12 +150,55 @@ impl Icon {
 
         Ok(icon)
     }
+
+    /**
+        Creates a new bitmap from the icon data. The icon mask becomes the bitmap alpha channel.
+        This is the inverse of `Bitmap::copy_as_icon`.
+
+        Panics if the icon is not initialized
+    */
+    pub fn copy_as_bitmap(&self) -> Result<crate::Bitmap, NwgError> {
+        if self.handle.is_null() {
+            panic!("Icon was not initialized");
+        }
+
+        let handle = unsafe {
+            let (size, pixels) = rh::icon_to_rgba(self.handle)?;
+            rh::bitmap_from_rgba(size, &pixels)?
+        };
+
+        Ok(crate::Bitmap {
+            handle,
+            owned: true,
+        })
+    }
+
+    /**
+        Save the icon to a file. Files ending with `.ico` are saved as an icon file with a PNG image,
+        other extensions are saved as an image like `Bitmap::save`.
+
+        Requires the `image-decoder` feature.
+
+        Panics if the icon is not initialized
+    */
+    #[cfg(feature = "image-decoder")]
+    pub fn save<'a>(&self, path: &'a str) -> Result<(), NwgError> {
+        use crate::win32::image_decoder as img;
+
+        if self.handle.is_null() {
+            panic!("Icon was not initialized");
+        }
+
+        let (size, pixels) = unsafe { rh::icon_to_rgba(self.handle)? };
+        img::save_rgba(path, size, &pixels)
+    }
 }
 
 pub struct IconBuilder<'a> {
     source_text: Option<&'a str>,
     source_bin: Option<&'a [u8]>,
     source_system: Option<OemIcon>,
+    source_set: Option<&'a ImageSet>,
 
     #[cfg(feature = "embed-resource")]
     source_embed: Option<&'a EmbedResource>,
@@ -181,6 +229,11 @@ impl<'a> IconBuilder<'a> {
         self
     }
 
+    pub fn source_set(mut self, t: Option<&'a ImageSet>) -> IconBuilder<'a> {
+        self.source_set = t;
+        self
+    }
+
     #[cfg(feature = "embed-resource")]
     pub fn source_embed(mut self, em: Option<&'a EmbedResource>) -> IconBuilder<'a> {
         self.source_embed = em;
@@ -228,6 +281,10 @@ impl<'a> IconBuilder<'a> {
                 handle,
                 owned: true,
             };
+        } else if let Some(set) = self.source_set {
+            let (w, h) = self.size.unwrap_or((32, 32));
+            let (w, h) = unsafe { high_dpi::logical_to_physical(w as i32, h as i32) };
+            *b = set.icon((w as u32, h as u32))?;
         } else {
             #[cfg(feature = "embed-resource")]
             fn build_embed(builder: IconBuilder) -> Result<Icon, NwgError> {
@@ -1,6 +1,6 @@
 use crate::win32::image_decoder as img;
 use crate::{Bitmap, NwgError};
-use std::{mem, ptr};
+use std::{mem, ptr, time::Duration};
 use winapi::shared::winerror::S_OK;
 use winapi::um::wincodec::{
     IWICBitmapDecoder, IWICBitmapSource, IWICImagingFactory, WICPixelFormatGUID,
@@ -16,6 +16,8 @@ use winapi::um::wincodec::{
 
     Images loaded from a decoder cannot be used as-is by an image frame. They must first be converted to a bitmap resource.
 
+    To write images back to a file or to memory, see `ImageEncoder`.
+
     ```rust
     use native_windows_gui as nwg;
     fn open_image(decoder: &nwg::ImageDecoder) -> Result<nwg::ImageData, nwg::NwgError> {
@@ -131,6 +133,16 @@ impl ImageSource {
         }
     }
 
+    /**
+        Return the animation metadata of the requested frame: its delay, its disposal, and where it is drawn on the image.
+        Formats without animation metadata return a frame that covers the whole image with no delay.
+
+        To play an animated image, see `AnimatedImage`.
+    */
+    pub fn frame_info(&self, index: u32) -> Result<FrameInfo, NwgError> {
+        unsafe { img::frame_info(&*self.decoder, index) }
+    }
+
     /*  Retrieves the container format of the image source.
 
         See https://docs.microsoft.com/en-us/windows/win32/wic/-wic-guids-clsids#container-formats
@@ -270,6 +282,38 @@ impl ImageData {
     }
 }
 
+/// What happens to the area covered by a frame of an animated image before the next frame is drawn
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameDisposal {
+    /// The frame is left in place
+    None,
+
+    /// The area of the frame is cleared to transparent
+    Background,
+
+    /// The area of the frame is restored to what it was before the frame was drawn
+    Previous,
+}
+
+/// Animation metadata of a frame. See `ImageSource::frame_info`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// How long the frame is shown
+    pub delay: Duration,
+
+    /// What happens to the frame before the next frame is drawn
+    pub disposal: FrameDisposal,
+
+    /// The position of the frame on the image
+    pub position: (u32, u32),
+
+    /// The size of the frame
+    pub size: (u32, u32),
+
+    /// If the frame is blended over the previous frames. If false, the frame replaces the pixels it covers.
+    pub blend: bool,
+}
+
 /// A list of container format implemented in WIC
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ContainerFormat {
@@ -284,6 +328,30 @@ pub enum ContainerFormat {
     Wmp,
 }
 
+impl ContainerFormat {
+    /// Guess the container format from the extension of a file path. Returns `Unknown` if the extension is not recognized.
+    pub fn from_path<'a>(path: &'a str) -> ContainerFormat {
+        use ContainerFormat::*;
+
+        let extension = std::path::Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("dng") => Adng,
+            Some("bmp") | Some("dib") => Bmp,
+            Some("png") => Png,
+            Some("ico") => Ico,
+            Some("jpg") | Some("jpeg") | Some("jpe") | Some("jfif") => Jpeg,
+            Some("tif") | Some("tiff") => Tiff,
+            Some("gif") => Gif,
+            Some("wdp") | Some("jxr") | Some("hdp") => Wmp,
+            _ => Unknown,
+        }
+    }
+}
+
 //
 // IMPL
 //
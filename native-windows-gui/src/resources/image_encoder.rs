@@ -0,0 +1,274 @@
+use crate::win32::image_decoder as img;
+use crate::win32::resources_helper as rh;
+use crate::{Bitmap, ContainerFormat, ImageData, NwgError};
+use std::ptr;
+use winapi::um::objidlbase::IStream;
+use winapi::um::wincodec::{IWICBitmapEncoder, IWICBitmapSource, IWICImagingFactory};
+
+/**
+    A image encoder. Writes images to a file or to memory in any of the formats that have a native WIC encoder:
+    `PNG, JPEG, BMP, TIFF, GIF` and `WMP` (JPEG XR).
+
+    Like the ImageDecoder, ImageEncoder do not take any parameter to build, but it still provides a builder API to match the other component of NWG.
+    You can also use "ImageEncoder::new" to avoid the builder API.
+
+    Writing an image is done in three steps: open a target with `to_filename` or `to_stream`, add one or more frames
+    (from an `ImageData`, a `Bitmap`, or raw RGBA pixels), then `commit` the target. Only GIF and TIFF support more than one frame.
+
+    For the most common cases, `Bitmap::save` does all of this in one call.
+
+    Requires the `image-decoder` feature.
+
+    ```rust
+    use native_windows_gui as nwg;
+    fn save_thumbnail(encoder: &nwg::ImageEncoder, image: &nwg::ImageData) -> Result<(), nwg::NwgError> {
+        let mut target = encoder.to_filename("thumbnail.jpg", nwg::ContainerFormat::Jpeg)?;
+        target.set_quality(Some(0.8));
+        target.set_metadata("/app1/ifd/{ushort=270}", "A thumbnail");
+        target.add_frame(image)?;
+        target.commit()?;
+        Ok(())
+    }
+    ```
+
+    ```rust
+    use native_windows_gui as nwg;
+    fn png_bytes(encoder: &nwg::ImageEncoder, pixels: &[u8]) -> Result<Vec<u8>, nwg::NwgError> {
+        let target = encoder.to_stream(nwg::ContainerFormat::Png)?;
+        target.add_pixels((16, 16), pixels)?;
+        target.commit()
+    }
+    ```
+*/
+pub struct ImageEncoder {
+    pub factory: *mut IWICImagingFactory,
+}
+
+impl ImageEncoder {
+    pub fn new() -> Result<ImageEncoder, NwgError> {
+        let factory = unsafe { img::create_image_factory() }?;
+        Ok(ImageEncoder { factory })
+    }
+
+    pub fn builder() -> ImageEncoderBuilder {
+        ImageEncoderBuilder {}
+    }
+
+    /**
+        Create a target that writes an image to a file. The file is created or replaced.
+        The file is complete once the target is committed.
+
+        * If the file cannot be opened or if the format cannot be encoded, returns a NwgError.
+        * If the image encoder was not initialized, this method panics
+    */
+    pub fn to_filename<'a>(
+        &self,
+        path: &'a str,
+        format: ContainerFormat,
+    ) -> Result<ImageTarget, NwgError> {
+        if self.factory.is_null() {
+            panic!("ImageEncoder is not yet bound to a winapi object");
+        }
+
+        unsafe {
+            let stream = img::create_file_stream(&*self.factory, path)?;
+            self.target(stream, format, false)
+        }
+    }
+
+    /**
+        Create a target that writes an image in memory. `commit` returns the encoded bytes.
+
+        * If the format cannot be encoded, returns a NwgError.
+        * If the image encoder was not initialized, this method panics
+    */
+    pub fn to_stream(&self, format: ContainerFormat) -> Result<ImageTarget, NwgError> {
+        if self.factory.is_null() {
+            panic!("ImageEncoder is not yet bound to a winapi object");
+        }
+
+        unsafe {
+            let stream = img::create_memory_stream()?;
+            self.target(stream, format, true)
+        }
+    }
+
+    unsafe fn target(
+        &self,
+        stream: *mut IStream,
+        format: ContainerFormat,
+        in_memory: bool,
+    ) -> Result<ImageTarget, NwgError> {
+        let encoder = match img::create_encoder(&*self.factory, format, stream) {
+            Ok(encoder) => encoder,
+            Err(e) => {
+                (&*stream).Release();
+                return Err(e);
+            }
+        };
+
+        (&*self.factory).AddRef();
+
+        Ok(ImageTarget {
+            encoder,
+            factory: self.factory,
+            stream,
+            in_memory,
+            quality: None,
+            metadata: Vec::new(),
+        })
+    }
+}
+
+/**
+    Represents an image being written by an `ImageEncoder`. Frames are added with `add_frame`, `add_bitmap` or `add_pixels`,
+    and the image is written when the target is committed. Dropping a target without committing it discards the image.
+
+    The quality and the metadata apply to the frames added after they are set.
+*/
+pub struct ImageTarget {
+    pub encoder: *mut IWICBitmapEncoder,
+    factory: *mut IWICImagingFactory,
+    stream: *mut IStream,
+    in_memory: bool,
+    quality: Option<f32>,
+    metadata: Vec<(String, String)>,
+}
+
+impl ImageTarget {
+    /**
+        Set the quality of the lossy formats (JPEG and WMP), from 0.0 (smallest file) to 1.0 (best quality).
+        `None` uses the encoder default. Adding a frame fails if the format does not support this option.
+    */
+    pub fn set_quality(&mut self, quality: Option<f32>) {
+        self.quality = quality;
+    }
+
+    /**
+        Set a text metadata value with a WIC metadata query. The query depends on the format, for example:
+
+        * PNG: `/tEXt/{str=Description}`
+        * JPEG: `/app1/ifd/{ushort=270}` (the EXIF image description)
+        * TIFF: `/ifd/{ushort=270}`
+
+        See https://docs.microsoft.com/en-us/windows/win32/wic/-wic-native-image-format-metadata-queries
+
+        Adding a frame fails if the format does not support metadata (BMP) or if the query is not valid.
+    */
+    pub fn set_metadata<'a>(&mut self, query: &'a str, value: &'a str) {
+        match self.metadata.iter_mut().find(|(q, _)| q == query) {
+            Some(m) => m.1 = value.to_owned(),
+            None => self.metadata.push((query.to_owned(), value.to_owned())),
+        }
+    }
+
+    /// Remove the metadata set with `set_metadata`
+    pub fn clear_metadata(&mut self) {
+        self.metadata.clear();
+    }
+
+    /**
+        Add a frame from an image loaded by an `ImageDecoder`. The pixels are converted to a format supported by the encoder.
+    */
+    pub fn add_frame(&self, image: &ImageData) -> Result<(), NwgError> {
+        unsafe { self.encode(image.frame) }
+    }
+
+    /**
+        Add a frame from a bitmap resource.
+    */
+    pub fn add_bitmap(&self, bitmap: &Bitmap) -> Result<(), NwgError> {
+        if bitmap.handle.is_null() {
+            panic!("Bitmap was not initialized");
+        }
+
+        let (size, rgba) = unsafe { rh::bitmap_to_rgba(bitmap.handle as _)? };
+        self.add_pixels(size, &rgba)
+    }
+
+    /**
+        Add a frame from RGBA pixels (4 bytes per pixel, straight alpha, rows from top to bottom).
+        Returns an error if the buffer size does not match `size`.
+    */
+    pub fn add_pixels(&self, size: (u32, u32), rgba: &[u8]) -> Result<(), NwgError> {
+        unsafe {
+            let source = img::create_source_from_rgba(&*self.factory, size, rgba)?;
+            let result = self.encode(source);
+            (&*source).Release();
+            result
+        }
+    }
+
+    /**
+        Write the image. For targets created with `to_stream`, returns the encoded bytes.
+        For targets created with `to_filename`, returns an empty buffer.
+    */
+    pub fn commit(self) -> Result<Vec<u8>, NwgError> {
+        unsafe {
+            let result = (&*self.encoder).Commit();
+            if result != winapi::shared::winerror::S_OK {
+                return Err(NwgError::image_encoder(result, "Could not write the image"));
+            }
+
+            match self.in_memory {
+                true => img::read_stream(&*self.stream),
+                false => Ok(Vec::new()),
+            }
+        }
+    }
+
+    unsafe fn encode(&self, source: *mut IWICBitmapSource) -> Result<(), NwgError> {
+        img::encode_frame(
+            &*self.factory,
+            &*self.encoder,
+            source,
+            self.quality,
+            &self.metadata,
+        )
+    }
+}
+
+//
+// IMPL
+//
+
+impl Default for ImageEncoder {
+    fn default() -> ImageEncoder {
+        ImageEncoder {
+            factory: ptr::null_mut(),
+        }
+    }
+}
+
+impl Drop for ImageEncoder {
+    fn drop(&mut self) {
+        if !self.factory.is_null() {
+            unsafe {
+                (&*self.factory).Release();
+            }
+        }
+    }
+}
+
+impl Drop for ImageTarget {
+    fn drop(&mut self) {
+        unsafe {
+            (&*self.encoder).Release();
+            (&*self.stream).Release();
+            (&*self.factory).Release();
+        }
+    }
+}
+
+/**
+    A blank builder for the image encoder
+*/
+pub struct ImageEncoderBuilder {}
+
+impl ImageEncoderBuilder {
+    pub fn build(self, out: &mut ImageEncoder) -> Result<(), NwgError> {
+        let factory = unsafe { img::create_image_factory() }?;
+        *out = ImageEncoder { factory };
+        Ok(())
+    }
+}
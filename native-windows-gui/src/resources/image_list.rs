@@ -1,7 +1,12 @@
-use crate::{Bitmap, Icon, NwgError};
-use std::ptr;
-use winapi::shared::windef::{HBITMAP, HICON};
-use winapi::um::commctrl::{ImageList_AddMasked, HIMAGELIST};
+use crate::controls::ControlHandle;
+use crate::win32::{high_dpi, resources_helper as rh};
+use crate::{unbind_raw_event_handler, Bitmap, Icon, ImageSet, NwgError, RawEventHandler};
+use std::{cell::RefCell, ptr, rc::Rc};
+use winapi::shared::windef::{HBITMAP, HICON, HWND};
+use winapi::um::commctrl::{ImageList_AddMasked, ImageList_SetOverlayImage, HIMAGELIST};
+
+#[cfg(feature = "image-decoder")]
+use crate::ImageData;
 
 const NOT_BOUND: &'static str = "ImageList is not yet bound to a winapi object";
 
@@ -13,10 +18,22 @@ There are two kinds of image list in Winapi: masked. This is a wrapper over the
 
 Image list and the method that use them in controls are behind the "image-list" feature.
 
+Images with an alpha channel (from an `ImageSet`, or decoded with the `ImageDecoder`) keep their transparency.
+Images can be copied back out with `icon` and `bitmap`, used as overlays (`set_overlay`), or dragged (`begin_drag`).
+The icons used by Explorer are available in the shared system image list (`ImageList::system`).
+
+**DPI awareness:**
+A DPI aware image list keeps a copy of every image it holds as an `ImageSet`. Its size is logical and is scaled
+by the DPI. When the DPI changes (see `set_dpi` and `follow_dpi`), every image is rendered again from the
+variant of its set that best matches the new size, so a 16px list becomes a sharp 24px list at 150%.
+`follow_dpi` requires a per-monitor DPI aware process (see the method documentation).
+Images added from icon files keep all the sizes stored in the file.
+
 **Builder parameters:**
-  * `size`:    The size size of the images in the image list. Default `(32, 32)`
-  * `initial`: The initial size (in images) of the image list. Default `5`
-  * `grow`:    The number of images by which the image list can grow when the system needs to make room for new images. Default `5`
+  * `size`:      The size size of the images in the image list. Default `(32, 32)`
+  * `initial`:   The initial size (in images) of the image list. Default `5`
+  * `grow`:      The number of images by which the image list can grow when the system needs to make room for new images. Default `5`
+  * `dpi_aware`: If the size is logical and the images are rendered again when the DPI changes. Default `false`
 
 ```rust
 use native_windows_gui as nwg;
@@ -27,12 +44,60 @@ fn build_image_list(list: &mut nwg::ImageList) {
         .grow(1)
         .build(list);
 }
+
+fn build_dpi_aware_list(list: &mut nwg::ImageList, window: &nwg::Window) {
+    nwg::ImageList::builder()
+        .size((16, 16))
+        .dpi_aware(true)
+        .build(list)
+        .unwrap();
+
+    let open = nwg::ImageSet::from_files(&["open_16.png", "open_24.png", "open_32.png"]).unwrap();
+    list.add_set(&open).unwrap();
+    list.follow_dpi(window);
+}
 ```
 
 */
 pub struct ImageList {
     pub handle: HIMAGELIST,
     pub owned: bool,
+    dpi: Option<Rc<RefCell<DpiImages>>>,
+    handler0: RefCell<Option<RawEventHandler>>,
+    /// The list is an `IImageList` returned by `SHGetImageList` and must be released
+    shell: bool,
+}
+
+/// The images of a DPI aware image list
+struct DpiImages {
+    size: (i32, i32),
+    dpi: u32,
+    images: Vec<ImageSet>,
+    overlays: Vec<(i32, u8)>,
+}
+
+impl DpiImages {
+    fn physical_size(&self) -> (u32, u32) {
+        let scale = |v: i32| ((v as i64 * self.dpi as i64 + 48) / 96).max(1) as u32;
+        (scale(self.size.0), scale(self.size.1))
+    }
+
+    /// Clear the image list and render every image again
+    unsafe fn render(&self, handle: HIMAGELIST) -> Result<(), NwgError> {
+        use winapi::um::commctrl::ImageList_SetIconSize;
+
+        let (w, h) = self.physical_size();
+        ImageList_SetIconSize(handle, w as i32, h as i32);
+        for set in self.images.iter() {
+            render_set(handle, set, (w, h), None)?;
+        }
+
+        for &(index, overlay) in self.overlays.iter() {
+            ImageList_SetOverlayImage(handle, index, overlay as i32);
+        }
+
+        Ok(())
+    }
 }
 
 impl ImageList {
@@ -41,6 +106,18 @@ impl ImageList {
             size: (32, 32),
             initial: 5,
             grow: 5,
+            dpi_aware: false,
+        }
+    }
+
+    /// An image list that is owned by a control
+    pub(crate) fn borrowed(handle: HIMAGELIST) -> ImageList {
+        ImageList {
+            handle,
+            owned: false,
+            dpi: None,
+            handler0: RefCell::new(None),
+            shell: false,
         }
     }
 
@@ -60,7 +137,11 @@ impl ImageList {
         size
     }
 
-    /// Sets the size of the image list. This clears all current image data.
+    /**
+        Sets the size of the image list. This clears all current image data.
+
+        The images of a DPI aware image list are not cleared. The size is logical and every image is rendered again at the new size.
+    */
     pub fn set_size(&self, size: (i32, i32)) {
         use winapi::um::commctrl::ImageList_SetIconSize;
 
@@ -68,6 +149,15 @@ impl ImageList {
             panic!("{}", NOT_BOUND);
         }
 
+        if let Some(dpi) = self.dpi.as_ref() {
+            let mut dpi = dpi.borrow_mut();
+            dpi.size = size;
+            unsafe {
+                drop(dpi.render(self.handle));
+            }
+            return;
+        }
+
         let (w, h) = size;
         unsafe {
             ImageList_SetIconSize(self.handle, w, h);
@@ -94,6 +184,14 @@ impl ImageList {
             panic!("Bitmap was not initialized");
         }
 
+        if self.dpi.is_some() {
+            let mut set = ImageSet::default();
+            return match set.add_bitmap(bitmap) {
+                Ok(()) => self.add_set(&set).unwrap_or(-1),
+                Err(_) => -1,
+            };
+        }
+
         unsafe { ImageList_AddMasked(self.handle, bitmap.handle as HBITMAP, 0) }
     }
 
@@ -106,6 +204,10 @@ impl ImageList {
             panic!("{}", NOT_BOUND);
         }
 
+        if self.dpi.is_some() {
+            return self.add_set(&ImageSet::from_file(filename)?);
+        }
+
         let (w, h) = self.size();
         let mut bitmap = Bitmap::default();
         Bitmap::builder()
@@ -135,6 +237,14 @@ impl ImageList {
             panic!("Icon was not initialized");
         }
 
+        if self.dpi.is_some() {
+            let mut set = ImageSet::default();
+            return match set.add_icon(icon) {
+                Ok(()) => self.add_set(&set).unwrap_or(-1),
+                Err(_) => -1,
+            };
+        }
+
         // Extract the bitmap from the icon
         // Can't use `ImageList_AddIcon` because it doesn't always guess the mask
         unsafe {
@@ -159,6 +269,10 @@ impl ImageList {
             panic!("{}", NOT_BOUND);
         }
 
+        if self.dpi.is_some() {
+            return self.add_set(&ImageSet::from_file(filename)?);
+        }
+
         let (w, h) = self.size();
         let mut icon = Icon::default();
         Icon::builder()
@@ -184,6 +298,26 @@ impl ImageList {
             panic!("{}", NOT_BOUND);
         }
 
+        if let Some(dpi) = self.dpi.as_ref() {
+            let mut dpi = dpi.borrow_mut();
+            match index < 0 {
+                true => {
+                    dpi.images.clear();
+                    dpi.overlays.clear();
+                }
+                false if (index as usize) < dpi.images.len() => {
+                    dpi.images.remove(index as usize);
+                    dpi.overlays.retain(|&(i, _)| i != index);
+                    for (i, _) in dpi.overlays.iter_mut() {
+                        if *i > index {
+                            *i -= 1;
+                        }
+                    }
+                }
+                false => {}
+            }
+        }
+
         unsafe {
             ImageList_Remove(self.handle, index);
         }
@@ -200,6 +334,14 @@ impl ImageList {
             panic!("Bitmap was not initialized");
         }
 
+        if self.dpi.is_some() {
+            let mut set = ImageSet::default();
+            if set.add_bitmap(bitmap).is_ok() {
+                drop(self.replace_set(index, &set));
+            }
+            return;
+        }
+
         unsafe {
             ImageList_Replace(
                 self.handle,
@@ -221,29 +363,479 @@ impl ImageList {
             panic!("Icon was not initialized");
         }
 
+        if self.dpi.is_some() {
+            let mut set = ImageSet::default();
+            if set.add_icon(icon).is_ok() {
+                drop(self.replace_set(index, &set));
+            }
+            return;
+        }
+
         unsafe {
             ImageList_ReplaceIcon(self.handle, index, icon.handle as HICON);
         }
     }
+
+    /**
+        Adds an image set to the image list. The variant that best matches the image list size is used, and its alpha channel is kept.
+        A DPI aware image list keeps the set to render it again when the DPI changes.
+
+        Returns the index to the image or an error if the set is empty.
+    */
+    pub fn add_set(&self, set: &ImageSet) -> Result<i32, NwgError> {
+        if self.handle.is_null() {
+            panic!("{}", NOT_BOUND);
+        }
+
+        let (w, h) = self.size();
+        let index = unsafe { render_set(self.handle, set, (w as u32, h as u32), None)? };
+
+        if let Some(dpi) = self.dpi.as_ref() {
+            dpi.borrow_mut().images.push(set.clone());
+        }
+
+        Ok(index)
+    }
+
+    /// Replaces an image in the image list by an image set. Returns an error if the set is empty.
+    pub fn replace_set(&self, index: i32, set: &ImageSet) -> Result<(), NwgError> {
+        if self.handle.is_null() {
+            panic!("{}", NOT_BOUND);
+        }
+
+        let (w, h) = self.size();
+        unsafe {
+            render_set(self.handle, set, (w as u32, h as u32), Some(index))?;
+        }
+
+        if let Some(dpi) = self.dpi.as_ref() {
+            if let Some(image) = dpi.borrow_mut().images.get_mut(index as usize) {
+                *image = set.clone();
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+        Adds an image decoded by the `ImageDecoder`. The image is resized to the image list size
+        and its alpha channel is kept, for example for 32 bits PNG images.
+
+        Returns the index to the image or an error if the image pixels could not be read.
+    */
+    #[cfg(feature = "image-decoder")]
+    pub fn add_image(&self, image: &ImageData) -> Result<i32, NwgError> {
+        let mut set = ImageSet::default();
+        set.add_image(image)?;
+        self.add_set(&set)
+    }
+
+    /// Returns `true` if the image list was built with `dpi_aware`
+    pub fn dpi_aware(&self) -> bool {
+        self.dpi.is_some()
+    }
+
+    /**
+        Renders the images of a DPI aware image list for a new DPI (96 is 100%). Does nothing if the list is not DPI aware.
+
+        Controls do not notice that the size of their image list changed. The image list must be set again on
+        the controls that use it, or use `follow_dpi` which does it automatically.
+    */
+    pub fn set_dpi(&self, dpi: u32) {
+        if self.handle.is_null() {
+            panic!("{}", NOT_BOUND);
+        }
+
+        if let Some(images) = self.dpi.as_ref() {
+            let mut images = images.borrow_mut();
+            images.dpi = dpi;
+            unsafe {
+                drop(images.render(self.handle));
+            }
+        }
+    }
+
+    /**
+        Renders the images again every time the DPI of `window` changes (`WM_DPICHANGED`), and refreshes the
+        tree views, list views, tabs containers and extended combo boxes of the window that use this image list.
+        Does nothing if the list is not DPI aware.
+
+        Calling this function again follows another window instead. The window must be a top level window
+        and must outlive the image list.
+
+        Windows only sends `WM_DPICHANGED` to per-monitor DPI aware windows. `set_dpi_awareness` makes the process
+        system DPI aware, so the application manifest must declare per-monitor awareness
+        (`<dpiAwareness>PerMonitorV2</dpiAwareness>`) for this function to have any effect. Otherwise, call `set_dpi`.
+    */
+    pub fn follow_dpi<W: Into<ControlHandle>>(&self, window: W) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::shared::{basetsd::UINT_PTR, minwindef::LOWORD};
+        use winapi::um::winuser::WM_DPICHANGED;
+
+        if self.handle.is_null() {
+            panic!("{}", NOT_BOUND);
+        }
+
+        let images = match self.dpi.as_ref() {
+            Some(images) => images.clone(),
+            None => return,
+        };
+
+        let window = window.into();
+        if window.hwnd().is_none() {
+            panic!("follow_dpi requires a window");
+        }
+
+        let mut handler = self.handler0.borrow_mut();
+        if let Some(h) = handler.take() {
+            drop(unbind_raw_event_handler(&h));
+        }
+
+        let list = self.handle;
+        let new_handler =
+            bind_raw_event_handler_inner(&window, list as UINT_PTR, move |hwnd, msg, w, _l| {
+                if msg == WM_DPICHANGED {
+                    let mut images = images.borrow_mut();
+                    images.dpi = LOWORD(w as u32) as u32;
+                    unsafe {
+                        drop(images.render(list));
+                        refresh_controls(hwnd, list);
+                    }
+                }
+
+                None
+            });
+
+        *handler = new_handler.ok();
+    }
+
+    /**
+        Uses the image at `index` as an overlay. Overlays are drawn over the image of an item, for example
+        to show that a file is shared or modified. See `ListView::set_item_overlay` and `TreeView::set_item_overlay`.
+
+        `overlay` is the one-based index of the overlay slot. Image lists have 15 overlay slots,
+        but list views and tree views only draw the first 4. Returns an error if the slot is out of range
+        or if the image does not exist.
+    */
+    pub fn set_overlay(&self, index: i32, overlay: u8) -> Result<(), NwgError> {
+        if self.handle.is_null() {
+            panic!("{}", NOT_BOUND);
+        }
+        if overlay == 0 || overlay > 15 {
+            return Err(NwgError::resource_create(
+                "Overlay slots range from 1 to 15",
+            ));
+        }
+
+        if unsafe { ImageList_SetOverlayImage(self.handle, index, overlay as i32) } == 0 {
+            return Err(NwgError::resource_create("Failed to set the overlay image"));
+        }
+
+        if let Some(dpi) = self.dpi.as_ref() {
+            let mut dpi = dpi.borrow_mut();
+            dpi.overlays.retain(|&(_, o)| o != overlay);
+            dpi.overlays.push((index, overlay));
+        }
+
+        Ok(())
+    }
+
+    /// Returns a copy of the image at `index` as an icon, or `None` if the index is out of bounds
+    pub fn icon(&self, index: i32) -> Option<Icon> {
+        use winapi::um::commctrl::{ImageList_GetIcon, ILD_TRANSPARENT};
+
+        if self.handle.is_null() {
+            panic!("{}", NOT_BOUND);
+        }
+
+        let handle = unsafe { ImageList_GetIcon(self.handle, index, ILD_TRANSPARENT) };
+        match handle.is_null() {
+            true => None,
+            false => Some(Icon {
+                handle: handle as _,
+                owned: true,
+            }),
+        }
+    }
+
+    /**
+        Returns a copy of the image at `index` as a 32 bits bitmap with its transparency,
+        or `None` if the index is out of bounds
+    */
+    pub fn bitmap(&self, index: i32) -> Option<Bitmap> {
+        let icon = self.icon(index)?;
+        unsafe {
+            let (size, pixels) = rh::icon_to_rgba(icon.handle).ok()?;
+            rh::bitmap_from_rgba(size, &pixels)
+                .ok()
+                .map(|handle| Bitmap {
+                    handle,
+                    owned: true,
+                })
+        }
+    }
+
+    /**
+        Starts dragging the image at `index`. `hotspot` is the position of the cursor in the image.
+        Only one image can be dragged at a time.
+
+        The image is displayed with `ImageList::drag_enter`, moved with `ImageList::drag_move`
+        and the drag operation ends with `ImageList::end_drag`.
+    */
+    pub fn begin_drag(&self, index: i32, hotspot: (i32, i32)) -> Result<(), NwgError> {
+        use winapi::um::commctrl::ImageList_BeginDrag;
+
+        if self.handle.is_null() {
+            panic!("{}", NOT_BOUND);
+        }
+
+        let (x, y) = hotspot;
+        match unsafe { ImageList_BeginDrag(self.handle, index, x, y) } {
+            0 => Err(NwgError::resource_create(
+                "Failed to begin dragging the image",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /**
+        Displays the drag image at `position` in `window` and locks the window updates.
+        The position is relative to the upper-left corner of the window, not of its client area.
+    */
+    pub fn drag_enter<W: Into<ControlHandle>>(window: W, position: (i32, i32)) {
+        use winapi::um::commctrl::ImageList_DragEnter;
+
+        let window = window.into();
+        let hwnd = window.hwnd().expect("drag_enter requires a window");
+        let (x, y) = position;
+        unsafe {
+            ImageList_DragEnter(hwnd, x, y);
+        }
+    }
+
+    /// Moves the drag image to `position`, relative to the window passed to `drag_enter`
+    pub fn drag_move(position: (i32, i32)) {
+        use winapi::um::commctrl::ImageList_DragMove;
+
+        let (x, y) = position;
+        unsafe {
+            ImageList_DragMove(x, y);
+        }
+    }
+
+    /**
+        Shows or hides the drag image. The drag image must be hidden while the window is
+        repainted during a drag operation, for example when an item is highlighted.
+    */
+    pub fn drag_show(visible: bool) {
+        use winapi::um::commctrl::ImageList_DragShowNolock;
+
+        unsafe {
+            ImageList_DragShowNolock(visible as _);
+        }
+    }
+
+    /// Hides the drag image and unlocks the updates of `window`
+    pub fn drag_leave<W: Into<ControlHandle>>(window: W) {
+        use winapi::um::commctrl::ImageList_DragLeave;
+
+        let window = window.into();
+        let hwnd = window.hwnd().expect("drag_leave requires a window");
+        unsafe {
+            ImageList_DragLeave(hwnd);
+        }
+    }
+
+    /// Ends the drag operation started with `begin_drag`
+    pub fn end_drag() {
+        use winapi::um::commctrl::ImageList_EndDrag;
+
+        unsafe {
+            ImageList_EndDrag();
+        }
+    }
+
+    /**
+        Returns the system image list of the shell. It holds the icons that Explorer uses for
+        files, folders and drives, see `ImageList::system_index`.
+
+        The system image list is shared by every application and is not owned: it must never be modified.
+        The reference returned by the shell is released when the image list is dropped.
+    */
+    pub fn system(size: SystemImageSize) -> Result<ImageList, NwgError> {
+        use winapi::shared::guiddef::GUID;
+        use winapi::shared::winerror::S_OK;
+        use winapi::um::shellapi::{
+            SHGetImageList, SHIL_EXTRALARGE, SHIL_JUMBO, SHIL_LARGE, SHIL_SMALL,
+        };
+
+        // IID_IImageList. An `IImageList` pointer can be used as a `HIMAGELIST`.
+        const IID_IMAGE_LIST: GUID = GUID {
+            Data1: 0x46EB5926,
+            Data2: 0x582E,
+            Data3: 0x4017,
+            Data4: [0x9F, 0xDF, 0xE8, 0x99, 0x8D, 0xAA, 0x09, 0x50],
+        };
+
+        let kind = match size {
+            SystemImageSize::Small => SHIL_SMALL,
+            SystemImageSize::Large => SHIL_LARGE,
+            SystemImageSize::ExtraLarge => SHIL_EXTRALARGE,
+            SystemImageSize::Jumbo => SHIL_JUMBO,
+        };
+
+        let mut list = ptr::null_mut();
+        match unsafe { SHGetImageList(kind as i32, &IID_IMAGE_LIST, &mut list) } {
+            S_OK if !list.is_null() => {
+                let mut list = ImageList::borrowed(list as HIMAGELIST);
+                list.shell = true;
+                Ok(list)
+            }
+            _ => Err(NwgError::resource_create(
+                "Failed to load the system image list",
+            )),
+        }
+    }
+
+    /**
+        Returns the index of the icon of a file or a folder in the system image list.
+        Files that do not exist use the icon of their extension, so `"notes.txt"` returns the icon of text files.
+
+        Returns `None` if the shell does not know the file.
+    */
+    pub fn system_index(path: &str) -> Option<i32> {
+        super::shell_info::system_index(path)
+    }
+}
+
+/// The sizes of the system image list. See `ImageList::system`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SystemImageSize {
+    /// 16x16 pixels at 100% scaling
+    Small,
+    /// 32x32 pixels at 100% scaling
+    Large,
+    /// 48x48 pixels
+    ExtraLarge,
+    /// 256x256 pixels
+    Jumbo,
+}
+
+/**
+    Renders an image set at `size` and adds it to the image list, or replaces the image at `index`.
+    The DIB has straight alpha, which is what image lists expect.
+*/
+unsafe fn render_set(
+    handle: HIMAGELIST,
+    set: &ImageSet,
+    size: (u32, u32),
+    index: Option<i32>,
+) -> Result<i32, NwgError> {
+    use winapi::um::commctrl::{ImageList_Add, ImageList_Replace};
+    use winapi::um::wingdi::DeleteObject;
+
+    let pixels = set
+        .pixels(size)
+        .ok_or_else(|| NwgError::resource_create("The image set is empty"))?;
+    let bitmap = rh::dib_from_rgba(size, &pixels, false)? as HBITMAP;
+
+    let index = match index {
+        Some(index) => match ImageList_Replace(handle, index, bitmap, ptr::null_mut()) {
+            0 => -1,
+            _ => index,
+        },
+        None => ImageList_Add(handle, bitmap, ptr::null_mut()),
+    };
+
+    DeleteObject(bitmap as _);
+
+    match index {
+        -1 => Err(NwgError::resource_create(
+            "Failed to add the image to the image list",
+        )),
+        i => Ok(i),
+    }
+}
+
+/// Set the image list again on the children of `parent` that use it, so that they use the new image size
+unsafe fn refresh_controls(parent: HWND, list: HIMAGELIST) {
+    use winapi::shared::minwindef::{BOOL, LPARAM};
+    use winapi::um::winuser::EnumChildWindows;
+
+    unsafe extern "system" fn refresh(hwnd: HWND, list: LPARAM) -> BOOL {
+        use crate::win32::window_helper as wh;
+        use winapi::um::commctrl::{
+            CBEM_GETIMAGELIST, CBEM_SETIMAGELIST, LVM_GETIMAGELIST, LVM_SETIMAGELIST, LVSIL_NORMAL,
+            LVSIL_SMALL, LVSIL_STATE, TCM_GETIMAGELIST, TCM_SETIMAGELIST, TVM_GETIMAGELIST,
+            TVM_SETIMAGELIST, TVSIL_NORMAL, TVSIL_STATE,
+        };
+        use winapi::um::winuser::{GetClassNameW, InvalidateRect};
+
+        let mut class = [0u16; 64];
+        let length = GetClassNameW(hwnd, class.as_mut_ptr(), class.len() as i32);
+        let class = String::from_utf16_lossy(&class[..length.max(0) as usize]);
+
+        let (get, set, kinds): (u32, u32, &[usize]) = match class.as_str() {
+            "SysTreeView32" => (
+                TVM_GETIMAGELIST,
+                TVM_SETIMAGELIST,
+                &[TVSIL_NORMAL, TVSIL_STATE],
+            ),
+            "SysListView32" => (
+                LVM_GETIMAGELIST,
+                LVM_SETIMAGELIST,
+                &[
+                    LVSIL_NORMAL as usize,
+                    LVSIL_SMALL as usize,
+                    LVSIL_STATE as usize,
+                ],
+            ),
+            "SysTabControl32" => (TCM_GETIMAGELIST, TCM_SETIMAGELIST, &[0]),
+            "ComboBoxEx32" => (CBEM_GETIMAGELIST, CBEM_SETIMAGELIST, &[0]),
+            _ => return 1,
+        };
+
+        for &kind in kinds {
+            if wh::send_message(hwnd, get, kind, 0) == list {
+                // Controls ignore an image list that is already set
+                wh::send_message(hwnd, set, kind, 0);
+                wh::send_message(hwnd, set, kind, list);
+                InvalidateRect(hwnd, ptr::null(), 1);
+            }
+        }
+
+        1
+    }
+
+    EnumChildWindows(parent, Some(refresh), list as LPARAM);
 }
 
 impl Drop for ImageList {
     fn drop(&mut self) {
         use winapi::um::commctrl::ImageList_Destroy;
+        use winapi::um::unknwnbase::IUnknown;
+
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
         unsafe {
             if self.owned && !self.handle.is_null() {
                 ImageList_Destroy(self.handle);
             }
+
+            if self.shell && !self.handle.is_null() {
+                let list = self.handle as *mut IUnknown;
+                (&*list).Release();
+            }
         }
     }
 }
 
 impl Default for ImageList {
     fn default() -> ImageList {
-        ImageList {
-            handle: ptr::null_mut(),
-            owned: false,
-        }
+        ImageList::borrowed(ptr::null_mut())
     }
 }
 
@@ -257,6 +849,7 @@ pub struct ImageListBuilder {
     size: (i32, i32),
     initial: i32,
     grow: i32,
+    dpi_aware: bool,
 }
 
 impl ImageListBuilder {
@@ -275,18 +868,45 @@ impl ImageListBuilder {
         self
     }
 
+    pub fn dpi_aware(mut self, dpi_aware: bool) -> ImageListBuilder {
+        self.dpi_aware = dpi_aware;
+        self
+    }
+
     pub fn build(self, list: &mut ImageList) -> Result<(), NwgError> {
         use winapi::um::commctrl::{ImageList_Create, ILC_COLOR32, ILC_MASK};
 
+        let dpi = match self.dpi_aware {
+            true => Some(DpiImages {
+                size: self.size,
+                dpi: (high_dpi::scale_factor() * 96.0).round() as u32,
+                images: Vec::new(),
+                overlays: Vec::new(),
+            }),
+            false => None,
+        };
+
+        let (w, h) = match dpi.as_ref() {
+            Some(dpi) => {
+                let (w, h) = dpi.physical_size();
+                (w as i32, h as i32)
+            }
+            None => self.size,
+        };
+
         unsafe {
-            let (w, h) = self.size;
             let handle = ImageList_Create(w, h, ILC_COLOR32 | ILC_MASK, self.initial, self.grow);
             if handle.is_null() {
                 return Err(NwgError::resource_create("Failed to create image list"));
             }
 
-            list.handle = handle;
-            list.owned = true;
+            *list = ImageList {
+                handle,
+                owned: true,
+                dpi: dpi.map(|dpi| Rc::new(RefCell::new(dpi))),
+                handler0: RefCell::new(None),
+                shell: false,
+            };
         }
 
         Ok(())
@@ -0,0 +1,319 @@
+use crate::win32::resources_helper as rh;
+use crate::{Bitmap, Icon, NwgError};
+use std::rc::Rc;
+
+#[cfg(feature = "image-decoder")]
+use crate::ImageData;
+
+#[derive(Clone)]
+struct ImageVariant {
+    size: (u32, u32),
+    pixels: Vec<u8>,
+}
+
+/**
+A set of variants of the same image at different sizes. Image sets are used to create icons, bitmaps
+and image lists that look sharp at any DPI: the variant that best matches the requested size is picked,
+and it is scaled if no variant has the exact size.
+
+Icon files (`.ico`) load every image they contain. Other image types (PNG, BMP, JPEG...) add a single variant
+and require the `image-decoder` feature. Several files can be combined into a single set, for example
+`open_16.png`, `open_32.png` and `open_48.png`.
+
+The variants are kept in memory as RGBA pixels. An image set is a reference counted resource: cloning it
+does not copy the pixels.
+
+Image sets are used by:
+  * `Icon::builder().source_set(...)` and `Bitmap::builder().source_set(...)`
+  * `ImageList::add_set`. DPI aware image lists render their images again from their sets when the DPI changes.
+
+**Builder parameters:**
+  * `source_file`:   A file to load. Icon files add all their images.
+  * `source_bin`:    A binary blob to load. For example using `include_bytes!("open.ico")`.
+  * `source_files`:  Several files to load, usually the same image at different sizes.
+  * `source_bins`:   Several binary blobs to load.
+
+A set built without any source is empty. Images can be added later with `add_file`, `add_bin` or `add_pixels`.
+
+Example:
+
+```rust
+use native_windows_gui as nwg;
+
+fn load_set() -> nwg::ImageSet {
+    nwg::ImageSet::from_file("open.ico").unwrap()
+}
+
+fn load_set_builder() -> nwg::ImageSet {
+    let mut set = nwg::ImageSet::default();
+
+    nwg::ImageSet::builder()
+        .source_files(&["open_16.png", "open_32.png", "open_48.png"])
+        .build(&mut set)
+        .unwrap();
+
+    set
+}
+```
+*/
+#[derive(Clone, Default)]
+pub struct ImageSet {
+    variants: Rc<Vec<ImageVariant>>,
+}
+
+impl ImageSet {
+    pub fn builder<'a>() -> ImageSetBuilder<'a> {
+        ImageSetBuilder {
+            source_file: None,
+            source_bin: None,
+            source_files: &[],
+            source_bins: &[],
+        }
+    }
+
+    /**
+        Single line helper function over the image set builder api.
+
+        Use a file resource.
+    */
+    pub fn from_file(path: &str) -> Result<ImageSet, NwgError> {
+        let mut set = ImageSet::default();
+
+        ImageSet::builder()
+            .source_file(Some(path))
+            .build(&mut set)?;
+
+        Ok(set)
+    }
+
+    /**
+        Single line helper function over the image set builder api.
+
+        Use a binary resource.
+    */
+    pub fn from_bin(bin: &[u8]) -> Result<ImageSet, NwgError> {
+        let mut set = ImageSet::default();
+
+        ImageSet::builder().source_bin(Some(bin)).build(&mut set)?;
+
+        Ok(set)
+    }
+
+    /**
+        Single line helper function over the image set builder api.
+
+        Use several file resources.
+    */
+    pub fn from_files(paths: &[&str]) -> Result<ImageSet, NwgError> {
+        let mut set = ImageSet::default();
+
+        ImageSet::builder().source_files(paths).build(&mut set)?;
+
+        Ok(set)
+    }
+
+    /// Add the images of a file to the set. Icon files add all their images.
+    pub fn add_file(&mut self, path: &str) -> Result<(), NwgError> {
+        let data = std::fs::read(path)
+            .map_err(|e| NwgError::resource_create(format!("Could not read {}: {}", path, e)))?;
+
+        self.add_bin(&data)
+    }
+
+    /// Add the images of a binary blob to the set. Icon files add all their images.
+    pub fn add_bin(&mut self, bin: &[u8]) -> Result<(), NwgError> {
+        let images = match rh::is_icon_file(bin) {
+            true => unsafe { rh::icon_file_images(bin)? },
+            false => vec![decode_image(bin)?],
+        };
+
+        for (size, pixels) in images {
+            self.add_pixels(size, pixels)?;
+        }
+
+        Ok(())
+    }
+
+    /**
+        Add an image to the set from RGBA pixels with straight alpha.
+        An image with the same size as an existing variant replaces it.
+    */
+    pub fn add_pixels(&mut self, size: (u32, u32), pixels: Vec<u8>) -> Result<(), NwgError> {
+        if size.0 == 0 || size.1 == 0 || pixels.len() != (size.0 * size.1 * 4) as usize {
+            return Err(NwgError::resource_create(
+                "The pixel buffer does not match the image size",
+            ));
+        }
+
+        let variants = Rc::make_mut(&mut self.variants);
+        variants.retain(|v| v.size != size);
+        variants.push(ImageVariant { size, pixels });
+        variants.sort_by_key(|v| v.size.0 * v.size.1);
+
+        Ok(())
+    }
+
+    /// Add a copy of a bitmap to the set. Panics if the bitmap was not initialized
+    pub fn add_bitmap(&mut self, bitmap: &Bitmap) -> Result<(), NwgError> {
+        if bitmap.handle.is_null() {
+            panic!("Bitmap was not initialized");
+        }
+
+        let (size, pixels) = unsafe { rh::bitmap_to_rgba(bitmap.handle as _)? };
+        self.add_pixels(size, pixels)
+    }
+
+    /// Add a copy of an icon to the set. Panics if the icon was not initialized
+    pub fn add_icon(&mut self, icon: &Icon) -> Result<(), NwgError> {
+        if icon.handle.is_null() {
+            panic!("Icon was not initialized");
+        }
+
+        let (size, pixels) = unsafe { rh::icon_to_rgba(icon.handle)? };
+        self.add_pixels(size, pixels)
+    }
+
+    /// Add a decoded image to the set. The 32 bits alpha channel of the image is kept.
+    #[cfg(feature = "image-decoder")]
+    pub fn add_image(&mut self, image: &ImageData) -> Result<(), NwgError> {
+        use crate::win32::image_decoder as img;
+
+        let (size, pixels) = unsafe { img::source_to_rgba(image.frame)? };
+        self.add_pixels(size, pixels)
+    }
+
+    /// Return the number of variants in the set
+    pub fn len(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Check if the set has no variants
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    /// Return the size of every variant in the set, from the smallest to the largest
+    pub fn sizes(&self) -> Vec<(u32, u32)> {
+        self.variants.iter().map(|v| v.size).collect()
+    }
+
+    /**
+        Return the size of the variant used to render the image at `size` (in pixels).
+        This is the smallest variant that is at least as large as `size`, or the largest variant if none are.
+        Returns `None` if the set is empty.
+    */
+    pub fn best_size(&self, size: (u32, u32)) -> Option<(u32, u32)> {
+        self.best_variant(size).map(|v| v.size)
+    }
+
+    /**
+        Render the image at `size` (in pixels) as RGBA with straight alpha.
+        The best variant is scaled if its size does not match. Returns `None` if the set is empty.
+    */
+    pub fn pixels(&self, size: (u32, u32)) -> Option<Vec<u8>> {
+        self.best_variant(size)
+            .map(|v| rh::scale_rgba(v.size, &v.pixels, size))
+    }
+
+    /// Create an icon of `size` pixels from the best variant
+    pub fn icon(&self, size: (u32, u32)) -> Result<Icon, NwgError> {
+        let pixels = self.pixels(size).ok_or_else(empty_set)?;
+        let handle = unsafe { rh::icon_from_rgba(size, &pixels)? };
+
+        Ok(Icon {
+            handle,
+            owned: true,
+        })
+    }
+
+    /// Create a bitmap of `size` pixels from the best variant. The bitmap alpha is premultiplied.
+    pub fn bitmap(&self, size: (u32, u32)) -> Result<Bitmap, NwgError> {
+        let pixels = self.pixels(size).ok_or_else(empty_set)?;
+        let handle = unsafe { rh::bitmap_from_rgba(size, &pixels)? };
+
+        Ok(Bitmap {
+            handle,
+            owned: true,
+        })
+    }
+
+    /// Return the size of the largest variant, or `None` if the set is empty
+    pub(crate) fn largest_size(&self) -> Option<(u32, u32)> {
+        self.variants.last().map(|v| v.size)
+    }
+
+    fn best_variant(&self, size: (u32, u32)) -> Option<&ImageVariant> {
+        let (width, height) = size;
+        self.variants
+            .iter()
+            .find(|v| v.size.0 >= width && v.size.1 >= height)
+            .or_else(|| self.variants.last())
+    }
+}
+
+impl PartialEq for ImageSet {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.variants, &other.variants)
+    }
+}
+
+fn empty_set() -> NwgError {
+    NwgError::resource_create("The image set is empty")
+}
+
+#[cfg(feature = "image-decoder")]
+fn decode_image(bin: &[u8]) -> Result<((u32, u32), Vec<u8>), NwgError> {
+    unsafe { crate::win32::image_decoder::decode_rgba(bin) }
+}
+
+#[cfg(not(feature = "image-decoder"))]
+fn decode_image(_bin: &[u8]) -> Result<((u32, u32), Vec<u8>), NwgError> {
+    Err(NwgError::resource_create(
+        "Loading images other than icon files in an ImageSet requires the \"image-decoder\" feature",
+    ))
+}
+
+pub struct ImageSetBuilder<'a> {
+    source_file: Option<&'a str>,
+    source_bin: Option<&'a [u8]>,
+    source_files: &'a [&'a str],
+    source_bins: &'a [&'a [u8]],
+}
+
+impl<'a> ImageSetBuilder<'a> {
+    pub fn source_file(mut self, t: Option<&'a str>) -> ImageSetBuilder<'a> {
+        self.source_file = t;
+        self
+    }
+
+    pub fn source_bin(mut self, t: Option<&'a [u8]>) -> ImageSetBuilder<'a> {
+        self.source_bin = t;
+        self
+    }
+
+    pub fn source_files(mut self, t: &'a [&'a str]) -> ImageSetBuilder<'a> {
+        self.source_files = t;
+        self
+    }
+
+    pub fn source_bins(mut self, t: &'a [&'a [u8]]) -> ImageSetBuilder<'a> {
+        self.source_bins = t;
+        self
+    }
+
+    pub fn build(self, out: &mut ImageSet) -> Result<(), NwgError> {
+        let mut set = ImageSet::default();
+
+        for path in self.source_file.iter().chain(self.source_files.iter()) {
+            set.add_file(path)?;
+        }
+
+        for bin in self.source_bin.iter().chain(self.source_bins.iter()) {
+            set.add_bin(bin)?;
+        }
+
+        *out = set;
+
+        Ok(())
+    }
+}
@@ -2,11 +2,19 @@ mod bitmap;
 mod cursor;
 mod font;
 mod icon;
+mod image_set;
+mod shell_info;
 mod system_images;
 
 #[cfg(feature = "image-decoder")]
 mod image_decoder;
 
+#[cfg(feature = "image-decoder")]
+mod image_encoder;
+
+#[cfg(feature = "image-decoder")]
+mod animated_image;
+
 #[cfg(feature = "file-dialog")]
 mod file_dialog;
 
@@ -24,15 +32,24 @@ mod embed;
 
 pub use bitmap::{Bitmap, BitmapBuilder};
 pub use cursor::{Cursor, CursorBuilder};
-pub use font::{Font, FontBuilder, FontInfo, MemFont};
+pub use font::{Font, FontBuilder, FontInfo, FontMetrics, FontPitch, FontQuality, MemFont};
 pub use icon::{Icon, IconBuilder};
+pub use image_set::{ImageSet, ImageSetBuilder};
+pub use shell_info::{ShellFileInfo, ShellInfo};
 pub use system_images::*;
 
 #[cfg(feature = "image-decoder")]
 pub use image_decoder::{
-    ContainerFormat, ImageData, ImageDecoder, ImageDecoderBuilder, ImageSource,
+    ContainerFormat, FrameDisposal, FrameInfo, ImageData, ImageDecoder, ImageDecoderBuilder,
+    ImageSource,
 };
 
+#[cfg(feature = "image-decoder")]
+pub use image_encoder::{ImageEncoder, ImageEncoderBuilder, ImageTarget};
+
+#[cfg(feature = "image-decoder")]
+pub use animated_image::{AnimatedImage, AnimatedImageBuilder};
+
 #[cfg(feature = "file-dialog")]
 pub use file_dialog::{FileDialog, FileDialogAction, FileDialogBuilder};
 
@@ -43,7 +60,7 @@ pub use color_dialog::{ColorDialog, ColorDialogBuilder};
 pub use font_dialog::{FontDialog, FontDialogBuilder};
 
 #[cfg(feature = "image-list")]
-pub use image_list::{ImageList, ImageListBuilder};
+pub use image_list::{ImageList, ImageListBuilder, SystemImageSize};
 
 #[cfg(feature = "embed-resource")]
 pub use embed::*;
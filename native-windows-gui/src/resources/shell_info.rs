@@ -0,0 +1,252 @@
+use crate::win32::base_helper::{from_utf16, to_utf16};
+use crate::{Icon, ShellIconSize, StockIcon};
+use std::{mem, path::Path, ptr};
+use winapi::shared::windef::HICON;
+use winapi::um::shellapi::{
+    SHFILEINFOW, SHGFI_ICON, SHGFI_LARGEICON, SHGFI_SMALLICON, SHGFI_USEFILEATTRIBUTES,
+};
+
+/**
+    The information that Explorer displays for a file. See `ShellInfo::file` and `ShellInfo::extension`.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShellFileInfo {
+    /// The name of the file as displayed by Explorer. File extensions can be hidden.
+    pub display_name: String,
+
+    /// The description of the file type, for example "Text Document"
+    pub type_name: String,
+
+    /// The index of the file icon in the system image list (see `ImageList::system`)
+    pub system_index: i32,
+}
+
+/**
+    Functions to get the icons and the names that the Windows shell uses for files, file types,
+    drives and common actions, so that an application looks consistent with Explorer.
+
+    * `file` and `extension` return the display name, the type name and the system image list index of a file
+    * `file_icon` and `extension_icon` return the icon of a file
+    * `stock_icon` returns a shell icon such as a folder, a drive or the UAC shield (see `StockIcon`)
+    * `extract_icon` and `extract_icons` read the icons stored in executables and dlls (`.exe`, `.dll`, `.ico`)
+
+    Icons returned by these functions are owned and are destroyed when they are dropped.
+
+```rust
+use native_windows_gui as nwg;
+
+fn describe(path: &str) -> String {
+    match nwg::ShellInfo::file(path) {
+        Some(info) => format!("{} ({})", info.display_name, info.type_name),
+        None => path.to_string()
+    }
+}
+
+fn folder_icon() -> Option<nwg::Icon> {
+    nwg::ShellInfo::stock_icon(nwg::StockIcon::Folder, nwg::ShellIconSize::Small)
+}
+```
+*/
+pub struct ShellInfo;
+
+impl ShellInfo {
+    /**
+        Returns the shell information of a file or a folder. Files that do not exist are described
+        by their extension. Returns `None` if the shell does not know the path.
+    */
+    pub fn file(path: &str) -> Option<ShellFileInfo> {
+        unsafe { file_info(path, !Path::new(path).exists(), INFO_FLAGS).map(into_file_info) }
+    }
+
+    /**
+        Returns the shell information of a file type, for example `"txt"` or `".txt"`.
+        The display name is the extension itself.
+    */
+    pub fn extension(extension: &str) -> Option<ShellFileInfo> {
+        unsafe { file_info(&normalize_extension(extension), true, INFO_FLAGS).map(into_file_info) }
+    }
+
+    /// Returns the icon of a file or a folder, as displayed by Explorer
+    pub fn file_icon(path: &str, size: ShellIconSize) -> Option<Icon> {
+        let flags = SHGFI_ICON | size_flag(size);
+        unsafe {
+            file_info(path, !Path::new(path).exists(), flags)
+                .and_then(|info| owned_icon(info.hIcon))
+        }
+    }
+
+    /// Returns the icon of a file type, for example `"txt"` or `".txt"`
+    pub fn extension_icon(extension: &str, size: ShellIconSize) -> Option<Icon> {
+        let flags = SHGFI_ICON | size_flag(size);
+        unsafe {
+            file_info(&normalize_extension(extension), true, flags)
+                .and_then(|info| owned_icon(info.hIcon))
+        }
+    }
+
+    /// Returns a shell stock icon. Returns `None` if the icon is not available on this version of Windows.
+    pub fn stock_icon(icon: StockIcon, size: ShellIconSize) -> Option<Icon> {
+        use winapi::um::shellapi::SHGSI_ICON;
+
+        unsafe {
+            stock_icon_info(icon, SHGSI_ICON | size_flag(size))
+                .and_then(|info| owned_icon(info.hIcon))
+        }
+    }
+
+    /// Returns the index of a stock icon in the system image list (see `ImageList::system`)
+    pub fn stock_icon_index(icon: StockIcon) -> Option<i32> {
+        use winapi::um::shellapi::SHGSI_SYSICONINDEX;
+
+        unsafe { stock_icon_info(icon, SHGSI_SYSICONINDEX).map(|info| info.iSysImageIndex) }
+    }
+
+    /**
+        Returns the file that stores a stock icon and the index of the icon in this file.
+        The icon can then be loaded in any size with `extract_icon`.
+    */
+    pub fn stock_icon_location(icon: StockIcon) -> Option<(String, i32)> {
+        use winapi::um::shellapi::SHGSI_ICONLOCATION;
+
+        unsafe {
+            stock_icon_info(icon, SHGSI_ICONLOCATION)
+                .map(|info| (from_utf16(&info.szPath), info.iIcon))
+        }
+    }
+
+    /// Returns the number of icons stored in an executable, a dll or an icon file
+    pub fn icon_count(path: &str) -> u32 {
+        use winapi::um::shellapi::ExtractIconExW;
+
+        let path = to_utf16(path);
+        unsafe { ExtractIconExW(path.as_ptr(), -1, ptr::null_mut(), ptr::null_mut(), 0) }
+    }
+
+    /**
+        Extracts an icon from an executable, a dll or an icon file. A negative index (other than `-1`) is the resource
+        identifier of the icon, like the icon locations used in the registry (`shell32.dll,-16769`).
+
+        Returns `None` if the icon does not exist.
+    */
+    pub fn extract_icon(path: &str, index: i32, size: ShellIconSize) -> Option<Icon> {
+        // -1 returns the number of icons in the file
+        if index == -1 {
+            return None;
+        }
+
+        let mut icons = unsafe { extract(path, index, 1, size) };
+        icons.pop()
+    }
+
+    /// Extracts every icon stored in an executable, a dll or an icon file
+    pub fn extract_icons(path: &str, size: ShellIconSize) -> Vec<Icon> {
+        let count = ShellInfo::icon_count(path);
+        unsafe { extract(path, 0, count, size) }
+    }
+}
+
+const INFO_FLAGS: u32 = {
+    use winapi::um::shellapi::{SHGFI_DISPLAYNAME, SHGFI_SYSICONINDEX, SHGFI_TYPENAME};
+    SHGFI_DISPLAYNAME | SHGFI_TYPENAME | SHGFI_SYSICONINDEX
+};
+
+fn size_flag(size: ShellIconSize) -> u32 {
+    match size {
+        ShellIconSize::Small => SHGFI_SMALLICON,
+        ShellIconSize::Large => SHGFI_LARGEICON,
+    }
+}
+
+/// The shell describes file types from a path made of their extension
+fn normalize_extension(extension: &str) -> String {
+    format!(
+        ".{}",
+        extension.trim_start_matches('*').trim_start_matches('.')
+    )
+}
+
+fn into_file_info(info: SHFILEINFOW) -> ShellFileInfo {
+    ShellFileInfo {
+        display_name: from_utf16(&info.szDisplayName),
+        type_name: from_utf16(&info.szTypeName),
+        system_index: info.iIcon,
+    }
+}
+
+fn owned_icon(handle: HICON) -> Option<Icon> {
+    match handle.is_null() {
+        true => None,
+        false => Some(Icon {
+            handle: handle as _,
+            owned: true,
+        }),
+    }
+}
+
+/// The index of the icon of a file or a folder in the system image list. See `ImageList::system_index`.
+#[cfg(feature = "image-list")]
+pub(crate) fn system_index(path: &str) -> Option<i32> {
+    use winapi::um::shellapi::SHGFI_SYSICONINDEX;
+    unsafe { file_info(path, !Path::new(path).exists(), SHGFI_SYSICONINDEX).map(|info| info.iIcon) }
+}
+
+/// With `use_attributes`, the path does not need to exist: the shell only looks at its extension
+unsafe fn file_info(path: &str, use_attributes: bool, flags: u32) -> Option<SHFILEINFOW> {
+    use winapi::um::shellapi::SHGetFileInfoW;
+    use winapi::um::winnt::FILE_ATTRIBUTE_NORMAL;
+
+    let (attributes, flags) = match use_attributes {
+        true => (FILE_ATTRIBUTE_NORMAL, flags | SHGFI_USEFILEATTRIBUTES),
+        false => (0, flags),
+    };
+
+    let path = to_utf16(path);
+    let mut info: SHFILEINFOW = mem::zeroed();
+    let result = SHGetFileInfoW(
+        path.as_ptr(),
+        attributes,
+        &mut info,
+        mem::size_of::<SHFILEINFOW>() as u32,
+        flags,
+    );
+
+    match result {
+        0 => None,
+        _ => Some(info),
+    }
+}
+
+unsafe fn stock_icon_info(
+    icon: StockIcon,
+    flags: u32,
+) -> Option<winapi::um::shellapi::SHSTOCKICONINFO> {
+    use winapi::shared::winerror::S_OK;
+    use winapi::um::shellapi::{SHGetStockIconInfo, SHSTOCKICONINFO};
+
+    let mut info: SHSTOCKICONINFO = mem::zeroed();
+    info.cbSize = mem::size_of::<SHSTOCKICONINFO>() as u32;
+
+    match SHGetStockIconInfo(icon as u32, flags, &mut info) {
+        S_OK => Some(info),
+        _ => None,
+    }
+}
+
+unsafe fn extract(path: &str, index: i32, count: u32, size: ShellIconSize) -> Vec<Icon> {
+    use winapi::um::shellapi::ExtractIconExW;
+
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let path = to_utf16(path);
+    let mut handles: Vec<HICON> = vec![ptr::null_mut(); count as usize];
+    let (large, small) = match size {
+        ShellIconSize::Small => (ptr::null_mut(), handles.as_mut_ptr()),
+        ShellIconSize::Large => (handles.as_mut_ptr(), ptr::null_mut()),
+    };
+
+    ExtractIconExW(path.as_ptr(), index, large, small, count);
+
+    handles.into_iter().filter_map(owned_icon).collect()
+}
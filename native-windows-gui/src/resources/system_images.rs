@@ -76,6 +76,8 @@ pub enum OemBitmap {
 
 /**
     List of system icons. To use with the `Image` resource.
+
+    These are the legacy icons of `LoadIcon`. For the icons used by the shell, see `StockIcon` and `ShellInfo`.
 */
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum OemIcon {
@@ -86,3 +88,74 @@ pub enum OemIcon {
     Error = 32513,
     Information = 32516,
 }
+
+/**
+    List of the icons used by the Windows shell. To use with `ShellInfo::stock_icon`.
+
+    Unlike `OemIcon`, stock icons follow the current version of Windows and are available in several sizes.
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StockIcon {
+    /// Document of a type with no associated application
+    DocNoAssoc = 0,
+    /// Document of a type with an associated application
+    DocAssoc = 1,
+    Application = 2,
+    Folder = 3,
+    FolderOpen = 4,
+    DriveRemovable = 7,
+    DriveFixed = 8,
+    DriveNet = 9,
+    DriveNetDisabled = 10,
+    DriveCd = 11,
+    DriveRam = 12,
+    World = 13,
+    Server = 15,
+    Printer = 16,
+    MyNetwork = 17,
+    Find = 22,
+    Help = 23,
+    /// Overlay for shared items
+    Share = 28,
+    /// Overlay for shortcuts
+    Link = 29,
+    /// Overlay for slow files
+    SlowFile = 30,
+    Recycler = 31,
+    RecyclerFull = 32,
+    Lock = 47,
+    Stack = 55,
+    DriveUnknown = 58,
+    DriveDvd = 59,
+    AudioFiles = 71,
+    ImageFiles = 72,
+    VideoFiles = 73,
+    MixedFiles = 74,
+    /// The UAC shield
+    Shield = 77,
+    Warning = 78,
+    Info = 79,
+    Error = 80,
+    Key = 81,
+    Software = 82,
+    Rename = 83,
+    Delete = 84,
+    DesktopPc = 94,
+    MobilePc = 95,
+    Users = 96,
+    NetworkConnect = 103,
+    Internet = 104,
+    ZipFile = 105,
+    Settings = 106,
+}
+
+/**
+    Size of the icons returned by the shell (see `ShellInfo`)
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShellIconSize {
+    /// The small icon size (16x16 pixels at 100% scaling)
+    Small,
+    /// The large icon size (32x32 pixels at 100% scaling)
+    Large,
+}
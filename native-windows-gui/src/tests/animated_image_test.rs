@@ -0,0 +1,181 @@
+use super::{solid, RED};
+use crate::win32::animated_image::compose;
+use crate::*;
+use std::time::Duration;
+
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+fn pixel(pixels: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
+    let i = ((y * width + x) * 4) as usize;
+    [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+}
+
+fn frame(
+    position: (u32, u32),
+    size: (u32, u32),
+    disposal: FrameDisposal,
+    blend: bool,
+) -> FrameInfo {
+    FrameInfo {
+        delay: Duration::from_millis(50),
+        disposal,
+        position,
+        size,
+        blend,
+    }
+}
+
+#[test]
+fn animated_image_composition() {
+    let frames = vec![
+        (
+            frame((0, 0), (2, 2), FrameDisposal::None, true),
+            solid(2, 2, RED),
+        ),
+        (
+            frame((1, 1), (1, 1), FrameDisposal::Previous, true),
+            solid(1, 1, BLUE),
+        ),
+        (
+            frame((0, 0), (1, 1), FrameDisposal::Background, false),
+            solid(1, 1, [0, 255, 0, 128]),
+        ),
+        (
+            frame((1, 0), (2, 2), FrameDisposal::None, true),
+            solid(2, 2, [0, 0, 255, 0]),
+        ),
+    ];
+
+    let composed = compose((2, 2), frames);
+    assert_eq!(composed.len(), 4);
+    assert_eq!(composed[0].delay, Duration::from_millis(50));
+
+    assert_eq!(composed[0].pixels, solid(2, 2, RED));
+    assert_eq!(pixel(&composed[1].pixels, 2, 1, 1), BLUE);
+    assert_eq!(pixel(&composed[1].pixels, 2, 0, 1), RED);
+
+    // The second frame is disposed to the previous canvas. The third frame replaces its pixels.
+    assert_eq!(pixel(&composed[2].pixels, 2, 1, 1), RED);
+    assert_eq!(pixel(&composed[2].pixels, 2, 0, 0), [0, 255, 0, 128]);
+
+    // The third frame is cleared. The fourth frame is transparent and is clipped to the canvas.
+    assert_eq!(pixel(&composed[3].pixels, 2, 0, 0), CLEAR);
+    assert_eq!(pixel(&composed[3].pixels, 2, 1, 0), RED);
+}
+
+/// Return the chunks of a PNG image as (type, data)
+fn png_chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+    let mut chunks = Vec::new();
+    let mut offset = 8;
+    while offset < png.len() {
+        let length = u32::from_be_bytes([
+            png[offset],
+            png[offset + 1],
+            png[offset + 2],
+            png[offset + 3],
+        ]) as usize;
+        let mut kind = [0; 4];
+        kind.copy_from_slice(&png[offset + 4..offset + 8]);
+        chunks.push((kind, png[offset + 8..offset + 8 + length].to_vec()));
+        offset += length + 12;
+    }
+
+    chunks
+}
+
+/// The CRC of the chunks is not checked when an APNG is split into frames
+fn push_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&[0; 4]);
+}
+
+fn frame_control(sequence: u32, size: (u32, u32), position: (u32, u32), dispose: u8) -> Vec<u8> {
+    let mut data = Vec::new();
+    for value in [sequence, size.0, size.1, position.0, position.1].iter() {
+        data.extend_from_slice(&value.to_be_bytes());
+    }
+    data.extend_from_slice(&3u16.to_be_bytes());
+    data.extend_from_slice(&100u16.to_be_bytes());
+    data.extend_from_slice(&[dispose, 1]);
+    data
+}
+
+fn encode_png(size: (u32, u32), color: [u8; 4]) -> Vec<u8> {
+    let encoder = ImageEncoder::new().unwrap();
+    let target = encoder.to_stream(ContainerFormat::Png).unwrap();
+    target
+        .add_pixels(size, &solid(size.0, size.1, color))
+        .unwrap();
+    target.commit().unwrap()
+}
+
+#[test]
+fn animated_image_apng() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let first = png_chunks(&encode_png((4, 4), RED));
+    let second = png_chunks(&encode_png((2, 2), BLUE));
+    let image_data = |chunks: &[([u8; 4], Vec<u8>)]| -> Vec<Vec<u8>> {
+        chunks
+            .iter()
+            .filter(|(kind, _)| kind == b"IDAT")
+            .map(|(_, data)| data.clone())
+            .collect()
+    };
+
+    let mut apng = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    push_chunk(&mut apng, b"IHDR", &first[0].1);
+    push_chunk(&mut apng, b"acTL", &[0, 0, 0, 2, 0, 0, 0, 0]);
+    push_chunk(&mut apng, b"fcTL", &frame_control(0, (4, 4), (0, 0), 0));
+    for data in image_data(&first) {
+        push_chunk(&mut apng, b"IDAT", &data);
+    }
+    push_chunk(&mut apng, b"fcTL", &frame_control(1, (2, 2), (1, 2), 0));
+    for (i, data) in image_data(&second).iter().enumerate() {
+        let mut frame_data = (2 + i as u32).to_be_bytes().to_vec();
+        frame_data.extend_from_slice(data);
+        push_chunk(&mut apng, b"fdAT", &frame_data);
+    }
+    push_chunk(&mut apng, b"IEND", &[]);
+
+    let image = AnimatedImage::from_bin(&apng).unwrap();
+    assert_eq!(image.size(), (4, 4));
+    assert_eq!(image.frame_count(), 2);
+    assert_eq!(image.delay(0), Some(Duration::from_millis(30)));
+    assert_eq!(image.duration(), Duration::from_millis(60));
+    assert!(image.frame(2).is_none());
+
+    let pixels = image.frame(1).unwrap().to_rgba().unwrap();
+    assert_eq!(pixel(&pixels, 4, 0, 0), RED);
+    assert_eq!(pixel(&pixels, 4, 1, 2), BLUE);
+    assert_eq!(pixel(&pixels, 4, 2, 3), BLUE);
+    assert_eq!(pixel(&pixels, 4, 3, 3), RED);
+
+    // A PNG without animation is a single frame
+    let image = AnimatedImage::from_bin(&encode_png((3, 3), BLUE)).unwrap();
+    assert_eq!(image.frame_count(), 1);
+    assert_eq!(image.size(), (3, 3));
+}
+
+#[test]
+fn animated_image_gif() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let encoder = ImageEncoder::new().unwrap();
+    let target = encoder.to_stream(ContainerFormat::Gif).unwrap();
+    target.add_pixels((8, 8), &solid(8, 8, RED)).unwrap();
+    target.add_pixels((8, 8), &solid(8, 8, BLUE)).unwrap();
+    let gif = target.commit().unwrap();
+
+    let image = AnimatedImage::from_bin(&gif).unwrap();
+    assert_eq!(image.frame_count(), 2);
+    assert_eq!(image.size(), (8, 8));
+
+    let pixels = image.frame(1).unwrap().to_rgba().unwrap();
+    assert_eq!(pixel(&pixels, 8, 4, 4), BLUE);
+
+    assert!(AnimatedImage::from_bin(&[0, 1, 2, 3]).is_err());
+}
@@ -0,0 +1,130 @@
+use super::{solid, RED};
+use crate::win32::resources_helper as rh;
+use crate::*;
+use winapi::um::wingdi::DeleteObject;
+use winapi::um::winuser::{GetIconInfo, ICONINFO};
+
+static ICE_DATA: &'static [u8] = include_bytes!("../../test_rc/ice.cur");
+
+fn hotspot(cursor: &Cursor) -> (u32, u32) {
+    unsafe {
+        let mut info: ICONINFO = std::mem::zeroed();
+        assert!(GetIconInfo(cursor.handle as _, &mut info) != 0);
+        DeleteObject(info.hbmColor as _);
+        DeleteObject(info.hbmMask as _);
+        assert_eq!(info.fIcon, 0);
+        (info.xHotspot, info.yHotspot)
+    }
+}
+
+/// A minimal animated cursor with a single frame
+fn animated_cursor(frame: &[u8]) -> Vec<u8> {
+    fn chunk(id: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = id.to_vec();
+        chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            chunk.push(0);
+        }
+        chunk
+    }
+
+    // cbSize, frames, steps, width, height, bit count, planes, display rate (jiffies), flags (AF_ICON)
+    let header: Vec<u8> = [36u32, 1, 1, 0, 0, 0, 0, 10, 1]
+        .iter()
+        .flat_map(|v| v.to_le_bytes().to_vec())
+        .collect();
+
+    let mut frames = b"fram".to_vec();
+    frames.extend(chunk(b"icon", frame));
+
+    let mut body = b"ACON".to_vec();
+    body.extend(chunk(b"anih", &header));
+    body.extend(chunk(b"LIST", &frames));
+    chunk(b"RIFF", &body)
+}
+
+#[test]
+fn cursor_from_bitmap() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut set = ImageSet::default();
+    set.add_pixels((16, 16), solid(16, 16, RED)).unwrap();
+    let bitmap = set.bitmap((16, 16)).unwrap();
+
+    let cursor = Cursor::from_bitmap(&bitmap, (8, 7)).unwrap();
+    assert_eq!(hotspot(&cursor), (8, 7));
+    assert!(Cursor::from_bitmap(&bitmap, (16, 0)).is_err());
+
+    let bitmap = Bitmap::from_file("./test_rc/ferris.bmp", true).unwrap();
+    assert!(Cursor::from_bitmap(&bitmap, (0, 0)).is_ok());
+}
+
+#[test]
+fn cursor_from_bin() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let cursor = Cursor::from_bin(ICE_DATA).unwrap();
+    assert_eq!(hotspot(&cursor), (0, 0));
+    assert!(Cursor::from_bin(&[0, 0, 1, 0, 0, 0]).is_err());
+    assert!(Cursor::from_bin(&ICE_DATA[..30]).is_err());
+
+    let animated = animated_cursor(ICE_DATA);
+    assert!(rh::is_animated_cursor_file(&animated));
+    assert!(!rh::is_animated_cursor_file(ICE_DATA));
+    assert!(Cursor::from_bin(&animated).is_ok());
+
+    let mut cursor = Cursor::default();
+    Cursor::builder()
+        .source_bin(Some(ICE_DATA))
+        .size(Some((16, 16)))
+        .build(&mut cursor)
+        .unwrap();
+    assert!(!cursor.handle.is_null());
+}
+
+#[test]
+fn cursor_per_control() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut window = Window::default();
+    Window::builder()
+        .flags(WindowFlags::WINDOW)
+        .title("Cursors")
+        .build(&mut window)
+        .unwrap();
+
+    let mut button = Button::default();
+    Button::builder()
+        .parent(&window)
+        .build(&mut button)
+        .unwrap();
+
+    let grab = Cursor::from_bin(ICE_DATA).unwrap();
+    let grab_handle = grab.handle;
+
+    assert!(button.cursor().is_none());
+    button.set_cursor(Some(grab));
+    assert!(window.cursor().is_none());
+
+    // The control keeps the cursor it was given. The returned cursor does not own the handle
+    let cursor = button.cursor().unwrap();
+    assert_eq!(cursor.handle, grab_handle);
+    drop(cursor);
+    assert_eq!(button.cursor().map(|c| hotspot(&c)), Some((0, 0)));
+
+    let animated = Cursor::from_bin(&animated_cursor(ICE_DATA)).unwrap();
+    let animated_handle = animated.handle;
+    button.set_cursor(Some(animated));
+    assert_eq!(button.cursor().map(|c| c.handle), Some(animated_handle));
+
+    button.set_cursor(Some(Cursor::from_system(OemCursor::Cross)));
+    assert!(button.cursor().is_some());
+
+    button.set_cursor(None);
+    assert!(button.cursor().is_none());
+
+    window.set_cursor(Some(Cursor::from_system(OemCursor::Cross)));
+    assert!(window.cursor().is_some());
+    window.close();
+}
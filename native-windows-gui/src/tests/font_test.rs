@@ -0,0 +1,97 @@
+use crate::win32::resources_helper::font_families;
+use crate::*;
+
+#[test]
+fn font_families_from_data() {
+    let fredoka = include_bytes!("../../test_rc/FredokaOne-Regular.ttf");
+    assert_eq!(font_families(fredoka), vec!["Fredoka One".to_string()]);
+
+    let indie = include_bytes!("../../test_rc/IndieFlower-Regular.ttf");
+    assert_eq!(font_families(indie), vec!["Indie Flower".to_string()]);
+
+    assert!(font_families(&[]).is_empty());
+    assert!(font_families(&fredoka[..64]).is_empty());
+}
+
+#[test]
+fn font_info_roundtrip() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut font = Font::default();
+    Font::builder()
+        .family("Arial")
+        .size(24)
+        .weight(700)
+        .italic(true)
+        .underline(true)
+        .quality(FontQuality::NonAntialiased)
+        .pitch(FontPitch::Variable)
+        .build(&mut font)
+        .unwrap();
+
+    let info = font.info();
+    assert_eq!(info.name, "Arial");
+    assert_eq!(info.weight, 700);
+    assert!(info.italic && info.underline && !info.strike_out);
+    assert_eq!(info.quality, FontQuality::NonAntialiased as u8);
+
+    let copy = Font::from_info(&info).unwrap();
+    assert_eq!(copy.info(), info);
+
+    // A font saved with only a point size uses the screen DPI
+    let sized = Font::from_info(&FontInfo {
+        height: 0,
+        ..info.clone()
+    })
+    .unwrap();
+    assert_eq!(sized.info().point_size, info.point_size);
+}
+
+#[test]
+fn font_metrics() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut font = Font::default();
+    Font::builder()
+        .family("Arial")
+        .size(20)
+        .build(&mut font)
+        .unwrap();
+
+    let metrics = font.metrics();
+    assert_eq!(metrics.height, metrics.ascent + metrics.descent);
+    assert!(metrics.average_char_width > 0);
+    assert!(metrics.max_char_width >= metrics.average_char_width);
+
+    let (width, height) = font.measure_text("Hello");
+    assert!(width > 0);
+    assert_eq!(height, metrics.height as u32);
+
+    let (wide, _) = font.measure_text("Hello Hello");
+    assert!(wide > width);
+
+    let (lines_width, lines_height) = font.measure_text("Hello\r\n\nHi");
+    assert_eq!(lines_width, width);
+    assert_eq!(lines_height, height * 3);
+}
+
+#[test]
+fn font_memory_families() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut data = include_bytes!("../../test_rc/FredokaOne-Regular.ttf").to_vec();
+    let mem_font = Font::add_memory_font(&mut data).unwrap();
+    assert_eq!(mem_font.families(), vec!["Fredoka One".to_string()]);
+    assert!(Font::families().iter().any(|f| f == "Fredoka One"));
+
+    let mut font = Font::default();
+    Font::builder()
+        .family("Fredoka One")
+        .size(20)
+        .build(&mut font)
+        .unwrap();
+    assert_eq!(font.info().name, "Fredoka One");
+
+    Font::remove_memory_font(mem_font);
+    assert!(!Font::families().iter().any(|f| f == "Fredoka One"));
+}
@@ -0,0 +1,121 @@
+use crate::*;
+
+fn test_pixels(width: u32, height: u32) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let r = (x * 255 / (width - 1)) as u8;
+            let g = (y * 255 / (height - 1)) as u8;
+            pixels.extend_from_slice(&[r, g, 128, 255]);
+        }
+    }
+
+    pixels
+}
+
+fn decode_bitmap(bytes: &[u8]) -> Bitmap {
+    let decoder = ImageDecoder::new().unwrap();
+    let image = decoder.from_stream(bytes).unwrap().frame(0).unwrap();
+    image.as_bitmap().unwrap()
+}
+
+#[test]
+fn image_encoder_png_roundtrip() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let pixels = test_pixels(4, 3);
+    let encoder = ImageEncoder::new().unwrap();
+
+    let target = encoder.to_stream(ContainerFormat::Png).unwrap();
+    target.add_pixels((4, 3), &pixels).unwrap();
+    let bytes = target.commit().unwrap();
+    assert_eq!(&bytes[..4], &[0x89, b'P', b'N', b'G']);
+
+    let bitmap = decode_bitmap(&bytes);
+    assert_eq!(bitmap.size(), (4, 3));
+    assert_eq!(bitmap.to_rgba().unwrap(), pixels);
+
+    // Encoding the bitmap again gives the same pixels
+    let target = encoder.to_stream(ContainerFormat::Png).unwrap();
+    target.add_bitmap(&bitmap).unwrap();
+    let bitmap = decode_bitmap(&target.commit().unwrap());
+    assert_eq!(bitmap.to_rgba().unwrap(), pixels);
+
+    let target = encoder.to_stream(ContainerFormat::Png).unwrap();
+    assert!(target.add_pixels((5, 3), &pixels).is_err());
+}
+
+#[test]
+fn image_encoder_options() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let pixels = test_pixels(64, 64);
+    let encoder = ImageEncoder::new().unwrap();
+
+    let jpeg = |quality: f32| {
+        let mut target = encoder.to_stream(ContainerFormat::Jpeg).unwrap();
+        target.set_quality(Some(quality));
+        target.set_metadata("/app1/ifd/{ushort=270}", "NWG test image");
+        target.add_pixels((64, 64), &pixels).unwrap();
+        target.commit().unwrap()
+    };
+
+    let low = jpeg(0.1);
+    let high = jpeg(1.0);
+    assert_eq!(&low[..2], &[0xFF, 0xD8]);
+    assert!(low.len() < high.len());
+
+    // BMP does not support metadata
+    let mut target = encoder.to_stream(ContainerFormat::Bmp).unwrap();
+    target.set_metadata("/tEXt/{str=Description}", "Not supported");
+    assert!(target.add_pixels((64, 64), &pixels).is_err());
+
+    // Multiple GIF frames use a generated palette
+    let target = encoder.to_stream(ContainerFormat::Gif).unwrap();
+    target.add_pixels((64, 64), &pixels).unwrap();
+    target.add_pixels((64, 64), &pixels).unwrap();
+    let gif = target.commit().unwrap();
+    assert_eq!(&gif[..3], b"GIF");
+
+    let decoder = ImageDecoder::new().unwrap();
+    assert_eq!(decoder.from_stream(&gif).unwrap().frame_count(), 2);
+
+    assert!(encoder.to_stream(ContainerFormat::Ico).is_err());
+}
+
+#[test]
+fn image_encoder_icon_file() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let pixels = test_pixels(32, 32);
+    let encoder = ImageEncoder::new().unwrap();
+    let target = encoder.to_stream(ContainerFormat::Png).unwrap();
+    target.add_pixels((32, 32), &pixels).unwrap();
+    let bitmap = decode_bitmap(&target.commit().unwrap());
+
+    let icon = bitmap.copy_as_icon();
+    let copy = icon.copy_as_bitmap().unwrap();
+    assert_eq!(copy.size(), (32, 32));
+    assert_eq!(copy.to_rgba().unwrap(), pixels);
+
+    let path = std::env::temp_dir().join("nwg_image_encoder_test.ico");
+    let path = path.to_str().unwrap();
+    bitmap.save(path).unwrap();
+
+    let loaded = Icon::from_file(path, true).unwrap();
+    assert_eq!(loaded.copy_as_bitmap().unwrap().to_rgba().unwrap(), pixels);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn container_format_from_path() {
+    assert_eq!(ContainerFormat::from_path("a.png"), ContainerFormat::Png);
+    assert_eq!(
+        ContainerFormat::from_path("C:\\b.JPG"),
+        ContainerFormat::Jpeg
+    );
+    assert_eq!(ContainerFormat::from_path("c.tiff"), ContainerFormat::Tiff);
+    assert_eq!(ContainerFormat::from_path("d.ico"), ContainerFormat::Ico);
+    assert_eq!(ContainerFormat::from_path("e"), ContainerFormat::Unknown);
+}
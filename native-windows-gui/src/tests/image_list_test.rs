@@ -0,0 +1,108 @@
+use super::{solid, RED};
+use crate::*;
+
+const BLUE: [u8; 4] = [0, 0, 255, 128];
+
+fn solid_set(size: u32, color: [u8; 4]) -> ImageSet {
+    let mut set = ImageSet::default();
+    set.add_pixels((size, size), solid(size, size, color))
+        .unwrap();
+    set
+}
+
+fn build_list(dpi_aware: bool) -> ImageList {
+    let mut list = ImageList::default();
+    ImageList::builder()
+        .size((16, 16))
+        .dpi_aware(dpi_aware)
+        .build(&mut list)
+        .unwrap();
+
+    list.add_set(&solid_set(16, RED)).unwrap();
+    list.add_set(&solid_set(16, BLUE)).unwrap();
+    list
+}
+
+#[test]
+fn image_list_extraction() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let list = build_list(false);
+    assert!(list.icon(2).is_none());
+    assert!(list.bitmap(-5).is_none());
+
+    let bitmap = list.bitmap(0).unwrap();
+    assert_eq!(bitmap.size(), (16, 16));
+    let pixels = bitmap.to_rgba().unwrap();
+    assert_eq!(&pixels[0..4], &RED);
+
+    let icon = list.icon(0).unwrap();
+    let mut set = ImageSet::default();
+    set.add_icon(&icon).unwrap();
+    assert_eq!(set.pixels((16, 16)).unwrap()[0..4], RED);
+}
+
+#[test]
+fn image_list_overlays() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let list = build_list(true);
+    assert!(list.set_overlay(1, 0).is_err());
+    assert!(list.set_overlay(1, 16).is_err());
+    list.set_overlay(1, 1).unwrap();
+
+    // Overlays are kept when a DPI aware list is rendered again
+    list.set_dpi(192);
+    assert_eq!(list.size(), (32, 32));
+    list.remove(0);
+    list.set_dpi(96);
+    assert_eq!(list.len(), 1);
+
+    let mut window = Window::default();
+    Window::builder()
+        .flags(WindowFlags::WINDOW)
+        .title("Image list overlays")
+        .build(&mut window)
+        .unwrap();
+
+    let mut list_view = ListView::default();
+    ListView::builder()
+        .parent(&window)
+        .build(&mut list_view)
+        .unwrap();
+    list_view.set_image_list(Some(&list), ListViewImageListType::Small);
+    list_view.insert_item("Shared");
+    list_view.set_item_overlay(0, 1);
+    assert_eq!(list_view.item_overlay(0), 1);
+    list_view.set_item_overlay(0, 0);
+    assert_eq!(list_view.item_overlay(0), 0);
+    assert_eq!(list_view.item_overlay(10), 0);
+    assert!(list_view.create_drag_image(10).is_none());
+
+    let mut tree = TreeView::default();
+    TreeView::builder()
+        .parent(&window)
+        .build(&mut tree)
+        .unwrap();
+    tree.set_image_list(Some(&list));
+    let item = tree.insert_item("Modified", None, TreeInsert::Root);
+    tree.set_item_overlay(&item, 2);
+    assert_eq!(tree.item_overlay(&item), 2);
+}
+
+#[test]
+fn image_list_system() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let small = ImageList::system(SystemImageSize::Small).unwrap();
+    let large = ImageList::system(SystemImageSize::Large).unwrap();
+    assert!(!small.owned);
+    assert!(small.len() > 0);
+    assert!(large.size().0 > small.size().0);
+
+    // Files that do not exist use the icon of their extension
+    let text = ImageList::system_index("does_not_exist.txt").unwrap();
+    assert_eq!(ImageList::system_index("notes.txt"), Some(text));
+    assert!(small.icon(text).is_some());
+    assert!(ImageList::system_index("./test_rc").is_some());
+}
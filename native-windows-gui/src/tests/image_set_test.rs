@@ -0,0 +1,134 @@
+use super::{solid, RED};
+use crate::win32::resources_helper::scale_rgba;
+use crate::*;
+
+static COG_DATA: &'static [u8] = include_bytes!("../../test_rc/cog.ico");
+
+const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+#[test]
+fn image_set_scaling() {
+    assert_eq!(
+        scale_rgba((4, 4), &solid(4, 4, RED), (9, 7)),
+        solid(9, 7, RED)
+    );
+    assert_eq!(
+        scale_rgba((8, 8), &solid(8, 8, RED), (3, 3)),
+        solid(3, 3, RED)
+    );
+
+    // Transparent pixels do not darken the opaque pixels next to them
+    let mut pixels = solid(2, 1, CLEAR);
+    pixels[0..4].copy_from_slice(&RED);
+    let scaled = scale_rgba((2, 1), &pixels, (1, 1));
+    assert_eq!(&scaled[0..3], &RED[0..3]);
+    assert!(scaled[3] > 120 && scaled[3] < 135);
+}
+
+#[test]
+fn image_set_variants() {
+    let mut set = ImageSet::default();
+    assert!(set.is_empty());
+    assert!(set.pixels((16, 16)).is_none());
+    assert!(set.icon((16, 16)).is_err());
+
+    set.add_pixels((16, 16), solid(16, 16, RED)).unwrap();
+    set.add_pixels((48, 48), solid(48, 48, RED)).unwrap();
+    set.add_pixels((32, 32), solid(32, 32, RED)).unwrap();
+    set.add_pixels((32, 32), solid(32, 32, CLEAR)).unwrap();
+    assert!(set.add_pixels((2, 2), vec![0; 3]).is_err());
+
+    assert_eq!(set.sizes(), vec![(16, 16), (32, 32), (48, 48)]);
+    assert_eq!(set.best_size((16, 16)), Some((16, 16)));
+    assert_eq!(set.best_size((24, 24)), Some((32, 32)));
+    assert_eq!(set.best_size((64, 64)), Some((48, 48)));
+    assert_eq!(set.pixels((24, 24)), Some(solid(24, 24, CLEAR)));
+
+    // Clones share the variants until one of them is modified
+    let mut copy = set.clone();
+    assert!(copy == set);
+    copy.add_pixels((8, 8), solid(8, 8, RED)).unwrap();
+    assert!(copy != set);
+    assert_eq!(set.len(), 3);
+    assert_eq!(copy.len(), 4);
+}
+
+#[test]
+fn image_set_resources() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let set = ImageSet::from_bin(COG_DATA).unwrap();
+    assert!(!set.is_empty());
+    assert!(ImageSet::from_bin(&[0, 0, 1, 0, 1, 0]).is_err());
+
+    let icon = set.icon((20, 20)).unwrap();
+    let (size, _) = unsafe { crate::win32::resources_helper::icon_to_rgba(icon.handle).unwrap() };
+    assert_eq!(size, (20, 20));
+
+    let bitmap = set.bitmap((40, 30)).unwrap();
+    assert_eq!(bitmap.size(), (40, 30));
+
+    let mut icon = Icon::default();
+    Icon::builder()
+        .source_set(Some(&set))
+        .size(Some((16, 16)))
+        .build(&mut icon)
+        .unwrap();
+    assert!(!icon.handle.is_null());
+
+    let set = ImageSet::from_files(&["./test_rc/list_0.png", "./test_rc/list_1.png"]).unwrap();
+    assert!(!set.is_empty());
+}
+
+#[test]
+fn image_set_dpi_aware_image_list() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut set = ImageSet::default();
+    set.add_pixels((16, 16), solid(16, 16, RED)).unwrap();
+    set.add_pixels((32, 32), solid(32, 32, CLEAR)).unwrap();
+
+    let mut list = ImageList::default();
+    ImageList::builder()
+        .size((16, 16))
+        .dpi_aware(true)
+        .build(&mut list)
+        .unwrap();
+    assert!(list.dpi_aware());
+
+    list.set_dpi(96);
+    assert_eq!(list.add_set(&set).unwrap(), 0);
+    assert_eq!(list.add_set(&set).unwrap(), 1);
+    assert!(list.add_set(&ImageSet::default()).is_err());
+
+    list.set_dpi(192);
+    assert_eq!(list.size(), (32, 32));
+    assert_eq!(list.len(), 2);
+
+    list.remove(0);
+    list.set_dpi(144);
+    assert_eq!(list.size(), (24, 24));
+    assert_eq!(list.len(), 1);
+
+    list.set_size((20, 20));
+    assert_eq!(list.size(), (30, 30));
+    assert_eq!(list.len(), 1);
+
+    // Lists that are not DPI aware ignore the DPI and scale the images to their size
+    let mut fixed = ImageList::default();
+    ImageList::builder()
+        .size((16, 16))
+        .build(&mut fixed)
+        .unwrap();
+    assert_eq!(fixed.add_set(&set).unwrap(), 0);
+    fixed.set_dpi(192);
+    assert_eq!(fixed.size(), (16, 16));
+
+    let decoder = ImageDecoder::new().unwrap();
+    let image = decoder
+        .from_filename("./test_rc/list_0.png")
+        .unwrap()
+        .frame(0)
+        .unwrap();
+    assert_eq!(fixed.add_image(&image).unwrap(), 1);
+}
@@ -0,0 +1,62 @@
+use crate::controls::control_preferred_size;
+use crate::*;
+
+#[test]
+fn grid_layout_auto_images() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut window = Window::default();
+    Window::builder()
+        .flags(WindowFlags::WINDOW)
+        .size((300, 200))
+        .title("Layout")
+        .build(&mut window)
+        .unwrap();
+
+    let mut image = ImageFrame::default();
+    ImageFrame::builder()
+        .size((64, 48))
+        .parent(&window)
+        .build(&mut image)
+        .unwrap();
+
+    let mut label = Label::default();
+    Label::builder()
+        .text("")
+        .size((70, 20))
+        .parent(&window)
+        .build(&mut label)
+        .unwrap();
+
+    // Images and empty labels have no text to measure
+    assert_eq!(control_preferred_size(image.handle.hwnd().unwrap()), None);
+    assert_eq!(control_preferred_size(label.handle.hwnd().unwrap()), None);
+
+    let layout = GridLayout::default();
+    GridLayout::builder()
+        .parent(&window)
+        .margin([0, 0, 0, 0])
+        .columns(vec![
+            GridTrack::auto(),
+            GridTrack::auto(),
+            GridTrack::weight(1),
+        ])
+        .child(0, 0, &image)
+        .child(1, 0, &label)
+        .build(&layout)
+        .unwrap();
+
+    // The auto tracks fall back to the size of the controls when they were added
+    assert_eq!(image.size().0, 64);
+    assert_eq!(label.size().0, 70);
+
+    // The measured sizes are kept until `fit`
+    label.set_text("A longer text than the column");
+    layout.resize(300, 200);
+    assert_eq!(label.size().0, 70);
+
+    layout.fit();
+    assert!(label.size().0 > 70);
+
+    window.close();
+}
@@ -12,6 +12,36 @@ use freeing_test::*;
 
 mod other;
 
+mod snapshot;
+
+mod plotters_view_test;
+
+mod image_encoder_test;
+
+mod animated_image_test;
+
+mod font_test;
+
+mod image_set_test;
+
+mod image_list_test;
+
+mod shell_info_test;
+
+mod cursor_test;
+
+mod owner_draw_test;
+
+mod layout_test;
+
+/// An opaque red RGBA pixel
+const RED: [u8; 4] = [255, 0, 0, 255];
+
+/// The RGBA pixels of a `width` x `height` image filled with `color`
+fn solid(width: u32, height: u32, color: [u8; 4]) -> Vec<u8> {
+    color.repeat((width * height) as usize)
+}
+
 #[derive(Default)]
 pub struct TestControlPanel {
     window: Window,
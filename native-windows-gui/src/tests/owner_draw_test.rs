@@ -0,0 +1,76 @@
+use crate::*;
+use std::fmt;
+
+#[derive(Default)]
+struct Swatch {
+    name: &'static str,
+    color: [u8; 3],
+}
+
+impl fmt::Display for Swatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl OwnerDrawItem for Swatch {
+    fn draw(&self, data: &OwnerDrawData) {
+        data.draw_background();
+        data.fill_rect([2, 2, 14, 14], self.color);
+        data.draw_text(self.name, (18, 0));
+    }
+}
+
+fn swatches() -> Vec<Swatch> {
+    vec![
+        Swatch {
+            name: "Red",
+            color: [255, 0, 0],
+        },
+        Swatch {
+            name: "Green",
+            color: [0, 255, 0],
+        },
+        Swatch {
+            name: "Blue",
+            color: [0, 0, 255],
+        },
+    ]
+}
+
+#[test]
+fn owner_draw_list_box_strings() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut window = Window::default();
+    Window::builder()
+        .flags(WindowFlags::WINDOW)
+        .title("Owner draw")
+        .build(&mut window)
+        .unwrap();
+
+    let mut list = ListBox::default();
+    ListBox::builder()
+        .parent(&window)
+        .owner_draw(true)
+        .collection(swatches())
+        .build(&mut list)
+        .unwrap();
+
+    // Owner drawn items still store their text
+    list.set_selection(Some(1));
+    assert_eq!(list.selection_string(), Some("Green".to_string()));
+    assert_eq!(list.set_selection_string("Blu"), Some(2));
+    assert_eq!(list.selection_string(), Some("Blue".to_string()));
+
+    let mut combo = ComboBox::default();
+    ComboBox::builder()
+        .parent(&window)
+        .owner_draw(true)
+        .collection(swatches())
+        .build(&mut combo)
+        .unwrap();
+
+    assert_eq!(combo.set_selection_string("Gre"), Some(1));
+    assert_eq!(combo.selection_string(), Some("Green".to_string()));
+}
@@ -0,0 +1,54 @@
+use crate::*;
+
+fn test_view() -> PlottersView {
+    PlottersView {
+        x_min: 0.0,
+        x_max: 10.0,
+        y_min: -1.0,
+        y_max: 1.0,
+        plot_area: [50, 20, 450, 220],
+    }
+}
+
+fn assert_near(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn plotters_view_mapping() {
+    let view = test_view();
+
+    assert!(view.contains((50, 20)));
+    assert!(!view.contains((450, 220)));
+    assert!(!view.contains((10, 100)));
+
+    let (x, y) = view.to_data((250, 70));
+    assert_near(x, 5.0);
+    assert_near(y, 0.5);
+
+    assert_eq!(view.to_pixel((5.0, 0.5)), (250, 70));
+    assert_eq!(view.to_pixel(view.to_data((123, 187))), (123, 187));
+}
+
+#[test]
+fn plotters_view_zoom_and_pan() {
+    let view = test_view();
+
+    // The data under the cursor does not move when zooming
+    let center = (150, 170);
+    let before = view.to_data(center);
+    let zoomed = view.zoom(2.0, center);
+    let after = zoomed.to_data(center);
+    assert_near(before.0, after.0);
+    assert_near(before.1, after.1);
+    assert_near(zoomed.x_max - zoomed.x_min, 5.0);
+    assert_near(zoomed.y_max - zoomed.y_min, 1.0);
+
+    // Dragging to the right and down shows the data on the left and above
+    let panned = view.pan((40, 20));
+    assert_near(panned.x_min, -1.0);
+    assert_near(panned.x_max, 9.0);
+    assert_near(panned.y_min, -0.8);
+    assert_near(panned.y_max, 1.2);
+    assert_eq!(panned.plot_area, view.plot_area);
+}
@@ -0,0 +1,69 @@
+use crate::win32::resources_helper as rh;
+use crate::*;
+
+const SHELL32: &str = "C:\\Windows\\System32\\shell32.dll";
+
+fn icon_size(icon: &Icon) -> (u32, u32) {
+    unsafe { rh::icon_size(icon.handle).unwrap() }
+}
+
+#[test]
+fn shell_info_files() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let by_extension = ShellInfo::extension("txt").unwrap();
+    assert_eq!(ShellInfo::extension(".txt"), Some(by_extension.clone()));
+    assert_eq!(ShellInfo::extension("*.txt"), Some(by_extension.clone()));
+    assert!(!by_extension.type_name.is_empty());
+
+    // Files that do not exist are described by their extension
+    let missing = ShellInfo::file("does_not_exist.txt").unwrap();
+    assert_eq!(missing.type_name, by_extension.type_name);
+    assert_eq!(missing.system_index, by_extension.system_index);
+
+    let folder = ShellInfo::file("./test_rc").unwrap();
+    assert_eq!(folder.display_name, "test_rc");
+    assert_ne!(folder.type_name, by_extension.type_name);
+
+    let small = ShellInfo::extension_icon("txt", ShellIconSize::Small).unwrap();
+    let large = ShellInfo::file_icon("./test_rc/cog.ico", ShellIconSize::Large).unwrap();
+    assert!(icon_size(&small).0 < icon_size(&large).0);
+}
+
+#[test]
+fn shell_info_stock_icons() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let small = ShellInfo::stock_icon(StockIcon::Folder, ShellIconSize::Small).unwrap();
+    let large = ShellInfo::stock_icon(StockIcon::Folder, ShellIconSize::Large).unwrap();
+    assert!(icon_size(&small).0 < icon_size(&large).0);
+
+    let index = ShellInfo::stock_icon_index(StockIcon::Shield).unwrap();
+    let system = ImageList::system(SystemImageSize::Small).unwrap();
+    assert!(system.icon(index).is_some());
+
+    let (path, index) = ShellInfo::stock_icon_location(StockIcon::Folder).unwrap();
+    assert!(!path.is_empty());
+    assert!(ShellInfo::extract_icon(&path, index, ShellIconSize::Large).is_some());
+}
+
+#[test]
+fn shell_info_extract_icons() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let count = ShellInfo::icon_count(SHELL32);
+    assert!(count > 10);
+    assert_eq!(
+        ShellInfo::extract_icons(SHELL32, ShellIconSize::Small).len(),
+        count as usize
+    );
+
+    let icon = ShellInfo::extract_icon(SHELL32, 3, ShellIconSize::Large).unwrap();
+    assert!(icon_size(&icon).0 >= 32);
+    assert!(ShellInfo::extract_icon(SHELL32, -1, ShellIconSize::Large).is_none());
+    assert!(ShellInfo::extract_icon(SHELL32, count as i32, ShellIconSize::Large).is_none());
+
+    assert_eq!(ShellInfo::icon_count("./test_rc/cog.ico"), 1);
+    assert_eq!(ShellInfo::icon_count("./test_rc/does_not_exist.dll"), 0);
+    assert!(ShellInfo::extract_icons("./test_rc/ferris.bmp", ShellIconSize::Small).is_empty());
+}
@@ -0,0 +1,52 @@
+/*!
+    Snapshot tests. The drawing backends render into a bitmap in memory and the result is compared
+    against the reference images in `test_rc/snapshots`.
+
+    The reference images are rendered by Direct2D on Windows. A missing reference image fails the test.
+    Run the tests with `NWG_UPDATE_SNAPSHOTS=1` to create or update the reference images after an intended change.
+*/
+use crate::*;
+use plotters::prelude::*;
+
+#[test]
+fn plotters_software_snapshot() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let plot = SoftwarePlotters::new(320, 240).expect("Failed to create the software backend");
+    assert_eq!(plot.size(), (320, 240));
+
+    {
+        let root = plot.draw().unwrap();
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(10)
+            .build_cartesian_2d(0f32..10f32, 0f32..100f32)
+            .unwrap();
+
+        chart
+            .draw_series(LineSeries::new(
+                (0..=10).map(|x| (x as f32, (x * x) as f32)),
+                &RED,
+            ))
+            .unwrap();
+
+        chart
+            .draw_series(
+                (0..=10).map(|x| Circle::new((x as f32, (x * x) as f32), 4, BLUE.filled())),
+            )
+            .unwrap();
+
+        root.draw(&Rectangle::new([(2, 2), (317, 237)], &BLACK))
+            .unwrap();
+    }
+
+    let pixels = plot.to_rgba().unwrap();
+    assert_eq!(pixels.len(), 320 * 240 * 4);
+    assert_eq!(&pixels[..4], &[255, 255, 255, 255]);
+
+    let result = plot
+        .compare_snapshot("test_rc/snapshots/plotters_chart.png", 2)
+        .unwrap();
+
+    assert!(result.is_match(), "{:?}", result);
+}
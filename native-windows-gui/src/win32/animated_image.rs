@@ -0,0 +1,361 @@
+/*!
+    Decoding of animated images.
+
+    GIF frames are read with WIC. WIC does not play APNG animations (it only decodes the default image),
+    so each APNG frame is rebuilt as a standalone PNG image and decoded on its own.
+*/
+use super::image_decoder as img;
+use crate::{FrameDisposal, FrameInfo, NwgError};
+use std::time::Duration;
+use winapi::shared::winerror::S_OK;
+use winapi::um::wincodec::{IWICBitmapDecoder, IWICBitmapSource, IWICImagingFactory};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// A frame of an animation composed over the previous frames. The pixels are RGBA with straight alpha.
+pub struct ComposedFrame {
+    pub pixels: Vec<u8>,
+    pub delay: Duration,
+}
+
+/// Decode every frame of an animated image. Images that are not animated return a single frame.
+pub unsafe fn decode_animation(
+    fact: &IWICImagingFactory,
+    data: &[u8],
+) -> Result<((u32, u32), Vec<ComposedFrame>), NwgError> {
+    let (size, frames) = match apng_frames(data)? {
+        Some((size, frames)) => (size, decode_apng_frames(fact, frames)?),
+        None => {
+            let decoder = img::create_decoder_from_stream(fact, data)?;
+            let frames = decode_wic_frames(&*decoder);
+            (&*decoder).Release();
+            frames?
+        }
+    };
+
+    Ok((size, compose(size, frames)))
+}
+
+unsafe fn decode_wic_frames(
+    decoder: &IWICBitmapDecoder,
+) -> Result<((u32, u32), Vec<(FrameInfo, Vec<u8>)>), NwgError> {
+    let mut count = 0;
+    decoder.GetFrameCount(&mut count);
+    if count == 0 {
+        return Err(NwgError::image_decoder(0, "The image has no frames"));
+    }
+
+    let mut frames = Vec::with_capacity(count as usize);
+    for index in 0..count {
+        let info = img::frame_info(decoder, index)?;
+
+        let mut frame = std::ptr::null_mut();
+        let result = decoder.GetFrame(index, &mut frame);
+        if result != S_OK {
+            return Err(NwgError::image_decoder(
+                result,
+                "Could not read image frame",
+            ));
+        }
+
+        let pixels = img::source_to_rgba(frame as *mut IWICBitmapSource);
+        (&*frame).Release();
+
+        let (size, pixels) = pixels?;
+        frames.push((FrameInfo { size, ..info }, pixels));
+    }
+
+    let size = img::logical_size(decoder).unwrap_or_else(|| frames_extent(&frames));
+
+    Ok((size, frames))
+}
+
+unsafe fn decode_apng_frames(
+    fact: &IWICImagingFactory,
+    frames: Vec<(FrameInfo, Vec<u8>)>,
+) -> Result<Vec<(FrameInfo, Vec<u8>)>, NwgError> {
+    let mut decoded = Vec::with_capacity(frames.len());
+    for (info, png) in frames {
+        let decoder = img::create_decoder_from_stream(fact, &png)?;
+
+        let mut frame = std::ptr::null_mut();
+        let result = (&*decoder).GetFrame(0, &mut frame);
+        let pixels = match result {
+            S_OK => {
+                let pixels = img::source_to_rgba(frame as *mut IWICBitmapSource);
+                (&*frame).Release();
+                pixels
+            }
+            e => Err(NwgError::image_decoder(e, "Could not read image frame")),
+        };
+        (&*decoder).Release();
+
+        let (size, pixels) = pixels?;
+        decoded.push((FrameInfo { size, ..info }, pixels));
+    }
+
+    Ok(decoded)
+}
+
+fn frames_extent(frames: &[(FrameInfo, Vec<u8>)]) -> (u32, u32) {
+    frames.iter().fold((0, 0), |(w, h), (info, _)| {
+        let (x, y) = info.position;
+        let (fw, fh) = info.size;
+        (w.max(x + fw), h.max(y + fh))
+    })
+}
+
+/**
+    Draw the frames of an animation over each other, applying their blending and disposal.
+    `frames` holds the metadata and the RGBA pixels of each frame.
+*/
+pub fn compose(size: (u32, u32), frames: Vec<(FrameInfo, Vec<u8>)>) -> Vec<ComposedFrame> {
+    let (width, height) = size;
+    let mut canvas = vec![0u8; (width * height * 4) as usize];
+    let mut composed = Vec::with_capacity(frames.len());
+
+    for (info, pixels) in frames {
+        let previous = match info.disposal {
+            FrameDisposal::Previous => Some(canvas.clone()),
+            _ => None,
+        };
+
+        let (x, y) = info.position;
+        let (frame_width, frame_height) = info.size;
+        let visible_width = frame_width.min(width.saturating_sub(x));
+        let visible_height = frame_height.min(height.saturating_sub(y));
+
+        for row in 0..visible_height {
+            for column in 0..visible_width {
+                let src = (((row * frame_width) + column) * 4) as usize;
+                let dst = ((((y + row) * width) + x + column) * 4) as usize;
+                let (src, dst) = (&pixels[src..src + 4], &mut canvas[dst..dst + 4]);
+
+                match info.blend {
+                    true => blend_over(dst, src),
+                    false => dst.copy_from_slice(src),
+                }
+            }
+        }
+
+        composed.push(ComposedFrame {
+            pixels: canvas.clone(),
+            delay: info.delay,
+        });
+
+        match (info.disposal, previous) {
+            (FrameDisposal::Background, _) => {
+                for row in 0..visible_height {
+                    let start = ((((y + row) * width) + x) * 4) as usize;
+                    let end = start + (visible_width * 4) as usize;
+                    canvas[start..end].iter_mut().for_each(|b| *b = 0);
+                }
+            }
+            (FrameDisposal::Previous, Some(previous)) => {
+                canvas = previous;
+            }
+            _ => {}
+        }
+    }
+
+    composed
+}
+
+/// Blend a RGBA pixel over another one, with straight alpha
+fn blend_over(dst: &mut [u8], src: &[u8]) {
+    let src_alpha = src[3] as u32;
+    match src_alpha {
+        0 => {}
+        255 => dst.copy_from_slice(src),
+        _ => {
+            let dst_alpha = dst[3] as u32 * (255 - src_alpha) / 255;
+            let alpha = src_alpha + dst_alpha;
+            for c in 0..3 {
+                dst[c] = ((src[c] as u32 * src_alpha + dst[c] as u32 * dst_alpha) / alpha) as u8;
+            }
+            dst[3] = alpha as u8;
+        }
+    }
+}
+
+//
+// APNG
+//
+
+struct Chunk<'a> {
+    kind: [u8; 4],
+    data: &'a [u8],
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([data[offset], data[offset + 1]])
+}
+
+fn malformed() -> NwgError {
+    NwgError::image_decoder(0, "The APNG image is malformed")
+}
+
+fn read_chunks(data: &[u8]) -> Result<Vec<Chunk<'_>>, NwgError> {
+    let mut chunks = Vec::new();
+    let mut offset = PNG_SIGNATURE.len();
+
+    while offset + 12 <= data.len() {
+        let length = read_u32(data, offset) as usize;
+        let start = offset + 8;
+        let end = start.checked_add(length).ok_or_else(malformed)?;
+        if end + 4 > data.len() {
+            return Err(malformed());
+        }
+
+        let mut kind = [0; 4];
+        kind.copy_from_slice(&data[offset + 4..start]);
+        chunks.push(Chunk {
+            kind,
+            data: &data[start..end],
+        });
+
+        offset = end + 4;
+        if &kind == b"IEND" {
+            break;
+        }
+    }
+
+    Ok(chunks)
+}
+
+/// Read a `fcTL` (frame control) chunk
+fn frame_control(data: &[u8]) -> Result<FrameInfo, NwgError> {
+    if data.len() < 26 {
+        return Err(malformed());
+    }
+
+    let size = (read_u32(data, 4), read_u32(data, 8));
+    let position = (read_u32(data, 12), read_u32(data, 16));
+
+    // The delay is a fraction of a second. A denominator of 0 means hundredths of a second.
+    let numerator = read_u16(data, 20) as u64;
+    let denominator = match read_u16(data, 22) {
+        0 => 100,
+        d => d as u64,
+    };
+
+    let disposal = match data[24] {
+        1 => FrameDisposal::Background,
+        2 => FrameDisposal::Previous,
+        _ => FrameDisposal::None,
+    };
+
+    Ok(FrameInfo {
+        delay: Duration::from_millis(numerator * 1000 / denominator),
+        disposal,
+        position,
+        size,
+        blend: data[25] == 1,
+    })
+}
+
+/**
+    Split an APNG image into standalone PNG images, one for each frame.
+    Returns `None` if the data is not an animated PNG.
+*/
+pub fn apng_frames(
+    data: &[u8],
+) -> Result<Option<((u32, u32), Vec<(FrameInfo, Vec<u8>)>)>, NwgError> {
+    if !data.starts_with(&PNG_SIGNATURE) {
+        return Ok(None);
+    }
+
+    let chunks = read_chunks(data)?;
+    if !chunks.iter().any(|c| &c.kind == b"acTL") {
+        return Ok(None);
+    }
+
+    let header = chunks
+        .iter()
+        .find(|c| &c.kind == b"IHDR" && c.data.len() == 13)
+        .ok_or_else(malformed)?;
+    let size = (read_u32(header.data, 0), read_u32(header.data, 4));
+
+    // Chunks placed before the image data (palette, transparency, color space) are shared by every frame
+    let shared: Vec<&Chunk> = chunks
+        .iter()
+        .take_while(|c| &c.kind != b"IDAT")
+        .filter(|c| !matches!(&c.kind, b"IHDR" | b"acTL" | b"fcTL"))
+        .collect();
+
+    // The default image (IDAT) is only part of the animation if a fcTL chunk comes before it
+    let mut frames: Vec<(FrameInfo, Vec<&[u8]>)> = Vec::new();
+    for chunk in chunks.iter() {
+        match &chunk.kind {
+            b"fcTL" => frames.push((frame_control(chunk.data)?, Vec::new())),
+            b"IDAT" => {
+                if let Some((_, data)) = frames.last_mut() {
+                    data.push(chunk.data);
+                }
+            }
+            b"fdAT" => {
+                if chunk.data.len() < 4 {
+                    return Err(malformed());
+                }
+                if let Some((_, data)) = frames.last_mut() {
+                    data.push(&chunk.data[4..]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let frames = frames
+        .into_iter()
+        .filter(|(_, data)| !data.is_empty())
+        .map(|(info, data)| {
+            let mut frame_header = header.data.to_vec();
+            frame_header[0..4].copy_from_slice(&info.size.0.to_be_bytes());
+            frame_header[4..8].copy_from_slice(&info.size.1.to_be_bytes());
+
+            let mut png = PNG_SIGNATURE.to_vec();
+            write_chunk(&mut png, b"IHDR", &frame_header);
+            for chunk in shared.iter() {
+                write_chunk(&mut png, &chunk.kind, chunk.data);
+            }
+            for data in data {
+                write_chunk(&mut png, b"IDAT", data);
+            }
+            write_chunk(&mut png, b"IEND", &[]);
+
+            (info, png)
+        })
+        .collect();
+
+    Ok(Some((size, frames)))
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+
+    let crc = crc32_update(crc32_update(0xFFFF_FFFF, kind), data) ^ 0xFFFF_FFFF;
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (!(crc & 1)).wrapping_add(1);
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    crc
+}
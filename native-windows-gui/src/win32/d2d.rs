@@ -0,0 +1,413 @@
+/*!
+    Direct2D and DirectWrite setup shared by the plotters control and native-windows-canvas
+
+    Also includes a software render target that draws into a bitmap in memory, and the PNG helpers used to
+    compare its output against reference images in tests.
+*/
+use super::image_decoder::{create_image_factory, decode_rgba};
+use super::window_helper;
+use std::ptr;
+use winapi::ctypes::c_void;
+use winapi::shared::windef::HWND;
+use winapi::shared::winerror::S_OK;
+use winapi::um::d2d1::*;
+use winapi::um::dwrite::{DWriteCreateFactory, IDWriteFactory, DWRITE_FACTORY_TYPE_SHARED};
+use winapi::um::wincodec::IWICBitmap;
+use winapi::Interface;
+
+/**
+    A Direct2D factory and a DirectWrite factory.
+
+    The factories are released when the value is dropped. Resources created from them
+    hold their own reference and can outlive the factories.
+*/
+pub struct D2DFactories {
+    pub d2d: *mut ID2D1Factory,
+    pub write: *mut IDWriteFactory,
+}
+
+impl D2DFactories {
+    /// Create a single threaded Direct2D factory and a shared DirectWrite factory
+    pub fn new() -> Result<D2DFactories, &'static str> {
+        unsafe {
+            let mut write: *mut IDWriteFactory = ptr::null_mut();
+            let result = DWriteCreateFactory(
+                DWRITE_FACTORY_TYPE_SHARED,
+                &IDWriteFactory::uuidof(),
+                (&mut write as *mut *mut IDWriteFactory) as _,
+            );
+            if result != S_OK {
+                return Err("Failed to create the directWrite factory");
+            }
+
+            let mut d2d: *mut ID2D1Factory = ptr::null_mut();
+            let result = D2D1CreateFactory(
+                D2D1_FACTORY_TYPE_SINGLE_THREADED,
+                &ID2D1Factory::uuidof(),
+                ptr::null(),
+                (&mut d2d as *mut *mut ID2D1Factory) as *mut *mut c_void,
+            );
+            if result != S_OK {
+                (&*write).Release();
+                return Err("Failed to create the direct2D factory");
+            }
+
+            Ok(D2DFactories { d2d, write })
+        }
+    }
+
+    /**
+        Create a render target that draws into the client area of `hwnd`.
+        The size of the target matches the physical size of the window client area.
+
+        Safety: `hwnd` must be a valid window handle.
+    */
+    pub unsafe fn create_hwnd_render_target(
+        &self,
+        hwnd: HWND,
+    ) -> Result<*mut ID2D1HwndRenderTarget, &'static str> {
+        use winapi::shared::dxgiformat::DXGI_FORMAT_B8G8R8A8_UNORM;
+        use winapi::um::dcommon::{D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_PIXEL_FORMAT, D2D_SIZE_U};
+
+        let (width, height) = client_size(hwnd);
+        let size = D2D_SIZE_U { width, height };
+
+        let pixel_format = D2D1_PIXEL_FORMAT {
+            format: DXGI_FORMAT_B8G8R8A8_UNORM,
+            alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+        };
+
+        let render_props = D2D1_RENDER_TARGET_PROPERTIES {
+            _type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
+            pixelFormat: pixel_format,
+            dpiX: 0.0,
+            dpiY: 0.0,
+            usage: D2D1_RENDER_TARGET_USAGE_NONE,
+            minLevel: D2D1_FEATURE_LEVEL_DEFAULT,
+        };
+
+        let hwnd_render_props = D2D1_HWND_RENDER_TARGET_PROPERTIES {
+            hwnd,
+            pixelSize: size,
+            presentOptions: D2D1_PRESENT_OPTIONS_NONE,
+        };
+
+        let mut render_target: *mut ID2D1HwndRenderTarget = ptr::null_mut();
+        let result = (&*self.d2d).CreateHwndRenderTarget(
+            &render_props,
+            &hwnd_render_props,
+            &mut render_target,
+        );
+
+        match result {
+            S_OK => Ok(render_target),
+            _ => Err("Failed to create the direct2D render target"),
+        }
+    }
+
+    /**
+        Create a render target that rasterizes on the CPU into `bitmap`.
+        The target uses a dpi of 96, so one device independent pixel is one pixel of the bitmap.
+    */
+    pub fn create_software_render_target(
+        &self,
+        bitmap: &SoftwareBitmap,
+    ) -> Result<*mut ID2D1RenderTarget, &'static str> {
+        use winapi::shared::dxgiformat::DXGI_FORMAT_B8G8R8A8_UNORM;
+        use winapi::um::dcommon::{D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_PIXEL_FORMAT};
+
+        let render_props = D2D1_RENDER_TARGET_PROPERTIES {
+            _type: D2D1_RENDER_TARGET_TYPE_SOFTWARE,
+            pixelFormat: D2D1_PIXEL_FORMAT {
+                format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+            },
+            dpiX: 96.0,
+            dpiY: 96.0,
+            usage: D2D1_RENDER_TARGET_USAGE_NONE,
+            minLevel: D2D1_FEATURE_LEVEL_DEFAULT,
+        };
+
+        let mut render_target: *mut ID2D1RenderTarget = ptr::null_mut();
+        let result = unsafe {
+            (&*self.d2d).CreateWicBitmapRenderTarget(
+                bitmap.bitmap,
+                &render_props,
+                &mut render_target,
+            )
+        };
+
+        match result {
+            S_OK => Ok(render_target),
+            _ => Err("Failed to create the direct2D software render target"),
+        }
+    }
+}
+
+impl Drop for D2DFactories {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.d2d.is_null() {
+                (&*self.d2d).Release();
+            }
+
+            if !self.write.is_null() {
+                (&*self.write).Release();
+            }
+        }
+    }
+}
+
+/**
+    Return the physical size of the client area of a window.
+
+    Safety: `hwnd` must be a valid window handle.
+*/
+pub unsafe fn client_size(hwnd: HWND) -> (u32, u32) {
+    window_helper::get_window_physical_size(hwnd)
+}
+
+/// Return the null terminated name of the user default locale. Used when creating DirectWrite text formats.
+pub fn locale_name() -> Vec<u16> {
+    use winapi::um::winnls::GetUserDefaultLocaleName;
+    use winapi::um::winnt::LOCALE_NAME_MAX_LENGTH;
+
+    let mut name_buffer: Vec<u16> = vec![0; LOCALE_NAME_MAX_LENGTH];
+    unsafe {
+        GetUserDefaultLocaleName(name_buffer.as_mut_ptr(), LOCALE_NAME_MAX_LENGTH as i32);
+    }
+
+    name_buffer
+}
+
+/**
+    A 32 bits premultiplied BGRA bitmap in memory. Software render targets draw into it.
+
+    Requires COM to be initialized on the current thread (`nwg::init` does it).
+*/
+pub struct SoftwareBitmap {
+    pub bitmap: *mut IWICBitmap,
+    size: (u32, u32),
+}
+
+impl SoftwareBitmap {
+    pub fn new(width: u32, height: u32) -> Result<SoftwareBitmap, &'static str> {
+        use winapi::um::wincodec::{GUID_WICPixelFormat32bppPBGRA, WICBitmapCacheOnLoad};
+
+        unsafe {
+            let factory = create_image_factory()
+                .map_err(|_| "Failed to create the image factory. Is COM initialized?")?;
+            let mut bitmap = ptr::null_mut();
+            let result = (&*factory).CreateBitmap(
+                width,
+                height,
+                &GUID_WICPixelFormat32bppPBGRA,
+                WICBitmapCacheOnLoad,
+                &mut bitmap,
+            );
+            (&*factory).Release();
+
+            match result {
+                S_OK => Ok(SoftwareBitmap {
+                    bitmap,
+                    size: (width, height),
+                }),
+                _ => Err("Failed to create the software bitmap"),
+            }
+        }
+    }
+
+    /// Return the size of the bitmap in pixels
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Return a copy of the bitmap pixels as RGBA with straight alpha, row by row.
+    pub fn to_rgba(&self) -> Result<Vec<u8>, &'static str> {
+        let (width, height) = self.size;
+        let stride = width * 4;
+        let mut pixels = vec![0u8; (stride * height) as usize];
+
+        let result = unsafe {
+            (&*self.bitmap).CopyPixels(
+                ptr::null(),
+                stride,
+                pixels.len() as u32,
+                pixels.as_mut_ptr(),
+            )
+        };
+
+        if result != S_OK {
+            return Err("Failed to read the software bitmap pixels");
+        }
+
+        for px in pixels.chunks_exact_mut(4) {
+            let [b, g, r, a] = [px[0] as u32, px[1] as u32, px[2] as u32, px[3] as u32];
+            let unpremultiply = |c: u32| match a {
+                0 => 0,
+                a => ((c * 255 + a / 2) / a).min(255) as u8,
+            };
+
+            px[0] = unpremultiply(r);
+            px[1] = unpremultiply(g);
+            px[2] = unpremultiply(b);
+        }
+
+        Ok(pixels)
+    }
+
+    /// Save the bitmap to a PNG file
+    pub fn save_png(&self, path: &str) -> Result<(), &'static str> {
+        write_png(path, self.size, &self.to_rgba()?)
+    }
+}
+
+impl Drop for SoftwareBitmap {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.bitmap.is_null() {
+                (&*self.bitmap).Release();
+            }
+        }
+    }
+}
+
+/// Write RGBA pixels with straight alpha to a PNG file
+pub fn write_png(path: &str, size: (u32, u32), rgba: &[u8]) -> Result<(), &'static str> {
+    use crate::{ContainerFormat, ImageEncoder};
+
+    let (width, height) = size;
+    if rgba.len() != (width * height * 4) as usize {
+        return Err("The pixel buffer does not match the image size");
+    }
+
+    ImageEncoder::new()
+        .and_then(|encoder| encoder.to_filename(path, ContainerFormat::Png))
+        .and_then(|target| target.add_pixels(size, rgba).and_then(|_| target.commit()))
+        .map(|_| ())
+        .map_err(|_| "Failed to write the PNG file")
+}
+
+/// Read an image file (PNG or any format supported by WIC) as RGBA pixels with straight alpha. Returns the size and the pixels.
+pub fn read_png(path: &str) -> Result<((u32, u32), Vec<u8>), &'static str> {
+    let data = std::fs::read(path).map_err(|_| "Failed to read the image file")?;
+    unsafe { decode_rgba(&data).map_err(|_| "Failed to decode the image file") }
+}
+
+/**
+    The result of `compare_snapshot`
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotResult {
+    /// Updating snapshots was requested and the reference image was written
+    Created,
+
+    /// The pixels match the reference image
+    Matched,
+
+    /// The pixels do not match the reference image. `pixels` is the number of different pixels
+    /// and `max_difference` is the largest difference of a color channel.
+    Mismatch { pixels: usize, max_difference: u8 },
+}
+
+impl SnapshotResult {
+    /// Return false if the result is `Mismatch`
+    pub fn is_match(&self) -> bool {
+        match self {
+            SnapshotResult::Mismatch { .. } => false,
+            _ => true,
+        }
+    }
+}
+
+/**
+    Compare RGBA pixels with straight alpha against the reference PNG file at `path`.
+    Two pixels are equal if none of their channels differ by more than `tolerance`.
+
+    * If the `NWG_UPDATE_SNAPSHOTS` environment variable is set, the pixels are written to `path`
+      and `Created` is returned. The parent directories are created if needed.
+    * If the reference file does not exist, an error is returned.
+    * On a mismatch or a missing reference, the pixels are written next to the reference with the `.actual.png` extension.
+
+    ```rust,no_run
+    # use native_windows_gui as nwg;
+    # fn test(bitmap: &nwg::d2d::SoftwareBitmap) {
+    use nwg::d2d::compare_snapshot;
+
+    let result = compare_snapshot("test_rc/snapshots/chart.png", bitmap.size(), &bitmap.to_rgba().unwrap(), 2).unwrap();
+    assert!(result.is_match(), "{:?}", result);
+    # }
+    ```
+*/
+pub fn compare_snapshot(
+    path: &str,
+    size: (u32, u32),
+    rgba: &[u8],
+    tolerance: u8,
+) -> Result<SnapshotResult, &'static str> {
+    use std::path::Path;
+
+    let reference = Path::new(path);
+    let actual = format!("{}.actual.png", path.trim_end_matches(".png"));
+
+    let update = std::env::var_os("NWG_UPDATE_SNAPSHOTS").is_some();
+    if update || !reference.exists() {
+        if let Some(parent) = reference.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| "Failed to create the snapshot directory")?;
+        }
+    }
+
+    if update {
+        write_png(path, size, rgba)?;
+        return Ok(SnapshotResult::Created);
+    }
+
+    if !reference.exists() {
+        write_png(&actual, size, rgba)?;
+        return Err("The reference image does not exist. Run the tests with NWG_UPDATE_SNAPSHOTS=1 to create it");
+    }
+
+    let (expected_size, expected) = read_png(path)?;
+    let result = match expected_size == size {
+        true => compare_pixels(&expected, rgba, tolerance),
+        false => SnapshotResult::Mismatch {
+            pixels: (size.0 * size.1) as usize,
+            max_difference: 255,
+        },
+    };
+
+    if !result.is_match() {
+        write_png(&actual, size, rgba)?;
+    }
+
+    Ok(result)
+}
+
+fn compare_pixels(expected: &[u8], actual: &[u8], tolerance: u8) -> SnapshotResult {
+    let mut pixels = 0;
+    let mut max_difference = 0;
+
+    for (e, a) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+        let difference = e
+            .iter()
+            .zip(a.iter())
+            .map(|(&e, &a)| (e as i16 - a as i16).abs() as u8)
+            .max()
+            .unwrap_or(0);
+
+        if difference > tolerance {
+            pixels += 1;
+        }
+
+        max_difference = max_difference.max(difference);
+    }
+
+    match pixels {
+        0 => SnapshotResult::Matched,
+        pixels => SnapshotResult::Mismatch {
+            pixels,
+            max_difference,
+        },
+    }
+}
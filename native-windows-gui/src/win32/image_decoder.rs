@@ -1,9 +1,13 @@
-use crate::{Bitmap, ImageData, NwgError};
+use crate::{Bitmap, ContainerFormat, FrameDisposal, FrameInfo, ImageData, NwgError};
 use std::ptr;
 use winapi::ctypes::{c_uint, c_void};
+use winapi::shared::guiddef::GUID;
 use winapi::shared::winerror::S_OK;
 use winapi::um::objidlbase::IStream;
-use winapi::um::wincodec::{IWICBitmapDecoder, IWICImagingFactory};
+use winapi::um::wincodec::{
+    IWICBitmapDecoder, IWICBitmapEncoder, IWICBitmapFrameEncode, IWICBitmapSource,
+    IWICImagingFactory,
+};
 use winapi::Interface;
 
 pub unsafe fn create_image_factory() -> Result<*mut IWICImagingFactory, NwgError> {
@@ -223,3 +227,604 @@ pub unsafe fn resize_bitmap(
         frame: scaler as *mut IWICBitmapSource,
     })
 }
+
+/// Copy the pixels of an image as RGBA with straight alpha
+pub unsafe fn source_to_rgba(
+    source: *mut IWICBitmapSource,
+) -> Result<((u32, u32), Vec<u8>), NwgError> {
+    use winapi::um::wincodec::{GUID_WICPixelFormat32bppRGBA, WICConvertBitmapSource};
+
+    let mut converted = ptr::null_mut();
+    let result = WICConvertBitmapSource(&GUID_WICPixelFormat32bppRGBA, source, &mut converted);
+    if result != S_OK {
+        return Err(NwgError::image_decoder(
+            result,
+            "Could not convert image pixels",
+        ));
+    }
+
+    let (mut width, mut height) = (0, 0);
+    (&*converted).GetSize(&mut width, &mut height);
+
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+    let result = (&*converted).CopyPixels(
+        ptr::null(),
+        width * 4,
+        pixels.len() as u32,
+        pixels.as_mut_ptr(),
+    );
+    (&*converted).Release();
+
+    match result {
+        S_OK => Ok(((width, height), pixels)),
+        e => Err(NwgError::image_decoder(e, "Could not read image pixels")),
+    }
+}
+
+/// Decode the first frame of an image file stored in memory as RGBA with straight alpha
+pub unsafe fn decode_rgba(data: &[u8]) -> Result<((u32, u32), Vec<u8>), NwgError> {
+    let factory = create_image_factory()?;
+    let decoder = create_decoder_from_stream(&*factory, data);
+    (&*factory).Release();
+    let decoder = decoder?;
+
+    let mut frame = ptr::null_mut();
+    let result = (&*decoder).GetFrame(0, &mut frame);
+    let pixels = match result {
+        S_OK => {
+            let pixels = source_to_rgba(frame as *mut IWICBitmapSource);
+            (&*frame).Release();
+            pixels
+        }
+        e => Err(NwgError::image_decoder(e, "Could not read image frame")),
+    };
+    (&*decoder).Release();
+
+    pixels
+}
+
+/// Read an unsigned integer metadata value. Returns `None` if the query is missing or is not an integer.
+unsafe fn metadata_uint(
+    reader: &winapi::um::wincodec::IWICMetadataQueryReader,
+    query: &str,
+) -> Option<u32> {
+    use crate::win32::base_helper::to_utf16;
+    use std::mem;
+    use winapi::shared::wtypes::{VT_UI1, VT_UI2, VT_UI4};
+    use winapi::um::propidl::PROPVARIANT;
+
+    let query = to_utf16(query);
+    let mut value: PROPVARIANT = mem::zeroed();
+    if reader.GetMetadataByName(query.as_ptr(), &mut value) != S_OK {
+        return None;
+    }
+
+    match value.vt as u32 {
+        VT_UI1 => Some(*value.data.bVal() as u32),
+        VT_UI2 => Some(*value.data.uiVal() as u32),
+        VT_UI4 => Some(*value.data.ulVal()),
+        _ => None,
+    }
+}
+
+/**
+    Read the animation metadata of a frame. Formats without animation metadata
+    return a frame that covers the whole image with no delay.
+*/
+pub unsafe fn frame_info(decoder: &IWICBitmapDecoder, index: u32) -> Result<FrameInfo, NwgError> {
+    use std::time::Duration;
+
+    let mut frame = ptr::null_mut();
+    let result = decoder.GetFrame(index, &mut frame);
+    if result != S_OK {
+        return Err(NwgError::image_decoder(
+            result,
+            "Could not read image frame",
+        ));
+    }
+
+    let (mut width, mut height) = (0, 0);
+    (&*frame).GetSize(&mut width, &mut height);
+
+    let mut reader = ptr::null_mut();
+    let has_metadata = (&*frame).GetMetadataQueryReader(&mut reader) == S_OK;
+    let read = |query: &str| match has_metadata {
+        true => metadata_uint(&*reader, query),
+        false => None,
+    };
+
+    // GIF delays are in hundredths of a second
+    let delay = Duration::from_millis(read("/grctlext/Delay").unwrap_or(0) as u64 * 10);
+    let disposal = match read("/grctlext/Disposal") {
+        Some(2) => FrameDisposal::Background,
+        Some(3) => FrameDisposal::Previous,
+        _ => FrameDisposal::None,
+    };
+    let position = (
+        read("/imgdesc/Left").unwrap_or(0),
+        read("/imgdesc/Top").unwrap_or(0),
+    );
+
+    release(reader);
+    release(frame);
+
+    Ok(FrameInfo {
+        delay,
+        disposal,
+        position,
+        size: (width, height),
+        blend: true,
+    })
+}
+
+/// Return the size of the canvas of an animated image, if the format defines one (GIF)
+pub unsafe fn logical_size(decoder: &IWICBitmapDecoder) -> Option<(u32, u32)> {
+    let mut reader = ptr::null_mut();
+    if decoder.GetMetadataQueryReader(&mut reader) != S_OK {
+        return None;
+    }
+
+    let width = metadata_uint(&*reader, "/logscrdesc/Width");
+    let height = metadata_uint(&*reader, "/logscrdesc/Height");
+    release(reader);
+
+    match (width, height) {
+        (Some(w), Some(h)) if w > 0 && h > 0 => Some((w, h)),
+        _ => None,
+    }
+}
+
+//
+// Encoding
+//
+
+/// Return the GUID of the WIC container for `format`, if WIC has an encoder for it
+pub fn container_guid(format: ContainerFormat) -> Option<GUID> {
+    use winapi::um::wincodec::{
+        GUID_ContainerFormatBmp, GUID_ContainerFormatGif, GUID_ContainerFormatJpeg,
+        GUID_ContainerFormatPng, GUID_ContainerFormatTiff, GUID_ContainerFormatWmp,
+    };
+
+    match format {
+        ContainerFormat::Bmp => Some(GUID_ContainerFormatBmp),
+        ContainerFormat::Png => Some(GUID_ContainerFormatPng),
+        ContainerFormat::Jpeg => Some(GUID_ContainerFormatJpeg),
+        ContainerFormat::Tiff => Some(GUID_ContainerFormatTiff),
+        ContainerFormat::Gif => Some(GUID_ContainerFormatGif),
+        ContainerFormat::Wmp => Some(GUID_ContainerFormatWmp),
+        ContainerFormat::Unknown | ContainerFormat::Adng | ContainerFormat::Ico => None,
+    }
+}
+
+pub unsafe fn create_file_stream<'a>(
+    fact: &IWICImagingFactory,
+    path: &'a str,
+) -> Result<*mut IStream, NwgError> {
+    use crate::win32::base_helper::to_utf16;
+    use winapi::um::winnt::GENERIC_WRITE;
+
+    let path = to_utf16(path);
+
+    let mut stream = ptr::null_mut();
+    let mut result = fact.CreateStream(&mut stream);
+    if result == S_OK {
+        result = (&*stream).InitializeFromFilename(path.as_ptr(), GENERIC_WRITE);
+    }
+
+    if result != S_OK {
+        release(stream);
+        return Err(NwgError::image_encoder(
+            result,
+            "Failed to open the file for writing",
+        ));
+    }
+
+    Ok(stream as *mut IStream)
+}
+
+pub unsafe fn create_memory_stream() -> Result<*mut IStream, NwgError> {
+    let stream = SHCreateMemStream(ptr::null(), 0);
+    if stream.is_null() {
+        return Err(NwgError::resource_create(
+            "Failed to create memory stream, allocation failure",
+        ));
+    }
+
+    Ok(stream)
+}
+
+/// Read the whole content of a stream
+pub unsafe fn read_stream(stream: &IStream) -> Result<Vec<u8>, NwgError> {
+    use std::mem;
+    use winapi::um::objidlbase::{STREAM_SEEK_END, STREAM_SEEK_SET};
+
+    let mut size = mem::zeroed();
+    let mut result = stream.Seek(mem::zeroed(), STREAM_SEEK_END, &mut size);
+    if result == S_OK {
+        result = stream.Seek(mem::zeroed(), STREAM_SEEK_SET, ptr::null_mut());
+    }
+
+    let size = *size.QuadPart() as usize;
+    let mut buffer = vec![0u8; size];
+    let mut read = 0;
+    if result == S_OK {
+        result = stream.Read(buffer.as_mut_ptr() as _, size as u32, &mut read);
+    }
+
+    match result == S_OK && read as usize == size {
+        true => Ok(buffer),
+        false => Err(NwgError::image_encoder(
+            result,
+            "Could not read the encoded image",
+        )),
+    }
+}
+
+pub unsafe fn create_encoder(
+    fact: &IWICImagingFactory,
+    format: ContainerFormat,
+    stream: *mut IStream,
+) -> Result<*mut IWICBitmapEncoder, NwgError> {
+    use winapi::um::wincodec::WICBitmapEncoderNoCache;
+
+    let container = match container_guid(format) {
+        Some(guid) => guid,
+        None => {
+            return Err(NwgError::image_encoder(
+                0,
+                format!("{:?} images cannot be encoded", format),
+            ))
+        }
+    };
+
+    let mut encoder = ptr::null_mut();
+    let mut result = fact.CreateEncoder(&container, ptr::null(), &mut encoder);
+    if result == S_OK {
+        result = (&*encoder).Initialize(stream, WICBitmapEncoderNoCache);
+    }
+
+    if result != S_OK {
+        release(encoder);
+        return Err(NwgError::image_encoder(
+            result,
+            "Failed to create a bitmap encoder",
+        ));
+    }
+
+    Ok(encoder)
+}
+
+/// Create a WIC bitmap from RGBA pixels with straight alpha
+pub unsafe fn create_source_from_rgba(
+    fact: &IWICImagingFactory,
+    size: (u32, u32),
+    rgba: &[u8],
+) -> Result<*mut IWICBitmapSource, NwgError> {
+    use winapi::um::wincodec::GUID_WICPixelFormat32bppBGRA;
+
+    let (width, height) = size;
+    if rgba.len() != (width * height * 4) as usize {
+        return Err(NwgError::image_encoder(
+            0,
+            "The pixel buffer does not match the image size",
+        ));
+    }
+
+    let mut bgra = rgba.to_vec();
+    for px in bgra.chunks_exact_mut(4) {
+        px.swap(0, 2);
+    }
+
+    let mut bitmap = ptr::null_mut();
+    let result = fact.CreateBitmapFromMemory(
+        width,
+        height,
+        &GUID_WICPixelFormat32bppBGRA,
+        width * 4,
+        bgra.len() as u32,
+        bgra.as_ptr(),
+        &mut bitmap,
+    );
+
+    match result {
+        S_OK => Ok(bitmap as *mut IWICBitmapSource),
+        e => Err(NwgError::image_encoder(
+            e,
+            "Could not create a bitmap from the pixels",
+        )),
+    }
+}
+
+/**
+    Encode `source` in a new frame of `encoder`.
+
+    `quality` is the `ImageQuality` encoder option (0.0 to 1.0) and `metadata` is a list of metadata query/value pairs.
+    Indexed formats (GIF) get an optimal palette generated from the source.
+*/
+pub unsafe fn encode_frame(
+    fact: &IWICImagingFactory,
+    encoder: &IWICBitmapEncoder,
+    source: *mut IWICBitmapSource,
+    quality: Option<f32>,
+    metadata: &[(String, String)],
+) -> Result<(), NwgError> {
+    let mut frame = ptr::null_mut();
+    let mut options = ptr::null_mut();
+    let result = encoder.CreateNewFrame(&mut frame, &mut options);
+    if result != S_OK {
+        return Err(NwgError::image_encoder(
+            result,
+            "Could not create a new image frame",
+        ));
+    }
+
+    let written = write_frame(fact, &*frame, &*options, source, quality, metadata);
+
+    release(options);
+    release(frame);
+
+    written
+}
+
+unsafe fn write_frame(
+    fact: &IWICImagingFactory,
+    frame: &IWICBitmapFrameEncode,
+    options: &winapi::um::ocidl::IPropertyBag2,
+    source: *mut IWICBitmapSource,
+    quality: Option<f32>,
+    metadata: &[(String, String)],
+) -> Result<(), NwgError> {
+    use crate::win32::base_helper::to_utf16;
+    use std::mem;
+    use winapi::shared::guiddef::IsEqualGUID;
+    use winapi::shared::wtypes::{VARTYPE, VT_R4};
+    use winapi::um::oaidl::VARIANT;
+    use winapi::um::ocidl::PROPBAG2;
+    use winapi::um::wincodec::{
+        GUID_WICPixelFormat32bppBGRA, GUID_WICPixelFormat8bppIndexed, WICConvertBitmapSource,
+    };
+
+    let check = |result: i32, msg: &'static str| match result {
+        S_OK => Ok(()),
+        e => Err(NwgError::image_encoder(e, msg)),
+    };
+
+    if let Some(quality) = quality {
+        let mut name = to_utf16("ImageQuality");
+        let mut property: PROPBAG2 = mem::zeroed();
+        property.pstrName = name.as_mut_ptr();
+
+        let mut value: VARIANT = mem::zeroed();
+        let v = value.n1.n2_mut();
+        v.vt = VT_R4 as VARTYPE;
+        *v.n3.fltVal_mut() = quality.max(0.0).min(1.0);
+
+        check(
+            options.Write(1, &property, &value),
+            "The image format does not support the quality option",
+        )?;
+    }
+
+    check(
+        frame.Initialize(options),
+        "Could not initialize the image frame",
+    )?;
+
+    let (mut width, mut height) = (0, 0);
+    (&*source).GetSize(&mut width, &mut height);
+    check(frame.SetSize(width, height), "Could not set the image size")?;
+
+    let (mut dpi_x, mut dpi_y) = (0.0, 0.0);
+    (&*source).GetResolution(&mut dpi_x, &mut dpi_y);
+    if dpi_x <= 0.0 || dpi_y <= 0.0 {
+        dpi_x = 96.0;
+        dpi_y = 96.0;
+    }
+    check(
+        frame.SetResolution(dpi_x, dpi_y),
+        "Could not set the image resolution",
+    )?;
+
+    // The encoder replaces the pixel format with the closest one it supports
+    let mut format = GUID_WICPixelFormat32bppBGRA;
+    check(
+        frame.SetPixelFormat(&mut format),
+        "Could not set the image pixel format",
+    )?;
+
+    if !metadata.is_empty() {
+        write_metadata(frame, metadata)?;
+    }
+
+    let converted = match IsEqualGUID(&format, &GUID_WICPixelFormat8bppIndexed) {
+        true => indexed_source(fact, frame, source)?,
+        false => {
+            let mut converted = ptr::null_mut();
+            check(
+                WICConvertBitmapSource(&format, source, &mut converted),
+                "Could not convert image pixels",
+            )?;
+            converted
+        }
+    };
+
+    let result = frame.WriteSource(converted, ptr::null());
+    release(converted);
+
+    check(result, "Could not write the image pixels")?;
+    check(frame.Commit(), "Could not write the image frame")
+}
+
+/// Convert `source` to 8 bits indexed pixels with a palette generated from the source
+unsafe fn indexed_source(
+    fact: &IWICImagingFactory,
+    frame: &IWICBitmapFrameEncode,
+    source: *mut IWICBitmapSource,
+) -> Result<*mut IWICBitmapSource, NwgError> {
+    use winapi::shared::minwindef::TRUE;
+    use winapi::um::wincodec::{
+        GUID_WICPixelFormat8bppIndexed, WICBitmapDitherTypeErrorDiffusion,
+        WICBitmapPaletteTypeCustom,
+    };
+
+    let mut palette = ptr::null_mut();
+    let mut converter = ptr::null_mut();
+
+    let mut result = fact.CreatePalette(&mut palette);
+    if result == S_OK {
+        result = (&*palette).InitializeFromBitmap(source, 256, TRUE);
+    }
+    if result == S_OK {
+        result = fact.CreateFormatConverter(&mut converter);
+    }
+    if result == S_OK {
+        result = (&*converter).Initialize(
+            source,
+            &GUID_WICPixelFormat8bppIndexed,
+            WICBitmapDitherTypeErrorDiffusion,
+            palette,
+            0.0,
+            WICBitmapPaletteTypeCustom,
+        );
+    }
+    if result == S_OK {
+        result = frame.SetPalette(palette);
+    }
+
+    release(palette);
+
+    match result {
+        S_OK => Ok(converter as *mut IWICBitmapSource),
+        e => {
+            release(converter);
+            Err(NwgError::image_encoder(
+                e,
+                "Could not create the image palette",
+            ))
+        }
+    }
+}
+
+unsafe fn write_metadata(
+    frame: &IWICBitmapFrameEncode,
+    metadata: &[(String, String)],
+) -> Result<(), NwgError> {
+    use crate::win32::base_helper::to_utf16;
+    use std::{ffi::CString, mem};
+    use winapi::shared::wtypes::{VARTYPE, VT_LPSTR};
+    use winapi::um::propidl::PROPVARIANT;
+
+    let mut writer = ptr::null_mut();
+    let result = frame.GetMetadataQueryWriter(&mut writer);
+    if result != S_OK {
+        return Err(NwgError::image_encoder(
+            result,
+            "The image format does not support metadata",
+        ));
+    }
+
+    let mut written = Ok(());
+    for (name, value) in metadata {
+        let text = match CString::new(value.as_str()) {
+            Ok(text) => text,
+            Err(_) => {
+                written = Err(NwgError::image_encoder(
+                    0,
+                    format!(
+                        "The value of the metadata {:?} contains a null character",
+                        name
+                    ),
+                ));
+                break;
+            }
+        };
+
+        let query = to_utf16(name);
+        let mut value: PROPVARIANT = mem::zeroed();
+        value.vt = VT_LPSTR as VARTYPE;
+        *value.data.pszVal_mut() = text.as_ptr() as *mut _;
+
+        let result = (&*writer).SetMetadataByName(query.as_ptr(), &value);
+        if result != S_OK {
+            written = Err(NwgError::image_encoder(
+                result,
+                format!("Could not write the metadata {:?}", name),
+            ));
+            break;
+        }
+    }
+
+    release(writer);
+
+    written
+}
+
+/**
+    Wrap PNG data in an icon file. Icons can only be 256 pixels wide and high.
+*/
+pub fn icon_file(size: (u32, u32), png: &[u8]) -> Result<Vec<u8>, NwgError> {
+    let (width, height) = size;
+    if width == 0 || height == 0 || width > 256 || height > 256 {
+        return Err(NwgError::image_encoder(
+            0,
+            "Icons must be between 1 and 256 pixels wide and high",
+        ));
+    }
+
+    const HEADER_SIZE: u32 = 6 + 16;
+
+    let mut file = Vec::with_capacity(HEADER_SIZE as usize + png.len());
+
+    // ICONDIR: reserved, type (1 = icon), image count
+    file.extend_from_slice(&0u16.to_le_bytes());
+    file.extend_from_slice(&1u16.to_le_bytes());
+    file.extend_from_slice(&1u16.to_le_bytes());
+
+    // ICONDIRENTRY: a size of 0 means 256 pixels
+    file.push((width % 256) as u8);
+    file.push((height % 256) as u8);
+    file.push(0); // Color count
+    file.push(0); // Reserved
+    file.extend_from_slice(&1u16.to_le_bytes()); // Planes
+    file.extend_from_slice(&32u16.to_le_bytes()); // Bits per pixel
+    file.extend_from_slice(&(png.len() as u32).to_le_bytes());
+    file.extend_from_slice(&HEADER_SIZE.to_le_bytes());
+
+    file.extend_from_slice(png);
+
+    Ok(file)
+}
+
+/**
+    Save RGBA pixels to a file. The format is picked from the file extension.
+    Files ending with `.ico` are saved as an icon with a PNG image.
+*/
+pub fn save_rgba<'a>(path: &'a str, size: (u32, u32), rgba: &[u8]) -> Result<(), NwgError> {
+    use crate::ImageEncoder;
+
+    let encoder = ImageEncoder::new()?;
+    match ContainerFormat::from_path(path) {
+        ContainerFormat::Ico => {
+            let target = encoder.to_stream(ContainerFormat::Png)?;
+            target.add_pixels(size, rgba)?;
+            let icon = icon_file(size, &target.commit()?)?;
+
+            std::fs::write(path, icon).map_err(|e| {
+                NwgError::image_encoder(0, format!("Could not write the icon file: {}", e))
+            })
+        }
+        format => {
+            let target = encoder.to_filename(path, format)?;
+            target.add_pixels(size, rgba)?;
+            target.commit().map(|_| ())
+        }
+    }
+}
+
+unsafe fn release<T: Interface>(ptr: *mut T) {
+    use winapi::um::unknwnbase::IUnknown;
+
+    if !ptr.is_null() {
+        (&*(ptr as *mut IUnknown)).Release();
+    }
+}
@@ -24,9 +24,15 @@ pub(crate) mod extern_canvas;
 #[cfg(feature = "image-decoder")]
 pub(crate) mod image_decoder;
 
+#[cfg(feature = "image-decoder")]
+pub(crate) mod animated_image;
+
 #[cfg(feature = "rich-textbox")]
 pub(crate) mod richedit;
 
+#[cfg(any(feature = "plotting", feature = "extern-canvas"))]
+pub mod d2d;
+
 #[cfg(feature = "plotting")]
 pub(crate) mod plotters_d2d;
 
@@ -179,9 +185,12 @@ pub fn enable_visual_styles() {
 pub fn init_common_controls() -> Result<(), NwgError> {
     use winapi::shared::winerror::{S_FALSE, S_OK};
     use winapi::um::commctrl::{InitCommonControlsEx, INITCOMMONCONTROLSEX};
+    use winapi::um::commctrl::{
+        ICC_ANIMATE_CLASS, ICC_HOTKEY_CLASS, ICC_INTERNET_CLASSES, ICC_LINK_CLASS,
+    };
     use winapi::um::commctrl::{
         ICC_BAR_CLASSES, ICC_DATE_CLASSES, ICC_LISTVIEW_CLASSES, ICC_PROGRESS_CLASS,
-        ICC_STANDARD_CLASSES, ICC_TAB_CLASSES, ICC_TREEVIEW_CLASSES,
+        ICC_STANDARD_CLASSES, ICC_TAB_CLASSES, ICC_TREEVIEW_CLASSES, ICC_USEREX_CLASSES,
     };
     use winapi::um::libloaderapi::LoadLibraryW;
     use winapi::um::objbase::CoInitialize;
@@ -189,7 +198,7 @@ pub fn init_common_controls() -> Result<(), NwgError> {
     unsafe {
         let mut classes = ICC_BAR_CLASSES | ICC_STANDARD_CLASSES;
 
-        if cfg!(feature = "datetime-picker") {
+        if cfg!(any(feature = "datetime-picker", feature = "month-calendar")) {
             classes |= ICC_DATE_CLASSES;
         }
 
@@ -201,6 +210,26 @@ pub fn init_common_controls() -> Result<(), NwgError> {
             classes |= ICC_TAB_CLASSES;
         }
 
+        if cfg!(feature = "link-label") {
+            classes |= ICC_LINK_CLASS;
+        }
+
+        if cfg!(feature = "ip-address-input") {
+            classes |= ICC_INTERNET_CLASSES;
+        }
+
+        if cfg!(feature = "hotkey-input") {
+            classes |= ICC_HOTKEY_CLASS;
+        }
+
+        if cfg!(feature = "animation-frame") {
+            classes |= ICC_ANIMATE_CLASS;
+        }
+
+        if cfg!(feature = "combobox") {
+            classes |= ICC_USEREX_CLASSES;
+        }
+
         if cfg!(feature = "tree-view") {
             classes |= ICC_TREEVIEW_CLASSES;
         }
@@ -4,12 +4,11 @@
 use winapi::shared::windef::HWND;
 use winapi::shared::winerror::{D2DERR_RECREATE_TARGET, S_OK};
 use winapi::um::d2d1::*;
-use winapi::um::dwrite::{
-    DWriteCreateFactory, IDWriteFactory, IDWriteTextFormat, DWRITE_FACTORY_TYPE_SHARED,
-};
+use winapi::um::dwrite::IDWriteTextFormat;
 
 use super::base_helper::to_utf16;
-use super::{high_dpi, window_helper};
+use super::d2d::{client_size, locale_name, D2DFactories, SoftwareBitmap};
+use super::high_dpi;
 use std::{
     cell::{Ref, RefCell, RefMut},
     collections::HashMap,
@@ -88,6 +87,7 @@ impl<T: BackendTextStyle> From<&T> for FontFormat {
 pub enum PlottersError {
     RendererInit(String),
     Uninitialized,
+    Image(String),
     Unknown,
 }
 
@@ -97,6 +97,7 @@ impl std::fmt::Display for PlottersError {
         match self {
             RendererInit(reason) => write!(f, "Plotters inner canvas creation failed: {}", reason),
             Uninitialized => write!(f, "The plotters canvas is not initialized"),
+            Image(reason) => write!(f, "Plotters image operation failed: {}", reason),
             Unknown => write!(f, "An unexpected error occured"),
         }
     }
@@ -111,7 +112,7 @@ struct PixelBitmap {
 }
 
 struct Target {
-    render_target: *mut ID2D1HwndRenderTarget,
+    render_target: *mut ID2D1RenderTarget,
     brushes: HashMap<Color, *mut ID2D1SolidColorBrush>,
 
     // Target to draw pixels if draw_pixel is called
@@ -155,7 +156,7 @@ impl Target {
         *brush
     }
 
-    pub(crate) fn allocate_pixel_bitmap(&mut self, renderer: *mut ID2D1Factory) {
+    pub(crate) fn allocate_pixel_bitmap(&mut self) {
         use winapi::shared::dxgiformat::DXGI_FORMAT_R8G8B8A8_UNORM;
         use winapi::um::dcommon::{D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_PIXEL_FORMAT};
 
@@ -175,7 +176,7 @@ impl Target {
 
             let mut dpi_x = 0.0;
             let mut dpi_y = 0.0;
-            (&*renderer).GetDesktopDpi(&mut dpi_x, &mut dpi_y);
+            (&*self.render_target).GetDpi(&mut dpi_x, &mut dpi_y);
 
             (&*self.render_target).CreateBitmap(
                 D2D1_SIZE_U {
@@ -221,11 +222,12 @@ impl Drop for Target {
 }
 
 /**
-    Direct2D backend for the plotters control
+    Direct2D backend for the plotters control. Either draws into a window or, with `SoftwarePlotters`,
+    into a bitmap in memory using the Direct2D software rasterizer.
 */
 pub struct PlottersBackend {
-    renderer: *mut ID2D1Factory,
-    write_factory: *mut IDWriteFactory,
+    factories: D2DFactories,
+    software: Option<SoftwareBitmap>,
     text_formats: RefCell<HashMap<FontFormat, *mut IDWriteTextFormat>>,
     target: RefCell<Target>,
     simple_stroke_style: *mut ID2D1StrokeStyle,
@@ -236,6 +238,15 @@ impl PlottersBackend {
         unsafe { build_renderer(handle) }
     }
 
+    pub(crate) fn init_software(width: u32, height: u32) -> Result<PlottersBackend, PlottersError> {
+        unsafe { build_software_renderer(width, height) }
+    }
+
+    /// Return the bitmap of a software backend
+    pub(crate) fn software_bitmap(&self) -> Option<&SoftwareBitmap> {
+        self.software.as_ref()
+    }
+
     pub(crate) fn begin_draw(&self) {
         unsafe {
             let target = self.target();
@@ -306,15 +317,89 @@ impl PlottersBackend {
         }
     }
 
+    /// Draws the crosshair of an interactive control over the chart. `area` is `[left, top, right, bottom]`.
+    /// If `marker` is set, a circle is drawn around the hovered point.
+    pub(crate) fn draw_crosshair(
+        &self,
+        pos: (i32, i32),
+        area: [i32; 4],
+        marker: Option<(i32, i32)>,
+    ) {
+        let mut target = self.target_mut();
+        let line_brush = target.fetch_brush(Color {
+            r: 128,
+            g: 128,
+            b: 128,
+            a: 160,
+        });
+        let marker_brush = target.fetch_brush(Color {
+            r: 64,
+            g: 64,
+            b: 64,
+            a: 255,
+        });
+        let point = |x: i32, y: i32| D2D1_POINT_2F {
+            x: x as f32 + 0.5,
+            y: y as f32 + 0.5,
+        };
+        let [left, top, right, bottom] = area;
+
+        unsafe {
+            let render_target = &*target.render_target;
+            render_target.DrawLine(
+                point(left, pos.1),
+                point(right, pos.1),
+                line_brush as _,
+                1.0,
+                self.simple_stroke_style,
+            );
+            render_target.DrawLine(
+                point(pos.0, top),
+                point(pos.0, bottom),
+                line_brush as _,
+                1.0,
+                self.simple_stroke_style,
+            );
+
+            if let Some((x, y)) = marker {
+                let ellipse = D2D1_ELLIPSE {
+                    point: point(x, y),
+                    radiusX: 5.0,
+                    radiusY: 5.0,
+                };
+                render_target.DrawEllipse(
+                    &ellipse,
+                    marker_brush as _,
+                    2.0,
+                    self.simple_stroke_style,
+                );
+            }
+        }
+    }
+
     /// Rebuilds the inner target if needed
     pub(crate) fn rebuild(&self, handle: HWND) -> Result<(), PlottersError> {
         let mut target = self.target_mut();
         let new_size = unsafe { client_size(handle) };
         if target.size != new_size || target.last_error == D2DERR_RECREATE_TARGET {
-            *target = unsafe { build_render_target(handle, &mut *self.renderer)? };
+            *target = unsafe { build_render_target(handle, &self.factories)? };
+        }
+
+        target.allocate_pixel_bitmap();
+
+        Ok(())
+    }
+
+    /// Rebuilds the inner target of a software backend if it was lost
+    pub(crate) fn rebuild_software(&self) -> Result<(), PlottersError> {
+        let bitmap = self.software.as_ref().ok_or(PlottersError::Uninitialized)?;
+
+        let mut target = self.target_mut();
+        if target.last_error == D2DERR_RECREATE_TARGET {
+            *target = build_software_target(bitmap, &self.factories)?;
         }
 
-        target.allocate_pixel_bitmap(self.renderer);
+        target.allocate_pixel_bitmap();
 
         Ok(())
     }
@@ -328,7 +413,7 @@ impl PlottersBackend {
             DWRITE_FONT_STRETCH_NORMAL, DWRITE_FONT_WEIGHT_BOLD, DWRITE_FONT_WEIGHT_NORMAL,
         };
 
-        let write_factory = unsafe { &*self.write_factory };
+        let write_factory = unsafe { &*self.factories.write };
 
         // Setting a font with a size lesser than 100 will segfault direct2D
         fmt.size = fmt.size.max(100);
@@ -339,7 +424,7 @@ impl PlottersBackend {
 
             let font_size = (fmt.size as f32) / 100.0;
             let family_name = to_utf16(&fmt.family);
-            let locale = unsafe { locale_name() };
+            let locale = locale_name();
 
             let weight = match fmt.is_bold {
                 true => DWRITE_FONT_WEIGHT_BOLD,
@@ -381,14 +466,6 @@ impl Drop for PlottersBackend {
             if !self.simple_stroke_style.is_null() {
                 (&*self.simple_stroke_style).Release();
             }
-
-            if !self.renderer.is_null() {
-                (&*self.renderer).Release();
-            }
-
-            if !self.write_factory.is_null() {
-                (&*self.write_factory).Release();
-            }
         }
     }
 }
@@ -398,6 +475,12 @@ impl<'a> DrawingBackend for &'a PlottersBackend {
 
     fn get_size(&self) -> (u32, u32) {
         let (width, height) = self.target().size;
+
+        // Software targets are not scaled by the dpi
+        if self.software.is_some() {
+            return (width, height);
+        }
+
         let (width, height) = unsafe { high_dpi::physical_to_logical(width as i32, height as i32) };
         (width as u32, height as u32)
     }
@@ -418,7 +501,7 @@ impl<'a> DrawingBackend for &'a PlottersBackend {
         let mut target = self.target.borrow_mut();
 
         // Allocate the pixel bitmap if it was not allocated before
-        target.allocate_pixel_bitmap(self.renderer);
+        target.allocate_pixel_bitmap();
 
         // Tells the API to write the pixel bitmap on top of the canvas at the end of the drawing
         target.write_pixels = true;
@@ -591,7 +674,7 @@ impl<'a> DrawingBackend for &'a PlottersBackend {
         let brush = target.fetch_brush(Color::from(&style.color()));
 
         unsafe {
-            let fact = &*self.renderer;
+            let fact = &*self.factories.d2d;
             let mut path = ptr::null_mut();
             let mut sink = ptr::null_mut();
 
@@ -699,7 +782,7 @@ impl<'a> DrawingBackend for &'a PlottersBackend {
         let [width, height]: [u32; 2];
 
         unsafe {
-            let write = &*self.write_factory;
+            let write = &*self.factories.write;
             let mut layout = ptr::null_mut();
 
             write.CreateTextLayout(
@@ -736,55 +819,42 @@ impl<'a> DrawingBackend for &'a PlottersBackend {
 
 unsafe fn build_render_target(
     hwnd: HWND,
-    factory: &mut ID2D1Factory,
+    factories: &D2DFactories,
 ) -> Result<Target, PlottersError> {
-    use winapi::shared::dxgiformat::DXGI_FORMAT_B8G8R8A8_UNORM;
-    use winapi::um::dcommon::{D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_PIXEL_FORMAT, D2D_SIZE_U};
-
-    let (width, height) = client_size(hwnd);
-    let size = D2D_SIZE_U { width, height };
-
-    let pixel_format = D2D1_PIXEL_FORMAT {
-        format: DXGI_FORMAT_B8G8R8A8_UNORM,
-        alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
-    };
-
-    let render_props = D2D1_RENDER_TARGET_PROPERTIES {
-        _type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
-        pixelFormat: pixel_format,
-        dpiX: 0.0,
-        dpiY: 0.0,
-        usage: D2D1_RENDER_TARGET_USAGE_NONE,
-        minLevel: D2D1_FEATURE_LEVEL_DEFAULT,
-    };
-
-    let hwnd_render_props = D2D1_HWND_RENDER_TARGET_PROPERTIES {
-        hwnd: hwnd,
-        pixelSize: size,
-        presentOptions: D2D1_PRESENT_OPTIONS_NONE,
-    };
+    let render_target = factories
+        .create_hwnd_render_target(hwnd)
+        .map_err(|e| PlottersError::RendererInit(e.into()))?;
+
+    Ok(Target {
+        render_target: render_target as *mut ID2D1RenderTarget,
+        brushes: Default::default(),
+        pixel_bitmap: None,
+        write_pixels: false,
+        size: client_size(hwnd),
+        last_error: S_OK,
+    })
+}
 
-    let mut render_target: *mut ID2D1HwndRenderTarget = ptr::null_mut();
-    if factory.CreateHwndRenderTarget(&render_props, &hwnd_render_props, &mut render_target) != S_OK
-    {
-        factory.Release();
-        Err(PlottersError::RendererInit(
-            "Failed to create the direct2D render target".into(),
-        ))
-    } else {
-        Ok(Target {
-            render_target,
-            brushes: Default::default(),
-            pixel_bitmap: None,
-            write_pixels: false,
-            size: (width, height),
-            last_error: S_OK,
-        })
-    }
+fn build_software_target(
+    bitmap: &SoftwareBitmap,
+    factories: &D2DFactories,
+) -> Result<Target, PlottersError> {
+    let render_target = factories
+        .create_software_render_target(bitmap)
+        .map_err(|e| PlottersError::RendererInit(e.into()))?;
+
+    Ok(Target {
+        render_target,
+        brushes: Default::default(),
+        pixel_bitmap: None,
+        write_pixels: false,
+        size: bitmap.size(),
+        last_error: S_OK,
+    })
 }
 
 unsafe fn build_static_resources(backend: &mut PlottersBackend) -> Result<(), PlottersError> {
-    let f = &mut *backend.renderer;
+    let f = &mut *backend.factories.d2d;
 
     let props = D2D1_STROKE_STYLE_PROPERTIES {
         startCap: D2D1_CAP_STYLE_ROUND,
@@ -801,72 +871,44 @@ unsafe fn build_static_resources(backend: &mut PlottersBackend) -> Result<(), Pl
     Ok(())
 }
 
-unsafe fn client_size(hwnd: HWND) -> (u32, u32) {
-    window_helper::get_window_physical_size(hwnd)
-}
+unsafe fn build_renderer(handle: HWND) -> Result<PlottersBackend, PlottersError> {
+    let factories = D2DFactories::new().map_err(|e| PlottersError::RendererInit(e.into()))?;
 
-unsafe fn locale_name() -> Vec<u16> {
-    use winapi::um::winnls::GetUserDefaultLocaleName;
-    use winapi::um::winnt::LOCALE_NAME_MAX_LENGTH;
+    // Build the render target
+    let target = build_render_target(handle, &factories)?;
 
-    let mut name_buffer: Vec<u16> = vec![0; LOCALE_NAME_MAX_LENGTH];
-    GetUserDefaultLocaleName(name_buffer.as_mut_ptr(), LOCALE_NAME_MAX_LENGTH as i32);
+    let mut renderer = PlottersBackend {
+        factories,
+        software: None,
+        text_formats: RefCell::new(Default::default()),
+        target: RefCell::new(target),
+        simple_stroke_style: ptr::null_mut(),
+    };
 
-    name_buffer
-}
+    // Build static resources
+    build_static_resources(&mut renderer)?;
 
-unsafe fn build_renderer(handle: HWND) -> Result<PlottersBackend, PlottersError> {
-    use winapi::ctypes::c_void;
-    use winapi::Interface;
-
-    // Build the write factory
-    let mut write_factory: *mut IDWriteFactory = ptr::null_mut();
-    let result = DWriteCreateFactory(
-        DWRITE_FACTORY_TYPE_SHARED,
-        &IDWriteFactory::uuidof(),
-        (&mut write_factory as *mut *mut IDWriteFactory) as _,
-    );
-    if result != S_OK {
-        return Err(PlottersError::RendererInit(
-            "Failed to create the direct2D factory".into(),
-        ));
-    }
+    Ok(renderer)
+}
 
-    // Build the factory
-    let mut renderer: *mut ID2D1Factory = ptr::null_mut();
-    let result = D2D1CreateFactory(
-        D2D1_FACTORY_TYPE_SINGLE_THREADED,
-        &ID2D1Factory::uuidof(),
-        ptr::null(),
-        (&mut renderer as *mut *mut ID2D1Factory) as *mut *mut c_void,
-    );
-
-    if result != S_OK {
-        (&*write_factory).Release();
-        return Err(PlottersError::RendererInit(
-            "Failed to create the direct2D factory".into(),
-        ));
-    }
+unsafe fn build_software_renderer(
+    width: u32,
+    height: u32,
+) -> Result<PlottersBackend, PlottersError> {
+    let factories = D2DFactories::new().map_err(|e| PlottersError::RendererInit(e.into()))?;
+    let bitmap =
+        SoftwareBitmap::new(width, height).map_err(|e| PlottersError::RendererInit(e.into()))?;
 
-    // Build the render target
-    let target = match build_render_target(handle, &mut *renderer) {
-        Ok(target) => target,
-        e @ Err(_) => {
-            (&*renderer).Release();
-            (&*write_factory).Release();
-            e?
-        }
-    };
+    let target = build_software_target(&bitmap, &factories)?;
 
     let mut renderer = PlottersBackend {
-        renderer,
-        write_factory,
+        factories,
+        software: Some(bitmap),
         text_formats: RefCell::new(Default::default()),
         target: RefCell::new(target),
         simple_stroke_style: ptr::null_mut(),
     };
 
-    // Build static resources
     build_static_resources(&mut renderer)?;
 
     Ok(renderer)
@@ -1,9 +1,9 @@
 use winapi::ctypes::c_int;
-use winapi::shared::windef::{HBITMAP, HFONT};
+use winapi::shared::windef::{HBITMAP, HDC, HFONT};
 use winapi::um::winnt::HANDLE;
 
 use super::base_helper::{get_system_error, to_utf16};
-use crate::resources::OemImage;
+use crate::resources::{FontInfo, OemImage};
 
 #[allow(unused_imports)]
 use crate::NwgError;
@@ -47,54 +47,143 @@ pub fn destroy_obj(obj: HANDLE) {
     }
 }
 
-pub unsafe fn build_font(
-    size: i32,
-    weight: u32,
-    style: [bool; 3],
-    family_name: Option<&str>,
-) -> Result<HFONT, NwgError> {
-    use winapi::um::wingdi::CreateFontW;
-    use winapi::um::wingdi::{
-        CLEARTYPE_QUALITY, CLIP_DEFAULT_PRECIS, DEFAULT_CHARSET, OUT_DEFAULT_PRECIS, VARIABLE_PITCH,
-    };
-    let [use_italic, use_underline, use_strikeout] = style;
+pub unsafe fn build_font(info: &FontInfo) -> Result<HFONT, NwgError> {
+    use winapi::um::wingdi::CreateFontIndirectW;
 
-    let fam;
-    let family_name_ptr;
-    if family_name.is_some() {
-        fam = to_utf16(&family_name.unwrap());
-        family_name_ptr = fam.as_ptr();
+    let handle = CreateFontIndirectW(&info.to_logfont());
+    if handle.is_null() {
+        Err(NwgError::resource_create("Failed to create font"))
     } else {
-        fam = Vec::new();
-        family_name_ptr = ptr::null();
+        Ok(handle)
     }
+}
 
-    let (size, _) = super::high_dpi::logical_to_physical(size as i32, 0);
+/**
+    Measures `text` with `font` using `GetTextExtentPoint32W`. If `font` is null, the font selected in `dc` is used.
+    Every line is measured separately. Returns the size in physical pixels.
+*/
+pub unsafe fn text_extent(dc: HDC, font: HFONT, text: &str) -> (i32, i32) {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::shared::windef::{HGDIOBJ, SIZE};
+    use winapi::um::wingdi::{GetTextExtentPoint32W, SelectObject};
+
+    let old_font = match font.is_null() {
+        true => None,
+        false => Some(SelectObject(dc, font as HGDIOBJ)),
+    };
 
-    let handle = CreateFontW(
-        size as c_int, // nHeight
-        0,
-        0,
-        0,                    // nWidth, nEscapement, nOrientation
-        weight as c_int,      // fnWeight
-        use_italic as u32,    // fdwItalic
-        use_underline as u32, // fdwUnderline
-        use_strikeout as u32, // fdwStrikeOut
-        DEFAULT_CHARSET,      // fdwCharSet
-        OUT_DEFAULT_PRECIS,   // fdwOutputPrecision
-        CLIP_DEFAULT_PRECIS,  // fdwClipPrecision
-        CLEARTYPE_QUALITY,    // fdwQuality
-        VARIABLE_PITCH,       // fdwPitchAndFamily
-        family_name_ptr,      // lpszFace
-    );
+    let (mut width, mut height) = (0, 0);
+    for line in text.split('\n') {
+        let line = line.trim_end_matches('\r');
 
-    drop(fam);
+        // Empty lines still have the height of the font
+        let line = match line.is_empty() {
+            true => " ",
+            false => line,
+        };
 
-    if handle.is_null() {
-        Err(NwgError::resource_create("Failed to create font"))
-    } else {
-        Ok(handle)
+        let line: Vec<u16> = OsStr::new(line).encode_wide().collect();
+        let mut size = SIZE { cx: 0, cy: 0 };
+        GetTextExtentPoint32W(dc, line.as_ptr(), line.len() as i32, &mut size);
+
+        if line != [b' ' as u16] {
+            width = width.max(size.cx);
+        }
+        height += size.cy;
+    }
+
+    if let Some(old_font) = old_font {
+        SelectObject(dc, old_font);
+    }
+
+    (width, height)
+}
+
+fn font_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn font_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/**
+    Read the family names of a TrueType or OpenType font (or font collection) from the `name` tables.
+    Only the family names used by GDI (name id 1) are returned.
+*/
+pub fn font_families(data: &[u8]) -> Vec<String> {
+    let mut fonts = Vec::new();
+    match data.get(0..4) {
+        Some(b"ttcf") => {
+            let count = font_u32(data, 8).unwrap_or(0) as usize;
+            fonts.extend((0..count).filter_map(|i| font_u32(data, 12 + i * 4)));
+        }
+        Some(_) => fonts.push(0),
+        None => {}
     }
+
+    let mut families: Vec<String> = Vec::new();
+    for offset in fonts {
+        if let Some(family) = font_family(data, offset as usize) {
+            if !families.contains(&family) {
+                families.push(family);
+            }
+        }
+    }
+
+    families
+}
+
+/// Read the family name of the font at `offset`. English names from the Windows platform are preferred.
+fn font_family(data: &[u8], offset: usize) -> Option<String> {
+    let tables = font_u16(data, offset + 4)? as usize;
+    let record = (0..tables)
+        .map(|i| offset + 12 + i * 16)
+        .find(|&r| data.get(r..r + 4) == Some(b"name"))?;
+
+    let table = font_u32(data, record + 8)? as usize;
+    let count = font_u16(data, table + 2)? as usize;
+    let strings = table + font_u16(data, table + 4)? as usize;
+
+    let mut family: Option<(u8, String)> = None;
+    for i in 0..count {
+        let record = table + 6 + i * 12;
+        let platform = font_u16(data, record)?;
+        let encoding = font_u16(data, record + 2)?;
+        let language = font_u16(data, record + 4)?;
+        let name_id = font_u16(data, record + 6)?;
+        let length = font_u16(data, record + 8)? as usize;
+        let start = strings + font_u16(data, record + 10)? as usize;
+
+        let bytes = match (name_id, data.get(start..start + length)) {
+            (1, Some(bytes)) => bytes,
+            _ => continue,
+        };
+
+        let utf16 = || {
+            let wide: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&wide)
+        };
+
+        let (score, name) = match (platform, encoding) {
+            (3, _) if language == 0x409 => (3, utf16()),
+            (3, _) | (0, _) => (2, utf16()),
+            (1, 0) => (1, bytes.iter().map(|&b| b as char).collect()),
+            _ => continue,
+        };
+
+        if family.as_ref().map(|(s, _)| score > *s).unwrap_or(true) {
+            family = Some((score, name));
+        }
+    }
+
+    family.map(|(_, name)| name)
 }
 
 pub unsafe fn build_image<'a>(
@@ -413,6 +502,619 @@ pub unsafe fn icon_from_memory(
     unimplemented!("Loading icons from memory require the \"image-decoder\" feature");
 }
 
+//
+// Pixel access
+//
+
+/// Header of a top-down 32 bits DIB
+fn dib_info(width: u32, height: u32) -> winapi::um::wingdi::BITMAPINFO {
+    use winapi::shared::{minwindef::DWORD, ntdef::LONG};
+    use winapi::um::wingdi::{BITMAPINFO, BITMAPINFOHEADER, BI_RGB, RGBQUAD};
+
+    let header = BITMAPINFOHEADER {
+        biSize: mem::size_of::<BITMAPINFOHEADER>() as DWORD,
+        biWidth: width as LONG,
+        biHeight: -(height as LONG),
+        biPlanes: 1,
+        biBitCount: 32,
+        biCompression: BI_RGB,
+        biSizeImage: width * height * 4,
+        biXPelsPerMeter: 0,
+        biYPelsPerMeter: 0,
+        biClrUsed: 0,
+        biClrImportant: 0,
+    };
+
+    let quad = RGBQUAD {
+        rgbBlue: 0,
+        rgbGreen: 0,
+        rgbRed: 0,
+        rgbReserved: 0,
+    };
+
+    BITMAPINFO {
+        bmiHeader: header,
+        bmiColors: [quad],
+    }
+}
+
+/// Return the size in pixels and the number of bits per pixel of a bitmap
+pub unsafe fn bitmap_info(bitmap: HBITMAP) -> Result<((u32, u32), u16), NwgError> {
+    use winapi::um::wingdi::{GetObjectW, BITMAP};
+
+    let mut info: BITMAP = mem::zeroed();
+    let size = mem::size_of::<BITMAP>() as c_int;
+    if GetObjectW(bitmap as _, size, &mut info as *mut BITMAP as _) == 0 {
+        return Err(NwgError::resource_create("The handle is not a bitmap"));
+    }
+
+    let size = (info.bmWidth as u32, info.bmHeight.abs() as u32);
+    Ok((size, info.bmBitsPixel))
+}
+
+/// Check if a bitmap is a 32 bits DIB section with an alpha channel, like the bitmaps created by the image decoder
+pub unsafe fn bitmap_has_alpha(bitmap: HBITMAP) -> bool {
+    use winapi::um::wingdi::{GetObjectW, DIBSECTION};
+
+    let mut info: DIBSECTION = mem::zeroed();
+    let size = mem::size_of::<DIBSECTION>() as c_int;
+    if GetObjectW(bitmap as _, size, &mut info as *mut DIBSECTION as _) != size {
+        return false;
+    }
+
+    let bitmap = &info.dsBm;
+    if bitmap.bmBitsPixel != 32 || bitmap.bmBits.is_null() {
+        return false;
+    }
+
+    let length = (bitmap.bmWidthBytes * bitmap.bmHeight.abs()) as usize;
+    let pixels = std::slice::from_raw_parts(bitmap.bmBits as *const u8, length);
+    pixels.chunks_exact(4).any(|px| px[3] != 0)
+}
+
+/// Copy the pixels of a bitmap as 32 bits BGRA, from top to bottom
+unsafe fn bitmap_bgra(bitmap: HBITMAP, size: (u32, u32)) -> Result<Vec<u8>, NwgError> {
+    use winapi::um::wingdi::{GetDIBits, DIB_RGB_COLORS};
+    use winapi::um::winuser::{GetDC, ReleaseDC};
+
+    let (width, height) = size;
+    let mut info = dib_info(width, height);
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+    let screen_dc = GetDC(ptr::null_mut());
+    let lines = GetDIBits(
+        screen_dc,
+        bitmap,
+        0,
+        height,
+        pixels.as_mut_ptr() as _,
+        &mut info,
+        DIB_RGB_COLORS,
+    );
+    ReleaseDC(ptr::null_mut(), screen_dc);
+
+    match lines as u32 == height {
+        true => Ok(pixels),
+        false => Err(NwgError::resource_create(
+            "Could not read the bitmap pixels",
+        )),
+    }
+}
+
+/// Convert BGRA pixels to RGBA in place. Pixels without alpha are made opaque.
+fn bgra_to_rgba(pixels: &mut [u8], has_alpha: bool, premultiplied: bool) {
+    for px in pixels.chunks_exact_mut(4) {
+        px.swap(0, 2);
+
+        let a = px[3] as u32;
+        if !has_alpha {
+            px[3] = 255;
+        } else if premultiplied && a > 0 && a < 255 {
+            for c in px[..3].iter_mut() {
+                *c = ((*c as u32 * 255 + a / 2) / a).min(255) as u8;
+            }
+        }
+    }
+}
+
+/**
+    Copy the pixels of a bitmap as RGBA with straight alpha. The alpha channel of 32 bits bitmaps is expected
+    to be premultiplied, as with the bitmaps created by the image decoder. Bitmaps without alpha are opaque.
+*/
+pub unsafe fn bitmap_to_rgba(bitmap: HBITMAP) -> Result<((u32, u32), Vec<u8>), NwgError> {
+    let (size, bits) = bitmap_info(bitmap)?;
+    let mut pixels = bitmap_bgra(bitmap, size)?;
+
+    let has_alpha = bits == 32 && pixels.chunks_exact(4).any(|px| px[3] != 0);
+    bgra_to_rgba(&mut pixels, has_alpha, true);
+
+    Ok((size, pixels))
+}
+
+/**
+    Copy the pixels of an icon or a cursor as RGBA with straight alpha.
+    If the icon color has no alpha channel, the transparency comes from the icon mask.
+*/
+pub unsafe fn icon_to_rgba(icon: HANDLE) -> Result<((u32, u32), Vec<u8>), NwgError> {
+    use winapi::um::wingdi::DeleteObject;
+    use winapi::um::winuser::{GetIconInfo, ICONINFO};
+
+    let mut info: ICONINFO = mem::zeroed();
+    if GetIconInfo(icon as _, &mut info) == 0 {
+        return Err(NwgError::resource_create("The handle is not an icon"));
+    }
+
+    let pixels = icon_info_to_rgba(&info);
+
+    if !info.hbmColor.is_null() {
+        DeleteObject(info.hbmColor as _);
+    }
+    if !info.hbmMask.is_null() {
+        DeleteObject(info.hbmMask as _);
+    }
+
+    pixels
+}
+
+/// Return the size in pixels of an icon or a cursor
+pub unsafe fn icon_size(icon: HANDLE) -> Result<(u32, u32), NwgError> {
+    use winapi::um::wingdi::DeleteObject;
+    use winapi::um::winuser::{GetIconInfo, ICONINFO};
+
+    let mut info: ICONINFO = mem::zeroed();
+    if GetIconInfo(icon as _, &mut info) == 0 {
+        return Err(NwgError::resource_create("The handle is not an icon"));
+    }
+
+    // Monochrome icons store the AND mask and the XOR mask in the same bitmap
+    let size = match info.hbmColor.is_null() {
+        true => bitmap_info(info.hbmMask).map(|((w, h), _)| (w, h / 2)),
+        false => bitmap_info(info.hbmColor).map(|(size, _)| size),
+    };
+
+    if !info.hbmColor.is_null() {
+        DeleteObject(info.hbmColor as _);
+    }
+    if !info.hbmMask.is_null() {
+        DeleteObject(info.hbmMask as _);
+    }
+
+    size
+}
+
+unsafe fn icon_info_to_rgba(
+    info: &winapi::um::winuser::ICONINFO,
+) -> Result<((u32, u32), Vec<u8>), NwgError> {
+    let (mask_size, _) = bitmap_info(info.hbmMask)?;
+    let mask = bitmap_bgra(info.hbmMask, mask_size)?;
+
+    // Monochrome icons store the AND mask and the XOR mask in the same bitmap
+    if info.hbmColor.is_null() {
+        let (width, height) = (mask_size.0, mask_size.1 / 2);
+        let half = (width * height * 4) as usize;
+        let (and_mask, xor_mask) = mask.split_at(half);
+
+        let mut pixels = xor_mask.to_vec();
+        for (px, and) in pixels.chunks_exact_mut(4).zip(and_mask.chunks_exact(4)) {
+            px[3] = if and[0] == 0 { 255 } else { 0 };
+        }
+
+        bgra_to_rgba(&mut pixels, true, false);
+        return Ok(((width, height), pixels));
+    }
+
+    let (size, bits) = bitmap_info(info.hbmColor)?;
+    let mut pixels = bitmap_bgra(info.hbmColor, size)?;
+    let has_alpha = bits == 32 && pixels.chunks_exact(4).any(|px| px[3] != 0);
+
+    if !has_alpha && mask_size == size {
+        for (px, m) in pixels.chunks_exact_mut(4).zip(mask.chunks_exact(4)) {
+            px[3] = if m[0] == 0 { 255 } else { 0 };
+        }
+    }
+
+    bgra_to_rgba(&mut pixels, has_alpha || mask_size == size, false);
+
+    Ok((size, pixels))
+}
+
+/**
+    Create a 32 bits bitmap from RGBA pixels with straight alpha.
+    The bitmap alpha is premultiplied, like the bitmaps created by the image decoder.
+*/
+pub unsafe fn bitmap_from_rgba(size: (u32, u32), rgba: &[u8]) -> Result<HANDLE, NwgError> {
+    dib_from_rgba(size, rgba, true)
+}
+
+/**
+    Create a 32 bits DIB section from RGBA pixels with straight alpha.
+    Icons and image lists expect straight alpha, `AlphaBlend` expects `premultiply`.
+*/
+pub unsafe fn dib_from_rgba(
+    size: (u32, u32),
+    rgba: &[u8],
+    premultiply: bool,
+) -> Result<HANDLE, NwgError> {
+    use winapi::um::wingdi::{CreateDIBSection, DIB_RGB_COLORS};
+    use winapi::um::winuser::{GetDC, ReleaseDC};
+
+    let (width, height) = size;
+    if rgba.len() != (width * height * 4) as usize {
+        return Err(NwgError::resource_create(
+            "The pixel buffer does not match the bitmap size",
+        ));
+    }
+
+    let info = dib_info(width, height);
+    let mut bits = ptr::null_mut();
+    let screen_dc = GetDC(ptr::null_mut());
+    let bitmap = CreateDIBSection(
+        screen_dc,
+        &info,
+        DIB_RGB_COLORS,
+        &mut bits,
+        ptr::null_mut(),
+        0,
+    );
+    ReleaseDC(ptr::null_mut(), screen_dc);
+
+    if bitmap.is_null() {
+        return Err(NwgError::resource_create("Could not create a bitmap"));
+    }
+
+    let bits = std::slice::from_raw_parts_mut(bits as *mut u8, rgba.len());
+    for (dst, src) in bits.chunks_exact_mut(4).zip(rgba.chunks_exact(4)) {
+        let a = match premultiply {
+            true => src[3] as u32,
+            false => 255,
+        };
+        dst[0] = ((src[2] as u32 * a + 127) / 255) as u8;
+        dst[1] = ((src[1] as u32 * a + 127) / 255) as u8;
+        dst[2] = ((src[0] as u32 * a + 127) / 255) as u8;
+        dst[3] = src[3];
+    }
+
+    Ok(bitmap as HANDLE)
+}
+
+/// Create an icon from RGBA pixels with straight alpha
+pub unsafe fn icon_from_rgba(size: (u32, u32), rgba: &[u8]) -> Result<HANDLE, NwgError> {
+    create_icon_from_rgba(size, rgba, None)
+}
+
+/**
+    Create a cursor from RGBA pixels with straight alpha. `hotspot` is the point of the image that
+    matches the position of the mouse and must be inside the image.
+*/
+pub unsafe fn cursor_from_rgba(
+    size: (u32, u32),
+    rgba: &[u8],
+    hotspot: (u32, u32),
+) -> Result<HANDLE, NwgError> {
+    if hotspot.0 >= size.0 || hotspot.1 >= size.1 {
+        return Err(NwgError::resource_create(
+            "The cursor hotspot is outside of the image",
+        ));
+    }
+
+    create_icon_from_rgba(size, rgba, Some(hotspot))
+}
+
+/// Icons and cursors are the same object. Cursors have a hotspot.
+unsafe fn create_icon_from_rgba(
+    size: (u32, u32),
+    rgba: &[u8],
+    hotspot: Option<(u32, u32)>,
+) -> Result<HANDLE, NwgError> {
+    use winapi::shared::minwindef::BOOL;
+    use winapi::um::wingdi::{CreateBitmap, DeleteObject};
+    use winapi::um::winuser::{CreateIconIndirect, ICONINFO};
+
+    let color = dib_from_rgba(size, rgba, false)?;
+
+    // The mask is ignored when the color bitmap has an alpha channel, but it must still exist
+    let (width, height) = size;
+    let mask_stride = (((width + 15) / 16) * 2) as usize;
+    let mask_bits = vec![0u8; mask_stride * height as usize];
+    let mask = CreateBitmap(
+        width as c_int,
+        height as c_int,
+        1,
+        1,
+        mask_bits.as_ptr() as _,
+    );
+
+    let (x, y) = hotspot.unwrap_or((0, 0));
+    let mut info = ICONINFO {
+        fIcon: hotspot.is_none() as BOOL,
+        xHotspot: x,
+        yHotspot: y,
+        hbmMask: mask,
+        hbmColor: color as HBITMAP,
+    };
+    let icon = CreateIconIndirect(&mut info);
+
+    DeleteObject(color);
+    DeleteObject(mask as _);
+
+    match (icon.is_null(), hotspot.is_some()) {
+        (true, false) => Err(NwgError::resource_create(
+            "Failed to create icon from pixels",
+        )),
+        (true, true) => Err(NwgError::resource_create(
+            "Failed to create cursor from pixels",
+        )),
+        (false, _) => Ok(icon as HANDLE),
+    }
+}
+
+/// The source pixels and their weights used to compute each pixel of a resampled row or column
+fn resample_weights(src: u32, dst: u32) -> Vec<Vec<(usize, f32)>> {
+    let scale = src as f32 / dst as f32;
+    let last = src as usize - 1;
+
+    (0..dst)
+        .map(|i| {
+            let mut weights = Vec::new();
+            if scale > 1.0 {
+                // Shrinking: average the source pixels covered by the destination pixel
+                let (start, end) = (i as f32 * scale, (i + 1) as f32 * scale);
+                let mut j = start.floor() as usize;
+                while (j as f32) < end && j <= last {
+                    let coverage = (end.min(j as f32 + 1.0) - start.max(j as f32)).max(0.0);
+                    weights.push((j, coverage / scale));
+                    j += 1;
+                }
+            } else {
+                // Enlarging: interpolate between the two nearest source pixels
+                let center = ((i as f32 + 0.5) * scale - 0.5).max(0.0);
+                let j = (center.floor() as usize).min(last);
+                let t = center - j as f32;
+                weights.push((j, 1.0 - t));
+                weights.push(((j + 1).min(last), t));
+            }
+            weights
+        })
+        .collect()
+}
+
+/**
+    Resize RGBA pixels with straight alpha. Images are interpolated when they are enlarged
+    and averaged when they are shrunk. Colors are premultiplied while filtering so that
+    transparent pixels do not bleed into their neighbours.
+*/
+pub fn scale_rgba(size: (u32, u32), rgba: &[u8], target: (u32, u32)) -> Vec<u8> {
+    let (width, height) = size;
+    let (target_width, target_height) = target;
+    if size == target {
+        return rgba.to_vec();
+    }
+    if width == 0 || height == 0 || target_width == 0 || target_height == 0 {
+        return vec![0; (target_width * target_height * 4) as usize];
+    }
+
+    let premultiplied: Vec<[f32; 4]> = rgba
+        .chunks_exact(4)
+        .map(|px| {
+            let a = px[3] as f32 / 255.0;
+            [
+                px[0] as f32 * a,
+                px[1] as f32 * a,
+                px[2] as f32 * a,
+                px[3] as f32,
+            ]
+        })
+        .collect();
+
+    // Resize the rows first, then the columns
+    let columns = resample_weights(width, target_width);
+    let mut rows_scaled = Vec::with_capacity((target_width * height) as usize);
+    for y in 0..height as usize {
+        let row = &premultiplied[y * width as usize..(y + 1) * width as usize];
+        for weights in columns.iter() {
+            let mut px = [0.0f32; 4];
+            for &(j, w) in weights.iter() {
+                for c in 0..4 {
+                    px[c] += row[j][c] * w;
+                }
+            }
+            rows_scaled.push(px);
+        }
+    }
+
+    let rows = resample_weights(height, target_height);
+    let mut out = Vec::with_capacity((target_width * target_height * 4) as usize);
+    for weights in rows.iter() {
+        for x in 0..target_width as usize {
+            let mut px = [0.0f32; 4];
+            for &(j, w) in weights.iter() {
+                let src = rows_scaled[j * target_width as usize + x];
+                for c in 0..4 {
+                    px[c] += src[c] * w;
+                }
+            }
+
+            let a = px[3].max(0.0).min(255.0);
+            for c in 0..3 {
+                let value = match a > 0.0 {
+                    true => px[c] * 255.0 / a,
+                    false => 0.0,
+                };
+                out.push(value.round().max(0.0).min(255.0) as u8);
+            }
+            out.push(a.round() as u8);
+        }
+    }
+
+    out
+}
+
+/// Check if the data starts with the header of an icon (.ico) or a cursor (.cur) file
+pub fn is_icon_file(data: &[u8]) -> bool {
+    data.len() >= 6 && data[0..2] == [0, 0] && (data[2..4] == [1, 0] || data[2..4] == [2, 0])
+}
+
+/**
+    Decode every image stored in an icon (.ico) or a cursor (.cur) file as RGBA pixels with straight alpha.
+    Both PNG and BMP entries are supported.
+*/
+pub unsafe fn icon_file_images(data: &[u8]) -> Result<Vec<((u32, u32), Vec<u8>)>, NwgError> {
+    use winapi::um::winuser::{CreateIconFromResourceEx, LR_DEFAULTCOLOR};
+
+    const ENTRY_SIZE: usize = 16;
+    let malformed = || NwgError::resource_create("The icon file is malformed");
+
+    if !is_icon_file(data) {
+        return Err(malformed());
+    }
+
+    let count = u16::from_le_bytes([data[4], data[5]]) as usize;
+    let mut images = Vec::with_capacity(count);
+    for index in 0..count {
+        let entry = 6 + index * ENTRY_SIZE;
+        if entry + ENTRY_SIZE > data.len() {
+            return Err(malformed());
+        }
+
+        let read_u32 = |offset: usize| {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(&data[entry + offset..entry + offset + 4]);
+            u32::from_le_bytes(bytes) as usize
+        };
+        let (length, offset) = (read_u32(8), read_u32(12));
+        let image = data
+            .get(offset..offset.saturating_add(length))
+            .ok_or_else(malformed)?;
+
+        // A size of 0 in the directory entry means 256 pixels. The real size is read back from the icon.
+        let icon = CreateIconFromResourceEx(
+            image.as_ptr() as *mut u8,
+            image.len() as u32,
+            1,
+            0x0003_0000,
+            0,
+            0,
+            LR_DEFAULTCOLOR,
+        );
+        if icon.is_null() {
+            return Err(malformed());
+        }
+
+        let pixels = icon_to_rgba(icon as HANDLE);
+        destroy_icon(icon as HANDLE);
+        images.push(pixels?);
+    }
+
+    Ok(images)
+}
+
+/// Check if `data` is an animated cursor file (.ani)
+pub fn is_animated_cursor_file(data: &[u8]) -> bool {
+    data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"ACON"
+}
+
+/**
+    Create a cursor from the content of a cursor file (.cur) or of an animated cursor file (.ani).
+    Animated cursors keep their animation.
+
+    For cursor files that contain several images, the image that best matches `size` is used. Without a size,
+    the image that best matches the default cursor size of the system is used.
+*/
+pub unsafe fn cursor_from_memory(
+    data: &[u8],
+    size: Option<(u32, u32)>,
+) -> Result<HANDLE, NwgError> {
+    use winapi::um::winuser::{
+        CreateIconFromResourceEx, GetSystemMetrics, LR_DEFAULTCOLOR, SM_CXCURSOR, SM_CYCURSOR,
+    };
+
+    const ENTRY_SIZE: usize = 16;
+    let malformed = || NwgError::resource_create("The cursor file is malformed");
+
+    let (width, height) = size.unwrap_or((0, 0));
+    let create = |bits: &[u8]| {
+        CreateIconFromResourceEx(
+            bits.as_ptr() as *mut u8,
+            bits.len() as u32,
+            0,
+            0x0003_0000,
+            width as c_int,
+            height as c_int,
+            LR_DEFAULTCOLOR,
+        )
+    };
+
+    // The system reads the animation frames and the timings from the RIFF chunks
+    if is_animated_cursor_file(data) {
+        let cursor = create(data);
+        return match cursor.is_null() {
+            true => Err(malformed()),
+            false => Ok(cursor as HANDLE),
+        };
+    }
+
+    if !(is_icon_file(data) && data[2..4] == [2, 0]) {
+        return Err(NwgError::resource_create(
+            "The data is not a cursor file (.cur) or an animated cursor file (.ani)",
+        ));
+    }
+
+    let target = match size {
+        Some(size) => size,
+        None => (
+            GetSystemMetrics(SM_CXCURSOR) as u32,
+            GetSystemMetrics(SM_CYCURSOR) as u32,
+        ),
+    };
+
+    let count = u16::from_le_bytes([data[4], data[5]]) as usize;
+    let mut entries = Vec::with_capacity(count);
+    for index in 0..count {
+        let entry = 6 + index * ENTRY_SIZE;
+        if entry + ENTRY_SIZE > data.len() {
+            return Err(malformed());
+        }
+
+        // A size of 0 in the directory entry means 256 pixels
+        let entry_size = |value: u8| match value {
+            0 => 256,
+            v => v as u32,
+        };
+        entries.push((entry, entry_size(data[entry]), entry_size(data[entry + 1])));
+    }
+
+    // The smallest image that is at least as large as the target, or the largest image
+    let area = |&&(_, w, h): &&(usize, u32, u32)| w * h;
+    let entry = entries
+        .iter()
+        .filter(|(_, w, h)| *w >= target.0 && *h >= target.1)
+        .min_by_key(area)
+        .or_else(|| entries.iter().max_by_key(area))
+        .map(|(entry, _, _)| *entry)
+        .ok_or_else(malformed)?;
+
+    let read_u32 = |offset: usize| {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&data[entry + offset..entry + offset + 4]);
+        u32::from_le_bytes(bytes) as usize
+    };
+    let (length, offset) = (read_u32(8), read_u32(12));
+    let image = data
+        .get(offset..offset.saturating_add(length))
+        .ok_or_else(malformed)?;
+
+    // Cursor resources start with the hotspot, which cursor files store in the directory entry
+    let mut bits = Vec::with_capacity(image.len() + 4);
+    bits.extend_from_slice(&data[entry + 4..entry + 8]);
+    bits.extend_from_slice(image);
+
+    let cursor = create(&bits);
+    match cursor.is_null() {
+        true => Err(malformed()),
+        false => Ok(cursor as HANDLE),
+    }
+}
+
 //
 // File dialog low level methods
 //
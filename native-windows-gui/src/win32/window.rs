@@ -5,9 +5,12 @@ Warning. Not for the faint of heart.
 */
 use super::base_helper::{to_utf16, CUSTOM_ID_BEGIN};
 use super::high_dpi;
-use super::window_helper::{NOTICE_MESSAGE, NWG_INIT, NWG_TIMER_STOP, NWG_TIMER_TICK, NWG_TRAY};
+use super::window_helper::{
+    NOTICE_MESSAGE, NWG_INIT, NWG_PLOTTERS_HOVER, NWG_PLOTTERS_VIEW_CHANGED, NWG_TIMER_STOP,
+    NWG_TIMER_TICK, NWG_TRAY, NWG_TREE_ITEM_MOVED,
+};
 use crate::controls::ControlHandle;
-use crate::{Event, EventData, NwgError};
+use crate::{Cursor, Event, EventData, NwgError};
 use std::ffi::OsString;
 use std::os::windows::prelude::OsStringExt;
 use std::rc::Rc;
@@ -15,7 +18,7 @@ use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use std::{mem, ptr};
 use winapi::shared::basetsd::{DWORD_PTR, UINT_PTR};
 use winapi::shared::minwindef::{BOOL, DWORD, HMODULE, LPARAM, LRESULT, UINT, WPARAM};
-use winapi::shared::windef::{HBRUSH, HMENU, HWND};
+use winapi::shared::windef::{HBRUSH, HCURSOR, HMENU, HWND};
 use winapi::um::commctrl::{NMTTDISPINFOW, SUBCLASSPROC};
 use winapi::um::winuser::{IDCANCEL, IDOK, NMHDR, WNDPROC};
 
@@ -684,6 +687,12 @@ unsafe extern "system" fn process_events(
                 TTN_GETDISPINFOW => {
                     handle_tooltip_callback(mem::transmute::<_, *mut NMTTDISPINFOW>(l), callback)
                 }
+                #[cfg(feature = "tree-view")]
+                winapi::um::commctrl::TVN_ITEMEXPANDINGW => {
+                    if handle_tree_item_expanding(l as *const NMHDR, callback) {
+                        return 1;
+                    }
+                }
                 _ => handle_default_notify_callback(mem::transmute::<_, *const NMHDR>(l), callback),
             }
         }
@@ -757,6 +766,10 @@ unsafe extern "system" fn process_events(
                 "Button" => callback(button_commands(message), NO_DATA, handle),
                 "Edit" => callback(edit_commands(message), NO_DATA, handle),
                 "ComboBox" => callback(combo_commands(message), NO_DATA, handle),
+                "ComboBoxEx32" => callback(combo_ex_commands(message), NO_DATA, handle),
+                "SysIPAddress32" => callback(edit_commands(message), NO_DATA, handle),
+                "msctls_hotkey32" => callback(hotkey_commands(message), NO_DATA, handle),
+                "SysAnimate32" => callback(animate_commands(message), NO_DATA, handle),
                 "Static" => callback(static_commands(child_handle, message), NO_DATA, handle),
                 "ListBox" => callback(listbox_commands(message), NO_DATA, handle),
                 _ => match w as i32 {
@@ -885,6 +898,8 @@ unsafe extern "system" fn process_events(
             ControlHandle::Timer(hwnd, w as u32),
         ),
         NWG_INIT => callback(Event::OnInit, NO_DATA, base_handle),
+        NWG_PLOTTERS_VIEW_CHANGED => callback(Event::OnPlottersViewChanged, NO_DATA, base_handle),
+        NWG_PLOTTERS_HOVER => callback(Event::OnPlottersHover, NO_DATA, base_handle),
         WM_CLOSE => {
             let mut should_exit = true;
             let data = EventData::OnWindowClose(WindowCloseData {
@@ -926,6 +941,80 @@ unsafe extern "system" fn process_raw_events(
     }
 }
 
+/// The subclass id of the cursor hook. The hook uses its own procedure so the id cannot collide with the event handlers.
+const CURSOR_SUBCLASS_ID: UINT_PTR = 0;
+
+/**
+    Sets the cursor displayed when the mouse is over the client area of a window. `None` removes the cursor
+    and the window goes back to its default cursor.
+
+    The window owns the cursor. It is dropped when the cursor is replaced or removed, or when the window is destroyed.
+    The cursor is not copied so animated cursors keep their frames.
+*/
+pub(crate) unsafe fn set_window_cursor(hwnd: HWND, cursor: Option<Cursor>) {
+    let proc: SUBCLASSPROC = Some(process_cursor);
+    let previous = window_cursor_data(hwnd);
+
+    match cursor {
+        Some(cursor) => {
+            let data = Box::into_raw(Box::new(cursor));
+            SetWindowSubclass(hwnd, proc, CURSOR_SUBCLASS_ID, data as DWORD_PTR);
+        }
+        None => {
+            RemoveWindowSubclass(hwnd, proc, CURSOR_SUBCLASS_ID);
+        }
+    }
+
+    if let Some(previous) = previous {
+        drop(Box::from_raw(previous));
+    }
+}
+
+/// Returns the handle of the cursor set with `set_window_cursor`
+pub(crate) unsafe fn get_window_cursor(hwnd: HWND) -> Option<HCURSOR> {
+    window_cursor_data(hwnd).map(|cursor| (*cursor).handle as HCURSOR)
+}
+
+unsafe fn window_cursor_data(hwnd: HWND) -> Option<*mut Cursor> {
+    let mut data: DWORD_PTR = 0;
+    match GetWindowSubclass(hwnd, Some(process_cursor), CURSOR_SUBCLASS_ID, &mut data) {
+        0 => None,
+        _ => Some(data as *mut Cursor),
+    }
+}
+
+/**
+    A window subclass procedure that replaces the cursor of a window. The subclass data is a boxed `Cursor`
+    that is dropped with the window. Children of the window keep their own cursor.
+*/
+unsafe extern "system" fn process_cursor(
+    hwnd: HWND,
+    msg: UINT,
+    w: WPARAM,
+    l: LPARAM,
+    id: UINT_PTR,
+    data: DWORD_PTR,
+) -> LRESULT {
+    use winapi::shared::minwindef::LOWORD;
+    use winapi::um::commctrl::DefSubclassProc;
+    use winapi::um::winuser::{SetCursor, HTCLIENT, WM_NCDESTROY, WM_SETCURSOR};
+
+    let cursor = data as *mut Cursor;
+
+    match msg {
+        WM_SETCURSOR if w as HWND == hwnd && LOWORD(l as u32) as isize == HTCLIENT => {
+            SetCursor((*cursor).handle as HCURSOR);
+            1
+        }
+        WM_NCDESTROY => {
+            RemoveWindowSubclass(hwnd, Some(process_cursor), id);
+            drop(Box::from_raw(cursor));
+            DefSubclassProc(hwnd, msg, w, l)
+        }
+        _ => DefSubclassProc(hwnd, msg, w, l),
+    }
+}
+
 fn button_commands(m: u16) -> Event {
     use winapi::um::winuser::{BN_CLICKED, BN_DBLCLK};
     match m {
@@ -944,6 +1033,25 @@ fn edit_commands(m: u16) -> Event {
     }
 }
 
+fn hotkey_commands(m: u16) -> Event {
+    use winapi::um::winuser::EN_CHANGE;
+
+    match m {
+        EN_CHANGE => Event::OnHotKeyChanged,
+        _ => Event::Unknown,
+    }
+}
+
+fn animate_commands(m: u16) -> Event {
+    use winapi::um::commctrl::{ACN_START, ACN_STOP};
+
+    match m as usize {
+        ACN_START => Event::OnAnimationStart,
+        ACN_STOP => Event::OnAnimationStop,
+        _ => Event::Unknown,
+    }
+}
+
 fn combo_commands(m: u16) -> Event {
     use winapi::um::winuser::{CBN_CLOSEUP, CBN_DROPDOWN, CBN_SELCHANGE};
     match m {
@@ -954,6 +1062,15 @@ fn combo_commands(m: u16) -> Event {
     }
 }
 
+/// Same as `combo_commands`, but the ComboBoxEx control also reports the edits of its text
+fn combo_ex_commands(m: u16) -> Event {
+    use winapi::um::winuser::CBN_EDITCHANGE;
+    match m {
+        CBN_EDITCHANGE => Event::OnTextInput,
+        m => combo_commands(m),
+    }
+}
+
 fn datetimepick_commands(m: u32) -> Event {
     use winapi::um::commctrl::{DTN_CLOSEUP, DTN_DATETIMECHANGE, DTN_DROPDOWN};
     match m {
@@ -964,6 +1081,16 @@ fn datetimepick_commands(m: u32) -> Event {
     }
 }
 
+fn month_calendar_commands(m: u32) -> Event {
+    use winapi::um::commctrl::{MCN_SELCHANGE, MCN_SELECT, MCN_VIEWCHANGE};
+    match m {
+        MCN_SELCHANGE => Event::OnMonthCalendarSelectionChanged,
+        MCN_SELECT => Event::OnMonthCalendarSelect,
+        MCN_VIEWCHANGE => Event::OnMonthCalendarViewChanged,
+        _ => Event::Unknown,
+    }
+}
+
 fn tabs_commands(m: u32) -> Event {
     use winapi::um::commctrl::{TCN_SELCHANGE, TCN_SELCHANGING};
     match m {
@@ -1000,6 +1127,7 @@ fn tree_commands(m: u32) -> Event {
         TVN_ITEMCHANGEDW => Event::OnTreeItemChanged,
         TVN_BEGINLABELEDITW => Event::OnTreeViewBeginItemEdit,
         TVN_ENDLABELEDITW => Event::OnTreeViewEndItemEdit,
+        NWG_TREE_ITEM_MOVED => Event::OnTreeItemMoved,
         _ => Event::Unknown,
     }
 }
@@ -1074,6 +1202,16 @@ fn tree_data(m: u32, notif_raw: *const NMHDR) -> EventData {
             };
             EventData::OnTreeItemSelectionChanged { old, new }
         }
+        NWG_TREE_ITEM_MOVED => {
+            let data = unsafe { &*(notif_raw as *const NMTREEVIEWW) };
+            let new = TreeItem {
+                handle: data.itemNew.hItem,
+            };
+            let old = TreeItem {
+                handle: data.itemOld.hItem,
+            };
+            EventData::OnTreeItemMoved { old, new }
+        }
         TVN_ITEMCHANGEDW => {
             let data = unsafe { &*(notif_raw as *const NMTVITEMCHANGE) };
             let item = TreeItem { handle: data.hItem };
@@ -1110,6 +1248,37 @@ fn tree_data(m: u32, notif_raw: *const NMHDR) -> EventData {
     }
 }
 
+/// Raise `OnTreeItemExpanding`. Returns `true` if the action was cancelled by the user.
+#[cfg(feature = "tree-view")]
+unsafe fn handle_tree_item_expanding(notif_raw: *const NMHDR, callback: &Callback) -> bool {
+    use crate::{ExpandState, TreeItem, TreeItemExpandingData};
+    use winapi::um::commctrl::{NMTREEVIEWW, TVE_COLLAPSE, TVE_EXPAND};
+
+    let data = &*(notif_raw as *const NMTREEVIEWW);
+    let action = match data.action as usize {
+        TVE_COLLAPSE => ExpandState::Collapse,
+        TVE_EXPAND => ExpandState::Expand,
+        _ => ExpandState::Toggle,
+    };
+
+    let mut cancel = false;
+    let data = EventData::OnTreeItemExpanding(TreeItemExpandingData {
+        item: TreeItem {
+            handle: data.itemNew.hItem,
+        },
+        action,
+        cancel: &mut cancel as *mut bool,
+    });
+
+    callback(
+        Event::OnTreeItemExpanding,
+        data,
+        ControlHandle::Hwnd((&*notif_raw).hwndFrom),
+    );
+
+    cancel
+}
+
 #[cfg(feature = "tree-view")]
 unsafe fn u16_ptr_to_string(ptr: *const u16) -> OsString {
     let len = (0..).take_while(|&i| *ptr.offset(i) != 0).count();
@@ -1225,6 +1394,17 @@ unsafe fn handle_default_notify_callback<'a>(notif_raw: *const NMHDR, callback:
 
     match &class_name as &str {
         "SysDateTimePick32" => callback(datetimepick_commands(code), NO_DATA, handle),
+        "SysMonthCal32" => callback(month_calendar_commands(code), NO_DATA, handle),
+        "SysLink" => {
+            if let Some(data) = link_data(code, notif_raw) {
+                callback(Event::OnLinkClick, data, handle)
+            }
+        }
+        "SysIPAddress32" => {
+            if let Some(data) = ip_address_data(code, notif_raw) {
+                callback(Event::OnIpAddressFieldChanged, data, handle)
+            }
+        }
         "SysTabControl32" => callback(tabs_commands(code), NO_DATA, handle),
         "msctls_trackbar32" => callback(track_commands(code), NO_DATA, handle),
         winapi::um::commctrl::WC_TREEVIEW => {
@@ -1235,10 +1415,69 @@ unsafe fn handle_default_notify_callback<'a>(notif_raw: *const NMHDR, callback:
             list_view_data(code, notif_raw),
             handle,
         ),
+        #[cfg(feature = "combobox")]
+        winapi::um::commctrl::WC_COMBOBOXEX => {
+            if let Some(text) = combo_ex_commit_text(code, notif_raw) {
+                callback(
+                    Event::OnComboBoxTextCommit,
+                    EventData::OnComboBoxTextCommit(text),
+                    handle,
+                )
+            }
+        }
         _ => {}
     }
 }
 
+unsafe fn link_data(code: u32, notif_raw: *const NMHDR) -> Option<EventData> {
+    use super::base_helper::from_utf16;
+    use winapi::um::commctrl::{NMLINK, NM_CLICK, NM_RETURN};
+
+    if code != NM_CLICK && code != NM_RETURN {
+        return None;
+    }
+
+    let link = &(&*(notif_raw as *const NMLINK)).item;
+    Some(EventData::OnLinkClick {
+        index: link.iLink as usize,
+        id: from_utf16(&link.szID),
+        url: from_utf16(&link.szUrl),
+    })
+}
+
+unsafe fn ip_address_data(code: u32, notif_raw: *const NMHDR) -> Option<EventData> {
+    use winapi::um::commctrl::{IPN_FIELDCHANGED, NMIPADDRESS};
+
+    if code != IPN_FIELDCHANGED {
+        return None;
+    }
+
+    let data = &*(notif_raw as *const NMIPADDRESS);
+    Some(EventData::OnIpAddressFieldChanged {
+        field: data.iField as usize,
+        value: data.iValue,
+    })
+}
+
+/// Returns the text committed by the user in a ComboBoxEx if it is not an item of the control
+#[cfg(feature = "combobox")]
+unsafe fn combo_ex_commit_text(code: u32, notif_raw: *const NMHDR) -> Option<String> {
+    use winapi::um::commctrl::{CBENF_KILLFOCUS, CBENF_RETURN, CBEN_ENDEDITW, NMCBEENDEDITW};
+    use winapi::um::winuser::CB_ERR;
+
+    if code != CBEN_ENDEDITW {
+        return None;
+    }
+
+    let data = &*(notif_raw as *const NMCBEENDEDITW);
+    let committed = data.iWhy == CBENF_RETURN || data.iWhy == CBENF_KILLFOCUS;
+    if data.fChanged == 0 || !committed || data.iNewSelection != CB_ERR as i32 {
+        return None;
+    }
+
+    Some(super::base_helper::from_utf16(&data.szText))
+}
+
 unsafe fn is_textbox_control(hwnd: HWND) -> bool {
     use winapi::um::winnt::WCHAR;
     use winapi::um::winuser::GetClassNameW;
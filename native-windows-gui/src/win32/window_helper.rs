@@ -9,12 +9,23 @@ use winapi::um::winuser::WM_USER;
 #[cfg(feature = "rich-textbox")]
 use winapi::um::winuser::WNDCLASSEXW;
 
+pub(crate) use super::window::{get_window_cursor, set_window_cursor};
+
 pub const NOTICE_MESSAGE: UINT = WM_USER + 100;
 pub const NWG_INIT: UINT = WM_USER + 101;
 pub const NWG_TRAY: UINT = WM_USER + 102;
 pub const NWG_TIMER_TICK: UINT = WM_USER + 103;
 pub const NWG_TIMER_STOP: UINT = WM_USER + 104;
 
+/// Notification code sent by a TreeView to its parent after an item was moved with drag and drop
+pub const NWG_TREE_ITEM_MOVED: UINT = WM_USER + 105;
+
+/// Message posted by a Plotters control to itself after the user zoomed or panned the view
+pub const NWG_PLOTTERS_VIEW_CHANGED: UINT = WM_USER + 106;
+
+/// Message posted by a Plotters control to itself when the hovered position changed
+pub const NWG_PLOTTERS_HOVER: UINT = WM_USER + 107;
+
 /// Returns the class info of a hwnd handle
 #[cfg(feature = "rich-textbox")]
 pub fn get_class_info(hwnd: HWND) -> Result<WNDCLASSEXW, ()> {
@@ -249,7 +260,7 @@ pub fn get_style(handle: HWND) -> UINT {
     get_window_long(handle, GWL_STYLE) as UINT
 }
 
-#[cfg(any(feature = "list-view", feature = "progress-bar"))]
+#[cfg(any(feature = "list-view", feature = "progress-bar", feature = "tree-view"))]
 pub fn set_style(handle: HWND, style: u32) {
     use ::winapi::um::winuser::GWL_STYLE;
     set_window_long(handle, GWL_STYLE, style as usize);
@@ -452,7 +463,6 @@ pub unsafe fn set_window_enabled(handle: HWND, enabled: bool) {
     UpdateWindow(handle);
 }
 
-#[cfg(feature = "tabs")]
 pub unsafe fn get_window_class_name(handle: HWND) -> String {
     use std::ffi::OsString;
     use std::os::windows::ffi::OsStringExt;
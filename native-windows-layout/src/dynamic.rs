@@ -0,0 +1,339 @@
+use crate::LayoutRect;
+
+/**
+    How a node of a dynamic layout follows the parent when it grows.
+
+    When the parent grows, the node moves by `mv` percent and grows by `sz` percent of the parent growth on each axis.
+    Percentages can be fractional. Negative or zero percentages keep the node at its initial position or size.
+
+    The anchors can also be defined by the edges of the parent the node is pinned to with `pinned`, or by the
+    fraction of the parent growth applied to each edge of the node with `edges`.
+
+    ```rust
+    use native_windows_layout::DynAnchors;
+
+    // A button in the bottom right corner
+    let corner = DynAnchors::pinned(false, false, true, true);
+    assert_eq!(corner, DynAnchors::percent((100, 100), (0, 0)));
+
+    // A text box that takes the left third of the extra space
+    let third = DynAnchors::edges(0.0, 0.0, 1.0 / 3.0, 1.0);
+    ```
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DynAnchors {
+    /// The horizontal and vertical move of the node in percent of the parent growth
+    pub mv: (f32, f32),
+
+    /// The horizontal and vertical growth of the node in percent of the parent growth
+    pub sz: (f32, f32),
+}
+
+impl DynAnchors {
+    /// Anchors from integer percentages, like the `mv` and `sz` arguments of `DynLayout::add_child`
+    pub fn percent(mv: (i32, i32), sz: (i32, i32)) -> DynAnchors {
+        DynAnchors {
+            mv: (mv.0 as f32, mv.1 as f32),
+            sz: (sz.0 as f32, sz.1 as f32),
+        }
+    }
+
+    /**
+        Anchors from the edges of the parent the node is pinned to. A node keeps its distance to the pinned edges.
+
+        On each axis, a node pinned to both edges grows with the parent, a node pinned to a single edge follows that edge
+        and a node that is not pinned stays centered with its initial size.
+    */
+    pub fn pinned(left: bool, top: bool, right: bool, bottom: bool) -> DynAnchors {
+        fn axis(start: bool, end: bool) -> (f32, f32) {
+            match (start, end) {
+                (true, true) => (0.0, 100.0),
+                (true, false) => (0.0, 0.0),
+                (false, true) => (100.0, 0.0),
+                (false, false) => (50.0, 0.0),
+            }
+        }
+
+        let (mv_x, sz_x) = axis(left, right);
+        let (mv_y, sz_y) = axis(top, bottom);
+
+        DynAnchors {
+            mv: (mv_x, mv_y),
+            sz: (sz_x, sz_y),
+        }
+    }
+
+    /// Anchors from the fraction of the parent growth applied to each edge of the node, from 0.0 to 1.0
+    pub fn edges(left: f32, top: f32, right: f32, bottom: f32) -> DynAnchors {
+        DynAnchors {
+            mv: (left * 100.0, top * 100.0),
+            sz: ((right - left) * 100.0, (bottom - top) * 100.0),
+        }
+    }
+}
+
+impl Default for DynAnchors {
+    /// A node that keeps its position and its size
+    fn default() -> DynAnchors {
+        DynAnchors {
+            mv: (0.0, 0.0),
+            sz: (0.0, 0.0),
+        }
+    }
+}
+
+/// A node placed by a dynamic layout
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DynItem<Id> {
+    /// The id of the node
+    pub id: Id,
+
+    /// The position of the node when the parent has a size of 0
+    pub position: (i32, i32),
+
+    /// The size of the node when the parent has a size of 0
+    pub size: (i32, i32),
+
+    /// How the node moves and grows with the parent
+    pub anchors: DynAnchors,
+
+    /// The minimum width and height of the node
+    pub min_size: (u32, u32),
+
+    /// The maximum width and height of the node
+    pub max_size: (u32, u32),
+}
+
+impl<Id> DynItem<Id> {
+    /// A node without size limits
+    pub fn new(id: Id, position: (i32, i32), size: (i32, i32), anchors: DynAnchors) -> DynItem<Id> {
+        DynItem {
+            id,
+            position,
+            size,
+            anchors,
+            min_size: (0, 0),
+            max_size: (u32::MAX, u32::MAX),
+        }
+    }
+
+    /**
+        Creates an item from the current geometry of a node in a parent of size `parent_size`.
+        The position and the size are moved back to what they would be if the parent had a size of 0.
+    */
+    pub fn capture(
+        id: Id,
+        position: (i32, i32),
+        size: (u32, u32),
+        anchors: DynAnchors,
+        parent_size: (u32, u32),
+    ) -> DynItem<Id> {
+        let (xdelta, ydelta) = deltas(parent_size.0, parent_size.1);
+        let DynAnchors { mv, sz } = anchors;
+
+        let position = (
+            position.0 - scaled(xdelta, mv.0),
+            position.1 - scaled(ydelta, mv.1),
+        );
+        let size = (
+            size.0 as i32 - scaled(xdelta, sz.0),
+            size.1 as i32 - scaled(ydelta, sz.1),
+        );
+
+        DynItem::new(id, position, size, anchors)
+    }
+}
+
+/**
+    Computes the rectangle of every item in `area`. The rectangles are returned in the order of `items`.
+
+    Sizes that would be negative are clamped to 0, then to the minimum and the maximum size of the item.
+    A clamped node keeps its left and top edges.
+*/
+pub fn solve_dyn<Id: Copy>(items: &[DynItem<Id>], area: LayoutRect) -> Vec<(Id, LayoutRect)> {
+    let (xdelta, ydelta) = deltas(area.width, area.height);
+
+    items
+        .iter()
+        .map(|item| {
+            let DynAnchors { mv, sz } = item.anchors;
+            let x = area.x + item.position.0 + scaled(xdelta, mv.0);
+            let y = area.y + item.position.1 + scaled(ydelta, mv.1);
+            let w = item.size.0 + scaled(xdelta, sz.0);
+            let h = item.size.1 + scaled(ydelta, sz.1);
+
+            let w = clamp(w, item.min_size.0, item.max_size.0);
+            let h = clamp(h, item.min_size.1, item.max_size.1);
+
+            (item.id, LayoutRect::new(x, y, w, h))
+        })
+        .collect()
+}
+
+/// Returns the size of the smallest area that contains the items at their initial position
+pub fn dyn_preferred_size<Id>(items: &[DynItem<Id>]) -> (u32, u32) {
+    items.iter().fold((0, 0), |(w, h), item| {
+        let width = clamp(item.size.0, item.min_size.0, item.max_size.0);
+        let height = clamp(item.size.1, item.min_size.1, item.max_size.1);
+        let right = (item.position.0 + width as i32).max(0) as u32;
+        let bottom = (item.position.1 + height as i32).max(0) as u32;
+        (w.max(right), h.max(bottom))
+    })
+}
+
+fn deltas(width: u32, height: u32) -> (f32, f32) {
+    (0.01 * width as f32, 0.01 * height as f32)
+}
+
+fn scaled(delta: f32, percent: f32) -> i32 {
+    match percent > 0.0 {
+        true => (delta * percent) as i32,
+        false => 0,
+    }
+}
+
+fn clamp(size: i32, min: u32, max: u32) -> u32 {
+    (size.max(0) as u32).min(max).max(min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        position: (i32, i32),
+        size: (i32, i32),
+        mv: (i32, i32),
+        sz: (i32, i32),
+    ) -> DynItem<u32> {
+        DynItem::new(0, position, size, DynAnchors::percent(mv, sz))
+    }
+
+    #[test]
+    fn static_items_do_not_move() {
+        let items = [item((10, 20), (30, 40), (0, 0), (0, 0))];
+        let out = solve_dyn(&items, LayoutRect::new(0, 0, 1000, 1000));
+
+        assert_eq!(out[0].1, LayoutRect::new(10, 20, 30, 40));
+    }
+
+    #[test]
+    fn items_move_and_grow_in_percent() {
+        let items = [
+            item((10, 20), (30, 40), (50, 0), (0, 0)),
+            item((10, 20), (30, 40), (0, 0), (25, 100)),
+        ];
+        let out = solve_dyn(&items, LayoutRect::new(0, 0, 200, 100));
+
+        assert_eq!(out[0].1, LayoutRect::new(110, 20, 30, 40));
+        assert_eq!(out[1].1, LayoutRect::new(10, 20, 80, 140));
+    }
+
+    #[test]
+    fn percentages_are_truncated() {
+        let items = [item((0, 0), (0, 0), (33, 0), (33, 0))];
+        let out = solve_dyn(&items, LayoutRect::new(0, 0, 10, 10));
+
+        // 3.3 pixels
+        assert_eq!(out[0].1, LayoutRect::new(3, 0, 3, 0));
+    }
+
+    #[test]
+    fn negative_percentages_are_ignored() {
+        let items = [item((10, 10), (10, 10), (-50, -50), (-50, -50))];
+        let out = solve_dyn(&items, LayoutRect::new(0, 0, 100, 100));
+
+        assert_eq!(out[0].1, LayoutRect::new(10, 10, 10, 10));
+    }
+
+    #[test]
+    fn negative_sizes_are_clamped() {
+        let items = [item((0, 0), (-50, -5), (0, 0), (10, 0))];
+        let out = solve_dyn(&items, LayoutRect::new(0, 0, 100, 100));
+
+        assert_eq!(out[0].1, LayoutRect::new(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn area_position_offsets_the_items() {
+        let items = [item((10, 10), (10, 10), (100, 0), (0, 0))];
+        let out = solve_dyn(&items, LayoutRect::new(50, 60, 100, 100));
+
+        assert_eq!(out[0].1, LayoutRect::new(160, 70, 10, 10));
+    }
+
+    #[test]
+    fn capture_is_the_inverse_of_solve() {
+        let anchors = DynAnchors::percent((50, 10), (20, 50));
+        let captured = DynItem::capture(7, (120, 50), (60, 40), anchors, (200, 100));
+        assert_eq!(captured.position, (20, 40));
+        assert_eq!(captured.size, (20, -10));
+
+        let out = solve_dyn(&[captured], LayoutRect::new(0, 0, 200, 100));
+        assert_eq!(out[0], (7, LayoutRect::new(120, 50, 60, 40)));
+    }
+
+    #[test]
+    fn fractional_percentages() {
+        let items = [DynItem::new(
+            0,
+            (0, 0),
+            (0, 0),
+            DynAnchors::edges(0.25, 0.0, 0.5, 0.125),
+        )];
+        let out = solve_dyn(&items, LayoutRect::new(0, 0, 200, 400));
+
+        assert_eq!(out[0].1, LayoutRect::new(50, 0, 50, 50));
+    }
+
+    #[test]
+    fn pinned_anchors() {
+        let area = LayoutRect::new(0, 0, 100, 100);
+        let solve = |anchors| solve_dyn(&[DynItem::new(0, (10, 10), (20, 20), anchors)], area)[0].1;
+
+        assert_eq!(
+            solve(DynAnchors::pinned(true, true, false, false)),
+            LayoutRect::new(10, 10, 20, 20)
+        );
+        assert_eq!(
+            solve(DynAnchors::pinned(false, false, true, true)),
+            LayoutRect::new(110, 110, 20, 20)
+        );
+        assert_eq!(
+            solve(DynAnchors::pinned(true, false, true, false)),
+            LayoutRect::new(10, 60, 120, 20)
+        );
+        assert_eq!(
+            solve(DynAnchors::pinned(false, false, false, false)),
+            LayoutRect::new(60, 60, 20, 20)
+        );
+    }
+
+    #[test]
+    fn sizes_are_clamped_to_the_limits() {
+        let mut item = DynItem::new(
+            0,
+            (10, 10),
+            (20, 20),
+            DynAnchors::pinned(true, true, true, true),
+        );
+        item.min_size = (0, 50);
+        item.max_size = (60, 100);
+        let out = solve_dyn(&[item], LayoutRect::new(0, 0, 100, 10));
+
+        assert_eq!(out[0].1, LayoutRect::new(10, 10, 60, 50));
+        assert_eq!(dyn_preferred_size(&[item]), (30, 60));
+    }
+
+    #[test]
+    fn preferred_size_is_the_bounding_box() {
+        let items = [
+            item((10, 10), (100, 20), (0, 0), (0, 0)),
+            item((50, 40), (10, 30), (0, 0), (0, 0)),
+            item((-100, -100), (10, 10), (0, 0), (0, 0)),
+        ];
+
+        assert_eq!(dyn_preferred_size(&items), (110, 70));
+        assert_eq!(dyn_preferred_size::<u32>(&[]), (0, 0));
+    }
+}
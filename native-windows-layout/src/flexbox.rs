@@ -0,0 +1,800 @@
+use crate::LayoutRect;
+use stretch::{
+    geometry::{Point, Size},
+    node::{MeasureFunc, Node, Stretch},
+    number::{Number, OrElse},
+    style::{Dimension, Style},
+};
+
+/// A node placed by a flexbox layout: an item or a nested flexbox layout with its own children
+#[derive(Clone, Debug)]
+pub enum FlexNode<Id> {
+    Item(Id, Style),
+
+    /// An item with the width and the height of its content.
+    /// The content size is used by stretch when the size or the flex basis of the item is `Auto`.
+    Measured(Id, Style, (u32, u32)),
+
+    Layout(Style, Vec<FlexNode<Id>>),
+}
+
+impl<Id> FlexNode<Id> {
+    /// The style of the node
+    pub fn style(&self) -> &Style {
+        match self {
+            FlexNode::Item(_, style) => style,
+            FlexNode::Measured(_, style, _) => style,
+            FlexNode::Layout(style, _) => style,
+        }
+    }
+
+    /// The content size of an item. `None` for items without content size and layouts.
+    fn content(&self) -> Option<(u32, u32)> {
+        match self {
+            FlexNode::Measured(_, _, content) => Some(*content),
+            _ => None,
+        }
+    }
+}
+
+/**
+    Computes the rectangle of every item of a flexbox layout of style `style` that fills `area`.
+    Items of nested layouts are placed relative to their parent layout and returned in the order of the tree.
+*/
+pub fn solve_flexbox<Id: Copy>(
+    style: &Style,
+    children: &[FlexNode<Id>],
+    area: LayoutRect,
+) -> Result<Vec<(Id, LayoutRect)>, stretch::Error> {
+    let mut stretch = Stretch::new();
+    let nodes = build_nodes(children, &mut stretch)?;
+
+    let mut style = *style;
+    style.size = Size {
+        width: Dimension::Points(area.width as f32),
+        height: Dimension::Points(area.height as f32),
+    };
+    let node = stretch.new_node(style, nodes.clone())?;
+
+    stretch.compute_layout(node, Size::undefined())?;
+
+    let mut rects = Vec::new();
+    collect_rects(&stretch, &nodes, children, (area.x, area.y), &mut rects)?;
+
+    Ok(rects)
+}
+
+/// Returns the size computed by stretch for the layout when the parent size is undefined.
+/// This is the smallest size that respects the style of the layout and of its children.
+pub fn flexbox_preferred_size<Id>(
+    style: &Style,
+    children: &[FlexNode<Id>],
+) -> Result<(u32, u32), stretch::Error> {
+    let mut stretch = Stretch::new();
+    let nodes = build_nodes(children, &mut stretch)?;
+
+    let mut style = *style;
+    style.size = Size {
+        width: Dimension::Auto,
+        height: Dimension::Auto,
+    };
+    let node = stretch.new_node(style, nodes)?;
+    stretch.compute_layout(node, Size::undefined())?;
+
+    let Size { width, height } = stretch.layout(node)?.size;
+    Ok((width.max(0.0) as u32, height.max(0.0) as u32))
+}
+
+fn build_nodes<Id>(
+    children: &[FlexNode<Id>],
+    stretch: &mut Stretch,
+) -> Result<Vec<Node>, stretch::Error> {
+    let mut nodes = Vec::with_capacity(children.len());
+
+    for child in children.iter() {
+        let node = match child {
+            FlexNode::Item(_, style) | FlexNode::Measured(_, style, _) => {
+                new_item(stretch, *style, child.content())?
+            }
+            FlexNode::Layout(style, children) => {
+                let child_nodes = build_nodes(children, stretch)?;
+                stretch.new_node(*style, child_nodes)?
+            }
+        };
+
+        nodes.push(node);
+    }
+
+    Ok(nodes)
+}
+
+fn collect_rects<Id: Copy>(
+    stretch: &Stretch,
+    nodes: &[Node],
+    children: &[FlexNode<Id>],
+    offset: (i32, i32),
+    rects: &mut Vec<(Id, LayoutRect)>,
+) -> Result<(), stretch::Error> {
+    for (&node, child) in nodes.iter().zip(children.iter()) {
+        let layout = stretch.layout(node)?;
+        let Point { x, y } = layout.location;
+        let Size { width, height } = layout.size;
+        let position = (x as i32 + offset.0, y as i32 + offset.1);
+
+        match child {
+            FlexNode::Item(id, _) | FlexNode::Measured(id, _, _) => {
+                let rect = LayoutRect::new(position.0, position.1, width as u32, height as u32);
+                rects.push((*id, rect));
+            }
+            FlexNode::Layout(_, children) => {
+                let child_nodes = stretch.children(node)?;
+                collect_rects(stretch, &child_nodes, children, position, rects)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A rectangle computed by a `FlexTree`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FlexRect<Id> {
+    /// The id of the item
+    pub id: Id,
+
+    /// The area of the item
+    pub rect: LayoutRect,
+
+    /// `false` if the item has the same rectangle as in the previous call to `FlexTree::solve`
+    pub changed: bool,
+}
+
+/**
+    A flexbox node tree that is kept between two layout updates.
+
+    `solve_flexbox` creates every stretch node each time it is called. A `FlexTree` only updates the styles
+    that changed since the last update and lets stretch reuse the cached layout of the nodes that are not dirty.
+    It also reports which items moved, so that the items that did not move can be skipped.
+*/
+pub struct FlexTree<Id> {
+    stretch: Stretch,
+    root: Option<Node>,
+    children: Vec<TreeNode<Id>>,
+    rects: Vec<(Id, LayoutRect)>,
+}
+
+struct TreeNode<Id> {
+    node: Node,
+    style: Style,
+    kind: TreeKind<Id>,
+}
+
+enum TreeKind<Id> {
+    Item(Id, Option<(u32, u32)>),
+    Layout(Vec<TreeNode<Id>>),
+}
+
+impl<Id: Copy + PartialEq> FlexTree<Id> {
+    pub fn new() -> FlexTree<Id> {
+        FlexTree {
+            stretch: Stretch::new(),
+            root: None,
+            children: Vec::new(),
+            rects: Vec::new(),
+        }
+    }
+
+    /**
+        Updates the tree so that it matches a layout of style `style` with the nodes `children`.
+
+        If the items and the nested layouts are the same as in the tree, only the styles that changed are updated
+        and marked as dirty. Otherwise the tree is rebuilt.
+    */
+    pub fn update(
+        &mut self,
+        style: &Style,
+        children: &[FlexNode<Id>],
+    ) -> Result<(), stretch::Error> {
+        match self.root {
+            Some(root) if same_shape(&self.children, children) => {
+                let mut root_style = *style;
+                root_style.size = self.stretch.style(root)?.size;
+                if !same_style(self.stretch.style(root)?, &root_style) {
+                    self.stretch.set_style(root, root_style)?;
+                }
+
+                update_styles(&mut self.stretch, &mut self.children, children)
+            }
+            _ => self.rebuild(style, children),
+        }
+    }
+
+    /**
+        Computes the rectangle of every item in `area`. The rectangles are returned in the order of the tree.
+        `FlexTree::update` must have been called at least once.
+    */
+    pub fn solve(&mut self, area: LayoutRect) -> Result<Vec<FlexRect<Id>>, stretch::Error> {
+        let root = match self.root {
+            Some(root) => root,
+            None => return Ok(Vec::new()),
+        };
+
+        let size = Size {
+            width: Dimension::Points(area.width as f32),
+            height: Dimension::Points(area.height as f32),
+        };
+        if self.stretch.style(root)?.size != size {
+            let mut style = *self.stretch.style(root)?;
+            style.size = size;
+            self.stretch.set_style(root, style)?;
+        }
+
+        self.stretch.compute_layout(root, Size::undefined())?;
+
+        let mut rects = Vec::with_capacity(self.rects.len());
+        collect_tree_rects(&self.stretch, &self.children, (area.x, area.y), &mut rects)?;
+
+        let placements = rects
+            .iter()
+            .enumerate()
+            .map(|(index, &(id, rect))| FlexRect {
+                id,
+                rect,
+                changed: self.rects.get(index) != Some(&(id, rect)),
+            })
+            .collect();
+
+        self.rects = rects;
+
+        Ok(placements)
+    }
+
+    /// Forgets the rectangles of the last `solve`. The next call reports every item as changed.
+    pub fn invalidate(&mut self) {
+        self.rects.clear();
+    }
+
+    fn rebuild(&mut self, style: &Style, children: &[FlexNode<Id>]) -> Result<(), stretch::Error> {
+        self.stretch.clear();
+        self.children = build_tree(&mut self.stretch, children)?;
+        self.rects.clear();
+
+        let nodes = self.children.iter().map(|child| child.node).collect();
+        self.root = Some(self.stretch.new_node(*style, nodes)?);
+
+        Ok(())
+    }
+}
+
+impl<Id: Copy + PartialEq> Default for FlexTree<Id> {
+    fn default() -> FlexTree<Id> {
+        FlexTree::new()
+    }
+}
+
+/**
+    Returns the items of `rects` that moved, with the item that must be before each of them in the tab order.
+    `after` is the item before the first item of the layout.
+
+    The items that did not move are skipped, but they are still used as the previous item of the next moved item,
+    so the tab order is the same as if every item was moved.
+*/
+pub fn changed_placements<Id: Copy>(
+    rects: &[FlexRect<Id>],
+    after: Option<Id>,
+) -> Vec<(Id, LayoutRect, Option<Id>)> {
+    let mut previous = after;
+    let mut placements = Vec::new();
+
+    for placement in rects.iter() {
+        if placement.changed {
+            placements.push((placement.id, placement.rect, previous));
+        }
+        previous = Some(placement.id);
+    }
+
+    placements
+}
+
+fn build_tree<Id: Copy>(
+    stretch: &mut Stretch,
+    children: &[FlexNode<Id>],
+) -> Result<Vec<TreeNode<Id>>, stretch::Error> {
+    let mut nodes = Vec::with_capacity(children.len());
+
+    for child in children.iter() {
+        let node = match child {
+            FlexNode::Item(id, style) | FlexNode::Measured(id, style, _) => TreeNode {
+                node: new_item(stretch, *style, child.content())?,
+                style: *style,
+                kind: TreeKind::Item(*id, child.content()),
+            },
+            FlexNode::Layout(style, children) => {
+                let children = build_tree(stretch, children)?;
+                let child_nodes = children.iter().map(|child| child.node).collect();
+                TreeNode {
+                    node: stretch.new_node(*style, child_nodes)?,
+                    style: *style,
+                    kind: TreeKind::Layout(children),
+                }
+            }
+        };
+
+        nodes.push(node);
+    }
+
+    Ok(nodes)
+}
+
+/// Returns `true` if the tree has the same items and the same nested layouts as `children`
+fn same_shape<Id: PartialEq>(tree: &[TreeNode<Id>], children: &[FlexNode<Id>]) -> bool {
+    tree.len() == children.len()
+        && tree
+            .iter()
+            .zip(children.iter())
+            .all(|(node, child)| match (&node.kind, child) {
+                (TreeKind::Item(a, _), FlexNode::Item(b, _)) => a == b,
+                (TreeKind::Item(a, _), FlexNode::Measured(b, _, _)) => a == b,
+                (TreeKind::Layout(a), FlexNode::Layout(_, b)) => same_shape(a, b),
+                _ => false,
+            })
+}
+
+fn update_styles<Id>(
+    stretch: &mut Stretch,
+    tree: &mut [TreeNode<Id>],
+    children: &[FlexNode<Id>],
+) -> Result<(), stretch::Error> {
+    for (node, child) in tree.iter_mut().zip(children.iter()) {
+        let style = child.style();
+        if !same_style(&node.style, style) {
+            node.style = *style;
+            stretch.set_style(node.node, *style)?;
+        }
+
+        if let TreeKind::Item(_, content) = &mut node.kind {
+            if *content != child.content() {
+                *content = child.content();
+                stretch.set_measure(node.node, content.map(measure))?;
+            }
+        }
+
+        if let (TreeKind::Layout(tree), FlexNode::Layout(_, children)) = (&mut node.kind, child) {
+            update_styles(stretch, tree, children)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn collect_tree_rects<Id: Copy>(
+    stretch: &Stretch,
+    tree: &[TreeNode<Id>],
+    offset: (i32, i32),
+    rects: &mut Vec<(Id, LayoutRect)>,
+) -> Result<(), stretch::Error> {
+    for node in tree.iter() {
+        let layout = stretch.layout(node.node)?;
+        let Point { x, y } = layout.location;
+        let Size { width, height } = layout.size;
+        let position = (x as i32 + offset.0, y as i32 + offset.1);
+
+        match &node.kind {
+            TreeKind::Item(id, _) => {
+                let rect = LayoutRect::new(position.0, position.1, width as u32, height as u32);
+                rects.push((*id, rect));
+            }
+            TreeKind::Layout(children) => {
+                collect_tree_rects(stretch, children, position, rects)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn new_item(
+    stretch: &mut Stretch,
+    style: Style,
+    content: Option<(u32, u32)>,
+) -> Result<Node, stretch::Error> {
+    match content {
+        Some(content) => stretch.new_leaf(style, measure(content)),
+        None => stretch.new_node(style, Vec::new()),
+    }
+}
+
+/// A measure function that returns the content size for the dimensions that are not known
+fn measure((width, height): (u32, u32)) -> MeasureFunc {
+    Box::new(move |known: Size<Number>| {
+        Ok(Size {
+            width: known.width.or_else(width as f32),
+            height: known.height.or_else(height as f32),
+        })
+    })
+}
+
+/// `Style` does not implement `PartialEq`
+fn same_style(a: &Style, b: &Style) -> bool {
+    a.display == b.display
+        && a.position_type == b.position_type
+        && a.direction == b.direction
+        && a.flex_direction == b.flex_direction
+        && a.flex_wrap == b.flex_wrap
+        && a.overflow == b.overflow
+        && a.align_items == b.align_items
+        && a.align_self == b.align_self
+        && a.align_content == b.align_content
+        && a.justify_content == b.justify_content
+        && a.position == b.position
+        && a.margin == b.margin
+        && a.padding == b.padding
+        && a.border == b.border
+        && a.flex_grow == b.flex_grow
+        && a.flex_shrink == b.flex_shrink
+        && a.flex_basis == b.flex_basis
+        && a.size == b.size
+        && a.min_size == b.min_size
+        && a.max_size == b.max_size
+        && a.aspect_ratio == b.aspect_ratio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stretch::geometry::Rect;
+    use stretch::style::FlexDirection;
+
+    fn points(value: f32) -> Rect<Dimension> {
+        Rect {
+            start: Dimension::Points(value),
+            end: Dimension::Points(value),
+            top: Dimension::Points(value),
+            bottom: Dimension::Points(value),
+        }
+    }
+
+    fn grow() -> Style {
+        Style {
+            flex_grow: 1.0,
+            flex_basis: Dimension::Points(0.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn items_share_the_row() {
+        let children = vec![FlexNode::Item(1, grow()), FlexNode::Item(2, grow())];
+        let out = solve_flexbox(
+            &Style::default(),
+            &children,
+            LayoutRect::new(0, 0, 200, 100),
+        )
+        .unwrap();
+
+        assert_eq!(out[0], (1, LayoutRect::new(0, 0, 100, 100)));
+        assert_eq!(out[1], (2, LayoutRect::new(100, 0, 100, 100)));
+    }
+
+    #[test]
+    fn padding_and_column_direction() {
+        let style = Style {
+            flex_direction: FlexDirection::Column,
+            padding: points(10.0),
+            ..Default::default()
+        };
+        let children = vec![FlexNode::Item(1, grow()), FlexNode::Item(2, grow())];
+        let out = solve_flexbox(&style, &children, LayoutRect::new(0, 0, 120, 220)).unwrap();
+
+        assert_eq!(out[0], (1, LayoutRect::new(10, 10, 100, 100)));
+        assert_eq!(out[1], (2, LayoutRect::new(10, 110, 100, 100)));
+    }
+
+    #[test]
+    fn area_position_offsets_the_items() {
+        let children = vec![FlexNode::Item(1, grow())];
+        let out = solve_flexbox(
+            &Style::default(),
+            &children,
+            LayoutRect::new(30, 40, 50, 60),
+        )
+        .unwrap();
+
+        assert_eq!(out[0], (1, LayoutRect::new(30, 40, 50, 60)));
+    }
+
+    #[test]
+    fn nested_layout_offsets() {
+        let nested_style = Style {
+            flex_direction: FlexDirection::Column,
+            padding: points(5.0),
+            ..grow()
+        };
+        let nested = FlexNode::Layout(
+            nested_style,
+            vec![FlexNode::Item(2, grow()), FlexNode::Item(3, grow())],
+        );
+        let children = vec![FlexNode::Item(1, grow()), nested];
+
+        let out = solve_flexbox(
+            &Style::default(),
+            &children,
+            LayoutRect::new(0, 0, 200, 110),
+        )
+        .unwrap();
+
+        // The nested layout starts at x=100. Its items are placed inside its padding.
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[0], (1, LayoutRect::new(0, 0, 100, 110)));
+        assert_eq!(out[1], (2, LayoutRect::new(105, 5, 90, 50)));
+        assert_eq!(out[2], (3, LayoutRect::new(105, 55, 90, 50)));
+    }
+
+    #[test]
+    fn deeply_nested_layout_offsets_accumulate() {
+        let inner = FlexNode::Layout(
+            Style {
+                padding: points(3.0),
+                ..grow()
+            },
+            vec![FlexNode::Item(9, grow())],
+        );
+        let middle = FlexNode::Layout(
+            Style {
+                padding: points(7.0),
+                ..grow()
+            },
+            vec![inner],
+        );
+        let children = vec![FlexNode::Item(1, grow()), middle];
+
+        let out = solve_flexbox(
+            &Style::default(),
+            &children,
+            LayoutRect::new(10, 20, 200, 100),
+        )
+        .unwrap();
+
+        // 10 (area) + 100 (first item) + 7 (middle padding) + 3 (inner padding)
+        assert_eq!(out[1], (9, LayoutRect::new(120, 30, 80, 80)));
+    }
+
+    #[test]
+    fn preferred_size_uses_fixed_children() {
+        let fixed = Style {
+            size: Size {
+                width: Dimension::Points(40.0),
+                height: Dimension::Points(20.0),
+            },
+            ..Default::default()
+        };
+        let style = Style {
+            padding: points(5.0),
+            ..Default::default()
+        };
+        let children = vec![FlexNode::Item(1, fixed), FlexNode::Item(2, fixed)];
+
+        assert_eq!(flexbox_preferred_size(&style, &children).unwrap(), (90, 30));
+    }
+
+    fn tree_children(grow_3: f32) -> Vec<FlexNode<u32>> {
+        let nested_style = Style {
+            flex_direction: FlexDirection::Column,
+            ..grow()
+        };
+        let item_3 = Style {
+            flex_grow: grow_3,
+            ..grow()
+        };
+
+        vec![
+            FlexNode::Item(1, grow()),
+            FlexNode::Layout(
+                nested_style,
+                vec![FlexNode::Item(2, grow()), FlexNode::Item(3, item_3)],
+            ),
+        ]
+    }
+
+    fn changed(rects: &[FlexRect<u32>]) -> Vec<u32> {
+        rects.iter().filter(|r| r.changed).map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn tree_matches_the_stateless_solver() {
+        let children = tree_children(1.0);
+        let area = LayoutRect::new(5, 5, 200, 100);
+
+        let mut tree = FlexTree::new();
+        tree.update(&Style::default(), &children).unwrap();
+        let rects: Vec<(u32, LayoutRect)> = tree
+            .solve(area)
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.id, r.rect))
+            .collect();
+
+        assert_eq!(
+            rects,
+            solve_flexbox(&Style::default(), &children, area).unwrap()
+        );
+    }
+
+    #[test]
+    fn tree_reports_unchanged_rects() {
+        let children = tree_children(1.0);
+        let area = LayoutRect::new(0, 0, 200, 100);
+
+        let mut tree = FlexTree::new();
+        tree.update(&Style::default(), &children).unwrap();
+        assert_eq!(changed(&tree.solve(area).unwrap()), vec![1, 2, 3]);
+
+        tree.update(&Style::default(), &children).unwrap();
+        assert_eq!(changed(&tree.solve(area).unwrap()), Vec::<u32>::new());
+
+        tree.invalidate();
+        assert_eq!(changed(&tree.solve(area).unwrap()), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn tree_updates_changed_styles_only() {
+        let area = LayoutRect::new(0, 0, 200, 100);
+
+        let mut tree = FlexTree::new();
+        tree.update(&Style::default(), &tree_children(1.0)).unwrap();
+        tree.solve(area).unwrap();
+
+        tree.update(&Style::default(), &tree_children(3.0)).unwrap();
+        let rects = tree.solve(area).unwrap();
+
+        assert_eq!(changed(&rects), vec![2, 3]);
+        assert_eq!(rects[1].rect, LayoutRect::new(100, 0, 100, 25));
+        assert_eq!(rects[2].rect, LayoutRect::new(100, 25, 100, 75));
+    }
+
+    #[test]
+    fn changed_placements_keep_the_tab_order() {
+        let area = LayoutRect::new(0, 0, 200, 100);
+
+        let mut tree = FlexTree::new();
+        tree.update(&Style::default(), &tree_children(1.0)).unwrap();
+        let rects = tree.solve(area).unwrap();
+        let all = changed_placements(&rects, Some(0));
+        assert_eq!(
+            all.iter().map(|p| (p.0, p.2)).collect::<Vec<_>>(),
+            vec![(1, Some(0)), (2, Some(1)), (3, Some(2))]
+        );
+
+        // Only the nested items move. The first one still goes after the item that did not move.
+        tree.update(&Style::default(), &tree_children(3.0)).unwrap();
+        let rects = tree.solve(area).unwrap();
+        assert_eq!(
+            changed_placements(&rects, Some(0)),
+            vec![
+                (2, LayoutRect::new(100, 0, 100, 25), Some(1)),
+                (3, LayoutRect::new(100, 25, 100, 75), Some(2)),
+            ]
+        );
+
+        // Without a previous control, only the first item of the layout goes first in the tab order
+        tree.invalidate();
+        let rects = tree.solve(area).unwrap();
+        assert_eq!(changed_placements(&rects, None)[0].2, None);
+        assert_eq!(changed_placements(&rects, None)[1].2, Some(1));
+
+        assert!(changed_placements(&tree.solve(area).unwrap(), Some(0)).is_empty());
+    }
+
+    #[test]
+    fn tree_resize() {
+        let mut tree = FlexTree::new();
+        tree.update(&Style::default(), &tree_children(1.0)).unwrap();
+        tree.solve(LayoutRect::new(0, 0, 200, 100)).unwrap();
+
+        let rects = tree.solve(LayoutRect::new(0, 0, 200, 200)).unwrap();
+        assert_eq!(changed(&rects), vec![1, 2, 3]);
+        assert_eq!(rects[0].rect, LayoutRect::new(0, 0, 100, 200));
+    }
+
+    #[test]
+    fn tree_is_rebuilt_when_the_children_change() {
+        let area = LayoutRect::new(0, 0, 300, 100);
+
+        let mut tree = FlexTree::new();
+        tree.update(&Style::default(), &tree_children(1.0)).unwrap();
+        tree.solve(area).unwrap();
+
+        let mut children = tree_children(1.0);
+        children.push(FlexNode::Item(4, grow()));
+        tree.update(&Style::default(), &children).unwrap();
+
+        let rects = tree.solve(area).unwrap();
+        assert_eq!(changed(&rects), vec![1, 2, 3, 4]);
+        assert_eq!(
+            rects[3],
+            FlexRect {
+                id: 4,
+                rect: LayoutRect::new(200, 0, 100, 100),
+                changed: true
+            }
+        );
+    }
+
+    #[test]
+    fn tree_root_style_change() {
+        let area = LayoutRect::new(0, 0, 200, 100);
+        let children = vec![FlexNode::Item(1, grow())];
+
+        let mut tree = FlexTree::new();
+        tree.update(&Style::default(), &children).unwrap();
+        tree.solve(area).unwrap();
+
+        let style = Style {
+            padding: points(10.0),
+            ..Default::default()
+        };
+        tree.update(&style, &children).unwrap();
+
+        let rects = tree.solve(area).unwrap();
+        assert_eq!(rects[0].rect, LayoutRect::new(10, 10, 180, 80));
+        assert!(rects[0].changed);
+    }
+
+    #[test]
+    fn measured_items_use_their_content_size() {
+        let style = Style {
+            align_items: stretch::style::AlignItems::FlexStart,
+            ..Default::default()
+        };
+        let children = vec![
+            FlexNode::Measured(1, Style::default(), (40, 20)),
+            FlexNode::Item(2, grow()),
+        ];
+        let out = solve_flexbox(&style, &children, LayoutRect::new(0, 0, 200, 100)).unwrap();
+
+        assert_eq!(out[0], (1, LayoutRect::new(0, 0, 40, 20)));
+        assert_eq!(out[1], (2, LayoutRect::new(40, 0, 160, 0)));
+
+        assert_eq!(flexbox_preferred_size(&style, &children).unwrap(), (40, 20));
+    }
+
+    #[test]
+    fn measured_items_with_a_fixed_size() {
+        let fixed = Style {
+            size: Size {
+                width: Dimension::Points(70.0),
+                height: Dimension::Auto,
+            },
+            ..Default::default()
+        };
+        let style = Style {
+            align_items: stretch::style::AlignItems::FlexStart,
+            ..Default::default()
+        };
+        let children = vec![FlexNode::Measured(1, fixed, (40, 20))];
+        let out = solve_flexbox(&style, &children, LayoutRect::new(0, 0, 200, 100)).unwrap();
+
+        assert_eq!(out[0], (1, LayoutRect::new(0, 0, 70, 20)));
+    }
+
+    #[test]
+    fn tree_updates_content_sizes() {
+        let style = Style {
+            align_items: stretch::style::AlignItems::FlexStart,
+            ..Default::default()
+        };
+        let area = LayoutRect::new(0, 0, 200, 100);
+
+        let mut tree = FlexTree::new();
+        tree.update(&style, &[FlexNode::Measured(1, Style::default(), (40, 20))])
+            .unwrap();
+        tree.solve(area).unwrap();
+
+        tree.update(&style, &[FlexNode::Measured(1, Style::default(), (60, 30))])
+            .unwrap();
+        let rects = tree.solve(area).unwrap();
+
+        assert_eq!(rects[0].rect, LayoutRect::new(0, 0, 60, 30));
+        assert!(rects[0].changed);
+    }
+}
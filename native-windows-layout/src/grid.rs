@@ -0,0 +1,817 @@
+use crate::LayoutRect;
+
+/// How the size of a row or a column of a GridLayout is computed
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GridTrackSize {
+    /// The track has a fixed size in pixels
+    Fixed(u32),
+
+    /// The track is as big as the biggest preferred size of the controls in the track.
+    /// Controls spanning more than one track are ignored.
+    Auto,
+
+    /// The space left by the fixed and auto tracks is shared between the weighted tracks.
+    /// A track with a weight of 3 is three times bigger than a track with a weight of 1.
+    Weight(u32),
+}
+
+/**
+    The definition of a row or a column of a GridLayout.
+    Tracks that are not defined use `GridTrack::weight(1)`, which shares the space equally.
+
+    ```rust
+    use native_windows_layout::GridTrack;
+    let columns = vec![GridTrack::fixed(100), GridTrack::weight(1).min(150)];
+    ```
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GridTrack {
+    /// How the size of the track is computed
+    pub size: GridTrackSize,
+
+    /// The minimum size of the track in pixels
+    pub min: u32,
+
+    /// The maximum size of the track in pixels
+    pub max: u32,
+}
+
+impl GridTrack {
+    /// A track with a fixed size in pixels
+    pub fn fixed(size: u32) -> GridTrack {
+        GridTrack {
+            size: GridTrackSize::Fixed(size),
+            ..Default::default()
+        }
+    }
+
+    /// A track sized from the preferred size of its controls
+    pub fn auto() -> GridTrack {
+        GridTrack {
+            size: GridTrackSize::Auto,
+            ..Default::default()
+        }
+    }
+
+    /// A track that takes a share of the remaining space
+    pub fn weight(weight: u32) -> GridTrack {
+        GridTrack {
+            size: GridTrackSize::Weight(weight),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the minimum size of the track
+    pub fn min(mut self, min: u32) -> GridTrack {
+        self.min = min;
+        self
+    }
+
+    /// Sets the maximum size of the track
+    pub fn max(mut self, max: u32) -> GridTrack {
+        self.max = max;
+        self
+    }
+
+    fn clamp(&self, size: u32) -> u32 {
+        size.min(self.max).max(self.min)
+    }
+}
+
+impl Default for GridTrack {
+    fn default() -> GridTrack {
+        GridTrack {
+            size: GridTrackSize::Weight(1),
+            min: 0,
+            max: u32::MAX,
+        }
+    }
+}
+
+/// How a control is placed in its cell
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GridAlign {
+    /// The control fills the cell
+    #[default]
+    Fill,
+
+    /// The control keeps its preferred size and is placed at the left or the top of the cell
+    Start,
+
+    /// The control keeps its preferred size and is centered in the cell
+    Center,
+
+    /// The control keeps its preferred size and is placed at the right or the bottom of the cell
+    End,
+}
+
+/// A node placed by a `Grid`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GridCell<Id> {
+    /// The id of the node
+    pub id: Id,
+
+    /// The column position of the node
+    pub col: u32,
+
+    /// The row position of the node
+    pub row: u32,
+
+    /// The number of columns the node spans
+    pub col_span: u32,
+
+    /// The number of rows the node spans
+    pub row_span: u32,
+
+    /// The preferred width and height of the node. Used by `Auto` tracks and aligned nodes.
+    pub preferred: [u32; 2],
+
+    /// The horizontal alignment of the node in its cell
+    pub h_align: GridAlign,
+
+    /// The vertical alignment of the node in its cell
+    pub v_align: GridAlign,
+}
+
+impl<Id> GridCell<Id> {
+    /// A node at `col` and `row` that fills a single cell
+    pub fn new(id: Id, col: u32, row: u32) -> GridCell<Id> {
+        GridCell {
+            id,
+            col,
+            row,
+            col_span: 1,
+            row_span: 1,
+            preferred: [0, 0],
+            h_align: GridAlign::Fill,
+            v_align: GridAlign::Fill,
+        }
+    }
+}
+
+/// The parameters of a grid layout
+#[derive(Copy, Clone, Debug)]
+pub struct Grid<'a> {
+    /// The top, right, bottom, left space around the layout
+    pub margins: [u32; 4],
+
+    /// The space between the nodes of the layout. Applied on both sides of every cell.
+    pub spacing: u32,
+
+    /// The minimum size of the layout. Used if the area is smaller than `min_size`.
+    pub min_size: [u32; 2],
+
+    /// The maximum size of the layout. Used if the area is bigger than `max_size`.
+    pub max_size: [u32; 2],
+
+    /// The number of columns. If `None`, the columns are computed from the cells and the track definitions.
+    pub column_count: Option<u32>,
+
+    /// The number of rows. If `None`, the rows are computed from the cells and the track definitions.
+    pub row_count: Option<u32>,
+
+    /// The definition of the columns. Missing columns share the remaining space equally.
+    pub columns: &'a [GridTrack],
+
+    /// The definition of the rows. Missing rows share the remaining space equally.
+    pub rows: &'a [GridTrack],
+}
+
+impl<'a> Default for Grid<'a> {
+    fn default() -> Grid<'a> {
+        Grid {
+            margins: [5, 5, 5, 5],
+            spacing: 5,
+            min_size: [0, 0],
+            max_size: [u32::MAX, u32::MAX],
+            column_count: None,
+            row_count: None,
+            columns: &[],
+            rows: &[],
+        }
+    }
+}
+
+impl<'a> Grid<'a> {
+    /// The number of columns and rows of the layout
+    pub fn track_count<Id>(&self, cells: &[GridCell<Id>]) -> (u32, u32) {
+        let column_count = match self.column_count {
+            Some(c) => c,
+            None => cells
+                .iter()
+                .map(|cell| cell.col + cell.col_span)
+                .max()
+                .unwrap_or(1)
+                .max(self.columns.len() as u32),
+        };
+
+        let row_count = match self.row_count {
+            Some(c) => c,
+            None => cells
+                .iter()
+                .map(|cell| cell.row + cell.row_span)
+                .max()
+                .unwrap_or(1)
+                .max(self.rows.len() as u32),
+        };
+
+        (column_count, row_count)
+    }
+
+    /**
+        Computes the rectangle of every cell in `area`. The rectangles are returned in the order of `cells`.
+
+        Returns `None` if the area is too small to fit the margins and the spacing of the layout.
+        In that case the nodes should not be moved.
+    */
+    pub fn solve<Id: Copy>(
+        &self,
+        cells: &[GridCell<Id>],
+        area: LayoutRect,
+    ) -> Option<Vec<(Id, LayoutRect)>> {
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let sp = self.spacing;
+        let sp2 = sp * 2;
+
+        let [min_w, min_h] = self.min_size;
+        let [max_w, max_h] = self.max_size;
+        let mut width = area.width.max(min_w).min(max_w);
+        let mut height = area.height.max(min_h).min(max_h);
+
+        let (column_count, row_count) = self.track_count(cells);
+
+        if width < (m_right + m_left) + (sp2 * column_count) {
+            return None;
+        }
+
+        if height < (m_top + m_bottom) + (sp2 * row_count) {
+            return None;
+        }
+
+        // Apply margins
+        width -= m_right + m_left;
+        height -= m_top + m_bottom;
+
+        // Apply spacing
+        width -= sp2 * column_count;
+        height -= sp2 * row_count;
+
+        let column_items = cells
+            .iter()
+            .filter(|cell| cell.col_span == 1)
+            .map(|cell| (cell.col, cell.preferred[0]));
+        let columns = solve_tracks(self.columns, column_count, width, column_items);
+
+        let row_items = cells
+            .iter()
+            .filter(|cell| cell.row_span == 1)
+            .map(|cell| (cell.row, cell.preferred[1]));
+        let rows = solve_tracks(self.rows, row_count, height, row_items);
+
+        let rects = cells
+            .iter()
+            .map(|cell| {
+                let x: u32 = m_left
+                    + (sp + (sp2 * cell.col))
+                    + columns[0..(cell.col as usize)].iter().sum::<u32>();
+                let y: u32 = m_top
+                    + (sp + (sp2 * cell.row))
+                    + rows[0..(cell.row as usize)].iter().sum::<u32>();
+
+                let local_width: u32 = columns
+                    [(cell.col as usize)..((cell.col + cell.col_span) as usize)]
+                    .iter()
+                    .sum::<u32>()
+                    + (sp2 * (cell.col_span - 1));
+                let local_height: u32 = rows
+                    [(cell.row as usize)..((cell.row + cell.row_span) as usize)]
+                    .iter()
+                    .sum::<u32>()
+                    + (sp2 * (cell.row_span - 1));
+
+                let [pref_w, pref_h] = cell.preferred;
+                let (x, local_width) = align_in_cell(cell.h_align, x, local_width, pref_w);
+                let (y, local_height) = align_in_cell(cell.v_align, y, local_height, pref_h);
+
+                let rect = LayoutRect::new(
+                    area.x + x as i32,
+                    area.y + y as i32,
+                    local_width,
+                    local_height,
+                );
+
+                (cell.id, rect)
+            })
+            .collect();
+
+        Some(rects)
+    }
+
+    /// Returns the smallest size that can display the cells: the margins, the spacing,
+    /// the fixed and auto tracks and the minimum size of the weighted tracks.
+    pub fn preferred_size<Id>(&self, cells: &[GridCell<Id>]) -> (u32, u32) {
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let sp2 = self.spacing * 2;
+        let (column_count, row_count) = self.track_count(cells);
+
+        let column_items = cells
+            .iter()
+            .filter(|cell| cell.col_span == 1)
+            .map(|cell| (cell.col, cell.preferred[0]));
+        let columns = solve_tracks(self.columns, column_count, 0, column_items);
+
+        let row_items = cells
+            .iter()
+            .filter(|cell| cell.row_span == 1)
+            .map(|cell| (cell.row, cell.preferred[1]));
+        let rows = solve_tracks(self.rows, row_count, 0, row_items);
+
+        let [min_w, min_h] = self.min_size;
+        let width = m_left + m_right + sp2 * column_count + columns.iter().sum::<u32>();
+        let height = m_top + m_bottom + sp2 * row_count + rows.iter().sum::<u32>();
+
+        (width.max(min_w), height.max(min_h))
+    }
+}
+
+/**
+    Computes the size of `count` tracks sharing `available` pixels.
+    `items` are the (track index, preferred size) of the controls spanning a single track, used by `Auto` tracks.
+
+    Fixed and auto tracks are sized first. The remaining space is then shared between the weighted tracks.
+    A weighted track that would be smaller than its minimum or bigger than its maximum is clamped, and the
+    space is shared again between the other weighted tracks. Pixels lost to rounding go to the first weighted tracks.
+*/
+pub fn solve_tracks<I>(tracks: &[GridTrack], count: u32, available: u32, items: I) -> Vec<u32>
+where
+    I: Iterator<Item = (u32, u32)>,
+{
+    let count = count as usize;
+    let tracks: Vec<GridTrack> = (0..count)
+        .map(|i| tracks.get(i).copied().unwrap_or_default())
+        .collect();
+
+    let mut auto_sizes = vec![0u32; count];
+    for (index, size) in items {
+        if let Some(auto) = auto_sizes.get_mut(index as usize) {
+            *auto = (*auto).max(size);
+        }
+    }
+
+    let mut sizes = vec![0u32; count];
+    let mut weighted = Vec::with_capacity(count);
+    for (i, track) in tracks.iter().enumerate() {
+        match track.size {
+            GridTrackSize::Fixed(size) => sizes[i] = track.clamp(size),
+            GridTrackSize::Auto => sizes[i] = track.clamp(auto_sizes[i]),
+            GridTrackSize::Weight(_) => weighted.push(i),
+        }
+    }
+
+    let fixed: u32 = sizes.iter().sum();
+    let mut remaining = available.saturating_sub(fixed);
+
+    // Clamp the tracks that cannot take their share until every remaining track fits its limits
+    loop {
+        let total_weight: u64 = weighted
+            .iter()
+            .map(|&i| track_weight(&tracks[i]) as u64)
+            .sum();
+        let pool = remaining as u64;
+        let share = |i: usize| match total_weight {
+            0 => 0,
+            total => (pool * track_weight(&tracks[i]) as u64 / total) as u32,
+        };
+
+        let clamped: Vec<usize> = weighted
+            .iter()
+            .copied()
+            .filter(|&i| {
+                let size = share(i);
+                size < tracks[i].min || size > tracks[i].max
+            })
+            .collect();
+
+        if clamped.is_empty() {
+            for &i in weighted.iter() {
+                sizes[i] = share(i);
+            }
+
+            let used: u32 = weighted.iter().map(|&i| sizes[i]).sum();
+            let mut extra = remaining - used;
+            for &i in weighted.iter() {
+                if extra == 0 {
+                    break;
+                }
+                if sizes[i] < tracks[i].max {
+                    sizes[i] += 1;
+                    extra -= 1;
+                }
+            }
+
+            break;
+        }
+
+        for &i in clamped.iter() {
+            sizes[i] = tracks[i].clamp(share(i));
+            remaining = remaining.saturating_sub(sizes[i]);
+        }
+        weighted.retain(|i| !clamped.contains(i));
+    }
+
+    sizes
+}
+
+fn track_weight(track: &GridTrack) -> u32 {
+    match track.size {
+        GridTrackSize::Weight(w) => w,
+        _ => 0,
+    }
+}
+
+/// Returns the position and the size of a control in a cell at `pos` of size `cell`
+fn align_in_cell(align: GridAlign, pos: u32, cell: u32, preferred: u32) -> (u32, u32) {
+    let size = preferred.min(cell);
+    match align {
+        GridAlign::Fill => (pos, cell),
+        GridAlign::Start => (pos, size),
+        GridAlign::Center => (pos + (cell - size) / 2, size),
+        GridAlign::End => (pos + cell - size, size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rects(grid: &Grid, cells: &[GridCell<usize>], area: LayoutRect) -> Vec<LayoutRect> {
+        grid.solve(cells, area)
+            .expect("Area too small")
+            .into_iter()
+            .map(|(_, rect)| rect)
+            .collect()
+    }
+
+    fn cells(positions: &[(u32, u32)]) -> Vec<GridCell<usize>> {
+        positions
+            .iter()
+            .enumerate()
+            .map(|(i, &(col, row))| GridCell::new(i, col, row))
+            .collect()
+    }
+
+    #[test]
+    fn equal_split_with_margins_and_spacing() {
+        let grid = Grid::default();
+        let cells = cells(&[(0, 0), (1, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 200, 100));
+
+        assert_eq!(out[0], LayoutRect::new(10, 10, 85, 80));
+        assert_eq!(out[1], LayoutRect::new(105, 10, 85, 80));
+    }
+
+    #[test]
+    fn ids_are_returned_in_cell_order() {
+        let grid = Grid::default();
+        let cells = vec![GridCell::new('b', 1, 0), GridCell::new('a', 0, 0)];
+        let ids: Vec<char> = grid
+            .solve(&cells, LayoutRect::new(0, 0, 200, 100))
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+
+        assert_eq!(ids, vec!['b', 'a']);
+    }
+
+    #[test]
+    fn custom_margins() {
+        let grid = Grid {
+            margins: [1, 2, 3, 4],
+            spacing: 0,
+            ..Default::default()
+        };
+        let cells = cells(&[(0, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 100, 50));
+
+        assert_eq!(out[0], LayoutRect::new(4, 1, 94, 46));
+    }
+
+    #[test]
+    fn spacing_between_cells() {
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 3,
+            ..Default::default()
+        };
+        let cells = cells(&[(0, 0), (0, 1)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 50, 112));
+
+        // 112 - 4 * 3 = 100 pixels shared by two rows
+        assert_eq!(out[0], LayoutRect::new(3, 3, 44, 50));
+        assert_eq!(out[1], LayoutRect::new(3, 59, 44, 50));
+    }
+
+    #[test]
+    fn vertical_span() {
+        let grid = Grid::default();
+        let mut cells = cells(&[(1, 0), (0, 0), (1, 1)]);
+        cells[1].row_span = 2;
+
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 200, 200));
+
+        // Two columns and two rows of 85 pixels. A span includes the spacing between the cells.
+        assert_eq!(out[0], LayoutRect::new(105, 10, 85, 85));
+        assert_eq!(out[1], LayoutRect::new(10, 10, 85, 180));
+        assert_eq!(out[2], LayoutRect::new(105, 105, 85, 85));
+    }
+
+    #[test]
+    fn horizontal_span() {
+        let grid = Grid::default();
+        let mut cells = cells(&[(0, 0), (0, 1), (1, 1)]);
+        cells[0].col_span = 2;
+
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 200, 200));
+
+        assert_eq!(out[0], LayoutRect::new(10, 10, 180, 85));
+        assert_eq!(out[1], LayoutRect::new(10, 105, 85, 85));
+        assert_eq!(out[2], LayoutRect::new(105, 105, 85, 85));
+    }
+
+    #[test]
+    fn leftover_pixels_go_to_the_first_tracks() {
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            ..Default::default()
+        };
+        let cells = cells(&[(0, 0), (1, 0), (2, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 101, 10));
+
+        assert_eq!(out[0], LayoutRect::new(0, 0, 34, 10));
+        assert_eq!(out[1], LayoutRect::new(34, 0, 34, 10));
+        assert_eq!(out[2], LayoutRect::new(68, 0, 33, 10));
+
+        let total: u32 = out.iter().map(|r| r.width).sum();
+        assert_eq!(total, 101);
+    }
+
+    #[test]
+    fn layout_min_size_is_applied() {
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            min_size: [300, 40],
+            ..Default::default()
+        };
+        let cells = cells(&[(0, 0), (1, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 100, 10));
+
+        assert_eq!(out[0], LayoutRect::new(0, 0, 150, 40));
+        assert_eq!(out[1], LayoutRect::new(150, 0, 150, 40));
+    }
+
+    #[test]
+    fn layout_max_size_is_applied() {
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            max_size: [100, 50],
+            ..Default::default()
+        };
+        let cells = cells(&[(0, 0), (1, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 1000, 1000));
+
+        assert_eq!(out[0], LayoutRect::new(0, 0, 50, 50));
+        assert_eq!(out[1], LayoutRect::new(50, 0, 50, 50));
+    }
+
+    #[test]
+    fn area_too_small() {
+        let grid = Grid::default();
+        let cells = cells(&[(0, 0), (1, 0)]);
+
+        // 10 pixels of margins and 20 pixels of spacing
+        assert!(grid.solve(&cells, LayoutRect::new(0, 0, 29, 100)).is_none());
+        assert!(grid.solve(&cells, LayoutRect::new(0, 0, 30, 100)).is_some());
+        assert!(grid.solve(&cells, LayoutRect::new(0, 0, 100, 19)).is_none());
+    }
+
+    #[test]
+    fn area_position_offsets_the_cells() {
+        let grid = Grid::default();
+        let cells = cells(&[(0, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(-20, 300, 100, 100));
+
+        assert_eq!(out[0], LayoutRect::new(-10, 310, 80, 80));
+    }
+
+    #[test]
+    fn fixed_count_adds_empty_tracks() {
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            column_count: Some(4),
+            row_count: Some(2),
+            ..Default::default()
+        };
+        let cells = cells(&[(0, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 400, 200));
+
+        assert_eq!(out[0], LayoutRect::new(0, 0, 100, 100));
+    }
+
+    #[test]
+    fn fixed_auto_and_weighted_tracks() {
+        let columns = [
+            GridTrack::fixed(40),
+            GridTrack::auto(),
+            GridTrack::weight(1),
+            GridTrack::weight(3),
+        ];
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            columns: &columns,
+            ..Default::default()
+        };
+
+        let mut cells = cells(&[(0, 0), (1, 0), (1, 1), (2, 0), (3, 0)]);
+        cells[1].preferred = [25, 0];
+        cells[2].preferred = [30, 0];
+
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 270, 100));
+
+        // 270 - 40 - 30 = 200 pixels shared 1:3
+        assert_eq!(out[0], LayoutRect::new(0, 0, 40, 50));
+        assert_eq!(out[1], LayoutRect::new(40, 0, 30, 50));
+        assert_eq!(out[2], LayoutRect::new(40, 50, 30, 50));
+        assert_eq!(out[3], LayoutRect::new(70, 0, 50, 50));
+        assert_eq!(out[4], LayoutRect::new(120, 0, 150, 50));
+    }
+
+    #[test]
+    fn auto_tracks_ignore_spanning_cells() {
+        let columns = [GridTrack::auto(), GridTrack::weight(1)];
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            columns: &columns,
+            ..Default::default()
+        };
+
+        let mut cells = cells(&[(0, 0), (0, 1)]);
+        cells[0].preferred = [20, 0];
+        cells[1].preferred = [500, 0];
+        cells[1].col_span = 2;
+
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 100, 100));
+
+        assert_eq!(out[0].width, 20);
+        assert_eq!(out[1].width, 100);
+    }
+
+    #[test]
+    fn weighted_track_max_is_clamped() {
+        let columns = [GridTrack::weight(1).max(50), GridTrack::weight(1)];
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            columns: &columns,
+            ..Default::default()
+        };
+        let cells = cells(&[(0, 0), (1, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 200, 10));
+
+        assert_eq!(out[0], LayoutRect::new(0, 0, 50, 10));
+        assert_eq!(out[1], LayoutRect::new(50, 0, 150, 10));
+    }
+
+    #[test]
+    fn weighted_track_min_is_clamped() {
+        let columns = [GridTrack::weight(1).min(150), GridTrack::weight(1)];
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            columns: &columns,
+            ..Default::default()
+        };
+        let cells = cells(&[(0, 0), (1, 0)]);
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 200, 10));
+
+        assert_eq!(out[0], LayoutRect::new(0, 0, 150, 10));
+        assert_eq!(out[1], LayoutRect::new(150, 0, 50, 10));
+    }
+
+    #[test]
+    fn fixed_and_auto_tracks_are_clamped() {
+        let tracks = [
+            GridTrack::fixed(100).max(60),
+            GridTrack::auto().min(30),
+            GridTrack::auto().max(10),
+        ];
+        let items = vec![(1, 5), (2, 80)];
+        let sizes = solve_tracks(&tracks, 3, 1000, items.into_iter());
+
+        assert_eq!(sizes, vec![60, 30, 10]);
+    }
+
+    #[test]
+    fn leftover_pixels_skip_tracks_at_their_max() {
+        let tracks = [GridTrack::weight(1).max(33), GridTrack::weight(1)];
+        let sizes = solve_tracks(&tracks, 2, 67, Vec::new().into_iter());
+
+        assert_eq!(sizes, vec![33, 34]);
+    }
+
+    #[test]
+    fn weighted_tracks_without_space() {
+        let tracks = [GridTrack::fixed(80), GridTrack::weight(1)];
+        let sizes = solve_tracks(&tracks, 2, 50, Vec::new().into_iter());
+
+        assert_eq!(sizes, vec![80, 0]);
+    }
+
+    #[test]
+    fn zero_weight_tracks_get_no_space() {
+        let tracks = [GridTrack::weight(0), GridTrack::weight(2)];
+        let sizes = solve_tracks(&tracks, 2, 100, Vec::new().into_iter());
+
+        assert_eq!(sizes, vec![0, 100]);
+    }
+
+    #[test]
+    fn cell_alignment() {
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            ..Default::default()
+        };
+
+        let mut cells = cells(&[(0, 0), (1, 0), (2, 0)]);
+        for cell in cells.iter_mut() {
+            cell.preferred = [20, 10];
+        }
+        cells[0].h_align = GridAlign::Start;
+        cells[0].v_align = GridAlign::End;
+        cells[1].h_align = GridAlign::Center;
+        cells[1].v_align = GridAlign::Center;
+        cells[2].h_align = GridAlign::End;
+        cells[2].v_align = GridAlign::Start;
+
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 150, 50));
+
+        assert_eq!(out[0], LayoutRect::new(0, 40, 20, 10));
+        assert_eq!(out[1], LayoutRect::new(65, 20, 20, 10));
+        assert_eq!(out[2], LayoutRect::new(130, 0, 20, 10));
+    }
+
+    #[test]
+    fn aligned_cell_bigger_than_preferred_size_is_shrunk() {
+        let grid = Grid {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            ..Default::default()
+        };
+
+        let mut cells = cells(&[(0, 0)]);
+        cells[0].preferred = [500, 500];
+        cells[0].h_align = GridAlign::Center;
+
+        let out = rects(&grid, &cells, LayoutRect::new(0, 0, 100, 100));
+
+        assert_eq!(out[0], LayoutRect::new(0, 0, 100, 100));
+    }
+
+    #[test]
+    fn preferred_size() {
+        let columns = [
+            GridTrack::fixed(40),
+            GridTrack::auto(),
+            GridTrack::weight(1).min(15),
+        ];
+        let rows = [GridTrack::auto()];
+        let grid = Grid {
+            columns: &columns,
+            rows: &rows,
+            ..Default::default()
+        };
+
+        let mut cells = cells(&[(0, 0), (1, 0), (2, 0)]);
+        cells[1].preferred = [25, 12];
+        cells[2].preferred = [100, 30];
+
+        // margins: 10, spacing: 2 * 5 per track
+        assert_eq!(
+            grid.preferred_size(&cells),
+            (10 + 30 + 40 + 25 + 15, 10 + 10 + 30)
+        );
+
+        let grid = Grid {
+            min_size: [500, 500],
+            ..grid
+        };
+        assert_eq!(grid.preferred_size(&cells), (500, 500));
+    }
+}
@@ -0,0 +1,37 @@
+/*!
+    The geometry solvers used by the layouts of native-windows-gui.
+
+    The solvers do not know anything about windows. They work over abstract node ids and return the rectangle
+    of every node in the order the nodes must appear in the tab order. Native-windows-gui applies the rectangles
+    to the controls with the win32 api.
+
+    Because this crate does not depend on winapi, the layout math can be tested on any host with `cargo test`.
+
+    The flexbox solver requires the `flexbox` feature.
+*/
+
+#[cfg(feature = "flexbox")]
+pub extern crate stretch;
+
+mod rect;
+pub use rect::LayoutRect;
+
+mod grid;
+pub use grid::{solve_tracks, Grid, GridAlign, GridCell, GridTrack, GridTrackSize};
+
+mod stack;
+pub use stack::{Stack, StackAlign, StackDirection, StackItem};
+
+mod wrap;
+pub use wrap::{Wrap, WrapItem};
+
+mod dynamic;
+pub use dynamic::{dyn_preferred_size, solve_dyn, DynAnchors, DynItem};
+
+#[cfg(feature = "flexbox")]
+mod flexbox;
+
+#[cfg(feature = "flexbox")]
+pub use flexbox::{
+    changed_placements, flexbox_preferred_size, solve_flexbox, FlexNode, FlexRect, FlexTree,
+};
@@ -0,0 +1,29 @@
+/// The area of a node computed by a layout solver. Positions are relative to the parent window.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LayoutRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl LayoutRect {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> LayoutRect {
+        LayoutRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the position of the rectangle as a tuple
+    pub fn position(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+
+    /// Returns the size of the rectangle as a tuple
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
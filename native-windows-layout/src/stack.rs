@@ -0,0 +1,349 @@
+use crate::LayoutRect;
+
+/// The direction in which the nodes of a stack are placed
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StackDirection {
+    /// The nodes are placed from the top to the bottom
+    #[default]
+    Vertical,
+
+    /// The nodes are placed from the left to the right
+    Horizontal,
+}
+
+/// How the nodes of a stack or a wrap layout are placed on an axis
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StackAlign {
+    /// The nodes fill the available space
+    #[default]
+    Fill,
+
+    /// The nodes keep their preferred size and are placed at the left or the top
+    Start,
+
+    /// The nodes keep their preferred size and are centered
+    Center,
+
+    /// The nodes keep their preferred size and are placed at the right or the bottom
+    End,
+}
+
+/// A node placed by a `Stack`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StackItem<Id> {
+    /// The id of the node
+    pub id: Id,
+
+    /// The preferred width and height of the node
+    pub preferred: [u32; 2],
+
+    /// The share of the free space of the stack given to the node. A node with a stretch of 0 keeps its preferred size.
+    pub stretch: u32,
+
+    /// The alignment of the node across the stack. If `None`, the alignment of the stack is used.
+    pub align: Option<StackAlign>,
+}
+
+impl<Id> StackItem<Id> {
+    /// A node with a preferred size that does not stretch
+    pub fn new(id: Id, preferred: [u32; 2]) -> StackItem<Id> {
+        StackItem {
+            id,
+            preferred,
+            stretch: 0,
+            align: None,
+        }
+    }
+}
+
+/**
+    The parameters of a stack layout. A stack places its nodes in a single row or column.
+
+    Along the stack, every node gets its preferred size and the free space is shared between the nodes with a stretch factor.
+    If no node stretches, `justify` places the nodes in the free space. If the stack is too small, the nodes shrink proportionally.
+
+    Across the stack, the nodes are aligned with `align`.
+*/
+#[derive(Copy, Clone, Debug)]
+pub struct Stack {
+    /// The direction of the stack
+    pub direction: StackDirection,
+
+    /// The top, right, bottom, left space around the layout
+    pub margins: [u32; 4],
+
+    /// The space between two nodes
+    pub spacing: u32,
+
+    /// The alignment of the nodes across the stack
+    pub align: StackAlign,
+
+    /// The placement of the nodes along the stack when no node stretches.
+    /// `Fill` shares the free space equally between the nodes.
+    pub justify: StackAlign,
+}
+
+impl Default for Stack {
+    fn default() -> Stack {
+        Stack {
+            direction: StackDirection::Vertical,
+            margins: [5, 5, 5, 5],
+            spacing: 5,
+            align: StackAlign::Fill,
+            justify: StackAlign::Start,
+        }
+    }
+}
+
+impl Stack {
+    /// Computes the rectangle of every node in `area`. The rectangles are returned in the order of `items`.
+    pub fn solve<Id: Copy>(
+        &self,
+        items: &[StackItem<Id>],
+        area: LayoutRect,
+    ) -> Vec<(Id, LayoutRect)> {
+        if items.is_empty() {
+            return Vec::new();
+        }
+
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let inner = LayoutRect::new(
+            area.x + m_left as i32,
+            area.y + m_top as i32,
+            area.width.saturating_sub(m_left + m_right),
+            area.height.saturating_sub(m_top + m_bottom),
+        );
+
+        let (main_start, cross_start) = self.split(inner.x, inner.y);
+        let (main_size, cross_size) = self.split(inner.width, inner.height);
+
+        let spacing = self.spacing * (items.len() as u32 - 1);
+        let available = main_size.saturating_sub(spacing);
+        let preferred: Vec<u32> = items
+            .iter()
+            .map(|i| self.split(i.preferred[0], i.preferred[1]).0)
+            .collect();
+        let (sizes, offset) = self.main_sizes(items, &preferred, available);
+
+        let mut position = main_start + offset as i32;
+        items
+            .iter()
+            .zip(sizes)
+            .map(|(item, size)| {
+                let pref_cross = self.split(item.preferred[0], item.preferred[1]).1;
+                let align = item.align.unwrap_or(self.align);
+                let (cross_offset, cross) = align_in(align, cross_size, pref_cross);
+
+                let (x, y) = self.join(position, cross_start + cross_offset as i32);
+                let (width, height) = self.join(size, cross);
+                position += (size + self.spacing) as i32;
+
+                (item.id, LayoutRect::new(x, y, width, height))
+            })
+            .collect()
+    }
+
+    /// Returns the smallest size that displays every node with its preferred size
+    pub fn preferred_size<Id>(&self, items: &[StackItem<Id>]) -> (u32, u32) {
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let spacing = self.spacing * (items.len().max(1) as u32 - 1);
+
+        let (main, cross) = items.iter().fold((spacing, 0), |(main, cross), item| {
+            let (m, c) = self.split(item.preferred[0], item.preferred[1]);
+            (main + m, cross.max(c))
+        });
+
+        let (width, height) = self.join(main, cross);
+        (width + m_left + m_right, height + m_top + m_bottom)
+    }
+
+    /// The size of every node along the stack and the offset of the first node
+    fn main_sizes<Id>(
+        &self,
+        items: &[StackItem<Id>],
+        preferred: &[u32],
+        available: u32,
+    ) -> (Vec<u32>, u32) {
+        let total: u32 = preferred.iter().sum();
+
+        if total > available {
+            let sizes = preferred
+                .iter()
+                .map(|&p| ((p as u64 * available as u64) / total as u64) as u32)
+                .collect();
+            return (sizes, 0);
+        }
+
+        let free = available - total;
+        let stretch: u32 = items.iter().map(|i| i.stretch).sum();
+        if stretch > 0 {
+            let weights: Vec<u32> = items.iter().map(|i| i.stretch).collect();
+            return (share(preferred, &weights, free), 0);
+        }
+
+        match self.justify {
+            StackAlign::Fill => (share(preferred, &vec![1; items.len()], free), 0),
+            StackAlign::Start => (preferred.to_vec(), 0),
+            StackAlign::Center => (preferred.to_vec(), free / 2),
+            StackAlign::End => (preferred.to_vec(), free),
+        }
+    }
+
+    /// Converts a width and a height to a size along and a size across the stack
+    fn split<T>(&self, a: T, b: T) -> (T, T) {
+        match self.direction {
+            StackDirection::Vertical => (b, a),
+            StackDirection::Horizontal => (a, b),
+        }
+    }
+
+    /// Converts a size along and a size across the stack to a width and a height
+    fn join<T>(&self, main: T, cross: T) -> (T, T) {
+        self.split(main, cross)
+    }
+}
+
+/// Adds `free` to `sizes` proportionally to `weights`. The rounding error goes to the last weighted size.
+pub(crate) fn share(sizes: &[u32], weights: &[u32], free: u32) -> Vec<u32> {
+    let total: u32 = weights.iter().sum();
+    let last = weights.iter().rposition(|&w| w > 0);
+
+    let mut given = 0;
+    sizes
+        .iter()
+        .zip(weights.iter())
+        .enumerate()
+        .map(|(i, (&size, &weight))| {
+            let extra = match Some(i) == last {
+                true => free - given,
+                false => ((free as u64 * weight as u64) / total as u64) as u32,
+            };
+            given += extra;
+            size + extra
+        })
+        .collect()
+}
+
+/// The offset and the size of a node with a preferred size of `preferred` aligned in `available`
+pub(crate) fn align_in(align: StackAlign, available: u32, preferred: u32) -> (u32, u32) {
+    let size = preferred.min(available);
+    match align {
+        StackAlign::Fill => (0, available),
+        StackAlign::Start => (0, size),
+        StackAlign::Center => ((available - size) / 2, size),
+        StackAlign::End => (available - size, size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rects(stack: &Stack, items: &[StackItem<usize>], area: LayoutRect) -> Vec<LayoutRect> {
+        stack
+            .solve(items, area)
+            .into_iter()
+            .map(|(_, rect)| rect)
+            .collect()
+    }
+
+    fn items(sizes: &[[u32; 2]]) -> Vec<StackItem<usize>> {
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &size)| StackItem::new(i, size))
+            .collect()
+    }
+
+    #[test]
+    fn vertical_stack_uses_preferred_heights() {
+        let stack = Stack::default();
+        let items = items(&[[50, 20], [80, 30]]);
+        let out = rects(&stack, &items, LayoutRect::new(0, 0, 100, 200));
+
+        assert_eq!(out[0], LayoutRect::new(5, 5, 90, 20));
+        assert_eq!(out[1], LayoutRect::new(5, 30, 90, 30));
+    }
+
+    #[test]
+    fn horizontal_stack_with_cross_alignment() {
+        let stack = Stack {
+            direction: StackDirection::Horizontal,
+            margins: [0, 0, 0, 0],
+            align: StackAlign::Center,
+            ..Default::default()
+        };
+        let mut items = items(&[[50, 20], [30, 100]]);
+        items[1].align = Some(StackAlign::End);
+        let out = rects(&stack, &items, LayoutRect::new(0, 0, 200, 40));
+
+        assert_eq!(out[0], LayoutRect::new(0, 10, 50, 20));
+        assert_eq!(out[1], LayoutRect::new(55, 0, 30, 40));
+    }
+
+    #[test]
+    fn stretch_factors_share_the_free_space() {
+        let stack = Stack {
+            direction: StackDirection::Horizontal,
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            ..Default::default()
+        };
+        let mut items = items(&[[10, 10], [10, 10], [10, 10]]);
+        items[1].stretch = 1;
+        items[2].stretch = 3;
+        let out = rects(&stack, &items, LayoutRect::new(0, 0, 110, 10));
+
+        assert_eq!(out[0].width, 10);
+        assert_eq!(out[1].width, 30);
+        assert_eq!(out[2].width, 70);
+        assert_eq!(out[2].x, 40);
+    }
+
+    #[test]
+    fn justify_places_the_nodes_without_stretch() {
+        let mut stack = Stack {
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            ..Default::default()
+        };
+        let items = items(&[[10, 10], [10, 10]]);
+        let area = LayoutRect::new(0, 0, 10, 100);
+
+        stack.justify = StackAlign::End;
+        assert_eq!(rects(&stack, &items, area)[0].y, 80);
+
+        stack.justify = StackAlign::Center;
+        assert_eq!(rects(&stack, &items, area)[0].y, 40);
+
+        stack.justify = StackAlign::Fill;
+        let out = rects(&stack, &items, area);
+        assert_eq!((out[0].height, out[1].y, out[1].height), (50, 50, 50));
+    }
+
+    #[test]
+    fn small_stacks_shrink_the_nodes() {
+        let stack = Stack {
+            margins: [0, 0, 0, 0],
+            spacing: 10,
+            ..Default::default()
+        };
+        let items = items(&[[10, 60], [10, 20]]);
+        let out = rects(&stack, &items, LayoutRect::new(0, 0, 10, 50));
+
+        assert_eq!(out[0].height, 30);
+        assert_eq!(out[1], LayoutRect::new(0, 40, 10, 10));
+    }
+
+    #[test]
+    fn preferred_size_of_a_stack() {
+        let stack = Stack {
+            direction: StackDirection::Horizontal,
+            ..Default::default()
+        };
+        let items = items(&[[50, 20], [30, 40]]);
+
+        assert_eq!(stack.preferred_size(&items), (95, 50));
+        assert_eq!(stack.preferred_size::<usize>(&[]), (10, 10));
+    }
+}
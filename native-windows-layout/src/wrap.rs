@@ -0,0 +1,217 @@
+use crate::stack::{align_in, share};
+use crate::{LayoutRect, StackAlign};
+
+/// A node placed by a `Wrap`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WrapItem<Id> {
+    /// The id of the node
+    pub id: Id,
+
+    /// The preferred width and height of the node
+    pub preferred: [u32; 2],
+}
+
+impl<Id> WrapItem<Id> {
+    /// A node with a preferred size
+    pub fn new(id: Id, preferred: [u32; 2]) -> WrapItem<Id> {
+        WrapItem { id, preferred }
+    }
+}
+
+/**
+    The parameters of a wrap layout. A wrap layout places its nodes from the left to the right with their preferred size
+    and starts a new line when a node does not fit in the current line, like the buttons of a toolbar.
+
+    A node wider than the layout is placed alone on its line and shrinks to the width of the layout.
+*/
+#[derive(Copy, Clone, Debug)]
+pub struct Wrap {
+    /// The top, right, bottom, left space around the layout
+    pub margins: [u32; 4],
+
+    /// The horizontal space between two nodes of a line and the vertical space between two lines
+    pub spacing: [u32; 2],
+
+    /// The vertical alignment of the nodes in their line. The height of a line is the height of its tallest node.
+    pub align: StackAlign,
+
+    /// The placement of the nodes in their line. `Fill` shares the free space of the line between its nodes.
+    pub justify: StackAlign,
+}
+
+impl Default for Wrap {
+    fn default() -> Wrap {
+        Wrap {
+            margins: [5, 5, 5, 5],
+            spacing: [5, 5],
+            align: StackAlign::Center,
+            justify: StackAlign::Start,
+        }
+    }
+}
+
+impl Wrap {
+    /// Computes the rectangle of every node in `area`. The rectangles are returned in the order of `items`.
+    pub fn solve<Id: Copy>(
+        &self,
+        items: &[WrapItem<Id>],
+        area: LayoutRect,
+    ) -> Vec<(Id, LayoutRect)> {
+        let [m_top, m_right, _, m_left] = self.margins;
+        let [h_spacing, v_spacing] = self.spacing;
+        let width = area.width.saturating_sub(m_left + m_right);
+
+        let mut rects = Vec::with_capacity(items.len());
+        let mut y = area.y + m_top as i32;
+
+        for line in self.lines(items, width) {
+            let widths: Vec<u32> = line.iter().map(|i| i.preferred[0].min(width)).collect();
+            let height = line.iter().map(|i| i.preferred[1]).max().unwrap_or(0);
+
+            let used = widths.iter().sum::<u32>() + h_spacing * (line.len() as u32 - 1);
+            let free = width.saturating_sub(used);
+            let (widths, offset) = match self.justify {
+                StackAlign::Fill => (share(&widths, &vec![1; line.len()], free), 0),
+                StackAlign::Start => (widths, 0),
+                StackAlign::Center => (widths, free / 2),
+                StackAlign::End => (widths, free),
+            };
+
+            let mut x = area.x + (m_left + offset) as i32;
+            for (item, w) in line.iter().zip(widths) {
+                let (item_y, h) = align_in(self.align, height, item.preferred[1]);
+                rects.push((item.id, LayoutRect::new(x, y + item_y as i32, w, h)));
+                x += (w + h_spacing) as i32;
+            }
+
+            y += (height + v_spacing) as i32;
+        }
+
+        rects
+    }
+
+    /// Returns the size needed to display every node on a single line
+    pub fn preferred_size<Id>(&self, items: &[WrapItem<Id>]) -> (u32, u32) {
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let spacing = self.spacing[0] * (items.len().max(1) as u32 - 1);
+
+        let width = items.iter().map(|i| i.preferred[0]).sum::<u32>() + spacing;
+        let height = items.iter().map(|i| i.preferred[1]).max().unwrap_or(0);
+
+        (width + m_left + m_right, height + m_top + m_bottom)
+    }
+
+    /// Returns the height needed to display every node if the layout is `width` pixels wide
+    pub fn preferred_height<Id>(&self, items: &[WrapItem<Id>], width: u32) -> u32 {
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let width = width.saturating_sub(m_left + m_right);
+
+        let lines = self.lines(items, width);
+        let spacing = self.spacing[1] * (lines.len().max(1) as u32 - 1);
+        let height: u32 = lines
+            .iter()
+            .map(|line| line.iter().map(|i| i.preferred[1]).max().unwrap_or(0))
+            .sum();
+
+        height + spacing + m_top + m_bottom
+    }
+
+    /// Splits the nodes in lines that fit in `width`
+    fn lines<'a, Id>(&self, items: &'a [WrapItem<Id>], width: u32) -> Vec<&'a [WrapItem<Id>]> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut line_width = 0;
+
+        for (i, item) in items.iter().enumerate() {
+            let w = item.preferred[0];
+            if i > start && line_width + self.spacing[0] + w > width {
+                lines.push(&items[start..i]);
+                start = i;
+                line_width = w;
+            } else if i > start {
+                line_width += self.spacing[0] + w;
+            } else {
+                line_width = w;
+            }
+        }
+
+        if start < items.len() {
+            lines.push(&items[start..]);
+        }
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rects(wrap: &Wrap, items: &[WrapItem<usize>], area: LayoutRect) -> Vec<LayoutRect> {
+        wrap.solve(items, area)
+            .into_iter()
+            .map(|(_, rect)| rect)
+            .collect()
+    }
+
+    fn items(sizes: &[[u32; 2]]) -> Vec<WrapItem<usize>> {
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &size)| WrapItem::new(i, size))
+            .collect()
+    }
+
+    #[test]
+    fn nodes_wrap_when_the_line_is_full() {
+        let wrap = Wrap::default();
+        let items = items(&[[40, 20], [40, 30], [40, 20]]);
+        let out = rects(&wrap, &items, LayoutRect::new(0, 0, 100, 100));
+
+        assert_eq!(out[0], LayoutRect::new(5, 10, 40, 20));
+        assert_eq!(out[1], LayoutRect::new(50, 5, 40, 30));
+        assert_eq!(out[2], LayoutRect::new(5, 40, 40, 20));
+    }
+
+    #[test]
+    fn wide_nodes_shrink_to_the_layout() {
+        let wrap = Wrap {
+            margins: [0, 0, 0, 0],
+            ..Default::default()
+        };
+        let items = items(&[[20, 10], [500, 10]]);
+        let out = rects(&wrap, &items, LayoutRect::new(0, 0, 100, 100));
+
+        assert_eq!(out[0], LayoutRect::new(0, 0, 20, 10));
+        assert_eq!(out[1], LayoutRect::new(0, 15, 100, 10));
+    }
+
+    #[test]
+    fn lines_are_justified() {
+        let mut wrap = Wrap {
+            margins: [0, 0, 0, 0],
+            spacing: [0, 0],
+            justify: StackAlign::End,
+            ..Default::default()
+        };
+        let items = items(&[[30, 10], [30, 10], [30, 10]]);
+        let area = LayoutRect::new(0, 0, 70, 100);
+
+        let out = rects(&wrap, &items, area);
+        assert_eq!((out[0].x, out[1].x, out[2].x), (10, 40, 40));
+
+        wrap.justify = StackAlign::Fill;
+        let out = rects(&wrap, &items, area);
+        assert_eq!((out[0].width, out[1].width, out[2].width), (35, 35, 70));
+    }
+
+    #[test]
+    fn preferred_sizes_of_a_wrap() {
+        let wrap = Wrap::default();
+        let items = items(&[[40, 20], [40, 30], [40, 20]]);
+
+        assert_eq!(wrap.preferred_size(&items), (140, 40));
+        assert_eq!(wrap.preferred_height(&items, 100), 65);
+        assert_eq!(wrap.preferred_height(&items, 200), 40);
+    }
+}